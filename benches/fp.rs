@@ -1,4 +1,4 @@
-///! Benchmarks for the Fp field.
+//! Benchmarks for the Fp field.
 extern crate fil_pasta_curves as pasta_curves;
 
 use criterion::{criterion_group, criterion_main, Bencher, Criterion};
@@ -17,9 +17,12 @@ fn criterion_benchmark(c: &mut Criterion) {
     group.bench_function("sub_assign", bench_fp_sub_assign);
     group.bench_function("mul_assign", bench_fp_mul_assign);
     group.bench_function("square", bench_fp_square);
+    group.bench_function("sum_of_products", bench_fp_sum_of_products);
     group.bench_function("invert", bench_fp_invert);
     group.bench_function("neg", bench_fp_neg);
     group.bench_function("sqrt", bench_fp_sqrt);
+    #[cfg(feature = "alloc")]
+    group.bench_function("batch_sqrt/1000", bench_fp_batch_sqrt);
     group.bench_function("to_repr", bench_fp_to_repr);
     group.bench_function("from_repr", bench_fp_from_repr);
 }
@@ -125,6 +128,20 @@ fn bench_fp_square(b: &mut Bencher) {
     });
 }
 
+fn bench_fp_sum_of_products(b: &mut Bencher) {
+    const SAMPLES: usize = 1000;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let a: Vec<Fp> = (0..SAMPLES).map(|_| Fp::random(&mut rng)).collect();
+    let c: Vec<Fp> = (0..SAMPLES).map(|_| Fp::random(&mut rng)).collect();
+
+    b.iter(|| Fp::sum_of_products(&a, &c));
+}
+
 fn bench_fp_invert(b: &mut Bencher) {
     const SAMPLES: usize = 1000;
 
@@ -183,6 +200,25 @@ fn bench_fp_sqrt(b: &mut Bencher) {
     });
 }
 
+#[cfg(feature = "alloc")]
+fn bench_fp_batch_sqrt(b: &mut Bencher) {
+    const SAMPLES: usize = 1000;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let v: Vec<Fp> = (0..SAMPLES)
+        .map(|_| {
+            let tmp = Fp::random(&mut rng);
+            tmp.square()
+        })
+        .collect();
+
+    b.iter(|| Fp::batch_sqrt(&v));
+}
+
 fn bench_fp_to_repr(b: &mut Bencher) {
     const SAMPLES: usize = 1000;
 
@@ -1,4 +1,4 @@
-///! Benchmarks for the Fq field.
+//! Benchmarks for the Fq field.
 extern crate fil_pasta_curves as pasta_curves;
 
 use criterion::{criterion_group, criterion_main, Bencher, Criterion};
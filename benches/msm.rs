@@ -0,0 +1,46 @@
+//! Benchmarks for multi-scalar multiplication.
+extern crate fil_pasta_curves as pasta_curves;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+use ff::Field;
+use group::{Curve, Group};
+use pasta_curves::arithmetic::CurveAffine;
+use pasta_curves::{pallas, vesta};
+
+fn criterion_benchmark(c: &mut Criterion) {
+    msm_bench::<pallas::Affine>(c, "Pallas", pallas::Point::multi_scalar_mul);
+    msm_bench::<vesta::Affine>(c, "Vesta", vesta::Point::multi_scalar_mul);
+}
+
+fn msm_bench<C: CurveAffine>(
+    c: &mut Criterion,
+    name: &str,
+    msm: fn(&[C], &[C::ScalarExt]) -> C::CurveExt,
+) {
+    let mut group = c.benchmark_group(format!("{} MSM", name));
+    group.sample_size(10);
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for &k in [4, 8, 12, 16].iter() {
+        let n = 1 << k;
+        let points: Vec<_> = (0..n).map(|_| C::CurveExt::random(&mut rng)).collect();
+        let mut bases = vec![C::identity(); n];
+        C::CurveExt::batch_normalize(&points, &mut bases);
+        let scalars: Vec<_> = (0..n).map(|_| C::ScalarExt::random(&mut rng)).collect();
+
+        group.bench_function(BenchmarkId::new("multi_scalar_mul", n), |bencher| {
+            bencher.iter(|| msm(&bases, &scalars))
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -3,6 +3,8 @@ extern crate fil_pasta_curves as pasta_curves;
 
 use criterion::{criterion_group, criterion_main, Criterion};
 
+use ff::PrimeField;
+use group::{GroupEncoding, UncompressedEncoding};
 use pasta_curves::arithmetic::CurveExt;
 use pasta_curves::{pallas, vesta};
 
@@ -11,7 +13,10 @@ fn criterion_benchmark(c: &mut Criterion) {
     point_bench::<vesta::Point>(c, "Vesta");
 }
 
-fn point_bench<C: CurveExt>(c: &mut Criterion, name: &str) {
+fn point_bench<C: CurveExt>(c: &mut Criterion, name: &str)
+where
+    C::AffineExt: UncompressedEncoding,
+{
     let mut group = c.benchmark_group(name);
 
     let a = C::generator();
@@ -39,6 +44,65 @@ fn point_bench<C: CurveExt>(c: &mut Criterion, name: &str) {
             bencher.iter(|| C::batch_normalize(input.as_slice(), output.as_mut_slice()));
         });
     }
+
+    // A full-width scalar.
+    let s = -C::ScalarExt::TWO_INV;
+    group.bench_function("point scalar multiplication", |bencher| {
+        bencher.iter(|| a * s)
+    });
+
+    let a = a.to_affine();
+    group.bench_function("affine scalar multiplication", |bencher| {
+        bencher.iter(|| a * s)
+    });
+
+    group.bench_function("affine to_bytes", |bencher| bencher.iter(|| a.to_bytes()));
+
+    let affine_repr = a.to_bytes();
+    group.bench_function("affine from_bytes", |bencher| {
+        bencher.iter(|| C::AffineExt::from_bytes(&affine_repr))
+    });
+
+    // Decoding is constant time, so these should take as long as a valid
+    // encoding: an x-coordinate that is not on the curve, a non-canonical
+    // x-coordinate, and the identity with the sign bit set.
+    let zero = <C::AffineExt as GroupEncoding>::Repr::default();
+    let off_curve = (1..=255u8)
+        .map(|i| {
+            let mut repr = zero;
+            repr.as_mut()[0] = i;
+            repr
+        })
+        .find(|repr| bool::from(C::AffineExt::from_bytes(repr).is_none()))
+        .unwrap();
+    group.bench_function("affine from_bytes off-curve", |bencher| {
+        bencher.iter(|| C::AffineExt::from_bytes(&off_curve))
+    });
+
+    let mut non_canonical = zero;
+    non_canonical.as_mut().fill(0xff);
+    group.bench_function("affine from_bytes non-canonical", |bencher| {
+        bencher.iter(|| C::AffineExt::from_bytes(&non_canonical))
+    });
+
+    let mut identity_with_sign = zero;
+    identity_with_sign.as_mut()[31] = 0x80;
+    group.bench_function("affine from_bytes identity with sign", |bencher| {
+        bencher.iter(|| C::AffineExt::from_bytes(&identity_with_sign))
+    });
+
+    group.bench_function("affine to_uncompressed", |bencher| {
+        bencher.iter(|| a.to_uncompressed())
+    });
+
+    let uncompressed = a.to_uncompressed();
+    group.bench_function("affine from_uncompressed", |bencher| {
+        bencher.iter(|| C::AffineExt::from_uncompressed(&uncompressed))
+    });
+
+    group.bench_function("affine from_uncompressed_unchecked", |bencher| {
+        bencher.iter(|| C::AffineExt::from_uncompressed_unchecked(&uncompressed))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,62 @@
+//! Benchmarks for serializing and deserializing large vectors of points and field
+//! elements with `serde`.
+extern crate fil_pasta_curves as pasta_curves;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+use ff::Field;
+use group::{prime::PrimeCurveAffine, Curve, Group};
+use pasta_curves::{pallas, Fp};
+use serde_crate::{de::DeserializeOwned, Serialize};
+
+const SIZES: [usize; 2] = [1 << 10, 1 << 16];
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let n = SIZES[SIZES.len() - 1];
+    let points: Vec<_> = (0..n).map(|_| pallas::Point::random(&mut rng)).collect();
+    let mut affine = vec![pallas::Affine::identity(); n];
+    pallas::Point::batch_normalize(&points, &mut affine);
+    let elements: Vec<_> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+
+    serde_bench(c, "Vec<EpAffine>", &affine);
+    serde_bench(c, "Vec<Fp>", &elements);
+}
+
+fn serde_bench<T: Serialize + DeserializeOwned>(c: &mut Criterion, name: &str, values: &[T]) {
+    let mut group = c.benchmark_group(format!("{} serde", name));
+    group.sample_size(10);
+
+    for &n in SIZES.iter() {
+        let values = &values[..n];
+        group.throughput(Throughput::Elements(n as u64));
+
+        group.bench_function(BenchmarkId::new("bincode serialize", n), |bencher| {
+            bencher.iter(|| bincode::serialize(values).unwrap())
+        });
+        let bytes = bincode::serialize(values).unwrap();
+        group.bench_function(BenchmarkId::new("bincode deserialize", n), |bencher| {
+            bencher.iter(|| bincode::deserialize::<Vec<T>>(&bytes).unwrap())
+        });
+
+        group.bench_function(BenchmarkId::new("json serialize", n), |bencher| {
+            bencher.iter(|| serde_json::to_string(values).unwrap())
+        });
+        let json = serde_json::to_string(values).unwrap();
+        group.bench_function(BenchmarkId::new("json deserialize", n), |bencher| {
+            bencher.iter(|| serde_json::from_str::<Vec<T>>(&json).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+//! With the `cuda` or `opencl` feature flag, generates the GPU kernels for the
+//! Pasta fields and curves and embeds them in the crate, so that dependents do not
+//! need to generate or compile kernels themselves. See `pasta_curves::gpu`.
+//!
+//! With the `ifma` feature flag, also checks that the compiler is recent enough for
+//! the AVX-512 intrinsics, and otherwise leaves the portable code paths in place.
+
+fn main() {
+    #[cfg(any(feature = "cuda", feature = "opencl"))]
+    kernels::generate();
+
+    #[cfg(feature = "ifma")]
+    ifma::detect();
+}
+
+#[cfg(feature = "ifma")]
+mod ifma {
+    use std::{env, process::Command};
+
+    /// The first release with stable AVX-512 intrinsics.
+    const MIN_MINOR: u32 = 89;
+
+    /// Returns the minor version of the compiler that builds the crate.
+    fn rustc_minor_version() -> Option<u32> {
+        let rustc = env::var_os("RUSTC")?;
+        let output = Command::new(rustc).arg("--version").output().ok()?;
+        let version = String::from_utf8(output.stdout).ok()?;
+        // For example "rustc 1.89.0 (29483883e 2025-08-04)".
+        version.split(['.', ' '].as_ref()).nth(2)?.parse().ok()
+    }
+
+    pub(crate) fn detect() {
+        let minor = match rustc_minor_version() {
+            Some(minor) => minor,
+            None => return,
+        };
+        // `rustc-check-cfg` is only understood from Rust 1.80.
+        if minor >= 80 {
+            println!("cargo:rustc-check-cfg=cfg(pasta_ifma)");
+        }
+        if minor >= MIN_MINOR {
+            println!("cargo:rustc-cfg=pasta_ifma");
+        } else {
+            println!(
+                "cargo:warning=the `ifma` feature needs Rust 1.{} or later, so it has no effect",
+                MIN_MINOR
+            );
+        }
+    }
+}
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+mod kernels {
+    use std::{env, fs, path::PathBuf};
+
+    use ec_gpu::{GpuField, GpuName};
+    use ec_gpu_gen::SourceBuilder;
+
+    fn u64_to_u32(limbs: &[u64; 4]) -> Vec<u32> {
+        limbs
+            .iter()
+            .flat_map(|limb| [*limb as u32, (limb >> 32) as u32])
+            .collect()
+    }
+
+    // The build script cannot use the crate's own types, so these stand in for them.
+    // Their names must match the `ec_gpu` implementations in `src/`.
+
+    macro_rules! gpu_field {
+        ($field:ident, $limbs:expr) => {
+            struct $field;
+
+            impl GpuName for $field {
+                fn name() -> String {
+                    String::from(concat!("pasta_", stringify!($field)))
+                }
+            }
+
+            impl GpuField for $field {
+                fn one() -> Vec<u32> {
+                    u64_to_u32(&$limbs.1)
+                }
+
+                fn r2() -> Vec<u32> {
+                    u64_to_u32(&$limbs.2)
+                }
+
+                fn modulus() -> Vec<u32> {
+                    u64_to_u32(&$limbs.0)
+                }
+            }
+        };
+    }
+
+    include!("src/cl/fields.rs");
+
+    gpu_field!(Fp, FP_LIMBS);
+    gpu_field!(Fq, FQ_LIMBS);
+
+    macro_rules! gpu_affine {
+        ($name_affine:ident) => {
+            struct $name_affine;
+
+            impl GpuName for $name_affine {
+                fn name() -> String {
+                    String::from(concat!("pasta_", stringify!($name_affine)))
+                }
+            }
+        };
+    }
+
+    gpu_affine!(EpAffine);
+    gpu_affine!(EqAffine);
+
+    include!("src/cl/source.rs");
+
+    pub(crate) fn generate() {
+        // docs.rs has no CUDA toolchain, so embed empty kernels there.
+        if env::var_os("DOCS_RS").is_some() {
+            let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+            let empty = out_dir.join("kernels.empty");
+            fs::write(&empty, []).unwrap();
+            println!("cargo:rustc-env=_EC_GPU_CUDA_KERNELS={}", empty.display());
+            println!("cargo:rustc-env=_EC_GPU_OPENCL_KERNELS={}", empty.display());
+            return;
+        }
+
+        // The same kernels as `pasta_curves::gpu::source_builder`.
+        ec_gpu_gen::generate(&kernels_source_builder());
+    }
+}
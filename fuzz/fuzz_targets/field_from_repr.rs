@@ -0,0 +1,30 @@
+#![no_main]
+
+use fil_pasta_curves::{arithmetic::FieldExt, Fp, Fq};
+use libfuzzer_sys::fuzz_target;
+
+fn check<F: FieldExt<Repr = [u8; 32]>>(bytes: [u8; 32]) {
+    let canonical = F::from_repr(bytes);
+    if let Some(a) = Option::<F>::from(canonical) {
+        // Canonical encodings round-trip, and agree with the wide reduction.
+        assert_eq!(a.to_repr(), bytes);
+        let mut wide = [0; 64];
+        wide[..32].copy_from_slice(&bytes);
+        assert_eq!(F::from_bytes_wide(&wide), a);
+        assert_eq!(a * F::ONE, a);
+    } else {
+        // Rejected encodings are not less than the modulus.
+        let mut modulus_minus_one = (-F::ONE).to_repr();
+        modulus_minus_one.reverse();
+        let mut be = bytes;
+        be.reverse();
+        assert!(be > modulus_minus_one);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(bytes) = <[u8; 32]>::try_from(data) {
+        check::<Fp>(bytes);
+        check::<Fq>(bytes);
+    }
+});
@@ -0,0 +1,33 @@
+#![no_main]
+
+use fil_pasta_curves::{
+    arithmetic::CurveAffine,
+    group::{Curve, GroupEncoding},
+    EpAffine, EqAffine,
+};
+use libfuzzer_sys::fuzz_target;
+
+fn check<C>(bytes: [u8; 32])
+where
+    C: CurveAffine<Repr = [u8; 32]>,
+    C::CurveExt: GroupEncoding<Repr = [u8; 32]>,
+{
+    let affine = Option::<C>::from(C::from_bytes(&bytes));
+    let projective = Option::<C::CurveExt>::from(C::CurveExt::from_bytes(&bytes));
+    assert_eq!(affine.is_some(), projective.is_some());
+
+    if let (Some(p), Some(q)) = (affine, projective) {
+        // Decoded points are on the curve, and their encodings are canonical.
+        assert!(bool::from(p.is_on_curve()));
+        assert_eq!(p.to_bytes(), bytes);
+        assert_eq!(q.to_affine(), p);
+        assert_eq!(q.to_bytes(), bytes);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(bytes) = <[u8; 32]>::try_from(data) {
+        check::<EpAffine>(bytes);
+        check::<EqAffine>(bytes);
+    }
+});
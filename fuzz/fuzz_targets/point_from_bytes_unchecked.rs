@@ -0,0 +1,47 @@
+#![no_main]
+
+use fil_pasta_curves::{arithmetic::CurveAffine, group::UncompressedEncoding, EpAffine, EqAffine};
+use libfuzzer_sys::fuzz_target;
+
+fn check_compressed<C: CurveAffine<Repr = [u8; 32]>>(bytes: [u8; 32]) {
+    // Compressed encodings can't skip the curve check, so the unchecked decoding
+    // must agree exactly with the checked one.
+    let checked = Option::<C>::from(C::from_bytes(&bytes));
+    let unchecked = Option::<C>::from(C::from_bytes_unchecked(&bytes));
+    assert_eq!(checked, unchecked);
+}
+
+fn check_uncompressed<C: CurveAffine + UncompressedEncoding>(data: &[u8]) {
+    let mut bytes = C::Uncompressed::default();
+    if data.len() != bytes.as_ref().len() {
+        return;
+    }
+    bytes.as_mut().copy_from_slice(data);
+
+    let checked = Option::<C>::from(C::from_uncompressed(&bytes));
+    let unchecked = Option::<C>::from(C::from_uncompressed_unchecked(&bytes));
+    match (checked, unchecked) {
+        (Some(p), Some(q)) => {
+            assert_eq!(p, q);
+            assert!(bool::from(p.is_on_curve()));
+            assert_eq!(p.to_uncompressed().as_ref(), data);
+        }
+        // Only the unchecked decoding accepts points that are not on the curve, but
+        // their coordinates must still be canonical.
+        (None, Some(q)) => {
+            assert!(!bool::from(q.is_on_curve()));
+            assert_eq!(q.to_uncompressed().as_ref(), data);
+        }
+        (Some(_), None) => panic!("the unchecked decoding rejected a valid point"),
+        (None, None) => (),
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(bytes) = <[u8; 32]>::try_from(data) {
+        check_compressed::<EpAffine>(bytes);
+        check_compressed::<EqAffine>(bytes);
+    }
+    check_uncompressed::<EpAffine>(data);
+    check_uncompressed::<EqAffine>(data);
+});
@@ -0,0 +1,80 @@
+#![no_main]
+
+use core::fmt::Debug;
+
+use fil_pasta_curves::{
+    arithmetic::CurveAffine, Compact, EpAffine, EqAffine, Fp, Fq, UncheckedPoint,
+};
+use libfuzzer_sys::fuzz_target;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Deserializes `data` with both formats, checking that whatever is accepted
+/// serializes back to an equal value.
+fn roundtrip<T: Serialize + DeserializeOwned + PartialEq + Debug>(data: &[u8]) -> Vec<T> {
+    let mut values = vec![];
+    if let Ok(value) = bincode::deserialize::<T>(data) {
+        let bytes = bincode::serialize(&value).unwrap();
+        assert_eq!(bincode::deserialize::<T>(&bytes).unwrap(), value);
+        values.push(value);
+    }
+    if let Ok(value) = serde_json::from_slice::<T>(data) {
+        let json = serde_json::to_vec(&value).unwrap();
+        assert_eq!(serde_json::from_slice::<T>(&json).unwrap(), value);
+        values.push(value);
+    }
+    values
+}
+
+fn check_points<C>(data: &[u8])
+where
+    C: CurveAffine + Serialize + DeserializeOwned,
+    C::CurveExt: Serialize + DeserializeOwned,
+    Compact<C>: Serialize + DeserializeOwned,
+{
+    for p in roundtrip::<C>(data) {
+        assert!(bool::from(p.is_on_curve()));
+    }
+    roundtrip::<C::CurveExt>(data);
+    for p in roundtrip::<Compact<C>>(data) {
+        assert!(bool::from(p.0.is_on_curve()));
+    }
+}
+
+fn check_unchecked<C>(data: &[u8])
+where
+    C: CurveAffine + Serialize + DeserializeOwned,
+    UncheckedPoint<C>: Serialize + DeserializeOwned,
+{
+    // Points that are on the curve must deserialize to the same point either way.
+    if let Ok(p) = bincode::deserialize::<UncheckedPoint<C>>(data) {
+        let bytes = bincode::serialize(&p).unwrap();
+        assert_eq!(
+            bincode::deserialize::<UncheckedPoint<C>>(&bytes).unwrap().0,
+            p.0
+        );
+        if bool::from(p.0.is_on_curve()) {
+            let checked = bincode::serialize(&p.0).unwrap();
+            assert_eq!(bincode::deserialize::<C>(&checked).unwrap(), p.0);
+        }
+    }
+    if let Ok(p) = serde_json::from_slice::<UncheckedPoint<C>>(data) {
+        let json = serde_json::to_vec(&p).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<UncheckedPoint<C>>(&json)
+                .unwrap()
+                .0,
+            p.0
+        );
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    roundtrip::<Fp>(data);
+    roundtrip::<Fq>(data);
+    roundtrip::<Compact<Fp>>(data);
+    roundtrip::<Compact<Fq>>(data);
+    check_points::<EpAffine>(data);
+    check_points::<EqAffine>(data);
+    check_unchecked::<EpAffine>(data);
+    check_unchecked::<EqAffine>(data);
+});
@@ -0,0 +1,407 @@
+#ifndef PASTA_CURVES_H
+#define PASTA_CURVES_H
+
+/* Generated with cbindgen from src/capi.rs. Do not edit by hand; regenerate with
+ * `cbindgen --config cbindgen.toml --output include/pasta_curves.h`. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The operation succeeded.
+ */
+#define PASTA_OK 0
+
+/**
+ * An input is not a valid encoding of a field element or a point, a domain
+ * prefix is not valid UTF-8 or is too long, or the byte length of an input
+ * overflows `size_t`.
+ */
+#define PASTA_ERR_INVALID_ENCODING 1
+
+/**
+ * The operation has no result, such as the inverse of zero or the square root of
+ * a non-square.
+ */
+#define PASTA_ERR_NO_RESULT 2
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Computes `a + b` in `Fp`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_fp_add(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Computes `a - b` in `Fp`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_fp_sub(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Computes `a * b` in `Fp`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_fp_mul(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Computes `a^2` in `Fp`.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fp_square(uint8_t *out, const uint8_t *a);
+
+/**
+ * Computes `-a` in `Fp`.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fp_neg(uint8_t *out, const uint8_t *a);
+
+/**
+ * Computes `1 / a` in `Fp`.
+ *
+ * Fails with `PASTA_ERR_NO_RESULT` if `a` is zero.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fp_invert(uint8_t *out, const uint8_t *a);
+
+/**
+ * Computes a square root of `a` in `Fp`.
+ *
+ * Fails with `PASTA_ERR_NO_RESULT` if `a` is not a square.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fp_sqrt(uint8_t *out, const uint8_t *a);
+
+/**
+ * Computes `a + b` in `Fq`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_fq_add(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Computes `a - b` in `Fq`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_fq_sub(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Computes `a * b` in `Fq`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_fq_mul(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Computes `a^2` in `Fq`.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fq_square(uint8_t *out, const uint8_t *a);
+
+/**
+ * Computes `-a` in `Fq`.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fq_neg(uint8_t *out, const uint8_t *a);
+
+/**
+ * Computes `1 / a` in `Fq`.
+ *
+ * Fails with `PASTA_ERR_NO_RESULT` if `a` is zero.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fq_invert(uint8_t *out, const uint8_t *a);
+
+/**
+ * Computes a square root of `a` in `Fq`.
+ *
+ * Fails with `PASTA_ERR_NO_RESULT` if `a` is not a square.
+ *
+ * # Safety
+ *
+ * `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+ */
+int32_t pasta_fq_sqrt(uint8_t *out, const uint8_t *a);
+
+/**
+ * Writes the compressed encoding of the Pallas generator.
+ *
+ * # Safety
+ *
+ * `out` must be valid for writes of 32 bytes.
+ */
+int32_t pasta_pallas_generator(uint8_t *out);
+
+/**
+ * Computes the sum of the Pallas points `a` and `b`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_pallas_add(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Multiplies the Pallas point `point` by `scalar`.
+ *
+ * # Safety
+ *
+ * `point` and `scalar` must be valid for reads of 32 bytes and `out` for
+ * writes of 32 bytes.
+ */
+int32_t pasta_pallas_scalar_mul(uint8_t *out, const uint8_t *point, const uint8_t *scalar);
+
+/**
+ * Compresses the uncompressed encoding of a Pallas point.
+ *
+ * # Safety
+ *
+ * `point` must be valid for reads of 64 bytes and `out` for writes of 32
+ * bytes.
+ */
+int32_t pasta_pallas_compress(uint8_t *out, const uint8_t *point);
+
+/**
+ * Decompresses the compressed encoding of a Pallas point.
+ *
+ * # Safety
+ *
+ * `point` must be valid for reads of 32 bytes and `out` for writes of 64
+ * bytes.
+ */
+int32_t pasta_pallas_decompress(uint8_t *out, const uint8_t *point);
+
+/**
+ * Hashes `message` to a Pallas point with a UTF-8 domain prefix.
+ *
+ * Fails with `PASTA_ERR_INVALID_ENCODING` if the domain prefix is not valid
+ * UTF-8, or is too long: together with the curve name and the 22 bytes that
+ * `hash_to_curve` appends, it must be shorter than 256 bytes.
+ *
+ * # Safety
+ *
+ * `domain_prefix` must be valid for reads of `domain_prefix_len` bytes,
+ * `message` for reads of `message_len` bytes, and `out` for writes of 32
+ * bytes. `domain_prefix` and `message` may be null if their lengths are
+ * zero.
+ */
+int32_t pasta_pallas_hash_to_curve(uint8_t *out,
+                                   const uint8_t *domain_prefix,
+                                   size_t domain_prefix_len,
+                                   const uint8_t *message,
+                                   size_t message_len);
+
+/**
+ * Computes the multi-scalar multiplication of `len` Pallas points and
+ * scalars, given as the concatenations of their encodings.
+ *
+ * This is not constant time with respect to the scalars.
+ *
+ * # Safety
+ *
+ * `points` and `scalars` must be valid for reads of `32 * len` bytes and `out`
+ * for writes of 32 bytes. `points` and `scalars` may be null if `len` is zero.
+ */
+int32_t pasta_pallas_msm(uint8_t *out,
+                         const uint8_t *points,
+                         const uint8_t *scalars,
+                         size_t len);
+
+/**
+ * Converts `len` Pallas points from Jacobian coordinates to the
+ * concatenation of their uncompressed encodings, with a single field
+ * inversion.
+ *
+ * Each point is given as the 96-byte concatenation of the little-endian
+ * encodings of its Jacobian coordinates $(X, Y, Z)$, and must be on the curve.
+ *
+ * # Safety
+ *
+ * `points` must be valid for reads of `96 * len` bytes and `out` for writes of
+ * `64 * len` bytes. `points` may be null if `len` is zero.
+ */
+int32_t pasta_pallas_batch_to_affine(uint8_t *out,
+                                     const uint8_t *points,
+                                     size_t len);
+
+/**
+ * Decompresses the compressed encodings of `len` Pallas points.
+ *
+ * # Safety
+ *
+ * `points` must be valid for reads of `32 * len` bytes and `out` for writes of
+ * `64 * len` bytes. `points` may be null if `len` is zero.
+ */
+int32_t pasta_pallas_batch_decompress(uint8_t *out,
+                                      const uint8_t *points,
+                                      size_t len);
+
+/**
+ * Writes the compressed encoding of the Vesta generator.
+ *
+ * # Safety
+ *
+ * `out` must be valid for writes of 32 bytes.
+ */
+int32_t pasta_vesta_generator(uint8_t *out);
+
+/**
+ * Computes the sum of the Vesta points `a` and `b`.
+ *
+ * # Safety
+ *
+ * `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+ * 32 bytes.
+ */
+int32_t pasta_vesta_add(uint8_t *out, const uint8_t *a, const uint8_t *b);
+
+/**
+ * Multiplies the Vesta point `point` by `scalar`.
+ *
+ * # Safety
+ *
+ * `point` and `scalar` must be valid for reads of 32 bytes and `out` for
+ * writes of 32 bytes.
+ */
+int32_t pasta_vesta_scalar_mul(uint8_t *out, const uint8_t *point, const uint8_t *scalar);
+
+/**
+ * Compresses the uncompressed encoding of a Vesta point.
+ *
+ * # Safety
+ *
+ * `point` must be valid for reads of 64 bytes and `out` for writes of 32
+ * bytes.
+ */
+int32_t pasta_vesta_compress(uint8_t *out, const uint8_t *point);
+
+/**
+ * Decompresses the compressed encoding of a Vesta point.
+ *
+ * # Safety
+ *
+ * `point` must be valid for reads of 32 bytes and `out` for writes of 64
+ * bytes.
+ */
+int32_t pasta_vesta_decompress(uint8_t *out, const uint8_t *point);
+
+/**
+ * Hashes `message` to a Vesta point with a UTF-8 domain prefix.
+ *
+ * Fails with `PASTA_ERR_INVALID_ENCODING` if the domain prefix is not valid
+ * UTF-8, or is too long: together with the curve name and the 22 bytes that
+ * `hash_to_curve` appends, it must be shorter than 256 bytes.
+ *
+ * # Safety
+ *
+ * `domain_prefix` must be valid for reads of `domain_prefix_len` bytes,
+ * `message` for reads of `message_len` bytes, and `out` for writes of 32
+ * bytes. `domain_prefix` and `message` may be null if their lengths are
+ * zero.
+ */
+int32_t pasta_vesta_hash_to_curve(uint8_t *out,
+                                  const uint8_t *domain_prefix,
+                                  size_t domain_prefix_len,
+                                  const uint8_t *message,
+                                  size_t message_len);
+
+/**
+ * Computes the multi-scalar multiplication of `len` Vesta points and
+ * scalars, given as the concatenations of their encodings.
+ *
+ * This is not constant time with respect to the scalars.
+ *
+ * # Safety
+ *
+ * `points` and `scalars` must be valid for reads of `32 * len` bytes and `out`
+ * for writes of 32 bytes. `points` and `scalars` may be null if `len` is zero.
+ */
+int32_t pasta_vesta_msm(uint8_t *out,
+                        const uint8_t *points,
+                        const uint8_t *scalars,
+                        size_t len);
+
+/**
+ * Converts `len` Vesta points from Jacobian coordinates to the
+ * concatenation of their uncompressed encodings, with a single field
+ * inversion.
+ *
+ * Each point is given as the 96-byte concatenation of the little-endian
+ * encodings of its Jacobian coordinates $(X, Y, Z)$, and must be on the curve.
+ *
+ * # Safety
+ *
+ * `points` must be valid for reads of `96 * len` bytes and `out` for writes of
+ * `64 * len` bytes. `points` may be null if `len` is zero.
+ */
+int32_t pasta_vesta_batch_to_affine(uint8_t *out,
+                                    const uint8_t *points,
+                                    size_t len);
+
+/**
+ * Decompresses the compressed encodings of `len` Vesta points.
+ *
+ * # Safety
+ *
+ * `points` must be valid for reads of `32 * len` bytes and `out` for writes of
+ * `64 * len` bytes. `points` may be null if `len` is zero.
+ */
+int32_t pasta_vesta_batch_decompress(uint8_t *out,
+                                     const uint8_t *points,
+                                     size_t len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* PASTA_CURVES_H */
@@ -0,0 +1,202 @@
+#!/usr/bin/env python3
+"""Generates the RFC 9380 test vectors for the suites
+pallas_XMD:SHA-256_SSWU_RO_ and vesta_XMD:SHA-256_SSWU_RO_, and for
+encode_to_curve with the suites pallas_XMD:SHA-256_SSWU_NU_ and
+vesta_XMD:SHA-256_SSWU_NU_ and hashing to a scalar, for the message "abc".
+
+This is a straight-line implementation of RFC 9380, sections 5.2, 5.3.1, 6.6.2,
+6.6.3 and appendix E.1, in affine coordinates with Python integers, and shares no
+code with the Rust implementation. The curve, isogeny and Z constants are those
+of the Zcash protocol specification, section 5.4.9.8.
+
+Usage: python3 scripts/hash_to_curve_vectors.py
+"""
+
+import hashlib
+
+CURVES = {
+    "pallas": {
+        "p": 0x40000000000000000000000000000000224698FC094CF91B992D30ED00000001,
+        # The isogenous curve y^2 = x^3 + a x + b.
+        "a": 0x18354A2EB0EA8C9C49BE2D7258370742B74134581A27A59F92BB4B0B657A014B,
+        "b": 1265,
+        "z": -13,
+        "iso": [
+            0x0E38E38E38E38E38E38E38E38E38E38E4081775473D8375B775F6034AAAAAAAB,
+            0x3509AFD51872D88E267C7FFA51CF412A0F93B82EE4B994958CF863B02814FB76,
+            0x17329B9EC525375398C7D7AC3D98FD13380AF066CFEB6D690EB64FAEF37EA4F7,
+            0x1C71C71C71C71C71C71C71C71C71C71C8102EEA8E7B06EB6EEBEC06955555580,
+            0x1D572E7DDC099CFF5A607FCCE0494A799C434AC1C96B6980C47F2AB668BCD71F,
+            0x325669BECAECD5D11D13BF2A7F22B105B4ABF9FB9A1FC81C2AA3AF1EAE5B6604,
+            0x1A12F684BDA12F684BDA12F684BDA12F7642B01AD461BAD25AD985B5E38E38E4,
+            0x1A84D7EA8C396C47133E3FFD28E7A09507C9DC17725CCA4AC67C31D8140A7DBB,
+            0x3FB98FF0D2DDCADD303216CCE1DB9FF11765E924F745937802E2BE87D225B234,
+            0x025ED097B425ED097B425ED097B425ED0AC03E8E134EB3E493E53AB371C71C4F,
+            0x0C02C5BCCA0E6B7F0790BFB3506DEFB65941A3A4A97AA1B35A28279B1D1B42AE,
+            0x17033D3C60C68173573B3D7F7D681310D976BBFABBC5661D4D90AB820B12320A,
+            0x40000000000000000000000000000000224698FC094CF91B992D30ECFFFFFDE5,
+        ],
+    },
+    "vesta": {
+        "p": 0x40000000000000000000000000000000224698FC0994A8DD8C46EB2100000001,
+        "a": 0x267F9B2EE592271A81639C4D96F787739673928C7D01B212C515AD7242EAA6B1,
+        "b": 1265,
+        "z": -13,
+        "iso": [
+            0x38E38E38E38E38E38E38E38E38E38E390205DD51CFA0961A43CD42C800000001,
+            0x1D935247B4473D17ACECF10F5F7C09A2216B8861EC72BD5D8B95C6AAF703BCC5,
+            0x18760C7F7A9AD20DED7EE4A9CDF78F8FD59D03D23B39CB11AEAC67BBEB586A3D,
+            0x31C71C71C71C71C71C71C71C71C71C71E1C521A795AC8356FB539A6F0000002B,
+            0x0A2DE485568125D51454798A5B5C56B2A3AD678129B604D3B7284F7EAF21A2E9,
+            0x14735171EE5427780C621DE8B91C242A30CD6D53DF49D235F169C187D2533465,
+            0x12F684BDA12F684BDA12F684BDA12F685601F4709A8ADCB36BEF1642AAAAAAAB,
+            0x2EC9A923DA239E8BD6767887AFBE04D121D910AEFB03B31D8BEE58E5FB81DE63,
+            0x19B0D87E16E2578866D1466E9DE10E6497A3CA5C24E9EA634986913AB4443034,
+            0x01ED097B425ED097B425ED097B425ED098BC32D36FB21A6A38F64842C55555533,
+            0x2F44D6C801C1B8BF9E7EB64F890A820C06A767BFC35B5BAC58DFECCE86B2745E,
+            0x3D59F455CAFC7668252659BA2B546C7E926847FB9DDD76A1D43D449776F99D2F,
+            0x40000000000000000000000000000000224698FC0994A8DD8C46EB20FFFFFDE5,
+        ],
+    },
+}
+
+MESSAGES = [b"", b"abc", b"abcdef0123456789", b"q128_" + b"q" * 128, b"a512_" + b"a" * 512]
+
+
+def expand_message_xmd(msg, dst, len_in_bytes):
+    # RFC 9380, section 5.3.1, with SHA-256.
+    b_in_bytes, s_in_bytes = 32, 64
+    ell = -(-len_in_bytes // b_in_bytes)
+    assert ell <= 255 and len(dst) <= 255
+    dst_prime = dst + bytes([len(dst)])
+    msg_prime = bytes(s_in_bytes) + msg + len_in_bytes.to_bytes(2, "big") + b"\0" + dst_prime
+    b0 = hashlib.sha256(msg_prime).digest()
+    b = [hashlib.sha256(b0 + b"\1" + dst_prime).digest()]
+    for i in range(2, ell + 1):
+        xored = bytes(x ^ y for x, y in zip(b0, b[-1]))
+        b.append(hashlib.sha256(xored + bytes([i]) + dst_prime).digest())
+    return b"".join(b)[:len_in_bytes]
+
+
+def hash_to_field(msg, dst, p, count=2, L=48):
+    # RFC 9380, section 5.2, with m = 1.
+    uniform = expand_message_xmd(msg, dst, count * L)
+    return [int.from_bytes(uniform[i * L : (i + 1) * L], "big") % p for i in range(count)]
+
+
+def is_square(x, p):
+    return pow(x, (p - 1) // 2, p) in (0, 1)
+
+
+def sqrt(x, p):
+    # Tonelli-Shanks.
+    x %= p
+    if x == 0:
+        return 0
+    q, s = p - 1, 0
+    while q % 2 == 0:
+        q, s = q // 2, s + 1
+    z = 2
+    while is_square(z, p):
+        z += 1
+    m, c, t, r = s, pow(z, q, p), pow(x, q, p), pow(x, (q + 1) // 2, p)
+    while t != 1:
+        i, tt = 0, t
+        while tt != 1:
+            tt, i = tt * tt % p, i + 1
+        b = pow(c, 1 << (m - i - 1), p)
+        m, c, t, r = i, b * b % p, t * b * b % p, r * b % p
+    assert r * r % p == x
+    return r
+
+
+def inv0(x, p):
+    return pow(x, p - 2, p)
+
+
+def sgn0(x):
+    return x % 2
+
+
+def map_to_curve_simple_swu(u, curve):
+    # RFC 9380, section 6.6.2, onto the isogenous curve.
+    p, a, b, z = curve["p"], curve["a"], curve["b"], curve["z"] % curve["p"]
+    tv1 = inv0((z * z * pow(u, 4, p) + z * u * u) % p, p)
+    if tv1 == 0:
+        x1 = b * inv0(z * a, p) % p
+    else:
+        x1 = (-b * inv0(a, p)) * (1 + tv1) % p
+    gx1 = (pow(x1, 3, p) + a * x1 + b) % p
+    x2 = z * u * u * x1 % p
+    gx2 = (pow(x2, 3, p) + a * x2 + b) % p
+    if is_square(gx1, p):
+        x, y = x1, sqrt(gx1, p)
+    else:
+        x, y = x2, sqrt(gx2, p)
+    if sgn0(u) != sgn0(y):
+        y = -y % p
+    return x, y
+
+
+def iso_map(point, curve):
+    # RFC 9380, section 6.6.3, with the rational maps of the Zcash specification.
+    p, c = curve["p"], curve["iso"]
+    x, y = point
+    x_num = (c[0] * x**3 + c[1] * x**2 + c[2] * x + c[3]) % p
+    x_den = (x**2 + c[4] * x + c[5]) % p
+    y_num = (c[6] * x**3 + c[7] * x**2 + c[8] * x + c[9]) % p
+    y_den = (x**3 + c[10] * x**2 + c[11] * x + c[12]) % p
+    return x_num * inv0(x_den, p) % p, y * y_num * inv0(y_den, p) % p
+
+
+def add(p1, p2, p):
+    # Affine addition on y^2 = x^3 + 5. The points are never the identity here.
+    (x1, y1), (x2, y2) = p1, p2
+    if x1 == x2:
+        assert y1 == y2
+        lam = 3 * x1 * x1 * inv0(2 * y1, p) % p
+    else:
+        lam = (y2 - y1) * inv0(x2 - x1, p) % p
+    x3 = (lam * lam - x1 - x2) % p
+    return x3, (lam * (x1 - x3) - y1) % p
+
+
+def fmt(x):
+    return '"0x%064x"' % x
+
+
+def main():
+    for name, curve in CURVES.items():
+        p = curve["p"]
+        dst = ("QUUX-V01-CS02-with-%s_XMD:SHA-256_SSWU_RO_" % name).encode()
+        print("// %s" % name)
+        for msg in MESSAGES:
+            u = hash_to_field(msg, dst, p)
+            q0 = iso_map(map_to_curve_simple_swu(u[0], curve), curve)
+            q1 = iso_map(map_to_curve_simple_swu(u[1], curve), curve)
+            for q in (q0, q1):
+                assert (q[1] ** 2 - q[0] ** 3 - 5) % p == 0
+            # The cofactor is 1.
+            P = add(q0, q1, p)
+            print("TestVector {")
+            print("    p: (%s, %s)," % (fmt(P[0]), fmt(P[1])))
+            print("    u: [%s, %s]," % (fmt(u[0]), fmt(u[1])))
+            print("    q0: (%s, %s)," % (fmt(q0[0]), fmt(q0[1])))
+            print("    q1: (%s, %s)," % (fmt(q1[0]), fmt(q1[1])))
+            print("},")
+
+    for name, curve in CURVES.items():
+        p = curve["p"]
+        # The scalar field is the base field of the other curve.
+        q = CURVES["vesta" if name == "pallas" else "pallas"]["p"]
+        dst = ("QUUX-V01-CS02-with-%s_XMD:SHA-256_SSWU_NU_" % name).encode()
+        (u,) = hash_to_field(b"abc", dst, p, count=1)
+        P = iso_map(map_to_curve_simple_swu(u, curve), curve)
+        (s,) = hash_to_field(b"abc", dst, q, count=1)
+        print("// %s encode_to_curve and hash_to_scalar" % name)
+        print("p: (%s, %s)," % (fmt(P[0]), fmt(P[1])))
+        print("s: %s," % fmt(s))
+
+
+if __name__ == "__main__":
+    main()
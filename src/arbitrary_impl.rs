@@ -0,0 +1,122 @@
+use arbitrary::{size_hint, Arbitrary, Result, Unstructured};
+use group::{prime::PrimeCurveAffine, Curve};
+
+use crate::arithmetic::FieldExt;
+use crate::curves::{EpAffine, EqAffine};
+use crate::fields::{Fp, Fq};
+
+macro_rules! impl_arbitrary_field {
+    ($field:ident) => {
+        /// Generates small elements, elements just below the modulus, and uniformly
+        /// distributed elements, so that fuzzers reach the edge cases of modular
+        /// reduction.
+        impl<'a> Arbitrary<'a> for $field {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok(match u.int_in_range(0u8..=2)? {
+                    // 0, 1, 2, ...
+                    0 => $field::from(u.arbitrary::<u64>()?),
+                    // p - 1, p - 2, ...
+                    1 => -$field::from(u.arbitrary::<u64>()?) - $field::one(),
+                    _ => {
+                        let mut wide = [0; 64];
+                        wide[..32].copy_from_slice(&u.arbitrary::<[u8; 32]>()?);
+                        wide[32..].copy_from_slice(&u.arbitrary::<[u8; 32]>()?);
+                        $field::from_bytes_wide(&wide)
+                    }
+                })
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                size_hint::and(
+                    u8::size_hint(depth),
+                    size_hint::or(u64::size_hint(depth), (64, Some(64))),
+                )
+            }
+        }
+    };
+}
+
+impl_arbitrary_field!(Fp);
+impl_arbitrary_field!(Fq);
+
+macro_rules! impl_arbitrary_affine {
+    ($name_affine:ident, $scalar:ident) => {
+        /// Generates the identity, the generator, and multiples of the generator by
+        /// arbitrary scalars.
+        impl<'a> Arbitrary<'a> for $name_affine {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok(match u.int_in_range(0u8..=2)? {
+                    0 => $name_affine::identity(),
+                    1 => $name_affine::generator(),
+                    _ => ($name_affine::generator() * $scalar::arbitrary(u)?).to_affine(),
+                })
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                size_hint::and(u8::size_hint(depth), $scalar::size_hint(depth))
+            }
+        }
+    };
+}
+
+impl_arbitrary_affine!(EpAffine, Fq);
+impl_arbitrary_affine!(EqAffine, Fp);
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use ff::Field;
+    use group::Curve;
+
+    use crate::arithmetic::CurveAffine;
+    use crate::{EpAffine, EqAffine, Fp, Fq};
+
+    fn check_field<'a, F: Field + Arbitrary<'a>>(data: &'a [u8]) {
+        let mut u = Unstructured::new(data);
+        let mut seen_zero = false;
+        let mut seen_minus_one = false;
+        let mut seen_large = false;
+        while !u.is_empty() {
+            let a = F::arbitrary(&mut u).unwrap();
+            seen_zero |= bool::from(a.is_zero());
+            seen_minus_one |= a == -F::ONE;
+            seen_large |= a.square() != a && a != -F::ONE;
+        }
+        assert!(seen_zero && seen_minus_one && seen_large);
+    }
+
+    fn check_affine<'a, C: CurveAffine + Arbitrary<'a>>(data: &'a [u8]) {
+        let mut u = Unstructured::new(data);
+        let mut seen_identity = false;
+        let mut seen_generator = false;
+        let mut seen_other = false;
+        while !u.is_empty() {
+            let p = C::arbitrary(&mut u).unwrap();
+            assert!(bool::from(p.is_on_curve()));
+            seen_identity |= bool::from(p.is_identity());
+            seen_generator |= p == C::generator();
+            seen_other |= !bool::from(p.is_identity())
+                && p != C::generator()
+                && p != (C::generator() + C::generator()).to_affine();
+        }
+        assert!(seen_identity && seen_generator && seen_other);
+    }
+
+    #[test]
+    fn test_arbitrary() {
+        // Each case is selected by its leading byte, followed by its payload.
+        let mut fields = std::vec![0];
+        fields.extend_from_slice(&[0; 8]);
+        fields.push(1);
+        fields.extend_from_slice(&[0; 8]);
+        fields.push(2);
+        fields.extend_from_slice(&[0xa5; 64]);
+        check_field::<Fp>(&fields);
+        check_field::<Fq>(&fields);
+
+        let mut points = std::vec![0, 1, 2, 2];
+        points.extend_from_slice(&[0xa5; 64]);
+        check_affine::<EpAffine>(&points);
+        check_affine::<EqAffine>(&points);
+    }
+}
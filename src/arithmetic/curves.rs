@@ -2,7 +2,10 @@
 //! write code that generalizes over a pair of groups.
 
 #[cfg(feature = "alloc")]
-use group::prime::{PrimeCurve, PrimeCurveAffine};
+use group::{
+    prime::{PrimeCurve, PrimeCurveAffine},
+    Curve as _,
+};
 #[cfg(feature = "alloc")]
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
@@ -84,6 +87,31 @@ pub trait CurveExt:
     /// Obtains a point given Jacobian coordinates $X : Y : Z$, failing
     /// if the coordinates are not on the curve.
     fn new_jacobian(x: Self::Base, y: Self::Base, z: Self::Base) -> CtOption<Self>;
+
+    /// Adds the affine point `rhs` to this point in constant time: unlike `+`, the
+    /// sequence of operations does not depend on whether either point is the
+    /// identity or whether the points are equal.
+    ///
+    /// The default implementation uses `+`, which is not constant time. The curves
+    /// of this crate override it.
+    fn add_mixed_ct(&self, rhs: &Self::AffineExt) -> Self {
+        *self + rhs
+    }
+}
+
+/// A curve that forms a 2-cycle with [`Self::Other`]: the scalar field of each
+/// curve is the base field of the other.
+///
+/// Pallas and Vesta form such a cycle, which lets recursive proof systems verify
+/// proofs over one curve in circuits over the other. Generic code can name "the
+/// other curve in the cycle" as `C::Other`, and the bounds on it guarantee at
+/// compile time that the fields line up, so for instance a base field element of
+/// `C::Other` can be used as a scalar of `C` without conversions.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait CycleCurve: CurveExt {
+    /// The other curve in the cycle.
+    type Other: CycleCurve<Other = Self> + CurveExt<Base = Self::ScalarExt, ScalarExt = Self::Base>;
 }
 
 /// This trait is the affine counterpart to `Curve` and is used for
@@ -123,6 +151,25 @@ pub trait CurveAffine:
     /// always be true unless an "unchecked" API was used.
     fn is_on_curve(&self) -> Choice;
 
+    /// Replaces each point of `lhs` with its sum with the corresponding point of
+    /// `rhs`, in affine coordinates.
+    ///
+    /// The default implementation adds each pair in projective coordinates and
+    /// normalizes the result. The curves of this crate instead share a single
+    /// field inversion between the slopes of all the sums (Montgomery's trick),
+    /// which for many independent pairs is considerably cheaper. Neither is
+    /// constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` have different lengths.
+    fn batch_add(lhs: &mut [Self], rhs: &[Self]) {
+        assert_eq!(lhs.len(), rhs.len());
+        for (p, q) in lhs.iter_mut().zip(rhs.iter()) {
+            *p = (*p + *q).to_affine();
+        }
+    }
+
     /// Returns the curve constant $a$.
     fn a() -> Self::Base;
 
@@ -3,6 +3,7 @@
 
 use core::mem::size_of;
 
+use ff::{FromUniformBytes, WithSmallOrderMulGroup};
 use static_assertions::const_assert;
 use subtle::{Choice, ConditionallySelectable, CtOption};
 
@@ -11,7 +12,9 @@ use super::Group;
 use core::assert;
 
 #[cfg(feature = "sqrt-table")]
-use alloc::{boxed::Box, vec::Vec};
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 #[cfg(feature = "sqrt-table")]
 use core::marker::PhantomData;
 
@@ -30,113 +33,127 @@ pub trait SqrtRatio: ff::PrimeField {
         ff::Field::pow_vartime(self, &Self::T_MINUS1_OVER2)
     }
 
-    /// Gets the lower 32 bits of this field element when expressed
-    /// canonically.
-    fn get_lower_32(&self) -> u32;
-
-    /// Computes:
+    /// Computes the square root of this element, if it exists, in constant time.
     ///
-    /// - $(\textsf{true}, \sqrt{\textsf{num}/\textsf{div}})$, if $\textsf{num}$ and
-    ///   $\textsf{div}$ are nonzero and $\textsf{num}/\textsf{div}$ is a square in the
-    ///   field;
-    /// - $(\textsf{true}, 0)$, if $\textsf{num}$ is zero;
-    /// - $(\textsf{false}, 0)$, if $\textsf{num}$ is nonzero and $\textsf{div}$ is zero;
-    /// - $(\textsf{false}, \sqrt{G_S \cdot \textsf{num}/\textsf{div}})$, if
-    ///   $\textsf{num}$ and $\textsf{div}$ are nonzero and $\textsf{num}/\textsf{div}$ is
-    ///   a nonsquare in the field;
-    ///
-    /// where $G_S$ is a non-square.
-    ///
-    /// For `pasta_curves`, $G_S$ is currently [`ff::PrimeField::root_of_unity`], a
-    /// generator of the order $2^S$ subgroup. Users of this crate should not rely on this
-    /// generator being fixed; it may be changed in future crate versions to simplify the
-    /// implementation of the SSWU hash-to-curve algorithm.
-    ///
-    /// The choice of root from sqrt is unspecified.
-    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
-        // General implementation:
-        //
-        // a = num * inv0(div)
-        //   = {    0    if div is zero
-        //     { num/div otherwise
-        //
-        // b = G_S * a
-        //   = {      0      if div is zero
-        //     { G_S*num/div otherwise
-        //
-        // Since G_S is non-square, a and b are either both zero (and both square), or
-        // only one of them is square. We can therefore choose the square root to return
-        // based on whether a is square, but for the boolean output we need to handle the
-        // num != 0 && div == 0 case specifically.
-
-        let a = div.invert().unwrap_or_else(Self::zero) * num;
-        let b = a * Self::root_of_unity();
-        let sqrt_a = a.sqrt();
-        let sqrt_b = b.sqrt();
-
-        let num_is_zero = num.is_zero();
-        let div_is_zero = div.is_zero();
-        let is_square = sqrt_a.is_some();
-        let is_nonsquare = sqrt_b.is_some();
-        assert!(bool::from(
-            num_is_zero | div_is_zero | (is_square ^ is_nonsquare)
-        ));
-
-        (
-            is_square & !(!num_is_zero & div_is_zero),
-            CtOption::conditional_select(&sqrt_b, &sqrt_a, is_square).unwrap(),
-        )
+    /// With the `sqrt-table` feature flag, [`ff::Field::sqrt`] looks up tables at
+    /// indices that depend on the element, which can leak it through the cache. This
+    /// always uses the Tonelli–Shanks algorithm, whose memory accesses and running
+    /// time do not depend on the element.
+    fn sqrt_ct(&self) -> CtOption<Self> {
+        sqrt_tonelli_shanks(self, Self::T_MINUS1_OVER2)
     }
 
-    /// Equivalent to `Self::sqrt_ratio(self, one())`.
-    fn sqrt_alt(&self) -> (Choice, Self) {
-        Self::sqrt_ratio(self, &Self::one())
-    }
+    /// Gets the lower 32 bits of this field element when expressed
+    /// canonically.
+    fn get_lower_32(&self) -> u32;
 }
 
 /// This trait is a common interface for dealing with elements of a finite
 /// field.
-pub trait FieldExt: SqrtRatio + From<bool> + Ord + Group<Scalar = Self> {
-    /// Modulus of the field written as a string for display purposes
-    const MODULUS: &'static str;
-
-    /// Inverse of `PrimeField::root_of_unity()`
-    const ROOT_OF_UNITY_INV: Self;
+pub trait FieldExt:
+    SqrtRatio
+    + WithSmallOrderMulGroup<3>
+    + FromUniformBytes<64>
+    + From<bool>
+    + Ord
+    + Group<Scalar = Self>
+{
+    /// Obtains a field element that is congruent to the provided little endian
+    /// byte representation of an integer.
+    fn from_bytes_wide(bytes: &[u8; 64]) -> Self;
 
-    /// Generator of the $t-order$ multiplicative subgroup
-    const DELTA: Self;
+    /// Gets the lower 128 bits of this field element when expressed
+    /// canonically.
+    fn get_lower_128(&self) -> u128;
+}
 
-    /// Inverse of $2$ in the field.
-    const TWO_INV: Self;
+/// Inverts every element of `elements` in place using Montgomery's trick, so that
+/// only a single field inversion is performed.
+///
+/// Zero elements are left unchanged. Returns the inverse of the product of all
+/// nonzero elements.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn batch_invert<F: ff::Field>(elements: &mut [F]) -> F {
+    // Compute the running products of all nonzero elements seen so far.
+    let mut acc = F::ONE;
+    let mut products = Vec::with_capacity(elements.len());
+    for e in elements.iter() {
+        products.push(acc);
+        acc = F::conditional_select(&(acc * e), &acc, e.is_zero());
+    }
 
-    /// Element of multiplicative order $3$.
-    const ZETA: Self;
+    // This is the inverse, as all skipped elements are zero.
+    acc = acc.invert().unwrap();
+    let all_inv = acc;
 
-    /// Obtains a field element congruent to the integer `v`.
-    fn from_u128(v: u128) -> Self;
+    for (e, product) in elements.iter_mut().rev().zip(products.into_iter().rev()) {
+        let skip = e.is_zero();
 
-    /// Obtains a field element that is congruent to the provided little endian
-    /// byte representation of an integer.
-    fn from_bytes_wide(bytes: &[u8; 64]) -> Self;
+        // Compute 1/e, and cancel e out of the denominator of `acc`.
+        let inv = acc * product;
+        acc = F::conditional_select(&(acc * *e), &acc, skip);
 
-    /// Exponentiates `self` by `by`, where `by` is a little-endian order
-    /// integer exponent.
-    fn pow(&self, by: &[u64; 4]) -> Self {
-        let mut res = Self::one();
-        for e in by.iter().rev() {
-            for i in (0..64).rev() {
-                res = res.square();
-                let mut tmp = res;
-                tmp *= self;
-                res.conditional_assign(&tmp, (((*e >> i) & 0x1) as u8).into());
-            }
-        }
-        res
+        *e = F::conditional_select(&inv, e, skip);
     }
 
-    /// Gets the lower 128 bits of this field element when expressed
-    /// canonically.
-    fn get_lower_128(&self) -> u128;
+    all_inv
+}
+
+/// Computes the Legendre symbol of `f` as $f^{(p-1)/2}$, which is $1$ if `f` is a
+/// nonzero square, $-1$ if it is a non-square, and $0$ if it is zero.
+///
+/// Since $(p-1)/2 = 2^{S-1} \cdot t$, this reuses the addition chain for
+/// $f^{(t-1)/2}$, squares the result and multiplies it by $f$ to obtain $f^t$, and
+/// then squares $S - 1$ more times.
+pub(crate) fn legendre_symbol<F: SqrtRatio>(f: &F) -> i8 {
+    // f^t = (f^((t - 1) / 2))^2 * f
+    let ft = f.pow_by_t_minus1_over2().square() * f;
+    let res = (1..F::S).fold(ft, |x, _| x.square());
+
+    let symbol = i8::conditional_select(&-1, &1, res.ct_eq(&F::ONE));
+    i8::conditional_select(&symbol, &0, res.ct_eq(&F::ZERO))
+}
+
+/// A generic implementation of [`ff::Field::sqrt_ratio`] in terms of
+/// [`ff::Field::sqrt`], for fields without the `sqrt-table` lookups.
+///
+/// The non-square $G_S$ is [`ff::PrimeField::ROOT_OF_UNITY`], a generator of the
+/// order $2^S$ subgroup.
+#[cfg_attr(feature = "sqrt-table", allow(dead_code))]
+pub(crate) fn sqrt_ratio_generic<F: ff::PrimeField>(num: &F, div: &F) -> (Choice, F) {
+    // General implementation:
+    //
+    // a = num * inv0(div)
+    //   = {    0    if div is zero
+    //     { num/div otherwise
+    //
+    // b = G_S * a
+    //   = {      0      if div is zero
+    //     { G_S*num/div otherwise
+    //
+    // Since G_S is non-square, a and b are either both zero (and both square), or
+    // only one of them is square. We can therefore choose the square root to return
+    // based on whether a is square, but for the boolean output we need to handle the
+    // num != 0 && div == 0 case specifically.
+
+    let a = div.invert().unwrap_or(F::ZERO) * num;
+    let b = a * F::ROOT_OF_UNITY;
+    let sqrt_a = a.sqrt();
+    let sqrt_b = b.sqrt();
+
+    let num_is_zero = num.is_zero();
+    let div_is_zero = div.is_zero();
+    let is_square = sqrt_a.is_some();
+    let is_nonsquare = sqrt_b.is_some();
+    assert!(bool::from(
+        num_is_zero | div_is_zero | (is_square ^ is_nonsquare)
+    ));
+
+    (
+        is_square & !(!num_is_zero & div_is_zero),
+        CtOption::conditional_select(&sqrt_b, &sqrt_a, is_square).unwrap(),
+    )
 }
 
 /// Tonelli–Shanks' square-root algorithm for `p mod 16 = 1`.
@@ -144,23 +161,30 @@ pub trait FieldExt: SqrtRatio + From<bool> + Ord + Group<Scalar = Self> {
 /// https://eprint.iacr.org/2012/685.pdf (page 12, algorithm 5)
 ///
 /// `tm1d2` should be set to `(t - 1) // 2`, where `t = (modulus - 1) >> F::S`.
-#[cfg(not(feature = "sqrt-table"))]
-#[cfg_attr(docsrs, doc(cfg(not(feature = "sqrt-table"))))]
+///
+/// This runs in constant time with respect to `f`.
 pub(crate) fn sqrt_tonelli_shanks<F: ff::PrimeField, S: AsRef<[u64]>>(
     f: &F,
     tm1d2: S,
 ) -> CtOption<F> {
-    use subtle::ConstantTimeEq;
-
     // w = self^((t - 1) // 2)
     let w = f.pow_vartime(tm1d2);
 
+    sqrt_tonelli_shanks_with_pow(f, &w)
+}
+
+/// The rest of [`sqrt_tonelli_shanks`], given `w = f^((t - 1) // 2)`.
+#[allow(clippy::many_single_char_names)]
+pub(crate) fn sqrt_tonelli_shanks_with_pow<F: ff::PrimeField>(f: &F, w: &F) -> CtOption<F> {
+    use subtle::ConstantTimeEq;
+
+    let w = *w;
     let mut v = F::S;
     let mut x = w * f;
     let mut b = x * w;
 
     // Initialize z as the 2^S root of unity.
-    let mut z = F::root_of_unity();
+    let mut z = F::ROOT_OF_UNITY;
 
     for max_v in (1..=F::S).rev() {
         let mut k = 1;
@@ -168,7 +192,7 @@ pub(crate) fn sqrt_tonelli_shanks<F: ff::PrimeField, S: AsRef<[u64]>>(
         let mut j_less_than_v: Choice = 1.into();
 
         for j in 2..max_v {
-            let tmp_is_one = tmp.ct_eq(&F::one());
+            let tmp_is_one = tmp.ct_eq(&F::ONE);
             let squared = F::conditional_select(&tmp, &z, tmp_is_one).square();
             tmp = F::conditional_select(&squared, &tmp, tmp_is_one);
             let new_z = F::conditional_select(&z, &squared, tmp_is_one);
@@ -178,7 +202,7 @@ pub(crate) fn sqrt_tonelli_shanks<F: ff::PrimeField, S: AsRef<[u64]>>(
         }
 
         let result = x * z;
-        x = F::conditional_select(&result, &x, b.ct_eq(&F::one()));
+        x = F::conditional_select(&result, &x, b.ct_eq(&F::ONE));
         z = z.square();
         b *= z;
         v = k;
@@ -190,6 +214,91 @@ pub(crate) fn sqrt_tonelli_shanks<F: ff::PrimeField, S: AsRef<[u64]>>(
     )
 }
 
+/// The width in bits of the exponent windows in [`pow_windowed`] and
+/// [`pow_vartime_windowed`].
+#[cfg(feature = "windowed-pow")]
+const POW_WINDOW: usize = 4;
+
+/// Returns the table $[1, f, f^2, \ldots, f^{2^w - 1}]$ for windows of width
+/// [`POW_WINDOW`].
+#[cfg(feature = "windowed-pow")]
+fn pow_table<F: ff::Field>(f: &F) -> [F; 1 << POW_WINDOW] {
+    let mut table = [F::ONE; 1 << POW_WINDOW];
+    for i in 1..table.len() {
+        table[i] = table[i - 1] * f;
+    }
+    table
+}
+
+/// Raises `f` to the power `exp`, given as little-endian 64-bit limbs, with a
+/// 4-bit fixed window.
+///
+/// This runs in constant time with respect to `f` and the value of `exp`: every
+/// window is processed with the same squarings and multiplication, and the table
+/// entry is selected without branching or secret-dependent memory accesses. Only
+/// the number of limbs of `exp` is leaked.
+#[cfg(feature = "windowed-pow")]
+pub(crate) fn pow_windowed<F: ff::Field>(f: &F, exp: &[u64]) -> F {
+    use subtle::ConstantTimeEq;
+
+    let table = pow_table(f);
+    let mut res = F::ONE;
+    for e in exp.iter().rev() {
+        for i in (0..64 / POW_WINDOW).rev() {
+            for _ in 0..POW_WINDOW {
+                res = res.square();
+            }
+
+            let window = ((e >> (i * POW_WINDOW)) & ((1 << POW_WINDOW) - 1)) as u8;
+            let mut entry = F::ONE;
+            for (j, t) in table.iter().enumerate() {
+                entry.conditional_assign(t, window.ct_eq(&(j as u8)));
+            }
+            res *= entry;
+        }
+    }
+    res
+}
+
+/// Raises `f` to the power `exp`, given as little-endian 64-bit limbs, in variable
+/// time with respect to `exp`.
+///
+/// Exponents shorter than a limb use square-and-multiply, as the window table
+/// would cost more than it saves. Longer exponents use a 4-bit fixed window,
+/// skipping the leading zero windows and the multiplications by zero windows.
+#[cfg(feature = "windowed-pow")]
+pub(crate) fn pow_vartime_windowed<F: ff::Field>(f: &F, exp: &[u64]) -> F {
+    let bits = exp
+        .iter()
+        .rposition(|e| *e != 0)
+        .map_or(0, |i| 64 * (i + 1) - exp[i].leading_zeros() as usize);
+
+    let mut res = F::ONE;
+    if bits < 64 {
+        for i in (0..bits).rev() {
+            res = res.square();
+            if (exp[0] >> i) & 1 == 1 {
+                res *= f;
+            }
+        }
+        return res;
+    }
+
+    let table = pow_table(f);
+    for i in (0..(bits + POW_WINDOW - 1) / POW_WINDOW).rev() {
+        for _ in 0..POW_WINDOW {
+            res = res.square();
+        }
+
+        let bit = i * POW_WINDOW;
+        let window = (exp[bit / 64] >> (bit % 64)) as usize & ((1 << POW_WINDOW) - 1);
+        if window != 0 {
+            res *= table[window];
+        }
+    }
+    res
+}
+
 /// Parameters for a perfect hash function used in square root computation.
 #[cfg(feature = "sqrt-table")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sqrt-table")))]
@@ -237,10 +346,10 @@ impl<F: FieldExt> SqrtTables<F> {
             marker: PhantomData,
         };
 
-        let mut gtab = (0..4).scan(F::root_of_unity(), |gi, _| {
+        let mut gtab = (0..4).scan(F::ROOT_OF_UNITY, |gi, _| {
             // gi == ROOT_OF_UNITY^(256^i)
             let gtab_i: Vec<F> = (0..256)
-                .scan(F::one(), |acc, _| {
+                .scan(F::ONE, |acc, _| {
                     let res = *acc;
                     *acc *= *gi;
                     Some(res)
@@ -335,7 +444,7 @@ impl<F: FieldExt> SqrtTables<F> {
 
         let sqdiv = res.square() * div;
         let is_square = (sqdiv - num).is_zero();
-        let is_nonsquare = (sqdiv - F::root_of_unity() * num).is_zero();
+        let is_nonsquare = (sqdiv - F::ROOT_OF_UNITY * num).is_zero();
         assert!(bool::from(
             num.is_zero() | div.is_zero() | (is_square ^ is_nonsquare)
         ));
@@ -345,14 +454,19 @@ impl<F: FieldExt> SqrtTables<F> {
 
     /// Same as sqrt_ratio(u, one()) but more efficient.
     pub fn sqrt_alt(&self, u: &F) -> (Choice, F) {
-        let v = u.pow_by_t_minus1_over2();
+        self.sqrt_alt_with_pow(u, &u.pow_by_t_minus1_over2())
+    }
+
+    /// Same as sqrt_alt(u), given v = u^((T-1)/2).
+    pub(crate) fn sqrt_alt_with_pow(&self, u: &F, v: &F) -> (Choice, F) {
+        let v = *v;
         let uv = *u * v;
 
         let res = self.sqrt_common(&uv, &v);
 
         let sq = res.square();
         let is_square = (sq - u).is_zero();
-        let is_nonsquare = (sq - F::root_of_unity() * u).is_zero();
+        let is_nonsquare = (sq - F::ROOT_OF_UNITY * u).is_zero();
         assert!(bool::from(u.is_zero() | (is_square ^ is_nonsquare)));
 
         (is_square, res)
@@ -0,0 +1,244 @@
+//! Base64 encodings of field elements and points.
+//!
+//! Field elements and points are encoded from their canonical 32-byte
+//! representations, either with the standard alphabet and padding of RFC 4648
+//! (44 characters), or with the URL-safe alphabet and no padding (43 characters),
+//! as used in JSON Web Tokens. Decoding is strict: the unused low bits of the last
+//! symbol must be zero, so that no two strings in the same alphabet decode to the
+//! same value, other than the padded and unpadded URL-safe forms.
+
+use alloc::string::String;
+use core::fmt;
+
+use ff::PrimeField;
+use group::GroupEncoding;
+
+use crate::curves::{EpAffine, EqAffine};
+use crate::fields::{Fp, Fq};
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// An error which can be returned when decoding a field element or point from
+/// base64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseBase64Error {
+    /// The string is not the encoding of 32 bytes, or is missing its padding.
+    InvalidLength,
+    /// The string contains a character outside the alphabet, or the last symbol
+    /// has nonzero unused bits.
+    InvalidSymbol,
+    /// The bytes do not encode a field element or a point on the curve.
+    InvalidEncoding,
+}
+
+impl fmt::Display for ParseBase64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseBase64Error::InvalidLength => "expected the base64 encoding of 32 bytes",
+            ParseBase64Error::InvalidSymbol => "invalid base64 symbol found in string",
+            ParseBase64Error::InvalidEncoding => "bytes don't encode a field element or point",
+        })
+    }
+}
+
+fn encode(bytes: &[u8; 32], alphabet: &[u8; 64], pad: bool) -> String {
+    let mut s = String::with_capacity(44);
+    for chunk in bytes.chunks(3) {
+        let mut b = [0; 3];
+        b[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+
+        // A final chunk of two bytes needs only three symbols.
+        let symbols = chunk.len() + 1;
+        for i in 0..symbols {
+            s.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if pad {
+            for _ in symbols..4 {
+                s.push('=');
+            }
+        }
+    }
+    s
+}
+
+fn decode(s: &str, alphabet: &[u8; 64], pad: bool) -> Result<[u8; 32], ParseBase64Error> {
+    let digits = match s.strip_suffix('=') {
+        Some(digits) => digits,
+        None if pad => return Err(ParseBase64Error::InvalidLength),
+        None => s,
+    };
+    if digits.len() != 43 || digits.ends_with('=') {
+        return Err(ParseBase64Error::InvalidLength);
+    }
+
+    let mut bytes = [0; 32];
+    let mut i = 0;
+    let (mut acc, mut bits) = (0u32, 0);
+    for c in digits.bytes() {
+        let value = alphabet
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(ParseBase64Error::InvalidSymbol)?;
+        acc = acc << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes[i] = (acc >> bits) as u8;
+            i += 1;
+        }
+        acc &= (1 << bits) - 1;
+    }
+
+    // 43 symbols carry 258 bits, and the last 2 must be zero.
+    if acc != 0 {
+        return Err(ParseBase64Error::InvalidSymbol);
+    }
+    Ok(bytes)
+}
+
+macro_rules! impl_base64 {
+    ($t:ident, |$value:ident| $encode:expr, |$bytes:ident| $decode:expr) => {
+        impl $t {
+            /// Encodes the canonical 32-byte representation with the standard base64
+            /// alphabet and padding.
+            #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+            pub fn to_base64(&self) -> String {
+                let $value = self;
+                encode(&$encode, STANDARD, true)
+            }
+
+            /// Encodes the canonical 32-byte representation with the URL-safe base64
+            /// alphabet and no padding.
+            #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+            pub fn to_base64_url(&self) -> String {
+                let $value = self;
+                encode(&$encode, URL_SAFE, false)
+            }
+
+            /// Decodes a value written by `to_base64`.
+            #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+            pub fn from_base64(s: &str) -> Result<Self, ParseBase64Error> {
+                let $bytes = decode(s, STANDARD, true)?;
+                Option::from($decode).ok_or(ParseBase64Error::InvalidEncoding)
+            }
+
+            /// Decodes a value written by `to_base64_url`. Padding is accepted but not
+            /// required.
+            #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+            pub fn from_base64_url(s: &str) -> Result<Self, ParseBase64Error> {
+                let $bytes = decode(s, URL_SAFE, false)?;
+                Option::from($decode).ok_or(ParseBase64Error::InvalidEncoding)
+            }
+        }
+    };
+}
+
+impl_base64!(Fp, |f| f.to_repr(), |bytes| Fp::from_repr(bytes));
+impl_base64!(Fq, |f| f.to_repr(), |bytes| Fq::from_repr(bytes));
+impl_base64!(EpAffine, |p| p.to_bytes(), |bytes| EpAffine::from_bytes(
+    &bytes
+));
+impl_base64!(EqAffine, |p| p.to_bytes(), |bytes| EqAffine::from_bytes(
+    &bytes
+));
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+
+    use super::ParseBase64Error;
+    use crate::curves::{Ep, EpAffine, Eq, EqAffine};
+    use crate::fields::{Fp, Fq};
+
+    #[test]
+    fn test_base64_roundtrip() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..100 {
+            let f = Fp::random(&mut rng);
+            assert_eq!(Fp::from_base64(&f.to_base64()), Ok(f));
+            assert_eq!(Fp::from_base64_url(&f.to_base64_url()), Ok(f));
+            let f = Fq::random(&mut rng);
+            assert_eq!(Fq::from_base64(&f.to_base64()), Ok(f));
+            assert_eq!(Fq::from_base64_url(&f.to_base64_url()), Ok(f));
+
+            let p = Ep::random(&mut rng).to_affine();
+            assert_eq!(EpAffine::from_base64(&p.to_base64()), Ok(p));
+            assert_eq!(EpAffine::from_base64_url(&p.to_base64_url()), Ok(p));
+            let p = Eq::random(&mut rng).to_affine();
+            assert_eq!(EqAffine::from_base64(&p.to_base64()), Ok(p));
+            assert_eq!(EqAffine::from_base64_url(&p.to_base64_url()), Ok(p));
+        }
+    }
+
+    #[test]
+    fn test_base64_vectors() {
+        let one = Fp::one();
+        assert_eq!(
+            one.to_base64(),
+            "AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+        );
+        assert_eq!(
+            one.to_base64_url(),
+            "AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"
+        );
+        assert_eq!(
+            EpAffine::generator().to_base64(),
+            "AAAAAO0wLZkb+UwJ/JhGIgAAAAAAAAAAAAAAAAAAAEA="
+        );
+        assert_eq!(
+            EpAffine::generator().to_base64_url(),
+            "AAAAAO0wLZkb-UwJ_JhGIgAAAAAAAAAAAAAAAAAAAEA"
+        );
+
+        // URL-safe decoding also accepts padding.
+        assert_eq!(
+            Fp::from_base64_url("AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="),
+            Ok(one)
+        );
+    }
+
+    #[test]
+    fn test_base64_errors() {
+        // Standard decoding requires padding.
+        assert_eq!(
+            Fp::from_base64("AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"),
+            Err(ParseBase64Error::InvalidLength)
+        );
+        assert_eq!(Fp::from_base64(""), Err(ParseBase64Error::InvalidLength));
+        assert_eq!(
+            Fp::from_base64("AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=="),
+            Err(ParseBase64Error::InvalidLength)
+        );
+
+        // The alphabets are not interchangeable.
+        assert_eq!(
+            EpAffine::from_base64("AAAAAO0wLZkb-UwJ_JhGIgAAAAAAAAAAAAAAAAAAAEA="),
+            Err(ParseBase64Error::InvalidSymbol)
+        );
+        assert_eq!(
+            EpAffine::from_base64_url("AAAAAO0wLZkb+UwJ/JhGIgAAAAAAAAAAAAAAAAAAAEA"),
+            Err(ParseBase64Error::InvalidSymbol)
+        );
+
+        // The unused bits of the last symbol must be zero.
+        assert_eq!(
+            Fp::from_base64("AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAB="),
+            Err(ParseBase64Error::InvalidSymbol)
+        );
+
+        // Non-canonical field elements and invalid points are rejected.
+        let max = "__________________________________________8";
+        assert_eq!(
+            Fq::from_base64_url(max),
+            Err(ParseBase64Error::InvalidEncoding)
+        );
+        assert_eq!(
+            EqAffine::from_base64_url(max),
+            Err(ParseBase64Error::InvalidEncoding)
+        );
+    }
+}
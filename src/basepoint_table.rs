@@ -0,0 +1,186 @@
+//! This module implements precomputed tables for fixed-base scalar multiplication.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Mul;
+
+use ff::PrimeField;
+use group::{Curve, Group};
+use subtle::ConstantTimeEq;
+
+use crate::arithmetic::{CurveAffine, CurveExt};
+
+#[cfg(feature = "basepoint-table")]
+use group::prime::PrimeCurveAffine;
+#[cfg(feature = "basepoint-table")]
+use lazy_static::lazy_static;
+
+#[cfg(feature = "basepoint-table")]
+use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq};
+
+/// The number of bits of the scalar consumed by each window of a [`BasepointTable`].
+const WINDOW_BITS: usize = 4;
+
+/// The number of multiples of the base stored for each window.
+pub(crate) const WINDOW_SIZE: usize = 1 << WINDOW_BITS;
+
+/// A table of precomputed multiples of a fixed base point, for fast scalar
+/// multiplication by that base.
+///
+/// The scalar is split into 4-bit windows, and the table stores
+/// $[j \cdot 16^i] B$ for every window $i$ and $0 \leq j < 16$, so that scalar
+/// multiplication requires no point doublings. Each window lookup scans the whole
+/// window, and the selected multiples are accumulated with
+/// [`CurveExt::add_mixed_ct`](crate::arithmetic::CurveExt::add_mixed_ct), so
+/// multiplying by a secret scalar is constant time.
+///
+/// Tables implement [`SerdeObject`](crate::SerdeObject), and with the `serde`
+/// feature also `Serialize` and `Deserialize`, so that a table can be built once and
+/// stored alongside other parameters instead of being rebuilt by every process.
+/// Deserializing a table checks that every point is valid and that the table has
+/// the right number of windows, but not that the points are the multiples of a
+/// single base, so tables must only be read from trusted sources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasepointTable<C: CurveAffine> {
+    pub(crate) windows: Vec<[C; WINDOW_SIZE]>,
+}
+
+impl<C: CurveAffine> BasepointTable<C> {
+    /// The number of windows in a table.
+    pub(crate) const NUM_WINDOWS: usize =
+        (C::ScalarExt::NUM_BITS as usize + WINDOW_BITS - 1) / WINDOW_BITS;
+
+    /// Builds the table of multiples of `base`.
+    pub fn new(base: &C) -> Self {
+        let num_windows = Self::NUM_WINDOWS;
+
+        let mut multiples = Vec::with_capacity(num_windows * WINDOW_SIZE);
+        let mut window_base = base.to_curve();
+        for _ in 0..num_windows {
+            let mut acc = C::Curve::identity();
+            for _ in 0..WINDOW_SIZE {
+                multiples.push(acc);
+                acc += window_base;
+            }
+            // acc is now [16] window_base.
+            window_base = acc;
+        }
+
+        let mut affine = vec![C::identity(); multiples.len()];
+        C::Curve::batch_normalize(&multiples, &mut affine);
+
+        let windows = affine
+            .chunks(WINDOW_SIZE)
+            .map(|chunk| {
+                let mut window = [C::identity(); WINDOW_SIZE];
+                window.copy_from_slice(chunk);
+                window
+            })
+            .collect();
+
+        BasepointTable { windows }
+    }
+
+    /// Returns the base point of this table.
+    pub fn base(&self) -> C {
+        self.windows[0][1]
+    }
+}
+
+impl<'a, 'b, C: CurveAffine> Mul<&'b C::ScalarExt> for &'a BasepointTable<C> {
+    type Output = C::Curve;
+
+    fn mul(self, scalar: &'b C::ScalarExt) -> C::Curve {
+        let repr = scalar.to_repr();
+        let bytes = repr.as_ref();
+
+        let mut acc = C::Curve::identity();
+        for (i, window) in self.windows.iter().enumerate() {
+            let digit = (bytes[i / 2] >> ((i % 2) * WINDOW_BITS)) & 0xf;
+
+            let mut p = C::identity();
+            for (j, q) in window.iter().enumerate() {
+                p.conditional_assign(q, (j as u8).ct_eq(&digit));
+            }
+            acc = acc.add_mixed_ct(&p);
+        }
+
+        acc
+    }
+}
+
+#[cfg(feature = "basepoint-table")]
+lazy_static! {
+    static ref EP_GENERATOR_TABLE: BasepointTable<EpAffine> =
+        BasepointTable::new(&EpAffine::generator());
+    static ref EQ_GENERATOR_TABLE: BasepointTable<EqAffine> =
+        BasepointTable::new(&EqAffine::generator());
+}
+
+#[cfg(feature = "basepoint-table")]
+#[cfg_attr(docsrs, doc(cfg(feature = "basepoint-table")))]
+impl Ep {
+    /// Multiplies the Pallas generator by `scalar`, using a table of precomputed
+    /// multiples that is built on first use.
+    pub fn mul_by_generator(scalar: &Fq) -> Ep {
+        &*EP_GENERATOR_TABLE * scalar
+    }
+}
+
+#[cfg(feature = "basepoint-table")]
+#[cfg_attr(docsrs, doc(cfg(feature = "basepoint-table")))]
+impl Eq {
+    /// Multiplies the Vesta generator by `scalar`, using a table of precomputed
+    /// multiples that is built on first use.
+    pub fn mul_by_generator(scalar: &Fp) -> Eq {
+        &*EQ_GENERATOR_TABLE * scalar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ff::Field;
+    use group::prime::PrimeCurveAffine;
+
+    use crate::arithmetic::CurveExt;
+    use crate::{pallas, vesta};
+
+    fn check_table<C: CurveExt>() {
+        let mut rng = crate::tests::rng();
+
+        let base = C::random(&mut rng).to_affine();
+        let table = BasepointTable::new(&base);
+        assert_eq!(table.base(), base);
+
+        for _ in 0..10 {
+            let s = C::ScalarExt::random(&mut rng);
+            assert_eq!(&table * &s, base * s);
+        }
+        assert_eq!(&table * &C::ScalarExt::ZERO, C::identity());
+        assert_eq!(&table * &-C::ScalarExt::ONE, -base.to_curve());
+    }
+
+    #[test]
+    fn test_basepoint_table() {
+        check_table::<pallas::Point>();
+        check_table::<vesta::Point>();
+    }
+
+    #[cfg(feature = "basepoint-table")]
+    #[test]
+    fn test_mul_by_generator() {
+        let s = pallas::Scalar::from(0xdead_beef_u64);
+        assert_eq!(
+            pallas::Point::mul_by_generator(&s),
+            pallas::Point::generator() * s
+        );
+
+        let s = vesta::Scalar::from(0xdead_beef_u64);
+        assert_eq!(
+            vesta::Point::mul_by_generator(&s),
+            vesta::Point::generator() * s
+        );
+    }
+}
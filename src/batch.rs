@@ -0,0 +1,129 @@
+//! Batch verification of multi-scalar multiplication equations.
+//!
+//! A [`Verifier`] accumulates claimed equations $\sum_i [a_i] P_i = Q$ and checks
+//! all of them with a single multi-scalar multiplication. Each equation $j$ is
+//! multiplied by an independent random challenge $c_j$, and the batch is valid if
+//!
+//! $$\sum_j c_j \left(\sum_i [a_{i,j}] P_{i,j} - Q_j\right) = \mathcal{O}.$$
+//!
+//! If any of the equations is false, the batch is valid with probability at most
+//! $1/q$ over the choice of the challenges, where $q$ is the order of the scalar
+//! field. The challenges are only drawn by [`Verifier::verify`], after all the
+//! equations have been queued, so they must come from a cryptographically secure
+//! random number generator that the provers cannot predict.
+
+use alloc::vec::Vec;
+
+use ff::Field;
+use group::Group;
+use rand::RngCore;
+
+use crate::arithmetic::CurveAffine;
+use crate::msm::multi_scalar_mul;
+
+/// A batch of multi-scalar multiplication equations to verify together.
+#[derive(Clone, Debug)]
+pub struct Verifier<C: CurveAffine> {
+    bases: Vec<C>,
+    scalars: Vec<C::ScalarExt>,
+    /// The end of each equation's terms in `bases` and `scalars`.
+    ends: Vec<usize>,
+}
+
+impl<C: CurveAffine> Default for Verifier<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: CurveAffine> Verifier<C> {
+    /// Constructs an empty batch.
+    pub fn new() -> Self {
+        Verifier {
+            bases: Vec::new(),
+            scalars: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    /// Queues the equation $\sum_i [a_i] P_i = Q$, where `terms` yields the pairs
+    /// $(a_i, P_i)$.
+    pub fn queue<I: IntoIterator<Item = (C::ScalarExt, C)>>(&mut self, terms: I, q: C) {
+        for (a, p) in terms {
+            self.scalars.push(a);
+            self.bases.push(p);
+        }
+        self.scalars.push(-C::ScalarExt::ONE);
+        self.bases.push(q);
+        self.ends.push(self.bases.len());
+    }
+
+    /// Returns the number of queued equations.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if no equations are queued.
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Checks all the queued equations, with challenges drawn from `rng`.
+    ///
+    /// Returns `true` if every equation holds, including for an empty batch, and
+    /// `false` with overwhelming probability otherwise. This is not constant time.
+    pub fn verify(mut self, mut rng: impl RngCore) -> bool {
+        let mut start = 0;
+        for &end in &self.ends {
+            let c = C::ScalarExt::random(&mut rng);
+            for a in &mut self.scalars[start..end] {
+                *a *= c;
+            }
+            start = end;
+        }
+
+        bool::from(multi_scalar_mul(&self.bases, &self.scalars).is_identity())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ff::Field;
+    use group::{Curve, Group};
+
+    use super::Verifier;
+    use crate::{pallas, vesta};
+
+    #[test]
+    fn test_batch_verifier() {
+        let mut rng = crate::tests::rng();
+
+        let mut batch = Verifier::<pallas::Affine>::new();
+        assert!(batch.is_empty());
+        for n in 0..8 {
+            let terms: Vec<_> = (0..n)
+                .map(|_| {
+                    (
+                        pallas::Scalar::random(&mut rng),
+                        pallas::Point::random(&mut rng).to_affine(),
+                    )
+                })
+                .collect();
+            let q = terms
+                .iter()
+                .fold(pallas::Point::identity(), |acc, (a, p)| acc + p * a);
+            batch.queue(terms, q.to_affine());
+        }
+        assert_eq!(batch.len(), 8);
+        assert!(batch.clone().verify(&mut rng));
+
+        // A single false equation invalidates the batch.
+        let p = pallas::Point::random(&mut rng).to_affine();
+        batch.queue([(pallas::Scalar::one(), p)], (p + p).to_affine());
+        assert!(!batch.verify(&mut rng));
+
+        assert!(Verifier::<vesta::Affine>::default().verify(&mut rng));
+    }
+}
@@ -0,0 +1,151 @@
+//! Emits JSON test vectors for cross-validating other implementations of the
+//! Pasta curves.
+//!
+//! Usage: `cargo run --features gen-vectors --bin gen-vectors [count] > vectors.json`
+//!
+//! Field elements and scalars are encoded as `0x`-prefixed hex strings of their
+//! 32-byte little-endian encodings, and points as hex strings of their 32-byte
+//! compressed encodings (or, for `uncompressed`, of the 64-byte concatenation of
+//! their coordinates). The inputs are derived deterministically with BLAKE2b, so
+//! the output only changes if the crate's behaviour does.
+extern crate fil_pasta_curves as pasta_curves;
+
+use std::env;
+use std::fmt::Write;
+
+use pasta_curves::arithmetic::{CurveAffine, CurveExt, FieldExt};
+use pasta_curves::group::ff::{Field, PrimeField};
+use pasta_curves::group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+use pasta_curves::{pallas, vesta};
+
+/// The domain prefix used to hash the base points of the scalar multiplication
+/// vectors, and the hash-to-curve domain prefix of the hash-to-curve vectors.
+const DOMAIN: &str = "fil_pasta_curves:vectors";
+
+fn hex(bytes: &[u8]) -> String {
+    let mut s = String::from("\"0x");
+    for byte in bytes {
+        write!(s, "{:02x}", byte).unwrap();
+    }
+    s.push('"');
+    s
+}
+
+fn hex_option<T: AsRef<[u8]>>(value: Option<T>) -> String {
+    value.map_or_else(|| "null".into(), |v| hex(v.as_ref()))
+}
+
+/// Derives the `i`-th input element of kind `label`.
+fn derive<F: FieldExt>(label: &str, i: usize) -> F {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(64)
+        .to_state()
+        .update(DOMAIN.as_bytes())
+        .update(label.as_bytes())
+        .update(&(i as u64).to_le_bytes())
+        .finalize();
+    F::from_bytes_wide(hash.as_array())
+}
+
+fn field_vectors<F: FieldExt>(label: &str, count: usize) -> String {
+    let mut cases = vec![];
+    for i in 0..count {
+        // Start with the edge cases.
+        let (a, b) = match i {
+            0 => (F::ZERO, F::ONE),
+            1 => (-F::ONE, -F::ONE),
+            _ => (derive::<F>(label, 2 * i), derive::<F>(label, 2 * i + 1)),
+        };
+        cases.push(format!(
+            "{{\"a\": {}, \"b\": {}, \"add\": {}, \"sub\": {}, \"mul\": {}, \"square\": {}, \
+             \"invert\": {}, \"sqrt\": {}}}",
+            hex(a.to_repr().as_ref()),
+            hex(b.to_repr().as_ref()),
+            hex((a + b).to_repr().as_ref()),
+            hex((a - b).to_repr().as_ref()),
+            hex((a * b).to_repr().as_ref()),
+            hex(a.square().to_repr().as_ref()),
+            hex_option(Option::from(a.invert()).map(|x: F| x.to_repr())),
+            hex_option(Option::from(a.sqrt()).map(|x: F| x.to_repr())),
+        ));
+    }
+    format!("[\n    {}\n  ]", cases.join(",\n    "))
+}
+
+fn curve_vectors<C: CurveAffine + UncompressedEncoding>(label: &str, count: usize) -> String {
+    let hasher = C::CurveExt::hash_to_curve(DOMAIN);
+
+    let mut scalar_mul = vec![];
+    let mut hash_to_curve = vec![];
+    let mut encodings = vec![];
+    for i in 0..count {
+        let point = hasher(format!("{} base {}", label, i).as_bytes());
+        let scalar = match i {
+            0 => C::ScalarExt::ZERO,
+            1 => -C::ScalarExt::ONE,
+            _ => derive::<C::ScalarExt>(label, i),
+        };
+        scalar_mul.push(format!(
+            "{{\"point\": {}, \"scalar\": {}, \"result\": {}}}",
+            hex(point.to_bytes().as_ref()),
+            hex(scalar.to_repr().as_ref()),
+            hex((point * scalar).to_bytes().as_ref()),
+        ));
+
+        let message = format!("message {}", i);
+        hash_to_curve.push(format!(
+            "{{\"domain\": \"{}\", \"message\": {}, \"point\": {}}}",
+            DOMAIN,
+            hex(message.as_bytes()),
+            hex(hasher(message.as_bytes()).to_bytes().as_ref()),
+        ));
+
+        let affine = match i {
+            0 => C::identity(),
+            1 => C::generator(),
+            _ => {
+                let k = derive::<C::ScalarExt>(label, count + i);
+                (C::CurveExt::generator() * k).to_affine()
+            }
+        };
+        encodings.push(format!(
+            "{{\"compressed\": {}, \"uncompressed\": {}}}",
+            hex(affine.to_bytes().as_ref()),
+            hex(affine.to_uncompressed().as_ref()),
+        ));
+    }
+
+    format!(
+        "{{\n    \"scalar_mul\": [\n      {}\n    ],\n    \"hash_to_curve\": [\n      {}\n    ],\n    \
+         \"encodings\": [\n      {}\n    ]\n  }}",
+        scalar_mul.join(",\n      "),
+        hash_to_curve.join(",\n      "),
+        encodings.join(",\n      "),
+    )
+}
+
+fn main() {
+    let count = match env::args().nth(1) {
+        Some(arg) => arg.parse().expect("count must be a number"),
+        None => 8,
+    };
+
+    println!("{{");
+    println!(
+        "  \"fp\": {},",
+        field_vectors::<pasta_curves::Fp>("fp", count)
+    );
+    println!(
+        "  \"fq\": {},",
+        field_vectors::<pasta_curves::Fq>("fq", count)
+    );
+    println!(
+        "  \"pallas\": {},",
+        curve_vectors::<pallas::Affine>("pallas", count)
+    );
+    println!(
+        "  \"vesta\": {}",
+        curve_vectors::<vesta::Affine>("vesta", count)
+    );
+    println!("}}");
+}
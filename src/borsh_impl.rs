@@ -0,0 +1,149 @@
+use borsh::{
+    maybestd::io::{Error, ErrorKind, Result, Write},
+    BorshDeserialize, BorshSerialize,
+};
+use ff::PrimeField;
+use group::GroupEncoding;
+
+use crate::{
+    curves::{EpAffine, EqAffine},
+    fields::{Fp, Fq},
+};
+
+/// Reads 32 bytes from the front of `buf`, advancing it past them.
+fn read_bytes(buf: &mut &[u8]) -> Result<[u8; 32]> {
+    if buf.len() < 32 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "unexpected length of input",
+        ));
+    }
+
+    let mut bytes = [0; 32];
+    bytes.copy_from_slice(&buf[..32]);
+    *buf = &buf[32..];
+    Ok(bytes)
+}
+
+impl BorshSerialize for Fp {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_repr())
+    }
+}
+
+impl BorshDeserialize for Fp {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let bytes = read_bytes(buf)?;
+        Option::from(Fp::from_repr(bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Pallas field element",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for Fq {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_repr())
+    }
+}
+
+impl BorshDeserialize for Fq {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let bytes = read_bytes(buf)?;
+        Option::from(Fq::from_repr(bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Vesta field element",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for EpAffine {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+impl BorshDeserialize for EpAffine {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let bytes = read_bytes(buf)?;
+        Option::from(EpAffine::from_bytes(&bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Pallas curve point",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for EqAffine {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+impl BorshDeserialize for EqAffine {
+    fn deserialize(buf: &mut &[u8]) -> Result<Self> {
+        let bytes = read_bytes(buf)?;
+        Option::from(EqAffine::from_bytes(&bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Vesta curve point",
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::fmt::Debug;
+
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+
+    use crate::curves::{Ep, Eq};
+
+    fn test_roundtrip<T: BorshSerialize + BorshDeserialize + Debug + PartialEq>(t: &T) {
+        let serialized = t.try_to_vec().unwrap();
+        assert_eq!(serialized.len(), 32);
+        assert_eq!(*t, T::try_from_slice(&serialized).unwrap());
+    }
+
+    #[test]
+    fn borsh_roundtrip() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..100 {
+            test_roundtrip(&Fp::random(&mut rng));
+            test_roundtrip(&Fq::random(&mut rng));
+            test_roundtrip(&Ep::random(&mut rng).to_affine());
+            test_roundtrip(&Eq::random(&mut rng).to_affine());
+        }
+        test_roundtrip(&EpAffine::identity());
+        test_roundtrip(&EqAffine::identity());
+
+        assert_eq!(
+            EpAffine::generator().try_to_vec().unwrap(),
+            EpAffine::generator().to_bytes()
+        );
+    }
+
+    #[test]
+    fn borsh_invalid() {
+        // Truncated input.
+        assert!(Fp::try_from_slice(&[0; 31]).is_err());
+        // Non-canonical field elements.
+        assert!(Fp::try_from_slice(&[0xff; 32]).is_err());
+        assert!(Fq::try_from_slice(&[0xff; 32]).is_err());
+        // x = 2 is not the x-coordinate of a point on either curve.
+        let mut bytes = [0; 32];
+        bytes[0] = 2;
+        assert!(EpAffine::try_from_slice(&bytes).is_err());
+        assert!(EqAffine::try_from_slice(&bytes).is_err());
+    }
+}
@@ -0,0 +1,733 @@
+//! C bindings, declared in the `include/pasta_curves.h` header generated by
+//! `cbindgen`.
+//!
+//! Field elements and scalars are passed as their 32-byte little-endian
+//! encodings, and points as their 32-byte compressed encodings, or as the 64-byte
+//! concatenation of the little-endian encodings of their coordinates for the
+//! uncompressed encoding. Every function writes its result to `out` and returns
+//! [`PASTA_OK`], or returns an error code and leaves `out` unchanged.
+//!
+//! Static and dynamic libraries also need the allocator and panic handler of
+//! `std`. To build one, run
+//! `cargo rustc --release --features capi,std --crate-type staticlib` (or `cdylib`).
+
+use alloc::vec::Vec;
+use core::{ptr, slice, str};
+
+use ff::{Field, PrimeField};
+use group::{prime::PrimeCurveAffine, Curve, GroupEncoding, UncompressedEncoding};
+
+use crate::arithmetic::{CurveAffine, CurveExt};
+use crate::{EpAffine, EqAffine, Fp, Fq};
+
+/// The operation succeeded.
+pub const PASTA_OK: i32 = 0;
+
+/// An input is not a valid encoding of a field element or a point, a domain
+/// prefix is not valid UTF-8 or is too long, or the byte length of an input
+/// overflows `size_t`.
+pub const PASTA_ERR_INVALID_ENCODING: i32 = 1;
+
+/// The operation has no result, such as the inverse of zero or the square root of
+/// a non-square.
+pub const PASTA_ERR_NO_RESULT: i32 = 2;
+
+/// Reads 32 bytes from `ptr`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of 32 bytes.
+unsafe fn read_32(ptr: *const u8) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    ptr::copy_nonoverlapping(ptr, bytes.as_mut_ptr(), 32);
+    bytes
+}
+
+/// Writes `result` to `out` if it is `Ok`, returning the status code.
+///
+/// # Safety
+///
+/// `out` must be valid for writes of the length of the result.
+unsafe fn write<T: AsRef<[u8]>>(out: *mut u8, result: Result<T, i32>) -> i32 {
+    match result {
+        Ok(bytes) => {
+            let bytes = bytes.as_ref();
+            // `out` may be null for empty results.
+            if !bytes.is_empty() {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+            }
+            PASTA_OK
+        }
+        Err(code) => code,
+    }
+}
+
+/// Returns the `len` bytes at `ptr`, which may be null if `len` is zero.
+///
+/// # Safety
+///
+/// If `len` is nonzero, `ptr` must be valid for reads of `len` bytes.
+unsafe fn read_slice<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// Returns `count * size`, failing with [`PASTA_ERR_INVALID_ENCODING`] if it
+/// overflows.
+fn byte_len(count: usize, size: usize) -> Result<usize, i32> {
+    count.checked_mul(size).ok_or(PASTA_ERR_INVALID_ENCODING)
+}
+
+fn read_field<F: PrimeField<Repr = [u8; 32]>>(bytes: [u8; 32]) -> Result<F, i32> {
+    Option::from(F::from_repr(bytes)).ok_or(PASTA_ERR_INVALID_ENCODING)
+}
+
+fn read_point<C: CurveAffine + GroupEncoding<Repr = [u8; 32]>>(bytes: [u8; 32]) -> Result<C, i32> {
+    Option::from(C::from_bytes(&bytes)).ok_or(PASTA_ERR_INVALID_ENCODING)
+}
+
+fn compress<C>(uncompressed: &[u8]) -> Result<[u8; 32], i32>
+where
+    C: CurveAffine + GroupEncoding<Repr = [u8; 32]> + UncompressedEncoding,
+{
+    let mut repr = C::Uncompressed::default();
+    repr.as_mut().copy_from_slice(uncompressed);
+    Option::from(C::from_uncompressed(&repr))
+        .map(|p: C| p.to_bytes())
+        .ok_or(PASTA_ERR_INVALID_ENCODING)
+}
+
+fn read_points<C: CurveAffine + GroupEncoding<Repr = [u8; 32]>>(
+    bytes: &[u8],
+) -> Result<Vec<C>, i32> {
+    bytes
+        .chunks(32)
+        .map(|chunk| {
+            let mut repr = [0; 32];
+            repr.copy_from_slice(chunk);
+            read_point(repr)
+        })
+        .collect()
+}
+
+fn msm<C: CurveAffine + GroupEncoding<Repr = [u8; 32]>>(
+    points: &[u8],
+    scalars: &[u8],
+) -> Result<[u8; 32], i32>
+where
+    C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+{
+    let bases = read_points::<C>(points)?;
+    let scalars = scalars
+        .chunks(32)
+        .map(|chunk| {
+            let mut repr = [0; 32];
+            repr.copy_from_slice(chunk);
+            read_field(repr)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(crate::msm::multi_scalar_mul(&bases, &scalars)
+        .to_affine()
+        .to_bytes())
+}
+
+fn batch_to_affine<C>(points: &[u8]) -> Result<Vec<u8>, i32>
+where
+    C: CurveAffine + UncompressedEncoding,
+    <C::CurveExt as CurveExt>::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let points = points
+        .chunks(96)
+        .map(|chunk| {
+            let mut coordinates = [[0; 32]; 3];
+            for (c, bytes) in coordinates.iter_mut().zip(chunk.chunks(32)) {
+                c.copy_from_slice(bytes);
+            }
+            let [x, y, z] = coordinates;
+            let (x, y, z) = (read_field(x)?, read_field(y)?, read_field(z)?);
+            Option::from(C::CurveExt::new_jacobian(x, y, z)).ok_or(PASTA_ERR_INVALID_ENCODING)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut affine = alloc::vec![C::identity(); points.len()];
+    C::CurveExt::batch_normalize(&points, &mut affine);
+    Ok(affine
+        .iter()
+        .flat_map(|p| p.to_uncompressed().as_ref().to_vec())
+        .collect())
+}
+
+fn batch_decompress<C>(points: &[u8]) -> Result<Vec<u8>, i32>
+where
+    C: CurveAffine + GroupEncoding<Repr = [u8; 32]> + UncompressedEncoding,
+{
+    Ok(read_points::<C>(points)?
+        .iter()
+        .flat_map(|p| p.to_uncompressed().as_ref().to_vec())
+        .collect())
+}
+
+fn hash_to_curve<C: CurveAffine + GroupEncoding<Repr = [u8; 32]>>(
+    domain_prefix: &[u8],
+    message: &[u8],
+) -> Result<[u8; 32], i32> {
+    let domain_prefix = str::from_utf8(domain_prefix).map_err(|_| PASTA_ERR_INVALID_ENCODING)?;
+    // The domain separation tag of `expand_message_xmd` must fit in 255 bytes,
+    // which `hash_to_curve` asserts.
+    if 22 + C::CurveExt::CURVE_ID.len() + domain_prefix.len() >= 256 {
+        return Err(PASTA_ERR_INVALID_ENCODING);
+    }
+    let hasher = C::CurveExt::hash_to_curve(domain_prefix);
+    Ok(hasher(message).to_affine().to_bytes())
+}
+
+macro_rules! field_capi {
+    (
+        $field:ident,
+        $add:ident,
+        $sub:ident,
+        $mul:ident,
+        $square:ident,
+        $neg:ident,
+        $invert:ident,
+        $sqrt:ident
+    ) => {
+        #[doc = concat!("Computes `a + b` in `", stringify!($field), "`.")]
+        ///
+        /// # Safety
+        ///
+        /// `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+        /// 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $add(out: *mut u8, a: *const u8, b: *const u8) -> i32 {
+            let result = read_field::<$field>(read_32(a))
+                .and_then(|a| Ok(a + read_field::<$field>(read_32(b))?))
+                .map(|c| c.to_repr());
+            write(out, result)
+        }
+
+        #[doc = concat!("Computes `a - b` in `", stringify!($field), "`.")]
+        ///
+        /// # Safety
+        ///
+        /// `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+        /// 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $sub(out: *mut u8, a: *const u8, b: *const u8) -> i32 {
+            let result = read_field::<$field>(read_32(a))
+                .and_then(|a| Ok(a - read_field::<$field>(read_32(b))?))
+                .map(|c| c.to_repr());
+            write(out, result)
+        }
+
+        #[doc = concat!("Computes `a * b` in `", stringify!($field), "`.")]
+        ///
+        /// # Safety
+        ///
+        /// `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+        /// 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $mul(out: *mut u8, a: *const u8, b: *const u8) -> i32 {
+            let result = read_field::<$field>(read_32(a))
+                .and_then(|a| Ok(a * read_field::<$field>(read_32(b))?))
+                .map(|c| c.to_repr());
+            write(out, result)
+        }
+
+        #[doc = concat!("Computes `a^2` in `", stringify!($field), "`.")]
+        ///
+        /// # Safety
+        ///
+        /// `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $square(out: *mut u8, a: *const u8) -> i32 {
+            let result = read_field::<$field>(read_32(a)).map(|a| a.square().to_repr());
+            write(out, result)
+        }
+
+        #[doc = concat!("Computes `-a` in `", stringify!($field), "`.")]
+        ///
+        /// # Safety
+        ///
+        /// `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $neg(out: *mut u8, a: *const u8) -> i32 {
+            let result = read_field::<$field>(read_32(a)).map(|a| (-a).to_repr());
+            write(out, result)
+        }
+
+        #[doc = concat!("Computes `1 / a` in `", stringify!($field), "`.")]
+        ///
+        /// Fails with `PASTA_ERR_NO_RESULT` if `a` is zero.
+        ///
+        /// # Safety
+        ///
+        /// `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $invert(out: *mut u8, a: *const u8) -> i32 {
+            let result = read_field::<$field>(read_32(a)).and_then(|a| {
+                Option::from(a.invert())
+                    .map(|a: $field| a.to_repr())
+                    .ok_or(PASTA_ERR_NO_RESULT)
+            });
+            write(out, result)
+        }
+
+        #[doc = concat!("Computes a square root of `a` in `", stringify!($field), "`.")]
+        ///
+        /// Fails with `PASTA_ERR_NO_RESULT` if `a` is not a square.
+        ///
+        /// # Safety
+        ///
+        /// `a` must be valid for reads of 32 bytes and `out` for writes of 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $sqrt(out: *mut u8, a: *const u8) -> i32 {
+            let result = read_field::<$field>(read_32(a)).and_then(|a| {
+                Option::from(a.sqrt())
+                    .map(|a: $field| a.to_repr())
+                    .ok_or(PASTA_ERR_NO_RESULT)
+            });
+            write(out, result)
+        }
+    };
+}
+
+field_capi!(
+    Fp,
+    pasta_fp_add,
+    pasta_fp_sub,
+    pasta_fp_mul,
+    pasta_fp_square,
+    pasta_fp_neg,
+    pasta_fp_invert,
+    pasta_fp_sqrt
+);
+field_capi!(
+    Fq,
+    pasta_fq_add,
+    pasta_fq_sub,
+    pasta_fq_mul,
+    pasta_fq_square,
+    pasta_fq_neg,
+    pasta_fq_invert,
+    pasta_fq_sqrt
+);
+
+macro_rules! curve_capi {
+    (
+        $name:expr,
+        $affine:ident,
+        $generator:ident,
+        $add:ident,
+        $scalar_mul:ident,
+        $compress:ident,
+        $decompress:ident,
+        $hash_to_curve:ident,
+        $msm:ident,
+        $batch_to_affine:ident,
+        $batch_decompress:ident
+    ) => {
+        #[doc = concat!("Writes the compressed encoding of the ", $name, " generator.")]
+        ///
+        /// # Safety
+        ///
+        /// `out` must be valid for writes of 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $generator(out: *mut u8) -> i32 {
+            write(out, Ok($affine::generator().to_bytes()))
+        }
+
+        #[doc = concat!("Computes the sum of the ", $name, " points `a` and `b`.")]
+        ///
+        /// # Safety
+        ///
+        /// `a` and `b` must be valid for reads of 32 bytes and `out` for writes of
+        /// 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $add(out: *mut u8, a: *const u8, b: *const u8) -> i32 {
+            let result = read_point::<$affine>(read_32(a))
+                .and_then(|a| Ok(a + read_point::<$affine>(read_32(b))?))
+                .map(|c| c.to_affine().to_bytes());
+            write(out, result)
+        }
+
+        #[doc = concat!("Multiplies the ", $name, " point `point` by `scalar`.")]
+        ///
+        /// # Safety
+        ///
+        /// `point` and `scalar` must be valid for reads of 32 bytes and `out` for
+        /// writes of 32 bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $scalar_mul(
+            out: *mut u8,
+            point: *const u8,
+            scalar: *const u8,
+        ) -> i32 {
+            let result = read_point::<$affine>(read_32(point))
+                .and_then(|p| {
+                    let s = read_field::<<$affine as CurveAffine>::ScalarExt>(read_32(scalar))?;
+                    Ok(p * s)
+                })
+                .map(|p| p.to_affine().to_bytes());
+            write(out, result)
+        }
+
+        #[doc = concat!("Compresses the uncompressed encoding of a ", $name, " point.")]
+        ///
+        /// # Safety
+        ///
+        /// `point` must be valid for reads of 64 bytes and `out` for writes of 32
+        /// bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $compress(out: *mut u8, point: *const u8) -> i32 {
+            write(out, compress::<$affine>(read_slice(point, 64)))
+        }
+
+        #[doc = concat!("Decompresses the compressed encoding of a ", $name, " point.")]
+        ///
+        /// # Safety
+        ///
+        /// `point` must be valid for reads of 32 bytes and `out` for writes of 64
+        /// bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $decompress(out: *mut u8, point: *const u8) -> i32 {
+            let result = read_point::<$affine>(read_32(point)).map(|p| p.to_uncompressed());
+            write(out, result)
+        }
+
+        #[doc = concat!("Hashes `message` to a ", $name, " point with a UTF-8 domain prefix.")]
+        ///
+        /// Fails with `PASTA_ERR_INVALID_ENCODING` if the domain prefix is not valid
+        /// UTF-8, or is too long: together with the curve name and the 22 bytes that
+        /// `hash_to_curve` appends, it must be shorter than 256 bytes.
+        ///
+        /// # Safety
+        ///
+        /// `domain_prefix` must be valid for reads of `domain_prefix_len` bytes,
+        /// `message` for reads of `message_len` bytes, and `out` for writes of 32
+        /// bytes. `domain_prefix` and `message` may be null if their lengths are
+        /// zero.
+        #[no_mangle]
+        pub unsafe extern "C" fn $hash_to_curve(
+            out: *mut u8,
+            domain_prefix: *const u8,
+            domain_prefix_len: usize,
+            message: *const u8,
+            message_len: usize,
+        ) -> i32 {
+            let result = hash_to_curve::<$affine>(
+                read_slice(domain_prefix, domain_prefix_len),
+                read_slice(message, message_len),
+            );
+            write(out, result)
+        }
+
+        #[doc = concat!("Computes the multi-scalar multiplication of `len` ", $name, " points and")]
+        /// scalars, given as the concatenations of their encodings.
+        ///
+        /// This is not constant time with respect to the scalars.
+        ///
+        /// # Safety
+        ///
+        /// `points` and `scalars` must be valid for reads of `32 * len` bytes and `out`
+        /// for writes of 32 bytes. `points` and `scalars` may be null if `len` is zero.
+        #[no_mangle]
+        pub unsafe extern "C" fn $msm(
+            out: *mut u8,
+            points: *const u8,
+            scalars: *const u8,
+            len: usize,
+        ) -> i32 {
+            let result = byte_len(len, 32)
+                .and_then(|n| msm::<$affine>(read_slice(points, n), read_slice(scalars, n)));
+            write(out, result)
+        }
+
+        #[doc = concat!("Converts `len` ", $name, " points from Jacobian coordinates to the")]
+        /// concatenation of their uncompressed encodings, with a single field
+        /// inversion.
+        ///
+        /// Each point is given as the 96-byte concatenation of the little-endian
+        /// encodings of its Jacobian coordinates $(X, Y, Z)$, and must be on the curve.
+        ///
+        /// # Safety
+        ///
+        /// `points` must be valid for reads of `96 * len` bytes and `out` for writes of
+        /// `64 * len` bytes. `points` may be null if `len` is zero.
+        #[no_mangle]
+        pub unsafe extern "C" fn $batch_to_affine(
+            out: *mut u8,
+            points: *const u8,
+            len: usize,
+        ) -> i32 {
+            let result =
+                byte_len(len, 96).and_then(|n| batch_to_affine::<$affine>(read_slice(points, n)));
+            write(out, result)
+        }
+
+        #[doc = concat!("Decompresses the compressed encodings of `len` ", $name, " points.")]
+        ///
+        /// # Safety
+        ///
+        /// `points` must be valid for reads of `32 * len` bytes and `out` for writes of
+        /// `64 * len` bytes. `points` may be null if `len` is zero.
+        #[no_mangle]
+        pub unsafe extern "C" fn $batch_decompress(
+            out: *mut u8,
+            points: *const u8,
+            len: usize,
+        ) -> i32 {
+            let result =
+                byte_len(len, 32).and_then(|n| batch_decompress::<$affine>(read_slice(points, n)));
+            write(out, result)
+        }
+    };
+}
+
+curve_capi!(
+    "Pallas",
+    EpAffine,
+    pasta_pallas_generator,
+    pasta_pallas_add,
+    pasta_pallas_scalar_mul,
+    pasta_pallas_compress,
+    pasta_pallas_decompress,
+    pasta_pallas_hash_to_curve,
+    pasta_pallas_msm,
+    pasta_pallas_batch_to_affine,
+    pasta_pallas_batch_decompress
+);
+curve_capi!(
+    "Vesta",
+    EqAffine,
+    pasta_vesta_generator,
+    pasta_vesta_add,
+    pasta_vesta_scalar_mul,
+    pasta_vesta_compress,
+    pasta_vesta_decompress,
+    pasta_vesta_hash_to_curve,
+    pasta_vesta_msm,
+    pasta_vesta_batch_to_affine,
+    pasta_vesta_batch_decompress
+);
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+
+    use ff::{Field, PrimeField};
+    use group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding, UncompressedEncoding};
+
+    use super::*;
+    use crate::arithmetic::CurveExt;
+    use crate::pallas;
+
+    #[test]
+    fn test_fields() {
+        let a = Fp::from(7u64);
+        let b = -Fp::from(3u64);
+        let mut out = [0; 32];
+        unsafe {
+            assert_eq!(
+                pasta_fp_add(out.as_mut_ptr(), a.to_repr().as_ptr(), b.to_repr().as_ptr()),
+                PASTA_OK
+            );
+            assert_eq!(out, (a + b).to_repr());
+            pasta_fp_sub(out.as_mut_ptr(), a.to_repr().as_ptr(), b.to_repr().as_ptr());
+            assert_eq!(out, (a - b).to_repr());
+            pasta_fp_mul(out.as_mut_ptr(), a.to_repr().as_ptr(), b.to_repr().as_ptr());
+            assert_eq!(out, (a * b).to_repr());
+            pasta_fp_square(out.as_mut_ptr(), a.to_repr().as_ptr());
+            assert_eq!(out, a.square().to_repr());
+            pasta_fp_neg(out.as_mut_ptr(), a.to_repr().as_ptr());
+            assert_eq!(out, (-a).to_repr());
+            pasta_fp_invert(out.as_mut_ptr(), a.to_repr().as_ptr());
+            assert_eq!(out, a.invert().unwrap().to_repr());
+            assert_eq!(
+                pasta_fq_sqrt(out.as_mut_ptr(), Fq::from(4u64).to_repr().as_ptr()),
+                PASTA_OK
+            );
+            assert_eq!(Fq::from_repr(out).unwrap().square(), Fq::from(4u64));
+
+            // Errors leave the output unchanged.
+            let before = out;
+            assert_eq!(
+                pasta_fp_invert(out.as_mut_ptr(), Fp::zero().to_repr().as_ptr()),
+                PASTA_ERR_NO_RESULT
+            );
+            assert_eq!(
+                pasta_fq_sqrt(out.as_mut_ptr(), Fq::from(5u64).to_repr().as_ptr()),
+                PASTA_ERR_NO_RESULT
+            );
+            assert_eq!(
+                pasta_fq_add(out.as_mut_ptr(), [0xff; 32].as_ptr(), [0; 32].as_ptr()),
+                PASTA_ERR_INVALID_ENCODING
+            );
+            assert_eq!(out, before);
+        }
+    }
+
+    #[test]
+    fn test_curves() {
+        let g = pallas::Affine::generator();
+        let s = pallas::Scalar::from(5u64);
+        let mut out = [0; 32];
+        let mut uncompressed = [0; 64];
+        unsafe {
+            pasta_pallas_generator(out.as_mut_ptr());
+            assert_eq!(out, g.to_bytes());
+            pasta_pallas_add(
+                out.as_mut_ptr(),
+                g.to_bytes().as_ptr(),
+                g.to_bytes().as_ptr(),
+            );
+            assert_eq!(out, (g + g).to_affine().to_bytes());
+            pasta_pallas_scalar_mul(
+                out.as_mut_ptr(),
+                g.to_bytes().as_ptr(),
+                s.to_repr().as_ptr(),
+            );
+            assert_eq!(out, (g * s).to_affine().to_bytes());
+
+            pasta_pallas_decompress(uncompressed.as_mut_ptr(), g.to_bytes().as_ptr());
+            assert_eq!(&uncompressed[..], g.to_uncompressed().as_ref());
+            pasta_pallas_compress(out.as_mut_ptr(), uncompressed.as_ptr());
+            assert_eq!(out, g.to_bytes());
+
+            let domain = b"z.cash:test";
+            assert_eq!(
+                pasta_vesta_hash_to_curve(
+                    out.as_mut_ptr(),
+                    domain.as_ptr(),
+                    domain.len(),
+                    ptr::null(),
+                    0
+                ),
+                PASTA_OK
+            );
+            assert_eq!(
+                out,
+                crate::vesta::Point::hash_to_curve("z.cash:test")(b"")
+                    .to_affine()
+                    .to_bytes()
+            );
+
+            assert_eq!(
+                pasta_pallas_decompress(uncompressed.as_mut_ptr(), [0xff; 32].as_ptr()),
+                PASTA_ERR_INVALID_ENCODING
+            );
+            assert_eq!(
+                pasta_pallas_hash_to_curve(out.as_mut_ptr(), [0xff].as_ptr(), 1, ptr::null(), 0),
+                PASTA_ERR_INVALID_ENCODING
+            );
+
+            // "pallas" leaves room for a domain prefix of at most 227 bytes.
+            let domain = [b'a'; 228];
+            assert_eq!(
+                pasta_pallas_hash_to_curve(out.as_mut_ptr(), domain.as_ptr(), 227, ptr::null(), 0),
+                PASTA_OK
+            );
+            assert_eq!(
+                pasta_pallas_hash_to_curve(out.as_mut_ptr(), domain.as_ptr(), 228, ptr::null(), 0),
+                PASTA_ERR_INVALID_ENCODING
+            );
+        }
+    }
+
+    #[test]
+    fn test_bulk() {
+        let g = pallas::Point::generator();
+        let points = [
+            g,
+            g.double(),
+            g * pallas::Scalar::from(3u64),
+            pallas::Point::identity(),
+        ];
+        let scalars = [2u64, 3, 4, 5].map(pallas::Scalar::from);
+        let mut affine = [pallas::Affine::identity(); 4];
+        pallas::Point::batch_normalize(&points, &mut affine);
+
+        let compressed: Vec<u8> = affine.iter().flat_map(|p| p.to_bytes()).collect();
+        let repr: Vec<u8> = scalars.iter().flat_map(|s| s.to_repr()).collect();
+        let uncompressed: Vec<u8> = affine
+            .iter()
+            .flat_map(|p| p.to_uncompressed().as_ref().to_vec())
+            .collect();
+        let jacobian: Vec<u8> = points
+            .iter()
+            .flat_map(|p| {
+                let (x, y, z) = p.jacobian_coordinates();
+                [x.to_repr(), y.to_repr(), z.to_repr()].concat()
+            })
+            .collect();
+
+        let mut out = [0; 32];
+        let mut out_bulk = [0; 4 * 64];
+        unsafe {
+            assert_eq!(
+                pasta_pallas_msm(out.as_mut_ptr(), compressed.as_ptr(), repr.as_ptr(), 4),
+                PASTA_OK
+            );
+            assert_eq!(
+                out,
+                (points[0] * scalars[0] + points[1] * scalars[1] + points[2] * scalars[2])
+                    .to_affine()
+                    .to_bytes()
+            );
+            pasta_pallas_msm(out.as_mut_ptr(), ptr::null(), ptr::null(), 0);
+            assert_eq!(out, pallas::Affine::identity().to_bytes());
+
+            assert_eq!(
+                pasta_pallas_batch_to_affine(out_bulk.as_mut_ptr(), jacobian.as_ptr(), 4),
+                PASTA_OK
+            );
+            assert_eq!(&out_bulk[..], &uncompressed[..]);
+            out_bulk = [0; 4 * 64];
+            assert_eq!(
+                pasta_pallas_batch_decompress(out_bulk.as_mut_ptr(), compressed.as_ptr(), 4),
+                PASTA_OK
+            );
+            assert_eq!(&out_bulk[..], &uncompressed[..]);
+
+            // Invalid points anywhere in the input are rejected.
+            let mut invalid = compressed;
+            invalid[64..96].copy_from_slice(&[0xff; 32]);
+            assert_eq!(
+                pasta_pallas_batch_decompress(out_bulk.as_mut_ptr(), invalid.as_ptr(), 4),
+                PASTA_ERR_INVALID_ENCODING
+            );
+
+            // Lengths whose byte counts overflow are rejected before reading.
+            let len = usize::MAX / 32 + 1;
+            assert_eq!(
+                pasta_pallas_msm(out.as_mut_ptr(), ptr::null(), ptr::null(), len),
+                PASTA_ERR_INVALID_ENCODING
+            );
+            assert_eq!(
+                pasta_pallas_batch_to_affine(
+                    out_bulk.as_mut_ptr(),
+                    ptr::null(),
+                    usize::MAX / 96 + 1
+                ),
+                PASTA_ERR_INVALID_ENCODING
+            );
+            assert_eq!(
+                pasta_pallas_batch_decompress(out_bulk.as_mut_ptr(), ptr::null(), len),
+                PASTA_ERR_INVALID_ENCODING
+            );
+            assert_eq!(
+                pasta_pallas_msm(out.as_mut_ptr(), invalid.as_ptr(), repr.as_ptr(), 4),
+                PASTA_ERR_INVALID_ENCODING
+            );
+            let mut off_curve = jacobian;
+            off_curve[0] ^= 1;
+            assert_eq!(
+                pasta_pallas_batch_to_affine(out_bulk.as_mut_ptr(), off_curve.as_ptr(), 4),
+                PASTA_ERR_INVALID_ENCODING
+            );
+        }
+    }
+}
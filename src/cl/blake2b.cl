@@ -0,0 +1,131 @@
+// BLAKE2b with 64-byte digests and no key, salt or personalization, which is the
+// hash function of `expand_message_xmd` in `pasta_curves::hashtocurve`. The input
+// is absorbed one byte at a time, as the hash-to-curve kernels are dominated by
+// field arithmetic rather than hashing.
+
+#ifdef __CUDACC__
+typedef unsigned long long blake2b_word;
+#define BLAKE2B_CONSTANT __constant__
+#else
+typedef ulong blake2b_word;
+#define BLAKE2B_CONSTANT __constant
+#endif
+
+BLAKE2B_CONSTANT blake2b_word BLAKE2B_IV[8] = {
+  0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+  0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179
+};
+
+// The message schedule for each of the 12 rounds.
+BLAKE2B_CONSTANT uint BLAKE2B_SIGMA[12][16] = {
+  {0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15},
+  {14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3},
+  {11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4},
+  {7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8},
+  {9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13},
+  {2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9},
+  {12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11},
+  {13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10},
+  {6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5},
+  {10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0},
+  {0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15},
+  {14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3}
+};
+
+typedef struct {
+  blake2b_word h[8];
+  // The block being filled, and the number of bytes in it.
+  blake2b_word m[16];
+  uint len;
+  // The number of bytes compressed so far. Inputs are much shorter than 2^64
+  // bytes, so the high word of the counter is always zero.
+  blake2b_word t;
+} blake2b_state;
+
+DEVICE blake2b_word blake2b_rotr(blake2b_word x, uint n) {
+  return (x >> n) | (x << (64 - n));
+}
+
+#define BLAKE2B_G(a, b, c, d, x, y)             \
+  v[a] = v[a] + v[b] + (x);                     \
+  v[d] = blake2b_rotr(v[d] ^ v[a], 32);         \
+  v[c] = v[c] + v[d];                           \
+  v[b] = blake2b_rotr(v[b] ^ v[c], 24);         \
+  v[a] = v[a] + v[b] + (y);                     \
+  v[d] = blake2b_rotr(v[d] ^ v[a], 16);         \
+  v[c] = v[c] + v[d];                           \
+  v[b] = blake2b_rotr(v[b] ^ v[c], 63);
+
+DEVICE void blake2b_compress(blake2b_state *s, bool last) {
+  blake2b_word v[16];
+  for (uint i = 0; i < 8; i++) {
+    v[i] = s->h[i];
+    v[i + 8] = BLAKE2B_IV[i];
+  }
+  v[12] ^= s->t;
+  if (last) {
+    v[14] = ~v[14];
+  }
+
+  for (uint r = 0; r < 12; r++) {
+    BLAKE2B_G(0, 4, 8, 12, s->m[BLAKE2B_SIGMA[r][0]], s->m[BLAKE2B_SIGMA[r][1]]);
+    BLAKE2B_G(1, 5, 9, 13, s->m[BLAKE2B_SIGMA[r][2]], s->m[BLAKE2B_SIGMA[r][3]]);
+    BLAKE2B_G(2, 6, 10, 14, s->m[BLAKE2B_SIGMA[r][4]], s->m[BLAKE2B_SIGMA[r][5]]);
+    BLAKE2B_G(3, 7, 11, 15, s->m[BLAKE2B_SIGMA[r][6]], s->m[BLAKE2B_SIGMA[r][7]]);
+    BLAKE2B_G(0, 5, 10, 15, s->m[BLAKE2B_SIGMA[r][8]], s->m[BLAKE2B_SIGMA[r][9]]);
+    BLAKE2B_G(1, 6, 11, 12, s->m[BLAKE2B_SIGMA[r][10]], s->m[BLAKE2B_SIGMA[r][11]]);
+    BLAKE2B_G(2, 7, 8, 13, s->m[BLAKE2B_SIGMA[r][12]], s->m[BLAKE2B_SIGMA[r][13]]);
+    BLAKE2B_G(3, 4, 9, 14, s->m[BLAKE2B_SIGMA[r][14]], s->m[BLAKE2B_SIGMA[r][15]]);
+  }
+
+  for (uint i = 0; i < 8; i++) {
+    s->h[i] ^= v[i] ^ v[i + 8];
+  }
+}
+
+DEVICE void blake2b_init(blake2b_state *s) {
+  for (uint i = 0; i < 8; i++) {
+    s->h[i] = BLAKE2B_IV[i];
+  }
+  // The parameter block: a digest length of 64 bytes, fanout 1 and depth 1.
+  s->h[0] ^= 0x01010040;
+  for (uint i = 0; i < 16; i++) {
+    s->m[i] = 0;
+  }
+  s->len = 0;
+  s->t = 0;
+}
+
+// Absorbs the low byte of `byte`.
+DEVICE void blake2b_update(blake2b_state *s, uint byte) {
+  // The last block is compressed differently, so a full block is only compressed
+  // once more input follows it.
+  if (s->len == 128) {
+    s->t += 128;
+    blake2b_compress(s, false);
+    for (uint i = 0; i < 16; i++) {
+      s->m[i] = 0;
+    }
+    s->len = 0;
+  }
+  s->m[s->len / 8] |= (blake2b_word)(byte & 0xff) << (8 * (s->len % 8));
+  s->len++;
+}
+
+// Absorbs `len` bytes starting at byte `start` of `words`, which holds bytes packed
+// little-endian into 32-bit words.
+DEVICE void blake2b_update_packed(blake2b_state *s, GLOBAL uint *words, uint start, uint len) {
+  for (uint i = start; i < start + len; i++) {
+    blake2b_update(s, words[i / 4] >> (8 * (i % 4)));
+  }
+}
+
+DEVICE void blake2b_finalize(blake2b_state *s) {
+  s->t += s->len;
+  blake2b_compress(s, true);
+}
+
+// Returns byte `i` of the digest of a finalized state.
+DEVICE uint blake2b_digest_byte(blake2b_state *s, uint i) {
+  return (uint)(s->h[i / 8] >> (8 * (i % 8))) & 0xff;
+}
@@ -0,0 +1,47 @@
+// The Montgomery constants of the Pasta fields, shared by the build script, which
+// cannot use the crate's own types, and a test in `src/gpu.rs` that checks them
+// against `Fp` and `Fq`.
+//
+// Each is the modulus, `R` and `R^2`, as little-endian 64-bit limbs.
+
+const FP_LIMBS: ([u64; 4], [u64; 4], [u64; 4]) = (
+    [
+        0x992d30ed00000001,
+        0x224698fc094cf91b,
+        0x0000000000000000,
+        0x4000000000000000,
+    ],
+    [
+        0x34786d38fffffffd,
+        0x992c350be41914ad,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ],
+    [
+        0x8c78ecb30000000f,
+        0xd7d30dbd8b0de0e7,
+        0x7797a99bc3c95d18,
+        0x096d41af7b9cb714,
+    ],
+);
+
+const FQ_LIMBS: ([u64; 4], [u64; 4], [u64; 4]) = (
+    [
+        0x8c46eb2100000001,
+        0x224698fc0994a8dd,
+        0x0000000000000000,
+        0x4000000000000000,
+    ],
+    [
+        0x5b2b3e9cfffffffd,
+        0x992c350be3420567,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ],
+    [
+        0xfc9678ff0000000f,
+        0x67bb433d891a16e3,
+        0x7fae231004ccf590,
+        0x096d41af7ccfdaa9,
+    ],
+);
@@ -0,0 +1,309 @@
+// Hashing to CURVE, computing the same points as `CurveExt::hash_to_curve` in
+// `pasta_curves`: `hash_to_field` with BLAKE2b, the simplified SWU map onto the
+// isogenous curve, and the degree 3 isogeny. The kernel is instantiated for a curve
+// by replacing the placeholder names in it with the names of the curve and of its
+// base field, whose arithmetic must be defined before it.
+//
+// The constants are passed in a buffer, in the Montgomery form of FIELD unless
+// noted otherwise:
+//
+//   0. R mod p (so its limbs are R^2 mod p)
+//   1. R^2 mod p (so its limbs are R^3 mod p)
+//   2. the coefficient a of the isogenous curve
+//   3. the coefficient b of the isogenous curve
+//   4. the SWU constant Z
+//   5. (T - 1) / 2, where p - 1 = 2^S * T with T odd, as plain limbs
+//   6. Z^T
+//   7. Z^((T + 1) / 2)
+//   8-20. the 13 isogeny constants
+
+// Returns the 32 bytes of the digest `s` starting at `offset` as a big-endian
+// integer, in plain limbs.
+DEVICE FIELD CURVE_h2c_digest_chunk(blake2b_state *s, uint offset) {
+  FIELD r;
+  for (uint j = 0; j < FIELD_LIMBS; j++) {
+    FIELD_limb limb = 0;
+    for (uint k = 0; k < FIELD_LIMB_BITS / 8; k++) {
+      uint i = j * (FIELD_LIMB_BITS / 8) + k;
+      limb |= (FIELD_limb)blake2b_digest_byte(s, offset + 31 - i) << (8 * k);
+    }
+    r.val[j] = limb;
+  }
+  return r;
+}
+
+// Reduces the digest `s`, read as a 512-bit big-endian integer, into a field element.
+DEVICE FIELD CURVE_h2c_from_digest(blake2b_state *s, GLOBAL FIELD *c) {
+  // As in `from_bytes_wide`, the low and high 256 bits are converted into
+  // Montgomery form by Montgomery multiplications with the limbs R^2 and R^3.
+  FIELD lo = CURVE_h2c_digest_chunk(s, 32);
+  FIELD hi = CURVE_h2c_digest_chunk(s, 0);
+  return FIELD_add(FIELD_mul(lo, c[0]), FIELD_mul(hi, c[1]));
+}
+
+// Absorbs DST' = DST || len(DST), as `expand_message_xmd` appends to every block.
+DEVICE void CURVE_h2c_update_dst(blake2b_state *s, GLOBAL uint *dst, uint dst_len) {
+  blake2b_update_packed(s, dst, 0, dst_len);
+  blake2b_update(s, dst_len);
+}
+
+DEVICE FIELD CURVE_h2c_pow(FIELD base, FIELD exponent) {
+  FIELD r = FIELD_ONE;
+  for (int i = FIELD_LIMBS * FIELD_LIMB_BITS - 1; i >= 0; i--) {
+    r = FIELD_sqr(r);
+    if ((exponent.val[i / FIELD_LIMB_BITS] >> (i % FIELD_LIMB_BITS)) & 1) {
+      r = FIELD_mul(r, base);
+    }
+  }
+  return r;
+}
+
+DEVICE FIELD CURVE_h2c_cmov(FIELD a, FIELD b, bool c) {
+  return c ? b : a;
+}
+
+// The `sqrt_ratio` algorithm of RFC 9380, appendix F.2.1.1. Sets `*y` to a square
+// root of u / v if it is square, and otherwise to a square root of Z * u / v.
+DEVICE bool CURVE_h2c_sqrt_ratio(FIELD u, FIELD v, GLOBAL FIELD *c, uint s, FIELD *y) {
+  FIELD tv1 = c[6];
+  // tv2 = v^(2^S - 1)
+  FIELD tv2 = v;
+  for (uint i = 1; i < s; i++) {
+    tv2 = FIELD_mul(FIELD_sqr(tv2), v);
+  }
+  FIELD tv3 = FIELD_mul(FIELD_sqr(tv2), v);
+  FIELD tv5 = FIELD_mul(u, tv3);
+  tv5 = CURVE_h2c_pow(tv5, c[5]);
+  tv5 = FIELD_mul(tv5, tv2);
+  tv2 = FIELD_mul(tv5, v);
+  tv3 = FIELD_mul(tv5, u);
+  FIELD tv4 = FIELD_mul(tv3, tv2);
+  // tv5 = tv4^(2^(S - 1))
+  tv5 = tv4;
+  for (uint i = 1; i < s; i++) {
+    tv5 = FIELD_sqr(tv5);
+  }
+  bool is_qr = FIELD_eq(tv5, FIELD_ONE);
+  tv2 = FIELD_mul(tv3, c[7]);
+  tv5 = FIELD_mul(tv4, tv1);
+  tv3 = CURVE_h2c_cmov(tv2, tv3, is_qr);
+  tv4 = CURVE_h2c_cmov(tv5, tv4, is_qr);
+  for (uint i = s; i >= 2; i--) {
+    tv5 = tv4;
+    for (uint j = 2; j < i; j++) {
+      tv5 = FIELD_sqr(tv5);
+    }
+    bool e1 = FIELD_eq(tv5, FIELD_ONE);
+    tv2 = FIELD_mul(tv3, tv1);
+    tv1 = FIELD_sqr(tv1);
+    tv5 = FIELD_mul(tv4, tv1);
+    tv3 = CURVE_h2c_cmov(tv2, tv3, e1);
+    tv4 = CURVE_h2c_cmov(tv5, tv4, e1);
+  }
+  *y = tv3;
+  return is_qr;
+}
+
+DEVICE bool CURVE_h2c_sgn0(FIELD a) {
+  return FIELD_unmont(a).val[0] & 1;
+}
+
+typedef struct {
+  FIELD x;
+  FIELD y;
+  FIELD z;
+} CURVE_h2c_jacobian;
+
+// The simplified SWU map, as in `map_to_curve_simple_swu`, returning a point on the
+// isogenous curve in Jacobian coordinates.
+DEVICE CURVE_h2c_jacobian CURVE_h2c_swu(FIELD u, GLOBAL FIELD *c, uint s) {
+  FIELD a = c[2];
+  FIELD b = c[3];
+  FIELD z = c[4];
+
+  FIELD z_u2 = FIELD_mul(z, FIELD_sqr(u));
+  FIELD ta = FIELD_add(FIELD_sqr(z_u2), z_u2);
+  FIELD num_x1 = FIELD_mul(b, FIELD_add(ta, FIELD_ONE));
+  bool ta_zero = FIELD_eq(ta, FIELD_ZERO);
+  FIELD div = FIELD_mul(a, CURVE_h2c_cmov(FIELD_sub(FIELD_ZERO, ta), z, ta_zero));
+  FIELD num2_x1 = FIELD_sqr(num_x1);
+  FIELD div2 = FIELD_sqr(div);
+  FIELD div3 = FIELD_mul(div2, div);
+  FIELD num_gx1 = FIELD_add(
+    FIELD_mul(FIELD_add(num2_x1, FIELD_mul(a, div2)), num_x1),
+    FIELD_mul(b, div3));
+  FIELD num_x2 = FIELD_mul(z_u2, num_x1);
+
+  // If gx1 is not square, y1 is a square root of Z * gx1, and since
+  // gx2 = Z^3 * u^6 * gx1, Z * u^3 * y1 is a square root of gx2.
+  FIELD y1;
+  bool gx1_square = CURVE_h2c_sqrt_ratio(num_gx1, div3, c, s, &y1);
+  FIELD y2 = FIELD_mul(FIELD_mul(z_u2, u), y1);
+  FIELD num_x = CURVE_h2c_cmov(num_x2, num_x1, gx1_square);
+  FIELD y = CURVE_h2c_cmov(y2, y1, gx1_square);
+  if (CURVE_h2c_sgn0(u) != CURVE_h2c_sgn0(y)) {
+    y = FIELD_sub(FIELD_ZERO, y);
+  }
+
+  CURVE_h2c_jacobian r;
+  r.x = FIELD_mul(num_x, div);
+  r.y = FIELD_mul(y, div3);
+  r.z = div;
+  return r;
+}
+
+// The degree 3 isogeny, as in `iso_map`.
+DEVICE CURVE_h2c_jacobian CURVE_h2c_iso_map(CURVE_h2c_jacobian p, GLOBAL FIELD *c) {
+  GLOBAL FIELD *iso = c + 8;
+  FIELD z2 = FIELD_sqr(p.z);
+  FIELD z3 = FIELD_mul(z2, p.z);
+  FIELD z4 = FIELD_sqr(z2);
+  FIELD z6 = FIELD_sqr(z3);
+
+  FIELD num_x = FIELD_add(FIELD_mul(iso[0], p.x), FIELD_mul(iso[1], z2));
+  num_x = FIELD_add(FIELD_mul(num_x, p.x), FIELD_mul(iso[2], z4));
+  num_x = FIELD_add(FIELD_mul(num_x, p.x), FIELD_mul(iso[3], z6));
+
+  FIELD div_x = FIELD_add(FIELD_mul(z2, p.x), FIELD_mul(iso[4], z4));
+  div_x = FIELD_add(FIELD_mul(div_x, p.x), FIELD_mul(iso[5], z6));
+
+  FIELD num_y = FIELD_add(FIELD_mul(iso[6], p.x), FIELD_mul(iso[7], z2));
+  num_y = FIELD_add(FIELD_mul(num_y, p.x), FIELD_mul(iso[8], z4));
+  num_y = FIELD_add(FIELD_mul(num_y, p.x), FIELD_mul(iso[9], z6));
+  num_y = FIELD_mul(num_y, p.y);
+
+  FIELD div_y = FIELD_add(p.x, FIELD_mul(iso[10], z2));
+  div_y = FIELD_add(FIELD_mul(div_y, p.x), FIELD_mul(iso[11], z4));
+  div_y = FIELD_add(FIELD_mul(div_y, p.x), FIELD_mul(iso[12], z6));
+  div_y = FIELD_mul(div_y, z3);
+
+  CURVE_h2c_jacobian r;
+  r.z = FIELD_mul(div_x, div_y);
+  r.x = FIELD_mul(FIELD_mul(num_x, div_y), r.z);
+  r.y = FIELD_mul(FIELD_mul(num_y, div_x), FIELD_sqr(r.z));
+  return r;
+}
+
+// Doubling on CURVE, which has a = 0 and no points of order 2.
+DEVICE CURVE_h2c_jacobian CURVE_h2c_double(CURVE_h2c_jacobian p) {
+  FIELD a = FIELD_sqr(p.x);
+  FIELD b = FIELD_sqr(p.y);
+  FIELD c = FIELD_sqr(b);
+  FIELD d = FIELD_sub(FIELD_sub(FIELD_sqr(FIELD_add(p.x, b)), a), c);
+  d = FIELD_double(d);
+  FIELD e = FIELD_add(FIELD_double(a), a);
+  FIELD f = FIELD_sqr(e);
+
+  CURVE_h2c_jacobian r;
+  r.z = FIELD_double(FIELD_mul(p.z, p.y));
+  r.x = FIELD_sub(f, FIELD_double(d));
+  c = FIELD_double(FIELD_double(FIELD_double(c)));
+  r.y = FIELD_sub(FIELD_mul(e, FIELD_sub(d, r.x)), c);
+  return r;
+}
+
+// Addition on CURVE, in Jacobian coordinates with the identity as Z = 0.
+DEVICE CURVE_h2c_jacobian CURVE_h2c_add(CURVE_h2c_jacobian p, CURVE_h2c_jacobian q) {
+  if (FIELD_eq(p.z, FIELD_ZERO)) {
+    return q;
+  }
+  if (FIELD_eq(q.z, FIELD_ZERO)) {
+    return p;
+  }
+
+  FIELD z1z1 = FIELD_sqr(p.z);
+  FIELD z2z2 = FIELD_sqr(q.z);
+  FIELD u1 = FIELD_mul(p.x, z2z2);
+  FIELD u2 = FIELD_mul(q.x, z1z1);
+  FIELD s1 = FIELD_mul(FIELD_mul(p.y, z2z2), q.z);
+  FIELD s2 = FIELD_mul(FIELD_mul(q.y, z1z1), p.z);
+
+  if (FIELD_eq(u1, u2)) {
+    if (FIELD_eq(s1, s2)) {
+      return CURVE_h2c_double(p);
+    }
+    CURVE_h2c_jacobian identity;
+    identity.x = FIELD_ZERO;
+    identity.y = FIELD_ONE;
+    identity.z = FIELD_ZERO;
+    return identity;
+  }
+
+  FIELD h = FIELD_sub(u2, u1);
+  FIELD i = FIELD_sqr(FIELD_double(h));
+  FIELD j = FIELD_mul(h, i);
+  FIELD r = FIELD_double(FIELD_sub(s2, s1));
+  FIELD v = FIELD_mul(u1, i);
+
+  CURVE_h2c_jacobian o;
+  o.x = FIELD_sub(FIELD_sub(FIELD_sub(FIELD_sqr(r), j), v), v);
+  o.y = FIELD_sub(FIELD_mul(r, FIELD_sub(v, o.x)), FIELD_double(FIELD_mul(s1, j)));
+  o.z = FIELD_mul(FIELD_sub(FIELD_sub(FIELD_sqr(FIELD_add(p.z, q.z)), z1z1), z2z2), h);
+  return o;
+}
+
+// Hashes message i, which is bytes offsets[i] to offsets[i + 1] of `messages`, and
+// writes the Jacobian coordinates of its point to out[3 * i] to out[3 * i + 2].
+// `messages` and `dst` hold bytes packed little-endian into 32-bit words, and
+// `dst` is the domain separation tag without its length byte.
+KERNEL void CURVE_hash_to_curve(
+  GLOBAL uint *messages,
+  GLOBAL uint *offsets,
+  uint n,
+  GLOBAL uint *dst,
+  uint dst_len,
+  GLOBAL FIELD *c,
+  uint s,
+  GLOBAL FIELD *out)
+{
+  uint i = GET_GLOBAL_ID();
+  if (i >= n) {
+    return;
+  }
+
+  // b_0 = H(Z_pad || msg || l_i_b_str || 0 || DST'), with len_in_bytes = 128.
+  blake2b_state b_0;
+  blake2b_init(&b_0);
+  for (uint k = 0; k < 128; k++) {
+    blake2b_update(&b_0, 0);
+  }
+  blake2b_update_packed(&b_0, messages, offsets[i], offsets[i + 1] - offsets[i]);
+  blake2b_update(&b_0, 0);
+  blake2b_update(&b_0, 128);
+  blake2b_update(&b_0, 0);
+  CURVE_h2c_update_dst(&b_0, dst, dst_len);
+  blake2b_finalize(&b_0);
+
+  // b_1 = H(b_0 || 1 || DST')
+  blake2b_state b_1;
+  blake2b_init(&b_1);
+  for (uint k = 0; k < 64; k++) {
+    blake2b_update(&b_1, blake2b_digest_byte(&b_0, k));
+  }
+  blake2b_update(&b_1, 1);
+  CURVE_h2c_update_dst(&b_1, dst, dst_len);
+  blake2b_finalize(&b_1);
+
+  // b_2 = H((b_0 ^ b_1) || 2 || DST')
+  blake2b_state b_2;
+  blake2b_init(&b_2);
+  for (uint k = 0; k < 64; k++) {
+    blake2b_update(&b_2, blake2b_digest_byte(&b_0, k) ^ blake2b_digest_byte(&b_1, k));
+  }
+  blake2b_update(&b_2, 2);
+  CURVE_h2c_update_dst(&b_2, dst, dst_len);
+  blake2b_finalize(&b_2);
+
+  FIELD u0 = CURVE_h2c_from_digest(&b_1, c);
+  FIELD u1 = CURVE_h2c_from_digest(&b_2, c);
+
+  // The isogeny is a group homomorphism, so the points for u0 and u1 can be mapped
+  // separately and added on CURVE, which has a = 0.
+  CURVE_h2c_jacobian q0 = CURVE_h2c_iso_map(CURVE_h2c_swu(u0, c, s), c);
+  CURVE_h2c_jacobian q1 = CURVE_h2c_iso_map(CURVE_h2c_swu(u1, c, s), c);
+  CURVE_h2c_jacobian r = CURVE_h2c_add(q0, q1);
+
+  out[3 * i] = r.x;
+  out[3 * i + 1] = r.y;
+  out[3 * i + 2] = r.z;
+}
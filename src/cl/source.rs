@@ -0,0 +1,33 @@
+// The source of the GPU kernels, shared by `src/gpu.rs` and the build script.
+//
+// This file is included by both, rather than compiled as a module, because the
+// build script cannot depend on the crate. It only names `Fp`, `Fq`, `EpAffine`
+// and `EqAffine`, which the build script defines with the same GPU names.
+
+/// Returns a [`SourceBuilder`] for all the kernels of this crate.
+fn kernels_source_builder() -> SourceBuilder {
+    SourceBuilder::new()
+        .add_field::<Fp>()
+        .add_field::<Fq>()
+        .add_fft::<Fp>()
+        .add_fft::<Fq>()
+        .add_multiexp::<EpAffine, Fp>()
+        .add_multiexp::<EqAffine, Fq>()
+        .append_source(hash_to_curve_source())
+}
+
+/// Returns the source of the hash-to-curve kernels for both curves, with the
+/// BLAKE2b functions that they use.
+fn hash_to_curve_source() -> String {
+    let mut source = String::from(include_str!("blake2b.cl"));
+    for (curve, field) in [
+        (EpAffine::name(), Fp::name()),
+        (EqAffine::name(), Fq::name()),
+    ] {
+        // The template names the curve `CURVE` and its base field `FIELD`.
+        source += &include_str!("hash_to_curve.cl")
+            .replace("CURVE", &curve)
+            .replace("FIELD", &field);
+    }
+    source
+}
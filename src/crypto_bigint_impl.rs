@@ -0,0 +1,85 @@
+use crypto_bigint::{Encoding, U256};
+use ff::PrimeField;
+use subtle::CtOption;
+
+use crate::arithmetic::FieldExt;
+use crate::fields::{Fp, Fq};
+
+macro_rules! impl_crypto_bigint {
+    ($field:ident) => {
+        impl From<&$field> for U256 {
+            fn from(f: &$field) -> U256 {
+                U256::from_le_bytes(f.to_repr())
+            }
+        }
+
+        impl From<$field> for U256 {
+            fn from(f: $field) -> U256 {
+                U256::from(&f)
+            }
+        }
+
+        impl $field {
+            /// Converts an integer into a field element, failing if it is not less
+            /// than the field modulus.
+            pub fn from_uint(n: U256) -> CtOption<Self> {
+                $field::from_repr(n.to_le_bytes())
+            }
+
+            /// Converts an integer into a field element, reducing it by the field
+            /// modulus.
+            pub fn from_uint_reduced(n: U256) -> Self {
+                let mut bytes = [0; 64];
+                bytes[..32].copy_from_slice(&n.to_le_bytes());
+                $field::from_bytes_wide(&bytes)
+            }
+        }
+    };
+}
+
+impl_crypto_bigint!(Fp);
+impl_crypto_bigint!(Fq);
+
+#[cfg(test)]
+mod tests {
+    use crypto_bigint::{Encoding, U256};
+    use ff::{Field, PrimeField};
+
+    use crate::{Fp, Fq};
+
+    #[test]
+    fn test_uint_conversions() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..100 {
+            let a = Fp::random(&mut rng);
+            assert_eq!(Fp::from_uint(U256::from(a)).unwrap(), a);
+            assert_eq!(Fp::from_uint_reduced(U256::from(a)), a);
+            let b = Fq::random(&mut rng);
+            assert_eq!(Fq::from_uint(U256::from(b)).unwrap(), b);
+            assert_eq!(Fq::from_uint_reduced(U256::from(b)), b);
+        }
+
+        assert_eq!(U256::from(Fp::one()), U256::ONE);
+        assert_eq!(Fq::from_uint(U256::from_u64(5)).unwrap(), Fq::from(5u64));
+        assert!(bool::from(Fp::from_uint(U256::MAX).is_none()));
+        assert!(bool::from(Fq::from_uint(U256::MAX).is_none()));
+
+        // 2^256 - 1 = (2^256 mod p) - 1
+        assert_eq!(
+            Fp::from_uint_reduced(U256::MAX),
+            Fp::from(1u64 << 32).square().square().square() - Fp::one()
+        );
+        assert_eq!(
+            Fq::from_uint_reduced(U256::MAX),
+            Fq::from(1u64 << 32).square().square().square() - Fq::one()
+        );
+
+        // p itself is rejected, and reduces to zero.
+        let mut p = (-Fp::one()).to_repr();
+        p[0] += 1;
+        let p = U256::from_le_bytes(p);
+        assert!(bool::from(Fp::from_uint(p).is_none()));
+        assert_eq!(Fp::from_uint_reduced(p), Fp::zero());
+    }
+}
@@ -5,24 +5,30 @@ use core::cmp;
 use core::fmt;
 use core::iter::Sum;
 use core::ops::{Add, Mul, Neg, Sub};
+use core::str::FromStr;
 
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
-use ff::{Field, PrimeField};
+use ff::{Field, PrimeField, WithSmallOrderMulGroup};
 use group::{
     cofactor::{CofactorCurve, CofactorGroup},
     prime::{PrimeCurve, PrimeCurveAffine, PrimeGroup},
-    Curve as _, Group as _, GroupEncoding,
+    Curve as _, Group as _, GroupEncoding, UncompressedEncoding,
 };
-use rand::RngCore;
+use rand::distributions::{Distribution, Standard};
+use rand::{Rng, RngCore};
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 use super::{Fp, Fq};
-use crate::arithmetic::Group;
+use crate::arithmetic::{Group, SqrtRatio};
 
 #[cfg(feature = "alloc")]
-use crate::arithmetic::{Coordinates, CurveAffine, CurveExt, FieldExt};
+use crate::arithmetic::{Coordinates, CurveAffine, CurveExt, CycleCurve};
+#[cfg(feature = "alloc")]
+use crate::SerdeObject;
 
 macro_rules! new_curve_impl {
     (($($privacy:tt)*), $name:ident, $name_affine:ident, $iso:ident, $base:ident, $scalar:ident,
@@ -44,6 +50,15 @@ macro_rules! new_curve_impl {
             const fn curve_constant_b() -> $base {
                 $base::from_raw($b_raw)
             }
+
+            /// Adds the affine point `rhs` to this point, using the mixed addition
+            /// formula, which is cheaper than adding two projective points. This is
+            /// equivalent to `self + rhs`.
+            ///
+            /// This is not constant time.
+            pub fn add_affine(&self, rhs: &$name_affine) -> Self {
+                self + rhs
+            }
         }
 
         /// Represents a point in the affine coordinate space (or the point at
@@ -73,8 +88,10 @@ macro_rules! new_curve_impl {
                     let x = $base::random(&mut rng);
                     let ysign = (rng.next_u32() % 2) as u8;
 
-                    let x3 = x.square() * x;
-                    let y = (x3 + $name::curve_constant_b()).sqrt();
+                    // y^2 = x^3 + ax + b
+                    let y2 = (x.square() + $name::curve_constant_a()) * x
+                        + $name::curve_constant_b();
+                    let y = y2.sqrt();
                     if let Some(y) = Option::<$base>::from(y) {
                         let sign = y.is_odd().unwrap_u8();
                         let y = if ysign ^ sign == 0 { y } else { -y };
@@ -88,7 +105,7 @@ macro_rules! new_curve_impl {
                 }
             }
 
-            impl_projective_curve_specific!($name, $base, $curve_type);
+            impl_projective_curve_specific!($name, $name_affine, $base, $curve_type);
 
             fn identity() -> Self {
                 Self {
@@ -133,7 +150,7 @@ macro_rules! new_curve_impl {
 
             const CURVE_ID: &'static str = $curve_id;
 
-            impl_projective_curve_ext!($name, $iso, $base, $curve_type);
+            impl_projective_curve_ext!($name, $iso, $base, $scalar, $curve_type);
 
             fn a() -> Self::Base {
                 $name::curve_constant_a()
@@ -148,6 +165,37 @@ macro_rules! new_curve_impl {
                 CtOption::new(p, p.is_on_curve())
             }
 
+            fn add_mixed_ct(&self, rhs: &$name_affine) -> $name {
+                // The same formula as `Add<&$name_affine>`, which is wrong when either
+                // point is the identity or when the points are equal or opposite, so
+                // the results for those cases are computed too and selected.
+                let z1z1 = self.z.square();
+                let u2 = rhs.x * z1z1;
+                let s2 = rhs.y * z1z1 * self.z;
+
+                let h = u2 - self.x;
+                let hh = h.square();
+                let i = hh + hh;
+                let i = i + i;
+                let j = h * i;
+                let r = s2 - self.y;
+                let r = r + r;
+                let v = self.x * i;
+                let x3 = r.square() - j - v - v;
+                let j = self.y * j;
+                let j = j + j;
+                let y3 = r * (v - x3) - j;
+                let z3 = (self.z + h).square() - z1z1 - hh;
+                let sum = $name { x: x3, y: y3, z: z3 };
+
+                let same_x = self.x.ct_eq(&u2);
+                let same_y = self.y.ct_eq(&s2);
+                let sum = $name::conditional_select(&sum, &self.double(), same_x & same_y);
+                let sum = $name::conditional_select(&sum, &$name::identity(), same_x & !same_y);
+                let sum = $name::conditional_select(&sum, &rhs.to_curve(), self.is_identity());
+                $name::conditional_select(&sum, self, rhs.is_identity())
+            }
+
             fn jacobian_coordinates(&self) -> ($base, $base, $base) {
                (self.x, self.y, self.z)
             }
@@ -165,9 +213,13 @@ macro_rules! new_curve_impl {
             }
         }
 
+        impl_curve_api!($name, $name_affine, $iso, $base, $scalar, $curve_type);
+
         impl group::Curve for $name {
             type AffineRepr = $name_affine;
 
+            /// Converts a batch of projective points into affine points, sharing a
+            /// single field inversion across all of their z-coordinates.
             fn batch_normalize(p: &[Self], q: &mut [Self::AffineRepr]) {
                 assert_eq!(p.len(), q.len());
 
@@ -285,6 +337,17 @@ macro_rules! new_curve_impl {
             }
         }
 
+        /// Zeroizing a point overwrites its coordinates, leaving the identity.
+        #[cfg(feature = "zeroize")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+        impl zeroize::Zeroize for $name {
+            fn zeroize(&mut self) {
+                zeroize::Zeroize::zeroize(&mut self.x);
+                zeroize::Zeroize::zeroize(&mut self.y);
+                zeroize::Zeroize::zeroize(&mut self.z);
+            }
+        }
+
         impl ConstantTimeEq for $name {
             fn ct_eq(&self, other: &Self) -> Choice {
                 // Is (xz^2, yz^3, z) equal to (x'z'^2, yz'^3, z') when converted to affine?
@@ -357,6 +420,24 @@ macro_rules! new_curve_impl {
             }
         }
 
+        impl Sum<$name_affine> for $name {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = $name_affine>,
+            {
+                iter.fold(Self::identity(), |acc, item| acc + item)
+            }
+        }
+
+        impl<'a> Sum<&'a $name_affine> for $name {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = &'a $name_affine>,
+            {
+                iter.fold(Self::identity(), |acc, item| acc + item)
+            }
+        }
+
         impl<'a, 'b> Add<&'a $name> for &'b $name {
             type Output = $name;
 
@@ -615,10 +696,7 @@ macro_rules! new_curve_impl {
             impl_affine_curve_specific!($name, $base, $curve_type);
 
             fn identity() -> Self {
-                Self {
-                    x: $base::zero(),
-                    y: $base::zero(),
-                }
+                Self::IDENTITY
             }
 
             fn is_identity(&self) -> Choice {
@@ -655,33 +733,61 @@ macro_rules! new_curve_impl {
             }
         }
 
-        impl GroupEncoding for $name_affine {
-            type Repr = [u8; 32];
+        impl $name_affine {
+            /// The point at infinity, represented as $(0, 0)$. This is
+            /// [`PrimeCurveAffine::identity`] as an associated constant.
+            pub const IDENTITY: Self = $name_affine {
+                x: $base::zero(),
+                y: $base::zero(),
+            };
 
-            fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+            /// Checks whether this point is on the curve, in constant time. This is
+            /// `CurveAffine::is_on_curve` without the `alloc` feature flag.
+            #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+            pub(crate) fn ct_is_on_curve(&self) -> Choice {
+                // y^2 - x^3 - ax ?= b
+                (self.y.square() - (self.x.square() + &$name::curve_constant_a()) * self.x).ct_eq(&$name::curve_constant_b())
+                    | self.is_identity()
+            }
+
+            /// Decodes a compressed encoding, in constant time with respect to `bytes`.
+            ///
+            /// Every encoding, whether valid or not (a non-canonical x-coordinate, an
+            /// x-coordinate that is not on the curve, or the identity with the sign bit
+            /// set), takes the same sequence of operations and memory accesses. In
+            /// particular the square root is always computed with
+            /// [`SqrtRatio::sqrt_ct`](crate::arithmetic::SqrtRatio::sqrt_ct), even with
+            /// the `sqrt-table` feature flag. [`GroupEncoding::from_bytes`] is
+            /// equivalent.
+            pub fn ct_decode(bytes: &[u8; 32]) -> CtOption<Self> {
                 let mut tmp = *bytes;
                 let ysign = Choice::from(tmp[31] >> 7);
                 tmp[31] &= 0b0111_1111;
 
+                // `CtOption::and_then` and `CtOption::or_else` always evaluate their
+                // closures, so the invalid cases cost as much as the valid ones.
                 $base::from_repr(tmp).and_then(|x| {
                     CtOption::new(Self::identity(), x.is_zero() & (!ysign)).or_else(|| {
-                        let x3 = x.square() * x;
-                        (x3 + $name::curve_constant_b()).sqrt().and_then(|y| {
+                        // y^2 = x^3 + ax + b
+                        let y2 = (x.square() + $name::curve_constant_a()) * x
+                            + $name::curve_constant_b();
+                        y2.sqrt_ct().map(|y| {
                             let sign = y.is_odd();
-
                             let y = $base::conditional_select(&y, &-y, ysign ^ sign);
-
-                            CtOption::new(
-                                $name_affine {
-                                    x,
-                                    y,
-                                },
-                                Choice::from(1u8),
-                            )
+                            $name_affine { x, y }
                         })
                     })
                 })
             }
+        }
+
+        impl GroupEncoding for $name_affine {
+            type Repr = [u8; 32];
+
+            /// Decodes a compressed encoding in constant time, as [`Self::ct_decode`].
+            fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+                Self::ct_decode(bytes)
+            }
 
             fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
                 // We can't avoid curve checks when parsing a compressed encoding.
@@ -689,16 +795,11 @@ macro_rules! new_curve_impl {
             }
 
             fn to_bytes(&self) -> [u8; 32] {
-                // TODO: not constant time
-                if bool::from(self.is_identity()) {
-                    [0; 32]
-                } else {
-                    let (x, y) = (self.x, self.y);
-                    let sign = y.is_odd().unwrap_u8() << 7;
-                    let mut xbytes = x.to_repr();
-                    xbytes[31] |= sign;
-                    xbytes
-                }
+                // The identity is (0, 0), so its encoding is all zeros without a branch.
+                let sign = self.y.is_odd().unwrap_u8() << 7;
+                let mut xbytes = self.x.to_repr();
+                xbytes[31] |= sign;
+                xbytes
             }
         }
 
@@ -709,10 +810,9 @@ macro_rules! new_curve_impl {
             type Base = $base;
             type CurveExt = $name;
 
+            /// Checks whether this point is on the curve, in constant time.
             fn is_on_curve(&self) -> Choice {
-                // y^2 - x^3 - ax ?= b
-                (self.y.square() - (self.x.square() + &$name::curve_constant_a()) * self.x).ct_eq(&$name::curve_constant_b())
-                    | self.is_identity()
+                self.ct_is_on_curve()
             }
 
             fn coordinates(&self) -> CtOption<Coordinates<Self>> {
@@ -726,6 +826,54 @@ macro_rules! new_curve_impl {
                 CtOption::new(p, p.is_on_curve())
             }
 
+            fn batch_add(lhs: &mut [Self], rhs: &[Self]) {
+                assert_eq!(lhs.len(), rhs.len());
+
+                // The denominator of the slope of each sum, or one where there is none.
+                let mut denominators: Vec<_> = lhs
+                    .iter()
+                    .zip(rhs.iter())
+                    .map(|(p, q)| {
+                        if bool::from(p.is_identity() | q.is_identity()) {
+                            $base::one()
+                        } else if p.x != q.x {
+                            q.x - p.x
+                        } else if p.y == q.y {
+                            // There are no points of order 2, so y is nonzero.
+                            p.y.double()
+                        } else {
+                            // p = -q
+                            $base::one()
+                        }
+                    })
+                    .collect();
+                crate::arithmetic::batch_invert(&mut denominators);
+
+                for ((p, q), inv) in lhs.iter_mut().zip(rhs.iter()).zip(denominators) {
+                    if bool::from(q.is_identity()) {
+                        continue;
+                    }
+                    if bool::from(p.is_identity()) {
+                        *p = *q;
+                        continue;
+                    }
+
+                    let lambda = if p.x != q.x {
+                        (q.y - p.y) * inv
+                    } else if p.y == q.y {
+                        let xx = p.x.square();
+                        (xx.double() + xx + $name::curve_constant_a()) * inv
+                    } else {
+                        *p = $name_affine::identity();
+                        continue;
+                    };
+
+                    let x = lambda.square() - p.x - q.x;
+                    let y = lambda * (p.x - x) - p.y;
+                    *p = $name_affine { x, y };
+                }
+            }
+
             fn a() -> Self::Base {
                 $name::curve_constant_a()
             }
@@ -741,6 +889,16 @@ macro_rules! new_curve_impl {
             }
         }
 
+        /// Zeroizing a point overwrites its coordinates, leaving the identity.
+        #[cfg(feature = "zeroize")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+        impl zeroize::Zeroize for $name_affine {
+            fn zeroize(&mut self) {
+                zeroize::Zeroize::zeroize(&mut self.x);
+                zeroize::Zeroize::zeroize(&mut self.y);
+            }
+        }
+
         impl<'a> From<&'a $name> for $name_affine {
             fn from(p: &'a $name) -> $name_affine {
                 p.to_affine()
@@ -767,6 +925,26 @@ macro_rules! new_curve_impl {
 
         impl cmp::Eq for $name_affine {}
 
+        impl core::hash::Hash for $name_affine {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.to_bytes().hash(state);
+            }
+        }
+
+        impl Distribution<$name> for Standard {
+            /// Samples a uniformly random point, as [`group::Group::random`].
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                <$name as group::Group>::random(rng)
+            }
+        }
+
+        impl Distribution<$name_affine> for Standard {
+            /// Samples a uniformly random point, as [`group::Group::random`].
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $name_affine {
+                <$name as group::Group>::random(rng).to_affine()
+            }
+        }
+
         impl ConditionallySelectable for $name_affine {
             fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
                 $name_affine {
@@ -800,17 +978,28 @@ macro_rules! new_curve_impl {
             }
         }
 
+        /// The name is fixed, rather than derived from the source location, so that
+        /// the kernels compiled by the build script can be looked up at runtime.
         #[cfg(feature = "gpu")]
         impl ec_gpu::GpuName for $name_affine {
             fn name() -> alloc::string::String {
-                ec_gpu::name!()
+                alloc::string::String::from(concat!("pasta_", stringify!($name_affine)))
+            }
+        }
+
+        /// The projective type is named after its affine type, with the same suffix
+        /// that `ec-gpu-gen` uses for the projective points in generated kernels.
+        #[cfg(feature = "gpu")]
+        impl ec_gpu::GpuName for $name {
+            fn name() -> alloc::string::String {
+                alloc::format!("{}_projective", <$name_affine as ec_gpu::GpuName>::name())
             }
         }
     };
 }
 
 macro_rules! impl_projective_curve_specific {
-    ($name:ident, $base:ident, special_a0_b5) => {
+    ($name:ident, $name_affine:ident, $base:ident, special_a0_b5) => {
         fn generator() -> Self {
             // NOTE: This is specific to b = 5
 
@@ -855,10 +1044,13 @@ macro_rules! impl_projective_curve_specific {
             $name::conditional_select(&tmp, &$name::identity(), self.is_identity())
         }
     };
-    ($name:ident, $base:ident, general) => {
-        /// Unimplemented: there is no standard generator for this curve.
+    ($name:ident, $name_affine:ident, $base:ident, general) => {
         fn generator() -> Self {
-            unimplemented!()
+            Self {
+                x: $name_affine::GENERATOR.x,
+                y: $name_affine::GENERATOR.y,
+                z: $base::one(),
+            }
         }
 
         fn double(&self) -> Self {
@@ -890,15 +1082,274 @@ macro_rules! impl_projective_curve_specific {
     };
 }
 
-#[cfg(feature = "alloc")]
-macro_rules! impl_projective_curve_ext {
-    ($name:ident, $iso:ident, $base:ident, special_a0_b5) => {
-        fn hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
-            use super::hashtocurve;
+macro_rules! impl_curve_api {
+    ($name:ident, $name_affine:ident, $iso:ident, $base:ident, $scalar:ident, special_a0_b5) => {
+        impl $name {
+            /// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of `bases`
+            /// $P_i$ and `scalars` $s_i$, using the bucket method (Pippenger's
+            /// algorithm).
+            ///
+            /// This is not constant time with respect to the scalars.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `bases` and `scalars` have different lengths.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn multi_scalar_mul(bases: &[$name_affine], scalars: &[$scalar]) -> Self {
+                crate::msm::multi_scalar_mul(bases, scalars)
+            }
+
+            /// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of `bases`
+            /// $P_i$ and `scalars` $s_i$ as in [`Self::multi_scalar_mul`], with the
+            /// window size, chunking and parallelism set by `config`.
+            ///
+            /// This is not constant time with respect to the scalars.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `bases` and `scalars` have different lengths, if the chunk size
+            /// of `config` is zero, or if its window size is not between 1 and 20 bits.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn multi_scalar_mul_with_config(
+                bases: &[$name_affine],
+                scalars: &[$scalar],
+                config: &crate::MsmConfig,
+            ) -> Self {
+                crate::msm::multi_scalar_mul_with_config(bases, scalars, config)
+            }
+
+            /// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of `bases`
+            /// $P_i$ and 64-bit `scalars` $s_i$.
+            ///
+            /// This gives the same result as [`Self::multi_scalar_mul`] with the
+            /// scalars converted to field elements, but only processes the windows
+            /// up to the bit length of the largest scalar, which makes it several
+            /// times faster for small scalars such as indices or counts.
+            ///
+            /// This is not constant time with respect to the scalars.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `bases` and `scalars` have different lengths.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn multi_scalar_mul_u64(bases: &[$name_affine], scalars: &[u64]) -> Self {
+                crate::msm::multi_scalar_mul_u64(bases, scalars)
+            }
+
+            /// Computes the multi-scalar multiplication of the `(base, scalar)` pairs
+            /// yielded by `terms`, processing them in chunks of `chunk_size` terms so
+            /// that at most one chunk is held in memory at a time.
+            ///
+            /// This allows commitments to inputs that are too large to collect into
+            /// vectors, such as terms streamed from disk. Larger chunks are faster, as
+            /// the bucket method costs less per term the more terms it processes at
+            /// once.
+            ///
+            /// This is not constant time with respect to the scalars.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `chunk_size` is zero.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn multi_scalar_mul_chunked<I: IntoIterator<Item = ($name_affine, $scalar)>>(
+                terms: I,
+                chunk_size: usize,
+            ) -> Self {
+                crate::msm::multi_scalar_mul_chunked(terms, chunk_size)
+            }
+
+            /// Computes $[a] P + [b] Q$ using interleaved wNAF (Straus's method), which
+            /// is faster than computing the two scalar multiplications separately.
+            ///
+            /// This is not constant time with respect to the scalars.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn double_scalar_mul(a: &$scalar, p: &Self, b: &$scalar, q: &Self) -> Self {
+                crate::wnaf::double_scalar_mul(a, p, b, q)
+            }
+
+            /// Multiplies this point by `scalar`, blinding the computation with
+            /// randomness from `rng`.
+            ///
+            /// The scalar is split into random shares as $k = (k - r) + r$, and the
+            /// Jacobian coordinates of the point are re-randomized as
+            /// $(\lambda^2 X, \lambda^3 Y, \lambda Z)$, so that neither the scalar nor
+            /// the coordinates processed by each multiplication are repeated across
+            /// calls. This hardens multiplications by secret scalars against power
+            /// and electromagnetic side channels, at twice the cost of `self * scalar`.
+            pub fn mul_blinded(&self, scalar: &$scalar, mut rng: impl RngCore) -> Self {
+                let r = $scalar::random(&mut rng);
+                let lambda = $base::random(&mut rng);
+                // A zero lambda would map the point to the identity.
+                let lambda = $base::conditional_select(&lambda, &$base::one(), lambda.is_zero());
+                let lambda2 = lambda.square();
+                let p = $name {
+                    x: self.x * lambda2,
+                    y: self.y * lambda2 * lambda,
+                    z: self.z * lambda,
+                };
+                p * (*scalar - r) + p * r
+            }
+
+            /// Maps a point on the isogenous curve to this curve, using the degree 3
+            /// isogeny with the coefficients [`Self::ISOGENY_CONSTANTS`].
+            ///
+            /// This is the last step of [`CurveExt::hash_to_curve`], and can be used to
+            /// check circuits that evaluate the isogeny.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn iso_map(p: &$iso) -> Self {
+                crate::hashtocurve::iso_map::<$base, $name, $iso>(p, &$name::ISOGENY_CONSTANTS)
+            }
+
+            /// Maps the field element `u` to a point on the isogenous curve, using the
+            /// simplified SWU map.
+            ///
+            /// [`CurveExt::hash_to_curve`] adds the points for two field elements and
+            /// maps the sum to this curve with [`Self::iso_map`].
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn map_to_curve_simple_swu(u: &$base) -> $iso {
+                crate::hashtocurve::map_to_curve_simple_swu::<$base, $name, $iso>(
+                    u,
+                    $name::THETA,
+                    $name::Z,
+                )
+            }
+
+            /// Deterministically maps the field element `u` to a point on this curve,
+            /// with the simplified SWU map followed by the isogeny.
+            ///
+            /// This is `map_to_curve` from RFC 9380. On its own, its output is not
+            /// uniformly distributed, so it should only be applied to field elements
+            /// that are already derived from a hash.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn map_to_curve(u: &$base) -> Self {
+                Self::iso_map(&Self::map_to_curve_simple_swu(u))
+            }
+
+            /// Hashes `message` to a point with the domain separation tag `dst`, using
+            /// the message expansion `X` and the simplified SWU map.
+            ///
+            /// This is `hash_to_curve` from RFC 9380, with the field
+            /// elements computed by [`hash_to_field_with`](crate::hash_to_field_with).
+            /// [`CurveExt::hash_to_curve`] instead uses BLAKE2b as specified for Zcash,
+            /// and its output is not compatible with this function.
+            #[cfg(feature = "digest")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+            pub fn hash_to_curve_with<X: crate::ExpandMessage>(dst: &[u8], message: &[u8]) -> Self {
+                use crate::hashtocurve;
+
+                let mut us = [$base::zero(); 2];
+                hashtocurve::hash_to_field_with::<$base, X>(dst, message, &mut us);
+                let q0 = hashtocurve::map_to_curve_simple_swu::<$base, $name, $iso>(
+                    &us[0],
+                    $name::THETA,
+                    $name::Z,
+                );
+                let q1 = hashtocurve::map_to_curve_simple_swu::<$base, $name, $iso>(
+                    &us[1],
+                    $name::THETA,
+                    $name::Z,
+                );
+                let r = q0 + &q1;
+                debug_assert!(bool::from(r.is_on_curve()));
+                hashtocurve::iso_map::<$base, $name, $iso>(&r, &$name::ISOGENY_CONSTANTS)
+            }
 
-            Box::new(move |message| {
-                let mut us = [Field::zero(); 2];
-                hashtocurve::hash_to_field($name::CURVE_ID, domain_prefix, message, &mut us);
+            /// Hashes `message` to a point with the domain separation tag `dst`, using
+            /// the RFC 9380 suite with `expand_message_xmd` and SHA-256,
+            /// [`pallas::SUITE_ID_XMD_SHA256`](crate::pallas::SUITE_ID_XMD_SHA256) or
+            /// [`vesta::SUITE_ID_XMD_SHA256`](crate::vesta::SUITE_ID_XMD_SHA256).
+            ///
+            /// The RFC recommends that `dst` ends with the suite ID.
+            #[cfg(feature = "sha256")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "sha256")))]
+            pub fn hash_to_curve_sha256(dst: &[u8], message: &[u8]) -> Self {
+                Self::hash_to_curve_with::<crate::ExpandMsgXmd<sha2::Sha256>>(dst, message)
+            }
+        }
+
+        impl $name_affine {
+            /// The generator $(-1, 2)$. This is [`PrimeCurveAffine::generator`] as an
+            /// associated constant, so it can be used to build static tables of points.
+            // NOTE: This is specific to b = 5
+            pub const GENERATOR: Self = $name_affine {
+                x: $base::neg(&$base::from_raw([1, 0, 0, 0])),
+                y: $base::from_raw([2, 0, 0, 0]),
+            };
+
+            /// Decodes each compressed encoding in `bytes`, with the same result as
+            /// [`GroupEncoding::from_bytes`], computing the square roots of all the
+            /// $y^2$ values with a single call to `batch_sqrt`.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn batch_from_bytes(bytes: &[[u8; 32]]) -> Vec<CtOption<Self>> {
+                let encodings: Vec<_> = bytes
+                    .iter()
+                    .map(|bytes| {
+                        let mut tmp = *bytes;
+                        let ysign = Choice::from(tmp[31] >> 7);
+                        tmp[31] &= 0b0111_1111;
+                        (ysign, $base::from_repr(tmp))
+                    })
+                    .collect();
+
+                // y^2 = x^3 + b, using x = 0 for invalid encodings of x.
+                let y2: Vec<_> = encodings
+                    .iter()
+                    .map(|(_, x)| {
+                        let x = x.unwrap_or($base::zero());
+                        x.square() * x + $name::curve_constant_b()
+                    })
+                    .collect();
+                let ys = $base::batch_sqrt(&y2);
+
+                encodings
+                    .iter()
+                    .zip(ys.iter())
+                    .map(|(&(ysign, x), y)| {
+                        x.and_then(|x| {
+                            CtOption::new(Self::identity(), x.is_zero() & (!ysign)).or_else(|| {
+                                y.map(|y| {
+                                    let sign = y.is_odd();
+                                    let y = $base::conditional_select(&y, &-y, ysign ^ sign);
+                                    $name_affine { x, y }
+                                })
+                            })
+                        })
+                    })
+                    .collect()
+            }
+
+            /// Applies the curve endomorphism $(x, y) \mapsto (\zeta \cdot x, y)$, where
+            /// $\zeta$ is [`WithSmallOrderMulGroup::ZETA`] in the base field.
+            ///
+            /// This is equivalent to multiplying the point by
+            /// [`WithSmallOrderMulGroup::ZETA`] in the scalar field.
+            pub fn apply_endomorphism(&self) -> Self {
+                $name_affine {
+                    x: self.x * $base::ZETA,
+                    y: self.y,
+                }
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<'a> crate::CurveHasher<'a, $name> {
+            /// Hashes the absorbed message to a point, with the same result as
+            /// [`CurveExt::hash_to_curve`] for the whole message.
+            pub fn finalize(self) -> $name {
+                use crate::hashtocurve;
+
+                let mut us = [$base::ZERO; 2];
+                self.inner.finalize(&mut us);
                 let q0 = hashtocurve::map_to_curve_simple_swu::<$base, $name, $iso>(
                     &us[0],
                     $name::THETA,
@@ -912,6 +1363,44 @@ macro_rules! impl_projective_curve_ext {
                 let r = q0 + &q1;
                 debug_assert!(bool::from(r.is_on_curve()));
                 hashtocurve::iso_map::<$base, $name, $iso>(&r, &$name::ISOGENY_CONSTANTS)
+            }
+        }
+    };
+    ($name:ident, $name_affine:ident, $iso:ident, $base:ident, $scalar:ident, general) => {
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<'a> crate::CurveHasher<'a, $name> {
+            /// Hashes the absorbed message to a point, with the same result as
+            /// [`CurveExt::hash_to_curve`] for the whole message.
+            pub fn finalize(self) -> $name {
+                use crate::hashtocurve;
+
+                let mut us = [$base::ZERO; 2];
+                self.inner.finalize(&mut us);
+                let q0 = hashtocurve::map_to_curve_simple_swu::<$base, $iso, $name>(
+                    &us[0],
+                    $iso::THETA,
+                    $iso::Z,
+                );
+                let q1 = hashtocurve::map_to_curve_simple_swu::<$base, $iso, $name>(
+                    &us[1],
+                    $iso::THETA,
+                    $iso::Z,
+                );
+                q0 + &q1
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_projective_curve_ext {
+    ($name:ident, $iso:ident, $base:ident, $scalar:ident, special_a0_b5) => {
+        fn hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+            Box::new(move |message| {
+                let mut hasher = crate::CurveHasher::<$name>::new(domain_prefix);
+                hasher.update(message);
+                hasher.finalize()
             })
         }
 
@@ -925,15 +1414,23 @@ macro_rules! impl_projective_curve_ext {
             }
         }
     };
-    ($name:ident, $iso:ident, $base:ident, general) => {
-        /// Unimplemented: hashing to this curve is not supported
-        fn hash_to_curve<'a>(_domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
-            unimplemented!()
+    ($name:ident, $iso:ident, $base:ident, $scalar:ident, general) => {
+        /// Hashes to this curve with the simplified SWU map. Unlike the other curve,
+        /// this does not apply the isogeny, and the domain separation tag contains
+        /// this curve's [`CurveExt::CURVE_ID`].
+        fn hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+            Box::new(move |message| {
+                let mut hasher = crate::CurveHasher::<$name>::new(domain_prefix);
+                hasher.update(message);
+                hasher.finalize()
+            })
         }
 
-        /// Unimplemented: no endomorphism is supported for this curve.
+        /// Multiplies the point by [`WithSmallOrderMulGroup::ZETA`] in the scalar
+        /// field. This curve has no efficient endomorphism of order 3, but this is the
+        /// one that commutes with the isogeny and the endomorphism of the other curve.
         fn endo(&self) -> Self {
-            unimplemented!()
+            self * $scalar::ZETA
         }
     };
 }
@@ -941,21 +1438,229 @@ macro_rules! impl_projective_curve_ext {
 macro_rules! impl_affine_curve_specific {
     ($name:ident, $base:ident, special_a0_b5) => {
         fn generator() -> Self {
-            // NOTE: This is specific to b = 5
+            Self::GENERATOR
+        }
+    };
+    ($name:ident, $base:ident, general) => {
+        fn generator() -> Self {
+            Self::GENERATOR
+        }
+    };
+}
 
-            const NEGATIVE_ONE: $base = $base::neg(&$base::from_raw([1, 0, 0, 0]));
-            const TWO: $base = $base::from_raw([2, 0, 0, 0]);
+/// An error which can be returned when parsing a point from a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParsePointError {
+    /// The string does not contain exactly 64 hex digits.
+    InvalidLength,
+    /// The string contains a character that is not a hex digit.
+    InvalidDigit,
+    /// The bytes do not encode a point on the curve.
+    InvalidEncoding,
+}
 
-            Self {
-                x: NEGATIVE_ONE,
-                y: TWO,
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParsePointError::InvalidLength => "expected 64 hex digits",
+            ParsePointError::InvalidDigit => "invalid hex digit found in string",
+            ParsePointError::InvalidEncoding => "bytes don't encode a point on the curve",
+        })
+    }
+}
+
+/// Decodes a hex string, optionally `0x`- or `0X`-prefixed, into 32 bytes.
+fn decode_hex(s: &str) -> Result<[u8; 32], ParsePointError> {
+    crate::fields::decode_hex(
+        crate::fields::strip_hex_prefix(s).unwrap_or(s),
+        ParsePointError::InvalidLength,
+        ParsePointError::InvalidDigit,
+    )
+}
+
+macro_rules! impl_point_str {
+    ($name_affine:ident) => {
+        impl fmt::Display for $name_affine {
+            /// Formats the compressed encoding of this point as a `0x`-prefixed hex
+            /// string.
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "0x")?;
+                for b in self.to_bytes().iter() {
+                    write!(f, "{:02x}", b)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl FromStr for $name_affine {
+            type Err = ParsePointError;
+
+            /// Parses the compressed encoding of a point from a hex string, which may
+            /// be `0x`- or `0X`-prefixed, like field elements.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let bytes = decode_hex(s)?;
+                Option::from($name_affine::from_bytes(&bytes))
+                    .ok_or(ParsePointError::InvalidEncoding)
+            }
+        }
+
+        impl $name_affine {
+            /// Encodes the compressed encoding of this point as 64 lowercase hex
+            /// digits, without the `0x` prefix of the `Display` format.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn to_hex(&self) -> alloc::string::String {
+                crate::fields::encode_hex(&self.to_bytes())
+            }
+
+            /// Decodes a point from the 64 hex digits written by `to_hex`, optionally
+            /// `0x`- or `0X`-prefixed. This is the same as parsing it with `FromStr`.
+            pub fn from_hex(s: &str) -> Result<Self, ParsePointError> {
+                s.parse()
             }
         }
     };
-    ($name:ident, $base:ident, general) => {
-        /// Unimplemented: there is no standard generator for this curve.
-        fn generator() -> Self {
-            unimplemented!()
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_serde_object {
+    ($name:ident, $name_affine:ident, $base:ident) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl SerdeObject for $name_affine {
+            const RAW_SIZE: usize = 64;
+
+            fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
+                assert_eq!(bytes.len(), Self::RAW_SIZE);
+                $name_affine {
+                    x: $base::from_raw_bytes_unchecked(&bytes[..32]),
+                    y: $base::from_raw_bytes_unchecked(&bytes[32..]),
+                }
+            }
+
+            fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+                if bytes.len() != Self::RAW_SIZE {
+                    return None;
+                }
+                let x = $base::from_raw_bytes(&bytes[..32])?;
+                let y = $base::from_raw_bytes(&bytes[32..])?;
+                let p = $name_affine { x, y };
+                bool::from(p.is_on_curve()).then(|| p)
+            }
+
+            fn to_raw_bytes(&self) -> Vec<u8> {
+                let mut res = self.x.to_raw_bytes();
+                res.extend(self.y.to_raw_bytes());
+                res
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl SerdeObject for $name {
+            const RAW_SIZE: usize = 96;
+
+            fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
+                assert_eq!(bytes.len(), Self::RAW_SIZE);
+                $name {
+                    x: $base::from_raw_bytes_unchecked(&bytes[..32]),
+                    y: $base::from_raw_bytes_unchecked(&bytes[32..64]),
+                    z: $base::from_raw_bytes_unchecked(&bytes[64..]),
+                }
+            }
+
+            fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+                if bytes.len() != Self::RAW_SIZE {
+                    return None;
+                }
+                let x = $base::from_raw_bytes(&bytes[..32])?;
+                let y = $base::from_raw_bytes(&bytes[32..64])?;
+                let z = $base::from_raw_bytes(&bytes[64..])?;
+                let p = $name { x, y, z };
+                bool::from(p.is_on_curve()).then(|| p)
+            }
+
+            fn to_raw_bytes(&self) -> Vec<u8> {
+                let mut res = self.x.to_raw_bytes();
+                res.extend(self.y.to_raw_bytes());
+                res.extend(self.z.to_raw_bytes());
+                res
+            }
+        }
+    };
+}
+
+macro_rules! impl_uncompressed_encoding {
+    ($name:ident, $name_affine:ident, $name_uncompressed:ident, $base:ident) => {
+        #[doc = concat!(
+            "The 64-byte uncompressed encoding of an [`",
+            stringify!($name_affine),
+            "`]: the little-endian encodings of the $x$ and $y$ coordinates, in that order.\n\n",
+            "The point at infinity is encoded as 64 zero bytes."
+        )]
+        #[derive(Copy, Clone)]
+        pub struct $name_uncompressed([u8; 64]);
+
+        impl fmt::Debug for $name_uncompressed {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0[..].fmt(f)
+            }
+        }
+
+        impl Default for $name_uncompressed {
+            fn default() -> Self {
+                $name_uncompressed([0; 64])
+            }
+        }
+
+        impl AsRef<[u8]> for $name_uncompressed {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl AsMut<[u8]> for $name_uncompressed {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl ConstantTimeEq for $name_uncompressed {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0[..].ct_eq(&other.0[..])
+            }
+        }
+
+        impl cmp::Eq for $name_uncompressed {}
+        impl PartialEq for $name_uncompressed {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                bool::from(self.ct_eq(other))
+            }
+        }
+
+        impl UncompressedEncoding for $name_affine {
+            type Uncompressed = $name_uncompressed;
+
+            fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self> {
+                Self::from_uncompressed_unchecked(bytes)
+                    .and_then(|p| CtOption::new(p, p.ct_is_on_curve()))
+            }
+
+            fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self> {
+                let mut xbytes = [0; 32];
+                let mut ybytes = [0; 32];
+                xbytes.copy_from_slice(&bytes.0[..32]);
+                ybytes.copy_from_slice(&bytes.0[32..]);
+
+                $base::from_repr(xbytes)
+                    .and_then(|x| $base::from_repr(ybytes).map(|y| $name_affine { x, y }))
+            }
+
+            fn to_uncompressed(&self) -> Self::Uncompressed {
+                let mut res = [0; 64];
+                res[..32].copy_from_slice(&self.x.to_repr());
+                res[32..].copy_from_slice(&self.y.to_repr());
+                $name_uncompressed(res)
+            }
         }
     };
 }
@@ -985,7 +1690,7 @@ new_curve_impl!(
     special_a0_b5
 );
 new_curve_impl!(
-    (pub(crate)),
+    (pub),
     IsoEp,
     IsoEpAffine,
     Ep,
@@ -1002,7 +1707,7 @@ new_curve_impl!(
     general
 );
 new_curve_impl!(
-    (pub(crate)),
+    (pub),
     IsoEq,
     IsoEqAffine,
     Eq,
@@ -1019,8 +1724,68 @@ new_curve_impl!(
     general
 );
 
+impl IsoEpAffine {
+    /// The generator $(1, y)$, where $y$ is even. There is no standard generator for
+    /// this curve, so this is the point with the smallest positive $x$-coordinate;
+    /// like Pallas, the curve has prime order, so it is generated by any point other
+    /// than the identity.
+    pub const GENERATOR: Self = IsoEpAffine {
+        x: Fp::from_raw([1, 0, 0, 0]),
+        y: Fp::from_raw([
+            0x80f8e76b01e1ca6e,
+            0x8f2574bfe48b5e92,
+            0x2eeb028b66027c75,
+            0x3f99326c486ee944,
+        ]),
+    };
+}
+
+impl IsoEqAffine {
+    /// The generator $(4, y)$, where $y$ is even. There is no standard generator for
+    /// this curve, so this is the point with the smallest positive $x$-coordinate;
+    /// like Vesta, the curve has prime order, so it is generated by any point other
+    /// than the identity.
+    pub const GENERATOR: Self = IsoEqAffine {
+        x: Fq::from_raw([4, 0, 0, 0]),
+        y: Fq::from_raw([
+            0xca4234c29b849fbe,
+            0x74df50641cf20103,
+            0x45eb4c64cb895cc9,
+            0x3b368024cec305b3,
+        ]),
+    };
+}
+
+impl_uncompressed_encoding!(Ep, EpAffine, EpUncompressed, Fp);
+impl_uncompressed_encoding!(Eq, EqAffine, EqUncompressed, Fq);
+impl_point_str!(EpAffine);
+impl_point_str!(EqAffine);
+#[cfg(feature = "alloc")]
+impl_serde_object!(Ep, EpAffine, Fp);
+#[cfg(feature = "alloc")]
+impl_serde_object!(Eq, EqAffine, Fq);
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl CycleCurve for Ep {
+    type Other = Eq;
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl CycleCurve for Eq {
+    type Other = Ep;
+}
+
 impl Ep {
     /// Constants used for computing the isogeny from IsoEp to Ep.
+    ///
+    /// With these constants $c_0, \ldots, c_{12}$, the isogeny maps the affine point
+    /// $(x, y)$ to $(x', y')$, where
+    /// $$
+    /// x' = \frac{c_0 x^3 + c_1 x^2 + c_2 x + c_3}{x^2 + c_4 x + c_5}, \quad
+    /// y' = y \cdot \frac{c_6 x^3 + c_7 x^2 + c_8 x + c_9}{x^3 + c_{10} x^2 + c_{11} x + c_{12}}.
+    /// $$
     pub const ISOGENY_CONSTANTS: [Fp; 13] = [
         Fp::from_raw([
             0x775f6034aaaaaaab,
@@ -1110,7 +1875,7 @@ impl Ep {
         0x4000000000000000,
     ]);
 
-    /// `(F::root_of_unity().invert().unwrap() * z).sqrt().unwrap()`
+    /// `(F::ROOT_OF_UNITY.invert().unwrap() * z).sqrt().unwrap()`
     pub const THETA: Fp = Fp::from_raw([
         0xca330bcc09ac318e,
         0x51f64fc4dc888857,
@@ -1121,6 +1886,13 @@ impl Ep {
 
 impl Eq {
     /// Constants used for computing the isogeny from IsoEq to Eq.
+    ///
+    /// With these constants $c_0, \ldots, c_{12}$, the isogeny maps the affine point
+    /// $(x, y)$ to $(x', y')$, where
+    /// $$
+    /// x' = \frac{c_0 x^3 + c_1 x^2 + c_2 x + c_3}{x^2 + c_4 x + c_5}, \quad
+    /// y' = y \cdot \frac{c_6 x^3 + c_7 x^2 + c_8 x + c_9}{x^3 + c_{10} x^2 + c_{11} x + c_{12}}.
+    /// $$
     pub const ISOGENY_CONSTANTS: [Fq; 13] = [
         Fq::from_raw([
             0x43cd42c800000001,
@@ -1210,7 +1982,7 @@ impl Eq {
         0x4000000000000000,
     ]);
 
-    /// `(F::root_of_unity().invert().unwrap() * z).sqrt().unwrap()`
+    /// `(F::ROOT_OF_UNITY.invert().unwrap() * z).sqrt().unwrap()`
     pub const THETA: Fq = Fq::from_raw([
         0x632cae9872df1b5d,
         0x38578ccadf03ac27,
@@ -1218,3 +1990,610 @@ impl Eq {
         0x2b3483a1ee9a382f,
     ]);
 }
+
+#[test]
+fn test_const_points() {
+    const PALLAS_POINTS: [EpAffine; 2] = [EpAffine::IDENTITY, EpAffine::GENERATOR];
+    const VESTA_POINTS: [EqAffine; 2] = [EqAffine::IDENTITY, EqAffine::GENERATOR];
+
+    assert_eq!(PALLAS_POINTS[0], EpAffine::identity());
+    assert_eq!(PALLAS_POINTS[1], EpAffine::generator());
+    assert_eq!(VESTA_POINTS[0], EqAffine::identity());
+    assert_eq!(VESTA_POINTS[1], EqAffine::generator());
+    assert!(bool::from(
+        EpAffine::from_bytes(&EpAffine::GENERATOR.to_bytes()).is_some()
+    ));
+    assert!(bool::from(
+        EqAffine::from_bytes(&EqAffine::GENERATOR.to_bytes()).is_some()
+    ));
+    assert!(bool::from(IsoEpAffine::IDENTITY.is_identity()));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+fn test_iso_curve<I, C>(iso_map: fn(&I) -> C)
+where
+    I: CurveExt,
+    C: CurveExt,
+{
+    let g = I::generator();
+    assert!(bool::from(g.is_on_curve()));
+    assert!(!bool::from(g.is_identity()));
+    assert_eq!(I::AffineExt::generator().to_curve(), g);
+    assert_eq!(
+        I::AffineExt::from_bytes(&g.to_affine().to_bytes()).unwrap(),
+        g.to_affine()
+    );
+
+    // The endomorphism commutes with the isogeny.
+    let p = g.double() + g;
+    assert_eq!(iso_map(&p.endo()), iso_map(&p).endo());
+    assert_eq!(p.endo().endo().endo(), p);
+
+    let hash = I::hash_to_curve("iso test");
+    let q = hash(b"message");
+    assert!(bool::from(q.is_on_curve()));
+    assert!(bool::from(iso_map(&q).is_on_curve()));
+    assert_ne!(q, hash(b"other message"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iso_curve_pallas() {
+    test_iso_curve::<IsoEp, Ep>(Ep::iso_map);
+
+    let mut hasher = crate::CurveHasher::<IsoEp>::new("iso test");
+    hasher.update(b"mess").update(b"age");
+    assert_eq!(
+        hasher.finalize(),
+        IsoEp::hash_to_curve("iso test")(b"message")
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iso_curve_vesta() {
+    test_iso_curve::<IsoEq, Eq>(Eq::iso_map);
+
+    let mut hasher = crate::CurveHasher::<IsoEq>::new("iso test");
+    hasher.update(b"mess").update(b"age");
+    assert_eq!(
+        hasher.finalize(),
+        IsoEq::hash_to_curve("iso test")(b"message")
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cycle_curve() {
+    // Multiplies by the x-coordinate of the other curve's generator, which is only
+    // possible because the other curve's base field is this curve's scalar field.
+    fn mul_by_other_x<C: CycleCurve>(p: C) -> C {
+        let (x, _, _) = C::Other::generator().jacobian_coordinates();
+        p * x
+    }
+
+    // The other curve's other curve is the curve itself.
+    fn round_trip<C: CycleCurve>(p: C) -> C {
+        mul_by_other_x::<<C::Other as CycleCurve>::Other>(p)
+    }
+
+    assert_eq!(
+        mul_by_other_x(Ep::generator()),
+        Ep::generator() * Eq::generator().jacobian_coordinates().0
+    );
+    assert_eq!(
+        round_trip(Eq::generator()),
+        Eq::generator() * Ep::generator().jacobian_coordinates().0
+    );
+}
+
+#[cfg(test)]
+fn test_batch_normalize<C: group::Curve>()
+where
+    C::AffineRepr: Copy + Default + core::fmt::Debug + PartialEq,
+{
+    let mut rng = crate::tests::rng();
+
+    let mut p = [C::identity(); 8];
+    for (i, p) in p.iter_mut().enumerate() {
+        // Leave some identities in the batch, which must be skipped.
+        if i % 3 != 0 {
+            *p = C::random(&mut rng).double();
+        }
+    }
+
+    let mut q = [C::AffineRepr::default(); 8];
+    C::batch_normalize(&p, &mut q);
+
+    for (p, q) in p.iter().zip(q.iter()) {
+        assert_eq!(p.to_affine(), *q);
+    }
+}
+
+#[test]
+fn test_batch_normalize_pallas() {
+    test_batch_normalize::<Ep>();
+}
+
+#[test]
+fn test_batch_normalize_vesta() {
+    test_batch_normalize::<Eq>();
+}
+
+#[cfg(test)]
+fn test_uncompressed_encoding<C: PrimeCurveAffine + UncompressedEncoding + core::fmt::Debug>() {
+    let mut rng = crate::tests::rng();
+
+    for _ in 0..100 {
+        let p = C::Curve::random(&mut rng).to_affine();
+        let bytes = p.to_uncompressed();
+        assert_eq!(bytes.as_ref().len(), 64);
+        assert_eq!(C::from_uncompressed(&bytes).unwrap(), p);
+        assert_eq!(C::from_uncompressed_unchecked(&bytes).unwrap(), p);
+    }
+
+    let identity = C::identity().to_uncompressed();
+    assert!(identity.as_ref().iter().all(|b| *b == 0));
+    assert!(bool::from(
+        C::from_uncompressed(&identity).unwrap().is_identity()
+    ));
+
+    // (1, 1) is not on the curve, so only the unchecked decoding accepts it.
+    let mut off_curve = C::Uncompressed::default();
+    off_curve.as_mut()[0] = 1;
+    off_curve.as_mut()[32] = 1;
+    assert!(bool::from(C::from_uncompressed(&off_curve).is_none()));
+    assert!(bool::from(
+        C::from_uncompressed_unchecked(&off_curve).is_some()
+    ));
+
+    // Non-canonical coordinates are rejected.
+    let mut non_canonical = C::Uncompressed::default();
+    non_canonical.as_mut()[..32].copy_from_slice(&[0xff; 32]);
+    assert!(bool::from(
+        C::from_uncompressed_unchecked(&non_canonical).is_none()
+    ));
+}
+
+#[test]
+fn test_uncompressed_encoding_pallas() {
+    test_uncompressed_encoding::<EpAffine>();
+}
+
+#[test]
+fn test_uncompressed_encoding_vesta() {
+    test_uncompressed_encoding::<EqAffine>();
+}
+
+#[cfg(test)]
+fn test_point_str<C>()
+where
+    C: PrimeCurveAffine + FromStr<Err = ParsePointError> + fmt::Display,
+{
+    use group::Group;
+
+    let mut rng = crate::tests::rng();
+
+    for _ in 0..10 {
+        let p = C::Curve::random(&mut rng).to_affine();
+        let s = format!("{}", p);
+        assert_eq!(s.len(), 66);
+        assert_eq!(s.parse::<C>(), Ok(p));
+        // The prefix is optional, and uppercase digits and prefixes are accepted.
+        assert_eq!(s[2..].parse::<C>(), Ok(p));
+        assert_eq!(s[2..].to_uppercase().parse::<C>(), Ok(p));
+        assert_eq!(s.to_uppercase().parse::<C>(), Ok(p));
+    }
+
+    let identity = format!("0x{}", "0".repeat(64));
+    assert_eq!(format!("{}", C::identity()), identity);
+    assert_eq!(identity.parse::<C>(), Ok(C::identity()));
+
+    let generator = format!("{}", C::generator());
+    assert_eq!(
+        generator[..64].parse::<C>(),
+        Err(ParsePointError::InvalidLength)
+    );
+    assert_eq!(
+        format!("{}00", generator).parse::<C>(),
+        Err(ParsePointError::InvalidLength)
+    );
+    assert_eq!(
+        format!("0x{}g", &generator[3..]).parse::<C>(),
+        Err(ParsePointError::InvalidDigit)
+    );
+    assert_eq!(
+        format!("0x{}", "f".repeat(64)).parse::<C>(),
+        Err(ParsePointError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_point_str_pallas() {
+    test_point_str::<EpAffine>();
+}
+
+#[test]
+fn test_point_str_vesta() {
+    test_point_str::<EqAffine>();
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+fn test_batch_from_bytes<C>(batch_from_bytes: fn(&[[u8; 32]]) -> Vec<CtOption<C>>)
+where
+    C: PrimeCurveAffine + GroupEncoding<Repr = [u8; 32]>,
+{
+    use group::Group;
+
+    let mut rng = crate::tests::rng();
+
+    let mut bytes: Vec<[u8; 32]> = (0..20)
+        .map(|_| C::Curve::random(&mut rng).to_affine().to_bytes())
+        .collect();
+    bytes.push(C::identity().to_bytes());
+    // The identity with the sign bit set, an x-coordinate that is not canonical,
+    // and (most likely) an x-coordinate that is not on the curve.
+    bytes.push({
+        let mut b = [0; 32];
+        b[31] = 0x80;
+        b
+    });
+    bytes.push([0xff; 32]);
+    bytes.extend((0..10u8).map(|i| {
+        let mut b = [0; 32];
+        b[0] = i + 1;
+        b
+    }));
+
+    let decoded = batch_from_bytes(&bytes);
+    assert_eq!(decoded.len(), bytes.len());
+    for (bytes, decoded) in bytes.iter().zip(decoded.iter()) {
+        let expected = C::from_bytes(bytes);
+        assert_eq!(
+            bool::from(decoded.is_some()),
+            bool::from(expected.is_some())
+        );
+        if bool::from(expected.is_some()) {
+            assert!(decoded.unwrap() == expected.unwrap());
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_from_bytes_pallas() {
+    test_batch_from_bytes::<EpAffine>(EpAffine::batch_from_bytes);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_from_bytes_vesta() {
+    test_batch_from_bytes::<EqAffine>(EqAffine::batch_from_bytes);
+}
+
+#[cfg(test)]
+fn test_mul_blinded<C: group::Group>(
+    mul_blinded: fn(&C, &C::Scalar, &mut rand_xorshift::XorShiftRng) -> C,
+) {
+    let mut rng = crate::tests::rng();
+
+    for _ in 0..10 {
+        let p = C::random(&mut rng);
+        let k = C::Scalar::random(&mut rng);
+        assert_eq!(mul_blinded(&p, &k, &mut rng), p * k);
+    }
+
+    let p = C::generator();
+    assert!(bool::from(
+        mul_blinded(&p, &C::Scalar::ZERO, &mut rng).is_identity()
+    ));
+    assert_eq!(mul_blinded(&p, &C::Scalar::ONE, &mut rng), p);
+    assert!(bool::from(
+        mul_blinded(&C::identity(), &C::Scalar::ONE, &mut rng).is_identity()
+    ));
+}
+
+#[test]
+fn test_mul_blinded_pallas() {
+    test_mul_blinded::<Ep>(|p, k, rng| p.mul_blinded(k, rng));
+}
+
+#[test]
+fn test_mul_blinded_vesta() {
+    test_mul_blinded::<Eq>(|p, k, rng| p.mul_blinded(k, rng));
+}
+
+#[cfg(test)]
+fn test_add_affine<C: PrimeCurve>(add_affine: fn(&C, &C::Affine) -> C) {
+    let mut rng = crate::tests::rng();
+
+    for _ in 0..10 {
+        let p = C::random(&mut rng);
+        let q = C::random(&mut rng);
+        assert_eq!(add_affine(&p, &q.to_affine()), p + q);
+    }
+
+    let p = C::random(&mut rng);
+    let identity = C::Affine::identity();
+    assert_eq!(add_affine(&p, &p.to_affine()), p.double());
+    assert!(bool::from(add_affine(&-p, &p.to_affine()).is_identity()));
+    assert_eq!(add_affine(&p, &identity), p);
+    assert_eq!(add_affine(&C::identity(), &p.to_affine()), p);
+}
+
+#[test]
+fn test_add_affine_pallas() {
+    test_add_affine::<Ep>(Ep::add_affine);
+}
+
+#[test]
+fn test_add_affine_vesta() {
+    test_add_affine::<Eq>(Eq::add_affine);
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+fn test_batch_add<C: CurveAffine>() {
+    use group::Group;
+
+    let mut rng = crate::tests::rng();
+
+    let p = C::CurveExt::random(&mut rng).to_affine();
+    let mut lhs = vec![p, p, p, C::identity(), C::identity(), p];
+    let mut rhs = vec![p, -p, C::identity(), p, C::identity(), C::identity()];
+    for _ in 0..10 {
+        lhs.push(C::CurveExt::random(&mut rng).to_affine());
+        rhs.push(C::CurveExt::random(&mut rng).to_affine());
+    }
+
+    let expected: Vec<C> = lhs
+        .iter()
+        .zip(rhs.iter())
+        .map(|(a, b)| (*a + *b).to_affine())
+        .collect();
+    C::batch_add(&mut lhs, &rhs);
+    assert_eq!(lhs, expected);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_add_pallas() {
+    test_batch_add::<EpAffine>();
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_add_vesta() {
+    test_batch_add::<EqAffine>();
+}
+
+/// Returns 16 valid encodings, followed by 16 encodings of each kind that
+/// `ct_decode` rejects: x-coordinates that are not on the curve, non-canonical
+/// x-coordinates, and the identity with the sign bit set.
+#[cfg(test)]
+fn ct_decode_classes<C>(ct_decode: fn(&[u8; 32]) -> CtOption<C>) -> [std::vec::Vec<[u8; 32]>; 4]
+where
+    C: PrimeCurveAffine + GroupEncoding<Repr = [u8; 32]>,
+{
+    use group::Group;
+
+    let mut rng = crate::tests::rng();
+
+    let valid: std::vec::Vec<[u8; 32]> = (0..16)
+        .map(|_| C::Curve::random(&mut rng).to_affine().to_bytes())
+        .collect();
+    // About half of the x-coordinates are not on the curve.
+    let off_curve: std::vec::Vec<[u8; 32]> = (1..=255u8)
+        .map(|i| {
+            let mut b = [0; 32];
+            b[0] = i;
+            b
+        })
+        .filter(|b| bool::from(ct_decode(b).is_none()))
+        .take(16)
+        .collect();
+    let non_canonical = vec![[0xff; 32]; 16];
+    let identity_with_sign = vec![
+        {
+            let mut b = [0; 32];
+            b[31] = 0x80;
+            b
+        };
+        16
+    ];
+    [valid, off_curve, non_canonical, identity_with_sign]
+}
+
+/// Checks that `ct_decode` decodes valid encodings and rejects each kind of
+/// invalid one.
+#[cfg(test)]
+fn test_ct_decode<C>(ct_decode: fn(&[u8; 32]) -> CtOption<C>)
+where
+    C: PrimeCurveAffine + GroupEncoding<Repr = [u8; 32]>,
+{
+    let classes = ct_decode_classes(ct_decode);
+    for b in &classes[0] {
+        assert_eq!(ct_decode(b).unwrap().to_bytes(), *b);
+    }
+    for class in &classes[1..] {
+        assert_eq!(class.len(), 16);
+        assert!(class.iter().all(|b| bool::from(ct_decode(b).is_none())));
+    }
+}
+
+#[test]
+fn test_ct_decode_pallas() {
+    test_ct_decode::<EpAffine>(EpAffine::ct_decode);
+}
+
+#[test]
+fn test_ct_decode_vesta() {
+    test_ct_decode::<EqAffine>(EqAffine::ct_decode);
+}
+
+/// Checks that decoding valid and invalid encodings takes about the same time.
+///
+/// This is a coarse check that the invalid cases do not return early, which would
+/// make them many times faster than the valid ones, so it only requires the times
+/// to be within a factor of two of each other. It takes the fastest decoding of
+/// each kind of encoding over several interleaved rounds, as noise from other tests
+/// running concurrently only ever adds time.
+///
+/// Timings are unreliable on shared machines, so the tests using this are ignored
+/// by default; run them with `cargo test -- --ignored`, or compare the decoding
+/// benchmarks in `benches/point.rs`.
+#[cfg(test)]
+fn test_ct_decode_timing<C>(ct_decode: fn(&[u8; 32]) -> CtOption<C>)
+where
+    C: PrimeCurveAffine + GroupEncoding<Repr = [u8; 32]>,
+{
+    use std::time::{Duration, Instant};
+
+    let classes = ct_decode_classes(ct_decode);
+    let mut fastest = [Duration::from_secs(u64::MAX); 4];
+    for _ in 0..20 {
+        for j in 0..16 {
+            for (i, class) in classes.iter().enumerate() {
+                let start = Instant::now();
+                let decoded = ct_decode(&class[j]);
+                fastest[i] = fastest[i].min(start.elapsed());
+                // Use the result, so that the decoding is not optimized away.
+                assert_eq!(bool::from(decoded.is_some()), i == 0);
+            }
+        }
+    }
+
+    let min = fastest.iter().min().unwrap().as_nanos() as f64;
+    let max = fastest.iter().max().unwrap().as_nanos() as f64;
+    assert!(max / min < 2.0, "decoding times differ: {:?}", fastest);
+}
+
+#[test]
+#[ignore]
+fn test_ct_decode_timing_pallas() {
+    test_ct_decode_timing::<EpAffine>(EpAffine::ct_decode);
+}
+
+#[test]
+#[ignore]
+fn test_ct_decode_timing_vesta() {
+    test_ct_decode_timing::<EqAffine>(EqAffine::ct_decode);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut p = Ep::generator();
+    p.zeroize();
+    assert!(bool::from(p.is_identity()));
+
+    let mut p = EqAffine::generator();
+    p.zeroize();
+    assert!(bool::from(p.is_identity()));
+}
+
+#[test]
+fn test_hash_affine() {
+    use std::collections::HashSet;
+
+    let g = Ep::generator();
+    let mut set = HashSet::new();
+    assert!(set.insert(g.double().to_affine()));
+    assert!(set.insert(EpAffine::identity()));
+    // Projective points with different coordinates give the same affine key.
+    assert!(!set.insert((g + g).to_affine()));
+    assert!(!set.insert((g * Fq::zero()).to_affine()));
+    assert_eq!(set.len(), 2);
+
+    let h = EqAffine::generator();
+    let set: HashSet<_> = [h, (h.to_curve() * Fp::one()).to_affine()].into();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn test_sum() {
+    let g = Ep::generator();
+    let points = [g, g.double(), Ep::identity(), -g];
+    let affine = [g.to_affine(), g.double().to_affine(), EpAffine::identity()];
+    assert_eq!(points.iter().sum::<Ep>(), g.double());
+    assert_eq!(points.into_iter().sum::<Ep>(), g.double());
+    assert_eq!(affine.iter().sum::<Ep>(), g * Fq::from(3u64));
+    assert_eq!(affine.into_iter().sum::<Ep>(), g * Fq::from(3u64));
+    assert_eq!(core::iter::empty::<EpAffine>().sum::<Ep>(), Ep::identity());
+
+    let h = EqAffine::generator();
+    assert_eq!([h, h].iter().sum::<Eq>(), h.to_curve().double());
+}
+
+#[test]
+fn test_standard_distribution() {
+    use crate::tests::rng;
+    use rand::Rng;
+
+    let p: Ep = rng().gen();
+    assert_eq!(p, Ep::random(rng()));
+    let p: EpAffine = rng().gen();
+    assert_eq!(p, Ep::random(rng()).to_affine());
+
+    let p: EqAffine = rng().gen();
+    assert_eq!(p, Eq::random(rng()).to_affine());
+}
+
+#[test]
+fn test_point_hex() {
+    let g = EpAffine::generator();
+    let hex = "00000000ed302d991bf94c09fc98462200000000000000000000000000000040";
+    assert_eq!(EpAffine::from_hex(hex), Ok(g));
+    assert_eq!(EpAffine::from_hex(&std::format!("0x{}", hex)), Ok(g));
+    #[cfg(feature = "alloc")]
+    {
+        assert_eq!(g.to_hex(), hex);
+        let h = (EqAffine::generator() * Fp::from(7u64)).to_affine();
+        assert_eq!(EqAffine::from_hex(&h.to_hex()), Ok(h));
+    }
+
+    assert_eq!(
+        EpAffine::from_hex(&hex[2..]),
+        Err(ParsePointError::InvalidLength)
+    );
+    assert_eq!(
+        EqAffine::from_hex(&"f".repeat(64)),
+        Err(ParsePointError::InvalidEncoding)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+fn test_add_mixed_ct<C: CurveExt>() {
+    let mut rng = crate::tests::rng();
+
+    let p = C::random(&mut rng);
+    let q = C::random(&mut rng).to_affine();
+    let identity = C::AffineExt::identity();
+    for (lhs, rhs) in [
+        (p, q),
+        (p, p.to_affine()),
+        (p, (-p).to_affine()),
+        (p, identity),
+        (C::identity(), q),
+        (C::identity(), identity),
+    ] {
+        assert_eq!(lhs.add_mixed_ct(&rhs), lhs + rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_add_mixed_ct_pallas() {
+    test_add_mixed_ct::<Ep>();
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_add_mixed_ct_vesta() {
+    test_add_mixed_ct::<Eq>();
+}
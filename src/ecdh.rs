@@ -0,0 +1,133 @@
+//! Elliptic-curve Diffie–Hellman key agreement over the Pallas curve.
+//!
+//! A party with the [`SecretKey`] $x \in \mathbb{F}_q$ and the other party's
+//! [`PublicKey`] $P$ computes the shared secret $[x] P$. The peer's public key is
+//! validated before use: it must be on the curve, and it cannot be the identity.
+//! As Pallas has prime order, this rules out small-subgroup attacks.
+//!
+//! The shared secret is a curve point, and should not be used directly as a key.
+//! [`SharedSecret::derive_key`] passes its encoding to a key derivation function
+//! of the caller's choice, and [`SharedSecret::to_key`] derives a 32-byte key with
+//! BLAKE2b.
+
+use core::fmt;
+
+use group::{prime::PrimeCurveAffine, Curve, GroupEncoding};
+use subtle::CtOption;
+
+use crate::keys::{PublicKey, SecretKey};
+use crate::pallas;
+
+/// The BLAKE2b personalization of the key derivation function used by
+/// [`SharedSecret::to_key`].
+const KDF_PERSONALIZATION: &[u8; 16] = b"fil_pasta_ECDH_K";
+
+/// A shared secret produced by [`diffie_hellman`].
+///
+/// With the `zeroize` feature flag, the secret is zeroized when dropped.
+#[derive(Clone)]
+pub struct SharedSecret(pallas::Affine);
+
+impl fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedSecret").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+impl SharedSecret {
+    /// Returns the compressed encoding of the shared point.
+    ///
+    /// This is not uniformly distributed, and should be passed through a key
+    /// derivation function before being used as a key.
+    pub fn raw_secret_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Derives a key by passing the compressed encoding of the shared point to
+    /// `kdf`.
+    pub fn derive_key<T, K: FnOnce(&[u8; 32]) -> T>(&self, kdf: K) -> T {
+        kdf(&self.raw_secret_bytes())
+    }
+
+    /// Derives a 32-byte key bound to the context string `info`, by hashing the
+    /// compressed encoding of the shared point and `info` with BLAKE2b.
+    pub fn to_key(&self, info: &[u8]) -> [u8; 32] {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(KDF_PERSONALIZATION)
+            .to_state()
+            .update(&self.raw_secret_bytes())
+            .update(info)
+            .finalize();
+
+        let mut key = [0; 32];
+        key.copy_from_slice(hash.as_bytes());
+        key
+    }
+}
+
+/// Computes the shared secret between the secret key `sk` and the peer's public
+/// key `pk`.
+///
+/// This cannot fail, as every [`PublicKey`] is on the curve and is not the
+/// identity.
+pub fn diffie_hellman(sk: &SecretKey, pk: &PublicKey) -> SharedSecret {
+    SharedSecret((*pk.as_point() * sk.as_scalar()).to_affine())
+}
+
+/// Computes the shared secret between the secret key `sk` and the peer's public
+/// key encoded as `pk`.
+///
+/// Fails if `pk` is not a valid point encoding or encodes the identity.
+pub fn diffie_hellman_bytes(sk: &SecretKey, pk: &[u8; 32]) -> CtOption<SharedSecret> {
+    // Decoded points are on the curve, so only the identity needs to be rejected.
+    pallas::Affine::from_bytes(pk).and_then(|pk| {
+        let shared = (pk * sk.as_scalar()).to_affine();
+        CtOption::new(SharedSecret(shared), !pk.is_identity())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{prime::PrimeCurveAffine, GroupEncoding};
+
+    use super::{diffie_hellman, diffie_hellman_bytes};
+    use crate::keys::SecretKey;
+    use crate::pallas;
+
+    #[test]
+    fn test_diffie_hellman() {
+        let mut rng = crate::tests::rng();
+
+        let a = SecretKey::random(&mut rng);
+        let b = SecretKey::random(&mut rng);
+        let ab = diffie_hellman(&a, &b.public_key());
+        let ba = diffie_hellman_bytes(&b, &a.public_key().to_bytes()).unwrap();
+        assert_eq!(ab.raw_secret_bytes(), ba.raw_secret_bytes());
+        assert_eq!(ab.to_key(b"info"), ba.to_key(b"info"));
+        assert_ne!(ab.to_key(b"info"), ab.to_key(b"other"));
+        assert_eq!(ab.derive_key(|bytes| bytes[0]), ba.raw_secret_bytes()[0]);
+
+        let c = SecretKey::random(&mut rng);
+        let ac = diffie_hellman(&a, &c.public_key());
+        assert_ne!(ab.to_key(b"info"), ac.to_key(b"info"));
+    }
+
+    #[test]
+    fn test_diffie_hellman_rejects_invalid_keys() {
+        let mut rng = crate::tests::rng();
+
+        let a = SecretKey::random(&mut rng);
+        assert!(bool::from(
+            diffie_hellman_bytes(&a, &pallas::Affine::identity().to_bytes()).is_none()
+        ));
+        assert!(bool::from(diffie_hellman_bytes(&a, &[0xff; 32]).is_none()));
+    }
+}
@@ -0,0 +1,446 @@
+//! ECDSA signatures over the Pallas and Vesta curves.
+//!
+//! The functions in this module are generic over the curve, which is selected by
+//! its affine point type, e.g. `ecdsa::sign::<pallas::Affine>(..)`. A signature
+//! on the message hash $e$ under the public key $Q = [d] G$ is a pair $(r, s)$,
+//! where $r$ is the x-coordinate of $R = [k] G$, reduced modulo the scalar field
+//! modulus $n$, and $s = k^{-1} (e + r d)$. The nonce $k$ is derived
+//! deterministically from $d$ and $e$ in the style of RFC 6979, so signing needs
+//! no randomness.
+//!
+//! Signing always produces a "low" $s$, at most $(n - 1) / 2$, and [`verify`]
+//! rejects signatures with a high $s$, so signatures are not malleable. Signing
+//! also returns a [`RecoveryId`], with which [`recover`] computes the public key
+//! of a signature.
+//!
+//! Messages are hashed into the scalar field with the crate's `hash_to_field`,
+//! using the domain prefix [`MESSAGE_DOMAIN`]. The `_prehashed` functions take
+//! the message hash $e$ directly.
+//!
+//! Over Pallas, [`sign_with_key`], [`verify_with_key`] and [`recover_key`] take and
+//! return the [`SecretKey`] and [`PublicKey`] types used by the other signature
+//! modules.
+
+use ff::{Field, PrimeField};
+use group::Curve;
+use subtle::CtOption;
+
+use crate::arithmetic::{CurveAffine, CurveExt, FieldExt};
+use crate::hashtocurve::hash_to_field;
+use crate::keys::{PublicKey, SecretKey};
+use crate::nonce::derive_nonce;
+use crate::pallas;
+
+/// The domain prefix used to hash messages.
+pub const MESSAGE_DOMAIN: &str = "fil_pasta_curves:ecdsa";
+
+/// The BLAKE2b personalization used to derive nonces.
+const NONCE_PERSONALIZATION: &[u8; 16] = b"fil_pasta_DSA_k_";
+
+/// An ECDSA signature over the curve `C`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature<C: CurveAffine> {
+    r: C::ScalarExt,
+    s: C::ScalarExt,
+}
+
+impl<C: CurveAffine> Signature<C> {
+    /// Returns $r$.
+    pub fn r(&self) -> &C::ScalarExt {
+        &self.r
+    }
+
+    /// Returns $s$.
+    pub fn s(&self) -> &C::ScalarExt {
+        &self.s
+    }
+
+    /// Returns whether $s$ is at most $(n - 1) / 2$.
+    pub fn is_low_s(&self) -> bool {
+        self.s <= -C::ScalarExt::TWO_INV
+    }
+
+    /// Returns this signature with $s$ replaced by $n - s$ if it is high. Both
+    /// signatures are valid under the same public key, but only the normalized one
+    /// is accepted by [`verify`].
+    pub fn normalize_s(&self) -> Self {
+        if self.is_low_s() {
+            *self
+        } else {
+            Signature {
+                r: self.r,
+                s: -self.s,
+            }
+        }
+    }
+
+    /// Returns the encoding of this signature, which is the little-endian encoding
+    /// of $r$ followed by the little-endian encoding of $s$.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut res = [0; 64];
+        res[..32].copy_from_slice(self.r.to_repr().as_ref());
+        res[32..].copy_from_slice(self.s.to_repr().as_ref());
+        res
+    }
+
+    /// Parses a signature from its encoding, failing if $r$ or $s$ is zero or not
+    /// canonical.
+    pub fn from_bytes(bytes: &[u8; 64]) -> CtOption<Self> {
+        let mut r = <C::ScalarExt as PrimeField>::Repr::default();
+        r.as_mut().copy_from_slice(&bytes[..32]);
+        let mut s = <C::ScalarExt as PrimeField>::Repr::default();
+        s.as_mut().copy_from_slice(&bytes[32..]);
+
+        C::ScalarExt::from_repr(r).and_then(|r| {
+            C::ScalarExt::from_repr(s)
+                .and_then(|s| CtOption::new(Signature { r, s }, !(r.is_zero() | s.is_zero())))
+        })
+    }
+}
+
+/// Identifies which of the (up to four) points with a given $r$ was the nonce
+/// commitment $R$ of a signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecoveryId(u8);
+
+impl RecoveryId {
+    /// Constructs a recovery ID from its two flags.
+    pub fn new(is_y_odd: bool, is_x_reduced: bool) -> Self {
+        RecoveryId(is_y_odd as u8 | (is_x_reduced as u8) << 1)
+    }
+
+    /// Returns whether the y-coordinate of $R$ is odd.
+    pub fn is_y_odd(&self) -> bool {
+        self.0 & 1 != 0
+    }
+
+    /// Returns whether the x-coordinate of $R$ was not less than $n$, so that $r$
+    /// is the x-coordinate minus $n$.
+    ///
+    /// This can only happen on Vesta, whose base field is larger than its scalar
+    /// field, and then only with negligible probability.
+    pub fn is_x_reduced(&self) -> bool {
+        self.0 & 2 != 0
+    }
+
+    /// Returns the byte encoding of this recovery ID, between 0 and 3.
+    pub fn to_byte(&self) -> u8 {
+        self.0
+    }
+
+    /// Parses a recovery ID from its byte encoding.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        if byte < 4 {
+            Some(RecoveryId(byte))
+        } else {
+            None
+        }
+    }
+}
+
+/// Hashes `msg` into the scalar field of `C`.
+pub fn hash_message<C: CurveAffine>(msg: &[u8]) -> C::ScalarExt {
+    let mut e = [C::ScalarExt::ZERO];
+    hash_to_field(C::CurveExt::CURVE_ID, MESSAGE_DOMAIN, msg, &mut e);
+    e[0]
+}
+
+/// Reduces the x-coordinate of a (non-identity) point modulo $n$, returning it with
+/// its recovery ID.
+fn reduce_x<C: CurveAffine>(point: &C) -> (C::ScalarExt, RecoveryId) {
+    let coordinates = point.coordinates().unwrap();
+    let x = coordinates.x().to_repr();
+
+    let mut repr = <C::ScalarExt as PrimeField>::Repr::default();
+    repr.as_mut().copy_from_slice(x.as_ref());
+    let is_x_reduced = bool::from(C::ScalarExt::from_repr(repr).is_none());
+
+    let mut wide = [0; 64];
+    wide[..32].copy_from_slice(x.as_ref());
+    let r = C::ScalarExt::from_bytes_wide(&wide);
+
+    let is_y_odd = bool::from(coordinates.y().is_odd());
+    (r, RecoveryId::new(is_y_odd, is_x_reduced))
+}
+
+/// Returns the public key $[d] G$ for the secret key $d$.
+pub fn public_key<C: CurveAffine>(sk: &C::ScalarExt) -> C {
+    (C::generator() * *sk).to_affine()
+}
+
+/// Signs `msg` with the secret key `sk`.
+///
+/// Signing is deterministic: the same key and message always give the same
+/// signature.
+pub fn sign<C: CurveAffine>(sk: &C::ScalarExt, msg: &[u8]) -> (Signature<C>, RecoveryId) {
+    sign_prehashed(sk, &hash_message::<C>(msg))
+}
+
+/// Signs the message hash `e` with the secret key `sk`.
+///
+/// Signing is deterministic: the same key and message hash always give the same
+/// signature.
+pub fn sign_prehashed<C: CurveAffine>(
+    sk: &C::ScalarExt,
+    e: &C::ScalarExt,
+) -> (Signature<C>, RecoveryId) {
+    let e_repr = e.to_repr();
+    // As in RFC 6979, a counter gives a fresh nonce in the unlikely event that r or
+    // s is zero.
+    let mut counter = 0u32;
+    loop {
+        let k = derive_nonce(
+            NONCE_PERSONALIZATION,
+            sk,
+            &[e_repr.as_ref(), &counter.to_le_bytes()],
+        );
+        counter += 1;
+        let (r, recid) = reduce_x(&(C::generator() * k).to_affine());
+        if bool::from(r.is_zero()) {
+            continue;
+        }
+        let s = k.invert().unwrap() * (*e + r * sk);
+        if bool::from(s.is_zero()) {
+            continue;
+        }
+
+        // Negating s negates R, flipping the parity of its y-coordinate.
+        let sig = Signature { r, s };
+        return if sig.is_low_s() {
+            (sig, recid)
+        } else {
+            (
+                sig.normalize_s(),
+                RecoveryId::new(!recid.is_y_odd(), recid.is_x_reduced()),
+            )
+        };
+    }
+}
+
+/// Verifies the signature `sig` on `msg` under the public key `pk`.
+///
+/// The identity is rejected as a public key, as are signatures with a high $s$.
+/// This is not constant time.
+pub fn verify<C: CurveAffine>(pk: &C, msg: &[u8], sig: &Signature<C>) -> bool {
+    verify_prehashed(pk, &hash_message::<C>(msg), sig)
+}
+
+/// Verifies the signature `sig` on the message hash `e` under the public key `pk`.
+///
+/// The identity is rejected as a public key, as are signatures with a high $s$.
+/// This is not constant time.
+pub fn verify_prehashed<C: CurveAffine>(pk: &C, e: &C::ScalarExt, sig: &Signature<C>) -> bool {
+    if bool::from(pk.is_identity() | sig.r.is_zero() | sig.s.is_zero()) || !sig.is_low_s() {
+        return false;
+    }
+
+    let s_inv = sig.s.invert().unwrap();
+    let r = (C::generator() * (*e * s_inv) + *pk * (sig.r * s_inv)).to_affine();
+    if bool::from(r.is_identity()) {
+        return false;
+    }
+    reduce_x(&r).0 == sig.r
+}
+
+/// Recovers the public key under which `sig` is a signature on `msg`, given the
+/// recovery ID returned when signing. Returns `None` if there is no such key.
+///
+/// This is not constant time.
+pub fn recover<C: CurveAffine>(msg: &[u8], sig: &Signature<C>, recid: RecoveryId) -> Option<C> {
+    recover_prehashed(&hash_message::<C>(msg), sig, recid)
+}
+
+/// Recovers the public key under which `sig` is a signature on the message hash
+/// `e`, given the recovery ID returned when signing. Returns `None` if there is no
+/// such key.
+///
+/// This is not constant time.
+pub fn recover_prehashed<C: CurveAffine>(
+    e: &C::ScalarExt,
+    sig: &Signature<C>,
+    recid: RecoveryId,
+) -> Option<C> {
+    if bool::from(sig.r.is_zero() | sig.s.is_zero()) || !sig.is_low_s() {
+        return None;
+    }
+
+    // The x-coordinate of R is r, or r + n if it was reduced.
+    let mut x = [0u8; 32];
+    x.copy_from_slice(sig.r.to_repr().as_ref());
+    if recid.is_x_reduced() {
+        let mut n = [0u8; 32];
+        n.copy_from_slice((-C::ScalarExt::ONE).to_repr().as_ref());
+        let mut carry = 1u16;
+        for (x, n) in x.iter_mut().zip(n.iter()) {
+            let sum = *x as u16 + *n as u16 + carry;
+            *x = sum as u8;
+            carry = sum >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+
+    // The compressed encoding of R sets the top bit when y is odd. A set top bit in
+    // x itself makes the x-coordinate non-canonical.
+    if x[31] >> 7 != 0 {
+        return None;
+    }
+    x[31] |= (recid.is_y_odd() as u8) << 7;
+    let mut repr = C::Repr::default();
+    repr.as_mut().copy_from_slice(&x);
+    let big_r: C = Option::from(C::from_bytes(&repr))?;
+    if bool::from(big_r.is_identity()) {
+        return None;
+    }
+
+    let r_inv = sig.r.invert().unwrap();
+    let pk = ((big_r * sig.s - C::generator() * *e) * r_inv).to_affine();
+    if bool::from(pk.is_identity()) {
+        None
+    } else {
+        Some(pk)
+    }
+}
+
+/// Signs `msg` over Pallas with the secret key `sk`, like [`sign`].
+pub fn sign_with_key(sk: &SecretKey, msg: &[u8]) -> (Signature<pallas::Affine>, RecoveryId) {
+    sign(sk.as_scalar(), msg)
+}
+
+/// Verifies the signature `sig` on `msg` over Pallas under the public key `pk`,
+/// like [`verify`].
+pub fn verify_with_key(pk: &PublicKey, msg: &[u8], sig: &Signature<pallas::Affine>) -> bool {
+    verify(pk.as_point(), msg, sig)
+}
+
+/// Recovers the public key under which `sig` is a signature on `msg` over Pallas,
+/// like [`recover`].
+pub fn recover_key(
+    msg: &[u8],
+    sig: &Signature<pallas::Affine>,
+    recid: RecoveryId,
+) -> Option<PublicKey> {
+    // `recover` never returns the identity.
+    recover(msg, sig, recid).map(|pk| PublicKey::from_point(pk).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+
+    use super::{
+        hash_message, public_key, recover, recover_key, recover_prehashed, sign, sign_prehashed,
+        sign_with_key, verify, verify_prehashed, verify_with_key, RecoveryId, Signature,
+    };
+    use crate::arithmetic::CurveAffine;
+    use crate::keys::SecretKey;
+    use crate::{pallas, vesta};
+
+    fn check_curve<C: CurveAffine>() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..20 {
+            let sk = C::ScalarExt::random(&mut rng);
+            let pk = public_key::<C>(&sk);
+            let (sig, recid) = sign::<C>(&sk, b"hello");
+            assert!(sig.is_low_s());
+            assert!(verify(&pk, b"hello", &sig));
+            assert_eq!(recover(b"hello", &sig, recid), Some(pk));
+
+            // The message and the key are bound.
+            assert!(!verify(&pk, b"hellO", &sig));
+            assert_ne!(recover(b"hellO", &sig, recid), Some(pk));
+            let other = public_key::<C>(&C::ScalarExt::random(&mut rng));
+            assert!(!verify(&other, b"hello", &sig));
+            assert!(!verify(&C::identity(), b"hello", &sig));
+
+            // The other parity recovers a different key.
+            let flipped = RecoveryId::new(!recid.is_y_odd(), recid.is_x_reduced());
+            assert_ne!(recover(b"hello", &sig, flipped), Some(pk));
+
+            // The high-s twin is a valid ECDSA signature, but is rejected.
+            let high = Signature::<C> {
+                r: sig.r,
+                s: -sig.s,
+            };
+            assert!(!high.is_low_s());
+            assert_eq!(high.normalize_s(), sig);
+            assert!(!verify(&pk, b"hello", &high));
+            assert_eq!(recover(b"hello", &high, recid), None);
+
+            let mut tampered = sig;
+            tampered.r += C::ScalarExt::ONE;
+            assert!(!verify(&pk, b"hello", &tampered));
+
+            // Prehashed signing uses the same hash.
+            let e = hash_message::<C>(b"hello");
+            assert_eq!(sign_prehashed(&sk, &e), (sig, recid));
+            assert!(verify_prehashed(&pk, &e, &sig));
+            assert_eq!(recover_prehashed(&e, &sig, recid), Some(pk));
+        }
+
+        // Signing is deterministic, but nonces differ between messages.
+        let sk = C::ScalarExt::random(&mut rng);
+        assert_eq!(sign::<C>(&sk, b"hello"), sign::<C>(&sk, b"hello"));
+        assert_ne!(sign::<C>(&sk, b"hello").0.r, sign::<C>(&sk, b"hellO").0.r);
+
+        // A reduced x-coordinate needs r + n to be a valid x-coordinate, which on
+        // Vesta it almost never is, and on Pallas it never is.
+        let (sig, recid) = sign::<C>(&sk, b"hello");
+        assert!(!recid.is_x_reduced());
+        let reduced = RecoveryId::new(recid.is_y_odd(), true);
+        assert_eq!(recover(b"hello", &sig, reduced), None);
+    }
+
+    #[test]
+    fn test_sign_verify_recover() {
+        check_curve::<pallas::Affine>();
+        check_curve::<vesta::Affine>();
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut rng = crate::tests::rng();
+
+        let sk = SecretKey::random(&mut rng);
+        let pk = sk.public_key();
+        let (sig, recid) = sign_with_key(&sk, b"hello");
+        assert_eq!((sig, recid), sign(sk.as_scalar(), b"hello"));
+        assert!(verify_with_key(&pk, b"hello", &sig));
+        assert!(!verify_with_key(&pk, b"hellO", &sig));
+        assert_eq!(recover_key(b"hello", &sig, recid), Some(pk));
+    }
+
+    #[test]
+    fn test_signature_encoding() {
+        let mut rng = crate::tests::rng();
+
+        let sk = pallas::Scalar::random(&mut rng);
+        let (sig, _) = sign::<pallas::Affine>(&sk, b"hello");
+        let bytes = sig.to_bytes();
+        assert_eq!(Signature::from_bytes(&bytes).unwrap(), sig);
+
+        // Non-canonical scalars are rejected.
+        let mut bytes = sig.to_bytes();
+        bytes[32..].copy_from_slice(&[0xff; 32]);
+        assert!(bool::from(
+            Signature::<pallas::Affine>::from_bytes(&bytes).is_none()
+        ));
+
+        // Zero scalars are rejected.
+        let mut bytes = sig.to_bytes();
+        bytes[..32].copy_from_slice(&[0; 32]);
+        assert!(bool::from(
+            Signature::<pallas::Affine>::from_bytes(&bytes).is_none()
+        ));
+
+        // Recovery IDs are two bits.
+        for byte in 0..4 {
+            assert_eq!(RecoveryId::from_byte(byte).unwrap().to_byte(), byte);
+        }
+        assert_eq!(RecoveryId::from_byte(4), None);
+        assert_eq!(RecoveryId::new(true, false).to_byte(), 1);
+        assert_eq!(RecoveryId::new(false, true).to_byte(), 2);
+    }
+}
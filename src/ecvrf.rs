@@ -0,0 +1,213 @@
+//! A verifiable random function over the Pallas curve, in the style of ECVRF.
+//!
+//! The output for the input $\alpha$ under the [`SecretKey`] $x$ is derived from
+//! $\Gamma = [x] H$, where $H$ is $\alpha$ (and the [`PublicKey`] $P = [x] G$) hashed
+//! to the curve with `hash_to_curve`, using the domain prefix
+//! [`ENCODE_DOMAIN`]. A [`Proof`] consists of $\Gamma$ and a Chaum–Pedersen proof
+//! that $\log_G P = \log_H \Gamma$: it is a pair $(c, s)$ with $s = k + c x$,
+//! where the challenge $c \in \mathbb{F}_q$ is derived from $P$, $H$, $\Gamma$,
+//! $[k] G$ and $[k] H$ with `hash_to_field`, using the domain prefix
+//! [`CHALLENGE_DOMAIN`]. As in RFC 9381, the nonce $k$ is derived
+//! deterministically from $x$ and $H$.
+//!
+//! The VRF output is the 64-byte BLAKE2b hash of $\Gamma$, and is returned by
+//! [`verify`] and [`Proof::to_hash`].
+
+use alloc::vec::Vec;
+
+use ff::PrimeField;
+use group::{Curve, Group, GroupEncoding};
+use subtle::CtOption;
+
+use crate::arithmetic::CurveExt;
+use crate::hashtocurve::hash_to_field;
+use crate::keys::{PublicKey, SecretKey};
+use crate::nonce::derive_nonce;
+use crate::pallas;
+
+/// The domain prefix used to hash inputs to the curve.
+pub const ENCODE_DOMAIN: &str = "fil_pasta_curves:ecvrf";
+
+/// The domain prefix used to hash the challenge.
+pub const CHALLENGE_DOMAIN: &str = "fil_pasta_curves:ecvrf-challenge";
+
+/// The BLAKE2b personalization of the VRF output.
+const OUTPUT_PERSONALIZATION: &[u8; 16] = b"fil_pasta_VRF_ou";
+
+/// The BLAKE2b personalization used to derive nonces.
+const NONCE_PERSONALIZATION: &[u8; 16] = b"fil_pasta_VRF_k_";
+
+/// A VRF proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Proof {
+    gamma: pallas::Affine,
+    c: pallas::Scalar,
+    s: pallas::Scalar,
+}
+
+impl Proof {
+    /// Returns the VRF output attested by this proof.
+    ///
+    /// This does not verify the proof; use [`verify`] for untrusted proofs.
+    pub fn to_hash(&self) -> [u8; 64] {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(64)
+            .personal(OUTPUT_PERSONALIZATION)
+            .hash(&self.gamma.to_bytes());
+        *hash.as_array()
+    }
+
+    /// Returns the encoding of this proof, which is the compressed encoding of
+    /// $\Gamma$ followed by the little-endian encodings of $c$ and $s$.
+    pub fn to_bytes(&self) -> [u8; 96] {
+        let mut res = [0; 96];
+        res[..32].copy_from_slice(&self.gamma.to_bytes());
+        res[32..64].copy_from_slice(&self.c.to_repr());
+        res[64..].copy_from_slice(&self.s.to_repr());
+        res
+    }
+
+    /// Parses a proof from its encoding, failing if $\Gamma$ is not a valid point
+    /// encoding or $c$ or $s$ is not canonical.
+    pub fn from_bytes(bytes: &[u8; 96]) -> CtOption<Self> {
+        let mut gamma = [0; 32];
+        gamma.copy_from_slice(&bytes[..32]);
+        let mut c = [0; 32];
+        c.copy_from_slice(&bytes[32..64]);
+        let mut s = [0; 32];
+        s.copy_from_slice(&bytes[64..]);
+
+        pallas::Affine::from_bytes(&gamma).and_then(|gamma| {
+            pallas::Scalar::from_repr(c)
+                .and_then(|c| pallas::Scalar::from_repr(s).map(|s| Proof { gamma, c, s }))
+        })
+    }
+}
+
+/// Hashes the input `alpha` under the public key `pk` to the curve.
+fn encode_to_curve(pk: &pallas::Affine, alpha: &[u8]) -> pallas::Point {
+    let mut input = Vec::with_capacity(32 + alpha.len());
+    input.extend_from_slice(&pk.to_bytes());
+    input.extend_from_slice(alpha);
+    pallas::Point::hash_to_curve(ENCODE_DOMAIN)(&input)
+}
+
+/// Computes the challenge $c$ from the points of the Chaum–Pedersen proof.
+fn challenge(points: &[pallas::Affine; 5]) -> pallas::Scalar {
+    let mut input = [0; 160];
+    for (chunk, point) in input.chunks_mut(32).zip(points.iter()) {
+        chunk.copy_from_slice(&point.to_bytes());
+    }
+
+    let mut c = [pallas::Scalar::zero()];
+    hash_to_field(pallas::Point::CURVE_ID, CHALLENGE_DOMAIN, &input, &mut c);
+    c[0]
+}
+
+/// Evaluates the VRF on `alpha` with the secret key `sk`, returning a proof of the
+/// output.
+///
+/// Proving is deterministic: the same key and input always give the same proof.
+#[allow(clippy::many_single_char_names)]
+pub fn prove(sk: &SecretKey, alpha: &[u8]) -> Proof {
+    let x = sk.as_scalar();
+    let pk = *sk.public_key().as_point();
+    let h = encode_to_curve(&pk, alpha);
+    let h_affine = h.to_affine();
+    let gamma = (h * x).to_affine();
+
+    let k = derive_nonce(NONCE_PERSONALIZATION, x, &[&h_affine.to_bytes()]);
+    let u = (pallas::Point::generator() * k).to_affine();
+    let v = (h * k).to_affine();
+    let c = challenge(&[pk, h_affine, gamma, u, v]);
+
+    Proof {
+        gamma,
+        c,
+        s: k + c * x,
+    }
+}
+
+/// Verifies the proof `proof` for the input `alpha` under the public key `pk`,
+/// returning the VRF output if it is valid.
+///
+/// This is not constant time.
+pub fn verify(pk: &PublicKey, alpha: &[u8], proof: &Proof) -> Option<[u8; 64]> {
+    let pk = pk.as_point();
+    let h = encode_to_curve(pk, alpha);
+    let u = (pallas::Point::generator() * proof.s - *pk * proof.c).to_affine();
+    let v = (h * proof.s - proof.gamma * proof.c).to_affine();
+    if challenge(&[*pk, h.to_affine(), proof.gamma, u, v]) == proof.c {
+        Some(proof.to_hash())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{prime::PrimeCurveAffine, Curve};
+
+    use super::{prove, verify, Proof};
+    use crate::keys::SecretKey;
+    use crate::pallas;
+
+    #[test]
+    fn test_prove_verify() {
+        let mut rng = crate::tests::rng();
+
+        let sk = SecretKey::random(&mut rng);
+        let pk = sk.public_key();
+        let proof = prove(&sk, b"alpha");
+        let output = verify(&pk, b"alpha", &proof).unwrap();
+        assert_eq!(output, proof.to_hash());
+
+        // Proving is deterministic.
+        assert_eq!(prove(&sk, b"alpha"), proof);
+
+        // The output depends on the input and the key.
+        let other_input = prove(&sk, b"alphA");
+        assert_ne!(verify(&pk, b"alphA", &other_input).unwrap(), output);
+        let other_sk = SecretKey::random(&mut rng);
+        let other_key = prove(&other_sk, b"alpha");
+        assert_ne!(
+            verify(&other_sk.public_key(), b"alpha", &other_key).unwrap(),
+            output
+        );
+
+        // The input, the key and the proof are all bound.
+        assert_eq!(verify(&pk, b"alphA", &proof), None);
+        assert_eq!(verify(&other_sk.public_key(), b"alpha", &proof), None);
+        let mut tampered = proof;
+        tampered.gamma = (tampered.gamma + pallas::Affine::generator()).to_affine();
+        assert_eq!(verify(&pk, b"alpha", &tampered), None);
+        tampered = proof;
+        tampered.s += pallas::Scalar::one();
+        assert_eq!(verify(&pk, b"alpha", &tampered), None);
+        tampered = proof;
+        tampered.c += pallas::Scalar::one();
+        assert_eq!(verify(&pk, b"alpha", &tampered), None);
+    }
+
+    #[test]
+    fn test_proof_encoding() {
+        let mut rng = crate::tests::rng();
+
+        let sk = SecretKey::random(&mut rng);
+        let proof = prove(&sk, b"alpha");
+        assert_eq!(Proof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+
+        // Non-canonical scalars are rejected.
+        let mut bytes = proof.to_bytes();
+        bytes[32..64].copy_from_slice(&[0xff; 32]);
+        assert!(bool::from(Proof::from_bytes(&bytes).is_none()));
+        let mut bytes = proof.to_bytes();
+        bytes[64..].copy_from_slice(&[0xff; 32]);
+        assert!(bool::from(Proof::from_bytes(&bytes).is_none()));
+
+        // Invalid points are rejected.
+        let mut bytes = proof.to_bytes();
+        bytes[..32].copy_from_slice(&[0xff; 32]);
+        assert!(bool::from(Proof::from_bytes(&bytes).is_none()));
+    }
+}
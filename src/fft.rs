@@ -0,0 +1,426 @@
+//! This module provides radix-2 fast Fourier transforms over the multiplicative
+//! subgroups of order $2^k$ in $\mathbb{F}_p$ and $\mathbb{F}_q$.
+//!
+//! Both fields have 2-adicity 32, so domains of any size up to $2^{32}$ are
+//! supported. The transforms operate in place on any [`Group`] whose scalar field is
+//! the domain's field, which includes the fields themselves as well as curve points.
+
+use ff::Field;
+use subtle::CtOption;
+
+use crate::arithmetic::{FieldExt, Group};
+
+/// The multiplicative subgroup of order $n = 2^k$ of a field, over which
+/// polynomials of degree less than $n$ can be evaluated and interpolated with
+/// [`EvaluationDomain::fft`] and [`EvaluationDomain::ifft`].
+#[derive(Clone, Copy, Debug)]
+pub struct EvaluationDomain<F: FieldExt> {
+    k: u32,
+    omega: F,
+    omega_inv: F,
+    n_inv: F,
+    g_coset: F,
+    g_coset_inv: F,
+}
+
+impl<F: FieldExt> EvaluationDomain<F> {
+    /// Constructs the evaluation domain of size $2^k$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if $k$ exceeds the 2-adicity of the field.
+    pub fn new(k: u32) -> Self {
+        assert!(k <= F::S, "the field has no subgroup of order 2^{}", k);
+
+        // Both roots of unity have order 2^S, so squaring them S - k times yields
+        // elements of order 2^k.
+        let mut omega = F::ROOT_OF_UNITY;
+        let mut omega_inv = F::ROOT_OF_UNITY_INV;
+        for _ in k..F::S {
+            omega = omega.square();
+            omega_inv = omega_inv.square();
+        }
+
+        let mut n_inv = F::ONE;
+        for _ in 0..k {
+            n_inv *= F::TWO_INV;
+        }
+
+        // The multiplicative generator has order p - 1, so it is not in any subgroup
+        // of order 2^k and the coset it generates is disjoint from the domain.
+        let g_coset = F::MULTIPLICATIVE_GENERATOR;
+        let g_coset_inv = g_coset.invert().unwrap();
+
+        EvaluationDomain {
+            k,
+            omega,
+            omega_inv,
+            n_inv,
+            g_coset,
+            g_coset_inv,
+        }
+    }
+
+    /// Returns $k$, the base-2 logarithm of the size of this domain.
+    pub fn k(&self) -> u32 {
+        self.k
+    }
+
+    /// Returns $n = 2^k$, the size of this domain.
+    pub fn size(&self) -> usize {
+        1 << self.k
+    }
+
+    /// Returns $\omega$, the generator of this domain.
+    pub fn omega(&self) -> F {
+        self.omega
+    }
+
+    /// Returns $\omega^{-1}$.
+    pub fn omega_inv(&self) -> F {
+        self.omega_inv
+    }
+
+    /// Returns $n^{-1}$.
+    pub fn n_inv(&self) -> F {
+        self.n_inv
+    }
+
+    /// Returns $g$, the generator of the multiplicative coset $g H$ of this domain
+    /// $H$ over which [`EvaluationDomain::coset_fft`] evaluates.
+    pub fn coset_generator(&self) -> F {
+        self.g_coset
+    }
+
+    /// Evaluates the vanishing polynomial $Z_H(x) = x^n - 1$ of this domain $H$,
+    /// which is zero exactly on the domain.
+    pub fn evaluate_vanishing_polynomial(&self, x: F) -> F {
+        (0..self.k).fold(x, |acc, _| acc.square()) - F::ONE
+    }
+
+    /// Computes $Z_H(x)^{-1}$, failing if $x$ is in the domain $H$.
+    pub fn evaluate_vanishing_polynomial_inv(&self, x: F) -> CtOption<F> {
+        self.evaluate_vanishing_polynomial(x).invert()
+    }
+
+    /// Evaluates the polynomial with coefficients `a` at $\omega^0, \ldots,
+    /// \omega^{n-1}$, replacing the coefficients with the evaluations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a.len()` is not the size of this domain.
+    pub fn fft<G: Group<Scalar = F>>(&self, a: &mut [G]) {
+        assert_eq!(a.len(), self.size());
+        best_fft(a, self.omega, self.k);
+    }
+
+    /// Interpolates the polynomial whose evaluations at $\omega^0, \ldots,
+    /// \omega^{n-1}$ are `a`, replacing the evaluations with the coefficients.
+    ///
+    /// This is the inverse of [`EvaluationDomain::fft`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a.len()` is not the size of this domain.
+    pub fn ifft<G: Group<Scalar = F>>(&self, a: &mut [G]) {
+        assert_eq!(a.len(), self.size());
+        best_fft(a, self.omega_inv, self.k);
+        for a in a.iter_mut() {
+            a.group_scale(&self.n_inv);
+        }
+    }
+
+    /// Evaluates the polynomial with coefficients `a` at $g \omega^0, \ldots, g
+    /// \omega^{n-1}$, where $g$ is [`EvaluationDomain::coset_generator`], replacing
+    /// the coefficients with the evaluations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a.len()` is not the size of this domain.
+    pub fn coset_fft<G: Group<Scalar = F>>(&self, a: &mut [G]) {
+        distribute_powers(a, self.g_coset);
+        self.fft(a);
+    }
+
+    /// Interpolates the polynomial whose evaluations at $g \omega^0, \ldots, g
+    /// \omega^{n-1}$ are `a`, replacing the evaluations with the coefficients.
+    ///
+    /// This is the inverse of [`EvaluationDomain::coset_fft`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a.len()` is not the size of this domain.
+    pub fn coset_ifft<G: Group<Scalar = F>>(&self, a: &mut [G]) {
+        self.ifft(a);
+        distribute_powers(a, self.g_coset_inv);
+    }
+}
+
+/// Multiplies the $i$-th element of `a` by $g^i$, which shifts the polynomial with
+/// coefficients `a` from being evaluated at $x$ to being evaluated at $g x$.
+fn distribute_powers<G: Group>(a: &mut [G], g: G::Scalar) {
+    let mut acc = G::Scalar::ONE;
+    for a in a.iter_mut() {
+        a.group_scale(&acc);
+        acc *= g;
+    }
+}
+
+/// The base-2 logarithm of the smallest domain for which [`parallel_fft`] is used.
+#[cfg(feature = "multicore")]
+const LOG_PARALLEL_THRESHOLD: u32 = 10;
+
+/// The number of butterflies handed to each parallel task within a stage.
+#[cfg(feature = "multicore")]
+const PARALLEL_BLOCK: usize = 1 << 8;
+
+/// Performs an in-place FFT of `a`, using [`parallel_fft`] when the `multicore`
+/// feature is enabled and the domain is large enough to benefit from it.
+fn best_fft<G: Group>(a: &mut [G], omega: G::Scalar, log_n: u32) {
+    #[cfg(feature = "multicore")]
+    if log_n >= LOG_PARALLEL_THRESHOLD && rayon::current_num_threads() > 1 {
+        return parallel_fft(a, omega, log_n);
+    }
+
+    serial_fft(a, omega, log_n)
+}
+
+/// Reverses the lowest `l` bits of `n`.
+fn bitreverse(mut n: usize, l: u32) -> usize {
+    let mut r = 0;
+    for _ in 0..l {
+        r = (r << 1) | (n & 1);
+        n >>= 1;
+    }
+    r
+}
+
+/// Performs an in-place iterative radix-2 Cooley-Tukey FFT of `a`, which has length
+/// $2^{\textsf{log_n}}$, using `omega` as the primitive $2^{\textsf{log_n}}$-th root of
+/// unity.
+fn serial_fft<G: Group>(a: &mut [G], omega: G::Scalar, log_n: u32) {
+    let n = a.len();
+    assert_eq!(n, 1 << log_n);
+
+    for i in 0..n {
+        let ri = bitreverse(i, log_n);
+        if i < ri {
+            a.swap(ri, i);
+        }
+    }
+
+    let mut half = 1;
+    for _ in 0..log_n {
+        let w_m = omega.pow_vartime(&[(n / (2 * half)) as u64]);
+
+        let mut start = 0;
+        while start < n {
+            let mut twiddle = G::Scalar::ONE;
+            for j in start..start + half {
+                let mut t = a[j + half];
+                t.group_scale(&twiddle);
+                a[j + half] = a[j];
+                a[j + half].group_sub(&t);
+                a[j].group_add(&t);
+                twiddle *= &w_m;
+            }
+
+            start += 2 * half;
+        }
+
+        half *= 2;
+    }
+}
+
+/// Performs the same transform as [`serial_fft`] on the rayon thread pool.
+///
+/// Each stage of butterflies is split into independent blocks whose twiddle
+/// factors are computed at the start of the block, so the output is identical to
+/// that of [`serial_fft`].
+#[cfg(feature = "multicore")]
+fn parallel_fft<G: Group>(a: &mut [G], omega: G::Scalar, log_n: u32) {
+    use rayon::prelude::*;
+
+    let n = a.len();
+    assert_eq!(n, 1 << log_n);
+
+    for i in 0..n {
+        let ri = bitreverse(i, log_n);
+        if i < ri {
+            a.swap(ri, i);
+        }
+    }
+
+    let mut half = 1;
+    for _ in 0..log_n {
+        let w_m = omega.pow_vartime(&[(n / (2 * half)) as u64]);
+
+        a.par_chunks_mut(2 * half).for_each(|chunk| {
+            let (lo, hi) = chunk.split_at_mut(half);
+            lo.par_chunks_mut(PARALLEL_BLOCK)
+                .zip(hi.par_chunks_mut(PARALLEL_BLOCK))
+                .enumerate()
+                .for_each(|(block, (lo, hi))| {
+                    let mut twiddle = w_m.pow_vartime(&[(block * PARALLEL_BLOCK) as u64]);
+                    for (lo, hi) in lo.iter_mut().zip(hi.iter_mut()) {
+                        let mut t = *hi;
+                        t.group_scale(&twiddle);
+                        *hi = *lo;
+                        hi.group_sub(&t);
+                        lo.group_add(&t);
+                        twiddle *= &w_m;
+                    }
+                });
+        });
+
+        half *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::EvaluationDomain;
+    use crate::arithmetic::FieldExt;
+    use crate::{pallas, Fp, Fq};
+
+    fn naive_dft<F: FieldExt>(a: &[F], omega: F) -> Vec<F> {
+        (0..a.len())
+            .map(|i| {
+                let x = omega.pow_vartime(&[i as u64]);
+                a.iter().rev().fold(F::ZERO, |acc, c| acc * x + c)
+            })
+            .collect()
+    }
+
+    fn check_fft<F: FieldExt>() {
+        let mut rng = crate::tests::rng();
+
+        for k in 0..7 {
+            let domain = EvaluationDomain::<F>::new(k);
+            assert_eq!(domain.omega().pow_vartime(&[domain.size() as u64]), F::ONE);
+            assert_eq!(domain.omega() * domain.omega_inv(), F::ONE);
+            if k > 0 {
+                // omega is a primitive root of unity.
+                assert_eq!(
+                    domain.omega().pow_vartime(&[domain.size() as u64 / 2]),
+                    -F::ONE
+                );
+            }
+
+            let coeffs: Vec<F> = (0..domain.size()).map(|_| F::random(&mut rng)).collect();
+
+            let mut a = coeffs.clone();
+            domain.fft(&mut a);
+            assert_eq!(a, naive_dft(&coeffs, domain.omega()));
+
+            domain.ifft(&mut a);
+            assert_eq!(a, coeffs);
+        }
+
+        for k in 0..7 {
+            let domain = EvaluationDomain::<F>::new(k);
+            let g = domain.coset_generator();
+            let coeffs: Vec<F> = (0..domain.size()).map(|_| F::random(&mut rng)).collect();
+
+            let mut a = coeffs.clone();
+            domain.coset_fft(&mut a);
+            for (i, eval) in a.iter().enumerate() {
+                let x = g * domain.omega().pow_vartime(&[i as u64]);
+                let expected = coeffs.iter().rev().fold(F::ZERO, |acc, c| acc * x + c);
+                assert_eq!(*eval, expected);
+            }
+
+            domain.coset_ifft(&mut a);
+            assert_eq!(a, coeffs);
+        }
+
+        // The largest domain is supported.
+        let domain = EvaluationDomain::<F>::new(F::S);
+        assert_eq!(domain.omega(), F::ROOT_OF_UNITY);
+        // The coset is disjoint from the domain.
+        assert_ne!(domain.coset_generator().pow_vartime(&[1 << F::S]), F::ONE);
+    }
+
+    #[test]
+    fn test_fft() {
+        check_fft::<Fp>();
+        check_fft::<Fq>();
+    }
+
+    #[test]
+    fn test_fft_points() {
+        use group::Group;
+
+        let domain = EvaluationDomain::<Fq>::new(3);
+        let coeffs: Vec<_> = (1..=8u64).map(Fq::from).collect();
+
+        let mut points: Vec<_> = coeffs
+            .iter()
+            .map(|c| pallas::Point::generator() * c)
+            .collect();
+        domain.fft(&mut points);
+
+        let mut evals = coeffs;
+        domain.fft(&mut evals);
+        for (p, e) in points.iter().zip(evals.iter()) {
+            assert_eq!(*p, pallas::Point::generator() * e);
+        }
+
+        domain.ifft(&mut points);
+        for (i, p) in points.iter().enumerate() {
+            assert_eq!(*p, pallas::Point::generator() * Fq::from(i as u64 + 1));
+        }
+    }
+
+    #[cfg(feature = "multicore")]
+    #[test]
+    fn test_parallel_fft() {
+        use ff::Field;
+
+        let mut rng = crate::tests::rng();
+
+        for k in 0..13 {
+            let domain = EvaluationDomain::<Fp>::new(k);
+            let coeffs: Vec<Fp> = (0..domain.size()).map(|_| Fp::random(&mut rng)).collect();
+
+            let mut serial = coeffs.clone();
+            super::serial_fft(&mut serial, domain.omega(), k);
+            let mut parallel = coeffs;
+            super::parallel_fft(&mut parallel, domain.omega(), k);
+            assert_eq!(serial, parallel);
+        }
+    }
+
+    #[test]
+    fn test_vanishing_polynomial() {
+        use ff::Field;
+
+        let domain = EvaluationDomain::<Fp>::new(4);
+        let mut x = Fp::ONE;
+        for _ in 0..domain.size() {
+            assert!(bool::from(
+                domain.evaluate_vanishing_polynomial(x).is_zero()
+            ));
+            assert!(bool::from(
+                domain.evaluate_vanishing_polynomial_inv(x).is_none()
+            ));
+            x *= domain.omega();
+        }
+
+        let x = domain.coset_generator();
+        let z = domain.evaluate_vanishing_polynomial(x);
+        assert_eq!(z, x.pow_vartime(&[16]) - Fp::ONE);
+        assert_eq!(
+            domain.evaluate_vanishing_polynomial_inv(x).unwrap() * z,
+            Fp::ONE
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_domain_too_large() {
+        EvaluationDomain::<Fp>::new(33);
+    }
+}
@@ -1,12 +1,304 @@
 //! This module contains implementations for the two finite fields of the Pallas
 //! and Vesta curves.
 
+#[macro_use]
+mod batch;
+
+#[cfg(all(
+    feature = "asm",
+    target_arch = "x86_64",
+    target_feature = "adx",
+    target_feature = "bmi2"
+))]
+mod asm;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+mod avx2;
+
+#[cfg(all(target_arch = "x86_64", pasta_ifma))]
+mod ifma;
+
+#[cfg(any(feature = "limb32", not(target_pointer_width = "64")))]
+mod limb32;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon", feature = "neon"))]
+mod neon;
+
+mod safegcd;
+
 mod fp;
 mod fq;
 
 pub use fp::*;
 pub use fq::*;
 
+use ff::PrimeField;
+use subtle::{ConstantTimeEq, CtOption};
+
+use crate::arithmetic::{adc, mac, sbb, FieldExt};
+
+macro_rules! impl_cycle_conversions {
+    ($scalar:ident, $base:ident, $curve:literal, $fits:literal) => {
+        impl $scalar {
+            #[doc = concat!("Converts an element of the ", $curve, " base field `", stringify!($base), "` to the ", $curve, " scalar field, failing if its canonical value is not less than the modulus of `", stringify!($scalar), "`.")]
+            ///
+            #[doc = $fits]
+            pub fn from_base_field_checked(x: $base) -> CtOption<Self> {
+                Self::from_repr(x.to_repr())
+            }
+
+            #[doc = concat!("Converts an element of the ", $curve, " base field `", stringify!($base), "` to the ", $curve, " scalar field, reducing its canonical value modulo the modulus of `", stringify!($scalar), "`.")]
+            ///
+            /// This is the conversion used by endoscaling, where the value is only
+            /// used as a scalar and wrapping around is harmless.
+            pub fn from_base_field_wrapping(x: $base) -> Self {
+                let mut wide = [0; 64];
+                wide[..32].copy_from_slice(&x.to_repr());
+                Self::from_bytes_wide(&wide)
+            }
+        }
+
+        impl $base {
+            #[doc = concat!("Converts this element to the ", $curve, " scalar field `", stringify!($scalar), "` by clearing bit 254 of its canonical value, which leaves a value less than $2^{254}$ that fits in either field.")]
+            ///
+            /// This loses information for values of at least $2^{254}$, so it is only
+            /// suitable for values known to be smaller, such as challenges sampled
+            /// with [`PrimeField::CAPACITY`] bits.
+            pub fn to_scalar_lossy(&self) -> $scalar {
+                let mut repr = self.to_repr();
+                repr[31] &= 0b0011_1111;
+                $scalar::from_repr(repr).unwrap()
+            }
+        }
+    };
+}
+
+impl_cycle_conversions!(
+    Fq,
+    Fp,
+    "Pallas",
+    "The modulus $p$ of `Fp` is less than the modulus $q$ of `Fq`, so this always succeeds."
+);
+impl_cycle_conversions!(
+    Fp,
+    Fq,
+    "Vesta",
+    "The modulus $p$ of `Fp` is less than the modulus $q$ of `Fq`, with $q - p \\approx 2^{86}$, so this only fails for about one in $2^{168}$ uniformly random elements of `Fq`."
+);
+
+/// Computes $\lfloor k g / 2^{256} \rfloor$ for the little-endian limbs of a 256-bit
+/// `k` and a 192-bit `g`, whose product must be less than $2^{384}$.
+fn mul_shr_256(k: &[u64; 4], g: &[u64; 3]) -> u128 {
+    let mut t = [0; 7];
+    for (i, &k_i) in k.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &g_j) in g.iter().enumerate() {
+            let (lo, hi) = mac(t[i + j], k_i, g_j, carry);
+            t[i + j] = lo;
+            carry = hi;
+        }
+        t[i + 3] = carry;
+    }
+    debug_assert_eq!(t[6], 0);
+    u128::from(t[4]) | (u128::from(t[5]) << 64)
+}
+
+/// Returns the magnitude of `x`, which must be congruent to an integer less than
+/// $2^{128}$ in absolute value, and whether that integer is negative.
+fn to_signed_u128<F: PrimeField<Repr = [u8; 32]>>(x: F) -> (u128, bool) {
+    let is_neg = !x.to_repr()[16..]
+        .iter()
+        .fold(0u8, |acc, b| acc | b)
+        .ct_eq(&0);
+    let abs = F::conditional_select(&x, &-x, is_neg);
+    let repr = abs.to_repr();
+    (
+        u128::from_le_bytes(repr[..16].try_into().unwrap()),
+        bool::from(is_neg),
+    )
+}
+
+macro_rules! impl_glv_decompose {
+    ($field:ident, $curve:literal, [$a1:expr, $b1:expr], [$a2:expr, $b2:expr], $g1:expr, $g2:expr) => {
+        impl $field {
+            #[doc = concat!("Decomposes this ", $curve, " scalar $k$ into mini-scalars $k_1$ and $k_2$ of at most 128 bits, such that")]
+            ///
+            /// $$k = (-1)^{s_1} k_1 + (-1)^{s_2} k_2 \cdot \zeta,$$
+            ///
+            /// where $\zeta$ is [`ff::WithSmallOrderMulGroup::ZETA`]. Multiplying a point by
+            /// $\zeta$ is the curve endomorphism $\phi$
+            /// ([`CurveExt::endo`](crate::arithmetic::CurveExt::endo)), so this gives
+            /// $[k] P = [(-1)^{s_1} k_1] P + [(-1)^{s_2} k_2] \phi(P)$, which is the
+            /// relation used by endomorphism-based scalar multiplication.
+            ///
+            /// Returns $(k_1, s_1, k_2, s_2)$, where the signs $s_i$ are `true` for
+            /// negative terms. The decomposition is computed in constant time from a
+            /// fixed short basis of the lattice of pairs $(a, b)$ with
+            /// $a + b \zeta = 0$, so it is the same as any other implementation using
+            /// that basis.
+            pub fn glv_decompose(&self) -> (u128, bool, u128, bool) {
+                // The short basis is (a1, -b1) and (a2, b2), and g1 and g2 are
+                // 2^256 * b2 / modulus and 2^256 * b1 / modulus, rounded.
+                const A1: u128 = $a1;
+                const B1: u128 = $b1;
+                const A2: u128 = $a2;
+                const B2: u128 = $b2;
+
+                let mut k = [0; 4];
+                for (limb, bytes) in k.iter_mut().zip(self.to_repr().chunks(8)) {
+                    *limb = u64::from_le_bytes(bytes.try_into().unwrap());
+                }
+                let c1 = $field::from_u128(mul_shr_256(&k, &$g1));
+                let c2 = $field::from_u128(mul_shr_256(&k, &$g2));
+
+                // k1 = k - c1 * a1 - c2 * a2
+                // k2 = c1 * b1 - c2 * b2
+                let k1 = self - c1 * $field::from_u128(A1) - c2 * $field::from_u128(A2);
+                let k2 = c1 * $field::from_u128(B1) - c2 * $field::from_u128(B2);
+
+                let (k1, k1_neg) = to_signed_u128(k1);
+                let (k2, k2_neg) = to_signed_u128(k2);
+                (k1, k1_neg, k2, k2_neg)
+            }
+        }
+    };
+}
+
+impl_glv_decompose!(
+    Fq,
+    "Pallas",
+    [
+        0x49e6_9d16_40f0_4915_7fca_e1c7_0000_0001,
+        0x49e6_9d16_40a8_9953_8cb1_2793_0000_0000
+    ],
+    [
+        0x49e6_9d16_40a8_9953_8cb1_2793_0000_0000,
+        0x93cd_3a2c_8198_e269_0c7c_095a_0000_0001
+    ],
+    [0x31f0256800000003, 0x4f34e8b2066389a4, 0x0000000000000002],
+    [0x32c49e4bffffffff, 0x279a745902a2654e, 0x0000000000000001]
+);
+impl_glv_decompose!(
+    Fp,
+    "Vesta",
+    [
+        0x49e6_9d16_40f0_4915_7fca_e1c7_0000_0000,
+        0x49e6_9d16_40a8_9953_8cb1_2793_0000_0001
+    ],
+    [
+        0x49e6_9d16_40a8_9953_8cb1_2793_0000_0001,
+        0x93cd_3a2c_8198_e269_0c7c_095a_0000_0001
+    ],
+    [0x31f0256800000003, 0x4f34e8b2066389a4, 0x0000000000000002],
+    [0x32c49e4c00000003, 0x279a745902a2654e, 0x0000000000000001]
+);
+
+/// Computes the Montgomery limbs of $\sum_i a_i b_i$ from the Montgomery limbs of
+/// the pairs $(a_i, b_i)$, with a single Montgomery reduction of the sum of the
+/// double-width products.
+///
+/// The modulus $m$ must be in $(2^{254}, 2^{254} + 2^{128})$, as for both Pasta
+/// fields, so that the quotient of the final reduction can be read from the top
+/// bits. This runs in constant time for a given number of pairs.
+#[allow(clippy::many_single_char_names)]
+fn sum_of_products_limbs<'a>(
+    pairs: impl Iterator<Item = (&'a [u64; 4], &'a [u64; 4])>,
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [u64; 4] {
+    debug_assert!(modulus[3] == 1 << 62 && modulus[2] == 0 && modulus[1] >> 63 == 0);
+
+    // Each product is less than m^2 < 2^509, so a sum of fewer than 2^60 products,
+    // which is more than fits in memory, is less than 2^569.
+    let mut t = [0u64; 9];
+    for (a, b) in pairs {
+        let mut product = [0u64; 8];
+        for (i, a_i) in a.iter().enumerate() {
+            let mut carry = 0;
+            for (j, b_j) in b.iter().enumerate() {
+                let (lo, hi) = mac(product[i + j], *a_i, *b_j, carry);
+                product[i + j] = lo;
+                carry = hi;
+            }
+            product[i + 4] = carry;
+        }
+
+        let mut carry = 0;
+        for (t, p) in t.iter_mut().zip(product.iter()) {
+            let (sum, c) = adc(*t, *p, carry);
+            *t = sum;
+            carry = c;
+        }
+        t[8] += carry;
+    }
+
+    // Divide by R = 2^256 modulo m, as in the Montgomery reduction of a product,
+    // leaving y = t[4..] < 2^313 + m.
+    for i in 0..4 {
+        let k = t[i].wrapping_mul(inv);
+        let mut carry = 0;
+        for (j, m_j) in modulus.iter().enumerate() {
+            let (lo, hi) = mac(t[i + j], k, *m_j, carry);
+            t[i + j] = lo;
+            carry = hi;
+        }
+        for t in t[i + 4..].iter_mut() {
+            let (sum, c) = adc(*t, 0, carry);
+            *t = sum;
+            carry = c;
+        }
+    }
+
+    // With q = floor(y / 2^254), y - q m is in (-m, 2^254), as q (m - 2^254) < m.
+    let q = t[7] >> 62 | t[8] << 2;
+    let mut res = [0u64; 4];
+    let (mut carry, mut borrow) = (0, 0);
+    for (j, m_j) in modulus.iter().enumerate() {
+        let (qm, c) = mac(0, q, *m_j, carry);
+        let (d, b) = sbb(t[j + 4], qm, borrow);
+        res[j] = d;
+        carry = c;
+        borrow = b;
+    }
+    let (_, borrow) = sbb(t[8], carry, borrow);
+
+    // Add the modulus back if the result is negative.
+    let mut carry = 0;
+    for (r, m_j) in res.iter_mut().zip(modulus.iter()) {
+        let (sum, c) = adc(*r, m_j & borrow, carry);
+        *r = sum;
+        carry = c;
+    }
+    res
+}
+
+macro_rules! impl_sum_of_products {
+    ($field:ident) => {
+        impl $field {
+            /// Computes $\sum_i a_i b_i$ for the elements $a_i$ of `a` and $b_i$ of `b`.
+            ///
+            /// This accumulates the double-width products and performs a single
+            /// Montgomery reduction, which is faster than multiplying and adding the
+            /// elements one at a time.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `a` and `b` have different lengths.
+            pub fn sum_of_products(a: &[Self], b: &[Self]) -> Self {
+                assert_eq!(a.len(), b.len());
+                $field(sum_of_products_limbs(
+                    a.iter().map(|a| &a.0).zip(b.iter().map(|b| &b.0)),
+                    &$field::MODULUS_LIMBS,
+                    $field::INV,
+                ))
+            }
+        }
+    };
+}
+
+impl_sum_of_products!(Fp);
+impl_sum_of_products!(Fq);
+
 /// Converts 64-bit little-endian limbs to 32-bit little endian limbs.
 #[cfg(feature = "gpu")]
 fn u64_to_u32(limbs: &[u64]) -> alloc::vec::Vec<u32> {
@@ -19,3 +311,184 @@ fn u64_to_u32(limbs: &[u64]) -> alloc::vec::Vec<u32> {
         })
         .collect()
 }
+
+/// An error which can be returned when parsing a field element from a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseFieldError {
+    /// The string, or the digits following the `0x` prefix, is empty.
+    Empty,
+    /// The string contains a character that is not a valid digit.
+    InvalidDigit,
+    /// The integer is not less than the field modulus.
+    OutOfRange,
+    /// The string does not contain exactly 64 hex digits.
+    InvalidLength,
+}
+
+impl core::fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            ParseFieldError::Empty => "cannot parse field element from empty string",
+            ParseFieldError::InvalidDigit => "invalid digit found in string",
+            ParseFieldError::OutOfRange => "integer is not less than the field modulus",
+            ParseFieldError::InvalidLength => "expected 64 hex digits",
+        })
+    }
+}
+
+/// Parses a `0x`-prefixed hexadecimal or a decimal integer less than $2^{256}$,
+/// returning its little-endian encoding.
+fn parse_repr(s: &str) -> Result<[u8; 32], ParseFieldError> {
+    let (digits, radix) = match strip_hex_prefix(s) {
+        Some(digits) => (digits, 16),
+        None => (s, 10),
+    };
+    if digits.is_empty() {
+        return Err(ParseFieldError::Empty);
+    }
+
+    let mut limbs = [0u64; 4];
+    for c in digits.chars() {
+        let digit = c.to_digit(radix).ok_or(ParseFieldError::InvalidDigit)?;
+
+        // limbs = limbs * radix + digit
+        let mut carry = u64::from(digit);
+        for limb in limbs.iter_mut() {
+            let t = u128::from(*limb) * u128::from(radix) + u128::from(carry);
+            *limb = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        if carry != 0 {
+            return Err(ParseFieldError::OutOfRange);
+        }
+    }
+
+    let mut repr = [0; 32];
+    for (bytes, limb) in repr.chunks_mut(8).zip(limbs.iter()) {
+        bytes.copy_from_slice(&limb.to_le_bytes());
+    }
+    Ok(repr)
+}
+
+/// Strips a `0x` or `0X` prefix from `s`, returning `None` if it has neither.
+pub(crate) fn strip_hex_prefix(s: &str) -> Option<&str> {
+    s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))
+}
+
+/// Decodes exactly 64 hex digits into 32 bytes in the order they are written,
+/// failing with `invalid_length` or `invalid_digit`.
+pub(crate) fn decode_hex<E: Copy>(
+    digits: &str,
+    invalid_length: E,
+    invalid_digit: E,
+) -> Result<[u8; 32], E> {
+    let digits = digits.as_bytes();
+    if digits.len() != 64 {
+        return Err(invalid_length);
+    }
+
+    let mut bytes = [0; 32];
+    for (byte, pair) in bytes.iter_mut().zip(digits.chunks(2)) {
+        let digit = |c: u8| (c as char).to_digit(16).ok_or(invalid_digit);
+        *byte = (digit(pair[0])? << 4 | digit(pair[1])?) as u8;
+    }
+    Ok(bytes)
+}
+
+/// Encodes bytes as lowercase hex digits, in the order they are stored.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode_hex(bytes: &[u8]) -> alloc::string::String {
+    use core::fmt::Write;
+
+    let mut s = alloc::string::String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        write!(s, "{:02x}", b).unwrap();
+    }
+    s
+}
+
+#[test]
+fn test_cycle_conversions() {
+    use ff::Field;
+
+    let mut rng = crate::tests::rng();
+
+    for _ in 0..100 {
+        let a = Fp::random(&mut rng);
+        let b = Fq::from_base_field_checked(a).unwrap();
+        assert_eq!(b.to_repr(), a.to_repr());
+        assert_eq!(Fq::from_base_field_wrapping(a), b);
+        assert_eq!(Fp::from_base_field_checked(b).unwrap(), a);
+    }
+
+    // -1 in Fq is q - 1 >= p, which doesn't fit in Fp and wraps to q - 1 - p.
+    let q_minus_one = -Fq::one();
+    assert!(bool::from(
+        Fp::from_base_field_checked(q_minus_one).is_none()
+    ));
+    let wrapped = Fp::from_base_field_wrapping(q_minus_one);
+    assert_eq!(
+        Fq::from_base_field_checked(wrapped).unwrap(),
+        q_minus_one - Fq::from_base_field_checked(-Fp::one()).unwrap() - Fq::one()
+    );
+
+    // Bit 254 of p - 1 is cleared.
+    assert_eq!(
+        (-Fp::one()).to_scalar_lossy(),
+        Fq::from_raw([0x992d30ed00000000, 0x224698fc094cf91b, 0, 0])
+    );
+    assert_eq!(Fq::from(7u64).to_scalar_lossy(), Fp::from(7u64));
+}
+
+#[cfg(test)]
+fn test_glv_decompose<F>(glv_decompose: fn(&F) -> (u128, bool, u128, bool))
+where
+    F: ff::WithSmallOrderMulGroup<3>,
+{
+    let mut rng = crate::tests::rng();
+
+    let signed = |k: u128, neg: bool| {
+        let k = F::from_u128(k);
+        if neg {
+            -k
+        } else {
+            k
+        }
+    };
+    let edge_cases = [F::ZERO, F::ONE, -F::ONE, F::ZETA, F::TWO_INV];
+    for k in edge_cases
+        .iter()
+        .cloned()
+        .chain((0..1000).map(|_| F::random(&mut rng)))
+    {
+        let (k1, k1_neg, k2, k2_neg) = glv_decompose(&k);
+        assert_eq!(signed(k1, k1_neg) + signed(k2, k2_neg) * F::ZETA, k);
+    }
+}
+
+#[test]
+fn test_glv_decompose_pallas() {
+    test_glv_decompose::<Fq>(Fq::glv_decompose);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_glv_decompose_endo() {
+    // The decomposition splits a scalar multiplication over the endomorphism.
+    use crate::arithmetic::CurveExt;
+    use group::Group;
+
+    let k = Fq::from_raw([0x0123456789abcdef, 0xfedcba9876543210, 0x1111, 0x2222]);
+    let (k1, k1_neg, k2, k2_neg) = k.glv_decompose();
+    let p = crate::Ep::generator();
+    let t1 = p * Fq::from_u128(k1);
+    let t2 = p.endo() * Fq::from_u128(k2);
+    let t1 = if k1_neg { -t1 } else { t1 };
+    let t2 = if k2_neg { -t2 } else { t2 };
+    assert_eq!(t1 + t2, p * k);
+}
+
+#[test]
+fn test_glv_decompose_vesta() {
+    test_glv_decompose::<Fp>(Fp::glv_decompose);
+}
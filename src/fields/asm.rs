@@ -0,0 +1,224 @@
+//! Montgomery multiplication in x86_64 assembly using the BMI2 `mulx` and ADX
+//! `adcx`/`adox` instructions.
+//!
+//! `mulx` does not modify the flags, and `adcx` and `adox` propagate carries through
+//! CF and OF respectively, so the low and high halves of each row of partial
+//! products are accumulated in two independent carry chains. Each row is followed
+//! by a Montgomery reduction step (CIOS), which keeps the accumulator in five
+//! registers. The registers holding the accumulator are renamed rather than moved
+//! after each step, so the four steps are written out explicitly.
+
+// Inline assembly is inherently `unsafe`.
+#![allow(unsafe_code)]
+
+use core::arch::asm;
+
+use crate::arithmetic::{adc, sbb};
+
+/// Computes $a b R^{-1} \bmod p$ for Montgomery-form elements $a$ and $b$, where
+/// $R = 2^{256}$, `modulus` is $p$ and `inv` is $-p^{-1} \bmod 2^{64}$.
+///
+/// The inputs must be fully reduced and $p$ must be less than $2^{255}$.
+#[inline]
+pub(crate) fn mul(a: &[u64; 4], b: &[u64; 4], modulus: &[u64; 4], inv: u64) -> [u64; 4] {
+    let (r0, r1, r2, r3): (u64, u64, u64, u64);
+
+    // SAFETY: the BMI2 and ADX target features are statically enabled, the pointers
+    // are valid for reads of four limbs, and only the listed registers are written.
+    unsafe {
+        asm!(
+            // Row 0: (t0, t1, t2, t3, t4) = a * b0.
+            "mov rdx, qword ptr [{b}]",
+            "mulx {t1}, {t0}, qword ptr [{a}]",
+            "mulx {t2}, {lo}, qword ptr [{a} + 8]",
+            "add {t1}, {lo}",
+            "mulx {t3}, {lo}, qword ptr [{a} + 16]",
+            "adc {t2}, {lo}",
+            "mulx {t4}, {lo}, qword ptr [{a} + 24]",
+            "adc {t3}, {lo}",
+            "adc {t4}, 0",
+            // Reduce (t0, t1, t2, t3, t4) into (t1, t2, t3, t4).
+            "mov rdx, {t0}",
+            "imul rdx, {inv}",
+            "xor {lo:e}, {lo:e}",
+            "mulx {hi}, {lo}, qword ptr [{p}]",
+            "adcx {t0}, {lo}",
+            "adox {t1}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 8]",
+            "adcx {t1}, {lo}",
+            "adox {t2}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 16]",
+            "adcx {t2}, {lo}",
+            "adox {t3}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 24]",
+            "adcx {t3}, {lo}",
+            "adox {t4}, {hi}",
+            "mov {lo:e}, 0",
+            "adcx {t4}, {lo}",
+            // Row 1: (t1, t2, t3, t4, t0) += a * b1.
+            "mov rdx, qword ptr [{b} + 8]",
+            "xor {t0:e}, {t0:e}",
+            "mulx {hi}, {lo}, qword ptr [{a}]",
+            "adcx {t1}, {lo}",
+            "adox {t2}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+            "adcx {t2}, {lo}",
+            "adox {t3}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+            "adcx {t3}, {lo}",
+            "adox {t4}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+            "adcx {t4}, {lo}",
+            "adox {t0}, {hi}",
+            "mov {lo:e}, 0",
+            "adcx {t0}, {lo}",
+            // Reduce (t1, t2, t3, t4, t0) into (t2, t3, t4, t0).
+            "mov rdx, {t1}",
+            "imul rdx, {inv}",
+            "xor {lo:e}, {lo:e}",
+            "mulx {hi}, {lo}, qword ptr [{p}]",
+            "adcx {t1}, {lo}",
+            "adox {t2}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 8]",
+            "adcx {t2}, {lo}",
+            "adox {t3}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 16]",
+            "adcx {t3}, {lo}",
+            "adox {t4}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 24]",
+            "adcx {t4}, {lo}",
+            "adox {t0}, {hi}",
+            "mov {lo:e}, 0",
+            "adcx {t0}, {lo}",
+            // Row 2: (t2, t3, t4, t0, t1) += a * b2.
+            "mov rdx, qword ptr [{b} + 16]",
+            "xor {t1:e}, {t1:e}",
+            "mulx {hi}, {lo}, qword ptr [{a}]",
+            "adcx {t2}, {lo}",
+            "adox {t3}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+            "adcx {t3}, {lo}",
+            "adox {t4}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+            "adcx {t4}, {lo}",
+            "adox {t0}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+            "adcx {t0}, {lo}",
+            "adox {t1}, {hi}",
+            "mov {lo:e}, 0",
+            "adcx {t1}, {lo}",
+            // Reduce (t2, t3, t4, t0, t1) into (t3, t4, t0, t1).
+            "mov rdx, {t2}",
+            "imul rdx, {inv}",
+            "xor {lo:e}, {lo:e}",
+            "mulx {hi}, {lo}, qword ptr [{p}]",
+            "adcx {t2}, {lo}",
+            "adox {t3}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 8]",
+            "adcx {t3}, {lo}",
+            "adox {t4}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 16]",
+            "adcx {t4}, {lo}",
+            "adox {t0}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 24]",
+            "adcx {t0}, {lo}",
+            "adox {t1}, {hi}",
+            "mov {lo:e}, 0",
+            "adcx {t1}, {lo}",
+            // Row 3: (t3, t4, t0, t1, t2) += a * b3.
+            "mov rdx, qword ptr [{b} + 24]",
+            "xor {t2:e}, {t2:e}",
+            "mulx {hi}, {lo}, qword ptr [{a}]",
+            "adcx {t3}, {lo}",
+            "adox {t4}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 8]",
+            "adcx {t4}, {lo}",
+            "adox {t0}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 16]",
+            "adcx {t0}, {lo}",
+            "adox {t1}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{a} + 24]",
+            "adcx {t1}, {lo}",
+            "adox {t2}, {hi}",
+            "mov {lo:e}, 0",
+            "adcx {t2}, {lo}",
+            // Reduce (t3, t4, t0, t1, t2) into (t4, t0, t1, t2).
+            "mov rdx, {t3}",
+            "imul rdx, {inv}",
+            "xor {lo:e}, {lo:e}",
+            "mulx {hi}, {lo}, qword ptr [{p}]",
+            "adcx {t3}, {lo}",
+            "adox {t4}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 8]",
+            "adcx {t4}, {lo}",
+            "adox {t0}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 16]",
+            "adcx {t0}, {lo}",
+            "adox {t1}, {hi}",
+            "mulx {hi}, {lo}, qword ptr [{p} + 24]",
+            "adcx {t1}, {lo}",
+            "adox {t2}, {hi}",
+            "mov {lo:e}, 0",
+            "adcx {t2}, {lo}",
+            a = in(reg) a.as_ptr(),
+            b = in(reg) b.as_ptr(),
+            p = in(reg) modulus.as_ptr(),
+            inv = in(reg) inv,
+            t0 = out(reg) r1,
+            t1 = out(reg) r2,
+            t2 = out(reg) r3,
+            t3 = out(reg) _,
+            t4 = out(reg) r0,
+            lo = out(reg) _,
+            hi = out(reg) _,
+            out("rdx") _,
+            options(pure, readonly, nostack),
+        );
+    }
+
+    // The result is less than 2p, so at most one subtraction is needed.
+    let (d0, borrow) = sbb(r0, modulus[0], 0);
+    let (d1, borrow) = sbb(r1, modulus[1], borrow);
+    let (d2, borrow) = sbb(r2, modulus[2], borrow);
+    let (d3, borrow) = sbb(r3, modulus[3], borrow);
+
+    let (d0, carry) = adc(d0, modulus[0] & borrow, 0);
+    let (d1, carry) = adc(d1, modulus[1] & borrow, carry);
+    let (d2, carry) = adc(d2, modulus[2] & borrow, carry);
+    let (d3, _) = adc(d3, modulus[3] & borrow, carry);
+
+    [d0, d1, d2, d3]
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+
+    use crate::{Fp, Fq};
+
+    #[test]
+    fn test_mul_matches_portable() {
+        let mut rng = crate::tests::rng();
+
+        let mut fp = [-Fp::one(), Fp::zero(), Fp::one(), Fp::random(&mut rng)];
+        let mut fq = [-Fq::one(), Fq::zero(), Fq::one(), Fq::random(&mut rng)];
+        for _ in 0..1000 {
+            for a in fp.iter() {
+                for b in fp.iter() {
+                    // `*` dispatches to the assembly, while the inherent `const fn`
+                    // is always portable.
+                    assert_eq!(a * b, Fp::mul(a, b));
+                }
+                assert_eq!(<Fp as Field>::square(a), Fp::square(a));
+            }
+            for a in fq.iter() {
+                for b in fq.iter() {
+                    assert_eq!(a * b, Fq::mul(a, b));
+                }
+                assert_eq!(<Fq as Field>::square(a), Fq::square(a));
+            }
+            fp[3] = Fp::random(&mut rng);
+            fq[3] = Fq::random(&mut rng);
+        }
+    }
+}
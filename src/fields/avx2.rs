@@ -0,0 +1,119 @@
+//! Montgomery multiplication of four independent field elements at a time using
+//! AVX2.
+//!
+//! AVX2 only provides 32×32 → 64-bit multiplication, so each element is split into
+//! eight 32-bit limbs, and limb $j$ of all four elements is held in the four 64-bit
+//! lanes of a single vector. The reduction is the word-by-word (CIOS) Montgomery
+//! algorithm with 32-bit words, which computes $a b 2^{-256} \bmod p$ and thus
+//! agrees with the 64-bit scalar implementation.
+
+// The intrinsics in `core::arch` are `unsafe` functions. They are only compiled in
+// when the `avx2` target feature is statically enabled, so calling them is sound.
+#![allow(unsafe_code)]
+
+use core::arch::x86_64::{
+    __m256i, _mm256_add_epi64, _mm256_and_si256, _mm256_mul_epu32, _mm256_set1_epi64x,
+    _mm256_set_epi64x, _mm256_setzero_si256, _mm256_srli_epi64, _mm256_storeu_si256,
+};
+
+use super::batch::simd::reduce_once;
+
+/// Returns the vector whose lane $e$ holds 32-bit limb `limb` of `x[e]`.
+#[inline(always)]
+fn load_limb(x: &[[u64; 4]; 4], limb: usize) -> __m256i {
+    let word = |e: usize| ((x[e][limb / 2] >> (32 * (limb % 2))) & 0xffff_ffff) as i64;
+    // SAFETY: AVX2 is statically enabled.
+    unsafe { _mm256_set_epi64x(word(3), word(2), word(1), word(0)) }
+}
+
+/// Returns the four lanes of `v`.
+#[inline(always)]
+fn store(v: __m256i) -> [u64; 4] {
+    let mut out = [0u64; 4];
+    // SAFETY: AVX2 is statically enabled, and `out` is 32 bytes long. The store is
+    // unaligned, so `out` needs no particular alignment.
+    unsafe { _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, v) };
+    out
+}
+
+/// Computes $\textsf{lhs}_e \cdot \textsf{rhs}_e \cdot R^{-1} \bmod p$ for each of the
+/// four pairs of Montgomery-form elements, where $R = 2^{256}$, `modulus` is $p$
+/// and `inv` is $-p^{-1} \bmod 2^{64}$.
+///
+/// The inputs must be fully reduced and $p$ must be less than $2^{255}$.
+#[inline]
+pub(crate) fn mul_4x(
+    lhs: &[[u64; 4]; 4],
+    rhs: &[[u64; 4]; 4],
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [[u64; 4]; 4] {
+    // SAFETY: AVX2 is statically enabled.
+    unsafe {
+        let mask = _mm256_set1_epi64x(0xffff_ffff);
+        // -p^{-1} mod 2^32 is the low half of -p^{-1} mod 2^64.
+        let inv = _mm256_set1_epi64x((inv & 0xffff_ffff) as i64);
+
+        let mut lhs_limbs = [_mm256_setzero_si256(); 8];
+        let mut p_limbs = [_mm256_setzero_si256(); 8];
+        for j in 0..8 {
+            lhs_limbs[j] = load_limb(lhs, j);
+            p_limbs[j] =
+                _mm256_set1_epi64x(((modulus[j / 2] >> (32 * (j % 2))) & 0xffff_ffff) as i64);
+        }
+
+        // Each lane of `t` holds a 32-bit value, so t[j] + x * y + carry is at most
+        // 2^64 - 1 and no lane ever overflows.
+        let mut t = [_mm256_setzero_si256(); 10];
+        for i in 0..8 {
+            let bi = load_limb(rhs, i);
+
+            // t += a * b_i
+            let mut carry = _mm256_setzero_si256();
+            for j in 0..8 {
+                let sum = _mm256_add_epi64(
+                    _mm256_add_epi64(t[j], _mm256_mul_epu32(lhs_limbs[j], bi)),
+                    carry,
+                );
+                t[j] = _mm256_and_si256(sum, mask);
+                carry = _mm256_srli_epi64(sum, 32);
+            }
+            let sum = _mm256_add_epi64(t[8], carry);
+            t[8] = _mm256_and_si256(sum, mask);
+            t[9] = _mm256_srli_epi64(sum, 32);
+
+            // t = (t + m * p) / 2^32, where m is chosen to clear the lowest word.
+            let m = _mm256_and_si256(_mm256_mul_epu32(t[0], inv), mask);
+            let sum = _mm256_add_epi64(t[0], _mm256_mul_epu32(m, p_limbs[0]));
+            let mut carry = _mm256_srli_epi64(sum, 32);
+            for j in 1..8 {
+                let sum = _mm256_add_epi64(
+                    _mm256_add_epi64(t[j], _mm256_mul_epu32(m, p_limbs[j])),
+                    carry,
+                );
+                t[j - 1] = _mm256_and_si256(sum, mask);
+                carry = _mm256_srli_epi64(sum, 32);
+            }
+            let sum = _mm256_add_epi64(t[8], carry);
+            t[7] = _mm256_and_si256(sum, mask);
+            t[8] = _mm256_add_epi64(t[9], _mm256_srli_epi64(sum, 32));
+        }
+
+        let mut words = [[0u64; 4]; 8];
+        for j in 0..8 {
+            words[j] = store(t[j]);
+        }
+
+        let mut out = [[0u64; 4]; 4];
+        for (e, out) in out.iter_mut().enumerate() {
+            let r = [
+                words[0][e] | (words[1][e] << 32),
+                words[2][e] | (words[3][e] << 32),
+                words[4][e] | (words[5][e] << 32),
+                words[6][e] | (words[7][e] << 32),
+            ];
+            *out = reduce_once(r, modulus);
+        }
+        out
+    }
+}
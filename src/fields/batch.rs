@@ -0,0 +1,283 @@
+//! Slice operations over field elements, which are dispatched to a SIMD backend
+//! when one is available.
+
+/// The Montgomery-form limbs of a field element, as consumed by the SIMD backends.
+pub(crate) trait MontgomeryLimbs: ff::Field {
+    /// The modulus $p$, in little-endian 64-bit limbs.
+    const MODULUS: [u64; 4];
+    /// $-p^{-1} \bmod 2^{64}$.
+    const INV: u64;
+
+    /// Returns the limbs of $a R \bmod p$, where $a$ is this element.
+    fn to_limbs(&self) -> [u64; 4];
+
+    /// Returns the element $a$ given the limbs of $a R \bmod p$.
+    fn from_limbs(limbs: [u64; 4]) -> Self;
+}
+
+/// Implements [`MontgomeryLimbs`], `batch_mul`, `batch_square` and `batch_sqrt` for
+/// a field type whose Montgomery form is given by the `MODULUS` and `INV` constants
+/// in scope, and whose square root is finished by the `pow_by_t_minus1_over2_chain`
+/// and `sqrt_with_pow` functions in scope.
+macro_rules! impl_batch_ops {
+    ($field:ident) => {
+        impl crate::fields::batch::MontgomeryLimbs for $field {
+            const MODULUS: [u64; 4] = MODULUS.0;
+            const INV: u64 = INV;
+
+            fn to_limbs(&self) -> [u64; 4] {
+                self.0
+            }
+
+            fn from_limbs(limbs: [u64; 4]) -> Self {
+                $field(limbs)
+            }
+        }
+
+        impl $field {
+            /// Multiplies each element of `a` by the corresponding element of `b`, in
+            /// place.
+            ///
+            /// When a SIMD backend is available, several independent multiplications are
+            /// performed at once. On `x86_64`, AVX-512 IFMA is used if the `ifma` feature
+            /// flag is enabled, the compiler is Rust 1.89 or later and the CPU supports
+            /// it, and AVX2 is used if the crate is compiled with that target feature.
+            /// On `aarch64`, NEON is used if the `neon` feature flag is enabled.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `a` and `b` have different lengths.
+            pub fn batch_mul(a: &mut [$field], b: &[$field]) {
+                crate::fields::batch::batch_mul(a, b)
+            }
+
+            /// Squares each element of `a`, in place.
+            ///
+            /// When a SIMD backend is available, several independent squarings are
+            /// performed at once, as for [`Self::batch_mul`].
+            pub fn batch_square(a: &mut [$field]) {
+                crate::fields::batch::batch_square(a)
+            }
+
+            /// Computes the square root of each element of `a`, if it exists, with the
+            /// same result as [`ff::Field::sqrt`].
+            ///
+            /// Almost all of the work of a square root is raising the element to the
+            /// power $(T-1)/2$. Here the addition chain for that power is evaluated on
+            /// several elements in lockstep with [`Self::batch_mul`] and
+            /// [`Self::batch_square`], so that it is shared between them and a SIMD
+            /// backend performs their multiplications at once. Neither square root
+            /// algorithm inverts, so there is no inversion to share. With the
+            /// `multicore` feature flag, groups of elements are also processed in
+            /// parallel.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn batch_sqrt(a: &[$field]) -> alloc::vec::Vec<subtle::CtOption<$field>> {
+                crate::fields::batch::batch_sqrt(
+                    a,
+                    |x| pow_by_t_minus1_over2_chain(x, crate::fields::batch::Lanes::square),
+                    sqrt_with_pow,
+                )
+            }
+        }
+    };
+}
+
+/// Sets `a[i] = a[i] * b[i]`, using the fastest available backend.
+pub(crate) fn batch_mul<F: MontgomeryLimbs>(a: &mut [F], b: &[F]) {
+    assert_eq!(a.len(), b.len());
+
+    let done = 0;
+
+    #[cfg(all(target_arch = "x86_64", pasta_ifma))]
+    let done = if super::ifma::is_supported() {
+        done + simd::mul_chunks::<F, _, 8>(&mut a[done..], &b[done..], |x, y| {
+            super::ifma::mul_8x(x, y, &F::MODULUS, F::INV)
+        })
+    } else {
+        done
+    };
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    let done = done
+        + simd::mul_chunks::<F, _, 4>(&mut a[done..], &b[done..], |x, y| {
+            super::avx2::mul_4x(x, y, &F::MODULUS, F::INV)
+        });
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon", feature = "neon"))]
+    let done = done
+        + simd::mul_chunks::<F, _, 2>(&mut a[done..], &b[done..], |x, y| {
+            super::neon::mul_2x(x, y, &F::MODULUS, F::INV)
+        });
+
+    for (a, b) in a[done..].iter_mut().zip(b[done..].iter()) {
+        *a *= b;
+    }
+}
+
+/// Sets `a[i] = a[i]^2`, using the fastest available backend.
+pub(crate) fn batch_square<F: MontgomeryLimbs>(a: &mut [F]) {
+    let done = 0;
+
+    #[cfg(all(target_arch = "x86_64", pasta_ifma))]
+    let done = if super::ifma::is_supported() {
+        done + simd::square_chunks::<F, _, 8>(&mut a[done..], |x| {
+            super::ifma::mul_8x(x, x, &F::MODULUS, F::INV)
+        })
+    } else {
+        done
+    };
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    let done = done
+        + simd::square_chunks::<F, _, 4>(&mut a[done..], |x| {
+            super::avx2::mul_4x(x, x, &F::MODULUS, F::INV)
+        });
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon", feature = "neon"))]
+    let done = done
+        + simd::square_chunks::<F, _, 2>(&mut a[done..], |x| {
+            super::neon::mul_2x(x, x, &F::MODULUS, F::INV)
+        });
+
+    for a in a[done..].iter_mut() {
+        *a = a.square();
+    }
+}
+
+/// The number of elements whose square roots [`batch_sqrt`] computes in lockstep.
+#[cfg(feature = "alloc")]
+const SQRT_LANES: usize = 8;
+
+/// A group of field elements that are multiplied and squared lane by lane with
+/// [`batch_mul`] and [`batch_square`], so that an addition chain written for a
+/// single element can be evaluated on several at once.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy)]
+pub(crate) struct Lanes<F>([F; SQRT_LANES]);
+
+#[cfg(feature = "alloc")]
+impl<F: MontgomeryLimbs> Lanes<F> {
+    pub(crate) fn square(mut self) -> Self {
+        batch_square(&mut self.0);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: MontgomeryLimbs> core::ops::Mul for Lanes<F> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self {
+        batch_mul(&mut self.0, &rhs.0);
+        self
+    }
+}
+
+/// Returns the square root of each element of `a`, if it exists, where `pow` raises
+/// every lane to the power $(T-1)/2$ and `sqrt_with_pow(x, x^((T-1)/2))` finishes
+/// the square root of a single element.
+#[cfg(feature = "alloc")]
+pub(crate) fn batch_sqrt<F: MontgomeryLimbs>(
+    a: &[F],
+    pow: fn(Lanes<F>) -> Lanes<F>,
+    sqrt_with_pow: fn(&F, &F) -> subtle::CtOption<F>,
+) -> alloc::vec::Vec<subtle::CtOption<F>> {
+    use alloc::vec::Vec;
+
+    let sqrt_chunk = |a: &[F]| -> Vec<subtle::CtOption<F>> {
+        // Unused lanes are padded with one, whose power is never read.
+        let mut lanes = Lanes([F::ONE; SQRT_LANES]);
+        lanes.0[..a.len()].copy_from_slice(a);
+        let w = pow(lanes);
+        a.iter()
+            .zip(w.0.iter())
+            .map(|(a, w)| sqrt_with_pow(a, w))
+            .collect()
+    };
+
+    #[cfg(feature = "multicore")]
+    {
+        use rayon::prelude::*;
+
+        a.par_chunks(SQRT_LANES).flat_map(sqrt_chunk).collect()
+    }
+
+    #[cfg(not(feature = "multicore"))]
+    a.chunks(SQRT_LANES).flat_map(sqrt_chunk).collect()
+}
+
+/// Helpers shared by the SIMD backends.
+#[cfg(any(
+    all(target_arch = "x86_64", target_feature = "avx2"),
+    all(target_arch = "x86_64", pasta_ifma),
+    all(target_arch = "aarch64", target_feature = "neon", feature = "neon"),
+))]
+pub(crate) mod simd {
+    use super::MontgomeryLimbs;
+    use crate::arithmetic::{adc, sbb};
+
+    /// Applies `kernel` to each complete chunk of `N` elements of `a` and `b`,
+    /// storing the products in `a`, and returns the number of elements processed.
+    pub(crate) fn mul_chunks<F, K, const N: usize>(a: &mut [F], b: &[F], kernel: K) -> usize
+    where
+        F: MontgomeryLimbs,
+        K: Fn(&[[u64; 4]; N], &[[u64; 4]; N]) -> [[u64; 4]; N],
+    {
+        let mut done = 0;
+        for (a, b) in a.chunks_exact_mut(N).zip(b.chunks_exact(N)) {
+            let r = kernel(&gather(a), &gather(b));
+            scatter(a, &r);
+            done += N;
+        }
+        done
+    }
+
+    /// Applies `kernel` to each complete chunk of `N` elements of `a`, storing the
+    /// squares in `a`, and returns the number of elements processed.
+    pub(crate) fn square_chunks<F, K, const N: usize>(a: &mut [F], kernel: K) -> usize
+    where
+        F: MontgomeryLimbs,
+        K: Fn(&[[u64; 4]; N]) -> [[u64; 4]; N],
+    {
+        let mut done = 0;
+        for a in a.chunks_exact_mut(N) {
+            let r = kernel(&gather(a));
+            scatter(a, &r);
+            done += N;
+        }
+        done
+    }
+
+    fn gather<F: MontgomeryLimbs, const N: usize>(chunk: &[F]) -> [[u64; 4]; N] {
+        let mut limbs = [[0; 4]; N];
+        for (limbs, f) in limbs.iter_mut().zip(chunk.iter()) {
+            *limbs = f.to_limbs();
+        }
+        limbs
+    }
+
+    fn scatter<F: MontgomeryLimbs, const N: usize>(chunk: &mut [F], limbs: &[[u64; 4]; N]) {
+        for (f, limbs) in chunk.iter_mut().zip(limbs.iter()) {
+            *f = F::from_limbs(*limbs);
+        }
+    }
+
+    /// Subtracts `modulus` from `r` if `r` is at least `modulus`, in constant time.
+    #[inline(always)]
+    pub(crate) fn reduce_once(r: [u64; 4], modulus: &[u64; 4]) -> [u64; 4] {
+        let (d0, borrow) = sbb(r[0], modulus[0], 0);
+        let (d1, borrow) = sbb(r[1], modulus[1], borrow);
+        let (d2, borrow) = sbb(r[2], modulus[2], borrow);
+        let (d3, borrow) = sbb(r[3], modulus[3], borrow);
+
+        // If the subtraction underflowed, borrow = 0xfff...fff and we add the modulus
+        // back.
+        let (d0, carry) = adc(d0, modulus[0] & borrow, 0);
+        let (d1, carry) = adc(d1, modulus[1] & borrow, carry);
+        let (d2, carry) = adc(d2, modulus[2] & borrow, carry);
+        let (d3, _) = adc(d3, modulus[3] & borrow, carry);
+
+        [d0, d1, d2, d3]
+    }
+}
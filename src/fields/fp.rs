@@ -1,8 +1,10 @@
 use core::fmt;
 use core::ops::{Add, Mul, Neg, Sub};
+use core::str::FromStr;
 
-use ff::PrimeField;
-use rand::RngCore;
+use ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use rand::distributions::{Distribution, Standard};
+use rand::{Rng, RngCore};
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 #[cfg(feature = "sqrt-table")]
@@ -13,6 +15,8 @@ use ff::{FieldBits, PrimeFieldBits};
 
 use crate::arithmetic::{adc, mac, sbb, FieldExt, Group, SqrtRatio};
 
+use super::ParseFieldError;
+
 #[cfg(feature = "sqrt-table")]
 use crate::arithmetic::SqrtTables;
 
@@ -39,6 +43,37 @@ impl fmt::Debug for Fp {
     }
 }
 
+impl fmt::Display for Fp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}", self)
+    }
+}
+
+impl fmt::LowerHex for Fp {
+    /// Formats the canonical integer representation as 64 hex digits, prefixed
+    /// with `0x` when the alternate flag is set.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        for &b in self.to_repr().iter().rev() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Fp {
+    type Err = ParseFieldError;
+
+    /// Parses a `0x`-prefixed hexadecimal or a decimal integer less than the
+    /// field modulus.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let repr = super::parse_repr(s)?;
+        Option::from(Fp::from_repr(repr)).ok_or(ParseFieldError::OutOfRange)
+    }
+}
+
 impl From<bool> for Fp {
     fn from(bit: bool) -> Fp {
         if bit {
@@ -55,6 +90,19 @@ impl From<u64> for Fp {
     }
 }
 
+impl From<u128> for Fp {
+    fn from(val: u128) -> Fp {
+        Fp::from_u128(val)
+    }
+}
+
+impl From<i64> for Fp {
+    fn from(val: i64) -> Fp {
+        let magnitude = Fp::from(val.unsigned_abs());
+        Fp::conditional_select(&magnitude, &-magnitude, Choice::from((val < 0) as u8))
+    }
+}
+
 impl ConstantTimeEq for Fp {
     fn ct_eq(&self, other: &Self) -> Choice {
         self.0[0].ct_eq(&other.0[0])
@@ -71,6 +119,19 @@ impl PartialEq for Fp {
     }
 }
 
+impl core::hash::Hash for Fp {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.to_repr().hash(state);
+    }
+}
+
+impl Distribution<Fp> for Standard {
+    /// Samples a uniformly random field element, as [`ff::Field::random`].
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fp {
+        ff::Field::random(rng)
+    }
+}
+
 impl core::cmp::Ord for Fp {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         let left = self.to_repr();
@@ -166,12 +227,29 @@ impl<'a, 'b> Mul<&'b Fp> for &'a Fp {
 
     #[inline]
     fn mul(self, rhs: &'b Fp) -> Fp {
+        #[cfg(all(
+            feature = "asm",
+            target_arch = "x86_64",
+            target_feature = "adx",
+            target_feature = "bmi2"
+        ))]
+        return Fp(super::asm::mul(&self.0, &rhs.0, &MODULUS.0, INV));
+
+        #[cfg(any(feature = "limb32", not(target_pointer_width = "64")))]
+        #[allow(unreachable_code)]
+        return Fp(super::limb32::mul(&self.0, &rhs.0, &MODULUS.0, INV));
+
+        #[allow(unreachable_code)]
         self.mul(rhs)
     }
 }
 
 impl_binops_additive!(Fp, Fp);
 impl_binops_multiplicative!(Fp, Fp);
+impl_sum_prod!(Fp);
+
+/// The constants for inverting modulo p with safegcd.
+const MODULUS_SAFEGCD: super::safegcd::Modulus = super::safegcd::Modulus::new(&MODULUS.0);
 
 /// INV = -(p^{-1} mod 2^64) mod 2^64
 const INV: u64 = 0x992d30ecffffffff;
@@ -246,7 +324,28 @@ impl Default for Fp {
     }
 }
 
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl zeroize::Zeroize for Fp {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
 impl Fp {
+    /// The modulus $p$, as little-endian 64-bit limbs.
+    pub const MODULUS_LIMBS: [u64; 4] = MODULUS.0;
+
+    /// $R = 2^{256} \bmod p$, as little-endian 64-bit limbs. This is the Montgomery
+    /// form of one.
+    pub const R: [u64; 4] = R.0;
+
+    /// $R^2 = 2^{512} \bmod p$, as little-endian 64-bit limbs.
+    pub const R2: [u64; 4] = R2.0;
+
+    /// $-p^{-1} \bmod 2^{64}$, the constant used by Montgomery reduction.
+    pub const INV: u64 = INV;
+
     /// Returns zero, the additive identity.
     #[inline]
     pub const fn zero() -> Fp {
@@ -282,16 +381,126 @@ impl Fp {
         // constant `R2` or `R3`.
         let d0 = Fp([limbs[0], limbs[1], limbs[2], limbs[3]]);
         let d1 = Fp([limbs[4], limbs[5], limbs[6], limbs[7]]);
-        // Convert to Montgomery form
-        d0 * R2 + d1 * R3
+        // Convert to Montgomery form. This uses the portable multiplication, as `d0`
+        // and `d1` may not be fully reduced.
+        (&d0).mul(&R2) + (&d1).mul(&R3)
     }
 
     /// Converts from an integer represented in little endian
     /// into its (congruent) `Fp` representation.
+    ///
+    /// This is a `const fn`, so it can be used to define constants.
     pub const fn from_raw(val: [u64; 4]) -> Self {
         (&Fp(val)).mul(&R2)
     }
 
+    /// Constructs an element from the little-endian 64-bit limbs of its Montgomery
+    /// form $a R \bmod p$, which must be less than the modulus.
+    ///
+    /// Unlike [`Fp::from_raw`], this does not multiply by [`Fp::R2`], so constants
+    /// whose Montgomery form was precomputed can be embedded without any arithmetic.
+    pub const fn from_montgomery_limbs(limbs: [u64; 4]) -> Self {
+        Fp(limbs)
+    }
+
+    /// Returns the little-endian 64-bit limbs of the Montgomery form of this element.
+    pub const fn to_montgomery_limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    /// Converts a 128-bit integer into its `Fp` representation.
+    pub const fn from_u128(val: u128) -> Self {
+        Fp::from_raw([val as u64, (val >> 64) as u64, 0, 0])
+    }
+
+    /// Converts a uniformly random 512-bit little-endian integer, such as the output
+    /// of a 512-bit hash, into a `Fp` by reducing it modulo $p$.
+    ///
+    /// As $p < 2^{255}$, the result is within statistical distance $2^{-257}$ of
+    /// uniform, unlike reducing a 256-bit integer or truncating to 255 bits. This
+    /// is [`FieldExt::from_bytes_wide`], available without importing the trait.
+    pub fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+        <Self as FieldExt>::from_bytes_wide(bytes)
+    }
+
+    /// Samples a uniformly random field element from the operating system's
+    /// random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator fails.
+    #[cfg(feature = "os-rng")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "os-rng")))]
+    pub fn random_os() -> Self {
+        ff::Field::random(rand::rngs::OsRng)
+    }
+
+    /// Encodes this element as 64 lowercase hex digits, without a prefix.
+    ///
+    /// The digits are those of the little-endian encoding returned by
+    /// [`PrimeField::to_repr`], as in the `serde` encoding. This is not the
+    /// [`LowerHex`](fmt::LowerHex) format, which writes the integer big-endian.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_hex(&self) -> alloc::string::String {
+        super::encode_hex(&self.to_repr())
+    }
+
+    /// Decodes an element from the 64 hex digits of its little-endian encoding, as
+    /// written by [`Fp::to_hex`], failing if they are not a canonical encoding.
+    ///
+    /// A `0x` prefix is rejected, as it would be ambiguous with the big-endian
+    /// integer written by `Display` and parsed by `FromStr`.
+    pub fn from_hex(s: &str) -> Result<Self, ParseFieldError> {
+        let repr = super::decode_hex(
+            s,
+            ParseFieldError::InvalidLength,
+            ParseFieldError::InvalidDigit,
+        )?;
+        Option::from(Fp::from_repr(repr)).ok_or(ParseFieldError::OutOfRange)
+    }
+
+    /// Hashes `message` to `count` field elements, using the same
+    /// `expand_message_xmd` construction with BLAKE2b and domain separation as
+    /// the Pallas `hash_to_curve`.
+    ///
+    /// With `count = 2`, this returns the two field elements that
+    /// `hash_to_curve` maps to the curve for the same `domain_prefix` and
+    /// `message`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than 255, or if `domain_prefix` is too long.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn hash_to_field(
+        domain_prefix: &str,
+        message: &[u8],
+        count: usize,
+    ) -> alloc::vec::Vec<Self> {
+        let mut res = alloc::vec![Fp::zero(); count];
+        crate::hashtocurve::hash_to_field(
+            <crate::Ep as crate::arithmetic::CurveExt>::CURVE_ID,
+            domain_prefix,
+            message,
+            &mut res,
+        );
+        res
+    }
+
+    /// Returns the Legendre symbol of this element: $1$ if it is a nonzero square,
+    /// $-1$ if it is a non-square, and $0$ if it is zero.
+    ///
+    /// This is cheaper than attempting to compute a square root.
+    pub fn legendre(&self) -> i8 {
+        crate::arithmetic::legendre_symbol(self)
+    }
+
+    /// Returns whether this element is a square, which includes zero.
+    pub fn is_quadratic_residue(&self) -> Choice {
+        !self.legendre().ct_eq(&-1)
+    }
+
     /// Squares this element.
     #[cfg_attr(not(feature = "uninline-portable"), inline)]
     pub const fn square(&self) -> Fp {
@@ -450,6 +659,8 @@ impl Fp {
     }
 }
 
+impl_batch_ops!(Fp);
+
 impl From<Fp> for [u8; 32] {
     fn from(value: Fp) -> [u8; 32] {
         value.to_repr()
@@ -493,13 +704,8 @@ impl ff::Field for Fp {
         ])
     }
 
-    fn zero() -> Self {
-        Self::zero()
-    }
-
-    fn one() -> Self {
-        Self::one()
-    }
+    const ZERO: Self = Self::zero();
+    const ONE: Self = Self::one();
 
     fn double(&self) -> Self {
         self.double()
@@ -507,6 +713,19 @@ impl ff::Field for Fp {
 
     #[inline(always)]
     fn square(&self) -> Self {
+        #[cfg(all(
+            feature = "asm",
+            target_arch = "x86_64",
+            target_feature = "adx",
+            target_feature = "bmi2"
+        ))]
+        return Fp(super::asm::mul(&self.0, &self.0, &MODULUS.0, INV));
+
+        #[cfg(any(feature = "limb32", not(target_pointer_width = "64")))]
+        #[allow(unreachable_code)]
+        return Fp(super::limb32::mul(&self.0, &self.0, &MODULUS.0, INV));
+
+        #[allow(unreachable_code)]
         self.square()
     }
 
@@ -522,44 +741,77 @@ impl ff::Field for Fp {
         crate::arithmetic::sqrt_tonelli_shanks(self, &T_MINUS1_OVER2)
     }
 
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        #[cfg(feature = "sqrt-table")]
+        {
+            FP_TABLES.sqrt_ratio(num, div)
+        }
+
+        #[cfg(not(feature = "sqrt-table"))]
+        crate::arithmetic::sqrt_ratio_generic(num, div)
+    }
+
+    #[cfg(feature = "sqrt-table")]
+    fn sqrt_alt(&self) -> (Choice, Self) {
+        FP_TABLES.sqrt_alt(self)
+    }
+
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
+    ///
+    /// This uses the constant-time safegcd algorithm of Bernstein and Yang.
     fn invert(&self) -> CtOption<Self> {
-        let tmp = self.pow_vartime(&[
-            0x992d30ecffffffff,
-            0x224698fc094cf91b,
-            0x0,
-            0x4000000000000000,
-        ]);
+        // The inverse of x R is x^{-1} R^{-1}, which R^3 brings back to Montgomery
+        // form.
+        let tmp = Fp(super::safegcd::invert(&self.0, &MODULUS_SAFEGCD)) * R3;
 
         CtOption::new(tmp, !self.ct_eq(&Self::zero()))
     }
 
-    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
-        let mut res = Self::one();
-        let mut found_one = false;
-        for e in exp.as_ref().iter().rev() {
-            for i in (0..64).rev() {
-                if found_one {
-                    res = res.square();
-                }
+    /// Exponentiates `self` by `exp`, where `exp` is a little-endian order integer
+    /// exponent, in constant time with respect to both.
+    ///
+    /// Use this when the exponent is secret. Only the number of limbs of `exp` is
+    /// leaked.
+    #[cfg(feature = "windowed-pow")]
+    fn pow<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        crate::arithmetic::pow_windowed(self, exp.as_ref())
+    }
 
-                if ((*e >> i) & 1) == 1 {
-                    found_one = true;
-                    res *= self;
-                }
-            }
-        }
-        res
+    /// Exponentiates `self` by `exp`, where `exp` is a little-endian order integer
+    /// exponent.
+    ///
+    /// This is not constant time with respect to `exp`, and should only be used
+    /// with public exponents.
+    #[cfg(feature = "windowed-pow")]
+    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        crate::arithmetic::pow_vartime_windowed(self, exp.as_ref())
     }
 }
 
 impl ff::PrimeField for Fp {
     type Repr = [u8; 32];
 
+    const MODULUS: &'static str =
+        "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001";
     const NUM_BITS: u32 = 255;
     const CAPACITY: u32 = 254;
+    const TWO_INV: Self = Fp::from_raw([
+        0xcc96987680000001,
+        0x11234c7e04a67c8d,
+        0x0000000000000000,
+        0x2000000000000000,
+    ]);
+    const MULTIPLICATIVE_GENERATOR: Self = GENERATOR;
     const S: u32 = S;
+    const ROOT_OF_UNITY: Self = ROOT_OF_UNITY;
+    const ROOT_OF_UNITY_INV: Self = Fp::from_raw([
+        0xf0b87c7db2ce91f6,
+        0x84a0a1d8859f066f,
+        0xb4ed8e647196dad1,
+        0x2cd5282c53116b5c,
+    ]);
+    const DELTA: Self = DELTA;
 
     fn from_repr(repr: Self::Repr) -> CtOption<Self> {
         let mut tmp = Fp([0, 0, 0, 0]);
@@ -605,12 +857,25 @@ impl ff::PrimeField for Fp {
         Choice::from(self.to_repr()[0] & 1)
     }
 
-    fn multiplicative_generator() -> Self {
-        GENERATOR
+    fn from_u128(v: u128) -> Self {
+        Fp::from_u128(v)
     }
+}
+
+impl WithSmallOrderMulGroup<3> for Fp {
+    const ZETA: Self = Fp::from_raw([
+        0x1dad5ebdfdfe4ab9,
+        0x1d1f8bd237ad3149,
+        0x2caad5dc57aab1b0,
+        0x12ccca834acdba71,
+    ]);
+}
 
-    fn root_of_unity() -> Self {
-        ROOT_OF_UNITY
+impl FromUniformBytes<64> for Fp {
+    /// Converts a 512-bit little endian integer into
+    /// a `Fp` by reducing by the modulus.
+    fn from_uniform_bytes(bytes: &[u8; 64]) -> Fp {
+        <Self as FieldExt>::from_bytes_wide(bytes)
     }
 }
 
@@ -669,38 +934,58 @@ lazy_static! {
     static ref FP_TABLES: SqrtTables<Fp> = SqrtTables::new(0x11BE, 1098);
 }
 
+/// The addition chain for [`SqrtRatio::pow_by_t_minus1_over2`], generic over the
+/// element type so that `batch_sqrt` can also evaluate it on several elements at once.
+fn pow_by_t_minus1_over2_chain<T: Copy + Mul<Output = T>>(x: T, square: impl Fn(T) -> T) -> T {
+    let sqr = |x: T, i: u32| (0..i).fold(x, |x, _| square(x));
+
+    let r10 = square(x);
+    let r11 = r10 * x;
+    let r110 = square(r11);
+    let r111 = r110 * x;
+    let r1001 = r111 * r10;
+    let r1101 = r111 * r110;
+    let ra = sqr(x, 129) * x;
+    let rb = sqr(ra, 7) * r1001;
+    let rc = sqr(rb, 7) * r1101;
+    let rd = sqr(rc, 4) * r11;
+    let re = sqr(rd, 6) * r111;
+    let rf = sqr(re, 3) * r111;
+    let rg = sqr(rf, 10) * r1001;
+    let rh = sqr(rg, 5) * r1001;
+    let ri = sqr(rh, 4) * r1001;
+    let rj = sqr(ri, 3) * r111;
+    let rk = sqr(rj, 4) * r1001;
+    let rl = sqr(rk, 5) * r11;
+    let rm = sqr(rl, 4) * r111;
+    let rn = sqr(rm, 4) * r11;
+    let ro = sqr(rn, 6) * r1001;
+    let rp = sqr(ro, 5) * r1101;
+    let rq = sqr(rp, 4) * r11;
+    let rr = sqr(rq, 7) * r111;
+    let rs = sqr(rr, 3) * r11;
+    square(rs) // rt
+}
+
+/// Finishes the square root of `a` as [`ff::Field::sqrt`] does, given
+/// `w = a^((T-1)/2)`.
+#[cfg(feature = "alloc")]
+fn sqrt_with_pow(a: &Fp, w: &Fp) -> CtOption<Fp> {
+    #[cfg(feature = "sqrt-table")]
+    {
+        let (is_square, res) = FP_TABLES.sqrt_alt_with_pow(a, w);
+        CtOption::new(res, is_square)
+    }
+
+    #[cfg(not(feature = "sqrt-table"))]
+    crate::arithmetic::sqrt_tonelli_shanks_with_pow(a, w)
+}
+
 impl SqrtRatio for Fp {
     const T_MINUS1_OVER2: [u64; 4] = T_MINUS1_OVER2;
 
     fn pow_by_t_minus1_over2(&self) -> Self {
-        let sqr = |x: Fp, i: u32| (0..i).fold(x, |x, _| x.square());
-
-        let r10 = self.square();
-        let r11 = r10 * self;
-        let r110 = r11.square();
-        let r111 = r110 * self;
-        let r1001 = r111 * r10;
-        let r1101 = r111 * r110;
-        let ra = sqr(*self, 129) * self;
-        let rb = sqr(ra, 7) * r1001;
-        let rc = sqr(rb, 7) * r1101;
-        let rd = sqr(rc, 4) * r11;
-        let re = sqr(rd, 6) * r111;
-        let rf = sqr(re, 3) * r111;
-        let rg = sqr(rf, 10) * r1001;
-        let rh = sqr(rg, 5) * r1001;
-        let ri = sqr(rh, 4) * r1001;
-        let rj = sqr(ri, 3) * r111;
-        let rk = sqr(rj, 4) * r1001;
-        let rl = sqr(rk, 5) * r11;
-        let rm = sqr(rl, 4) * r111;
-        let rn = sqr(rm, 4) * r11;
-        let ro = sqr(rn, 6) * r1001;
-        let rp = sqr(ro, 5) * r1101;
-        let rq = sqr(rp, 4) * r11;
-        let rr = sqr(rq, 7) * r111;
-        let rs = sqr(rr, 3) * r11;
-        rs.square() // rt
+        pow_by_t_minus1_over2_chain(*self, |x: Fp| x.square())
     }
 
     fn get_lower_32(&self) -> u32 {
@@ -709,45 +994,9 @@ impl SqrtRatio for Fp {
 
         tmp.0[0] as u32
     }
-
-    #[cfg(feature = "sqrt-table")]
-    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
-        FP_TABLES.sqrt_ratio(num, div)
-    }
-
-    #[cfg(feature = "sqrt-table")]
-    fn sqrt_alt(&self) -> (Choice, Self) {
-        FP_TABLES.sqrt_alt(self)
-    }
 }
 
 impl FieldExt for Fp {
-    const MODULUS: &'static str =
-        "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001";
-    const ROOT_OF_UNITY_INV: Self = Fp::from_raw([
-        0xf0b87c7db2ce91f6,
-        0x84a0a1d8859f066f,
-        0xb4ed8e647196dad1,
-        0x2cd5282c53116b5c,
-    ]);
-    const DELTA: Self = DELTA;
-    const TWO_INV: Self = Fp::from_raw([
-        0xcc96987680000001,
-        0x11234c7e04a67c8d,
-        0x0000000000000000,
-        0x2000000000000000,
-    ]);
-    const ZETA: Self = Fp::from_raw([
-        0x1dad5ebdfdfe4ab9,
-        0x1d1f8bd237ad3149,
-        0x2caad5dc57aab1b0,
-        0x12ccca834acdba71,
-    ]);
-
-    fn from_u128(v: u128) -> Self {
-        Fp::from_raw([v as u64, (v >> 64) as u64, 0, 0])
-    }
-
     /// Converts a 512-bit little endian integer into
     /// a `Fp` by reducing by the modulus.
     fn from_bytes_wide(bytes: &[u8; 64]) -> Fp {
@@ -770,10 +1019,12 @@ impl FieldExt for Fp {
     }
 }
 
+/// The name is fixed, rather than derived from the source location, so that the
+/// kernels compiled by the build script can be looked up at runtime.
 #[cfg(feature = "gpu")]
 impl ec_gpu::GpuName for Fp {
     fn name() -> alloc::string::String {
-        ec_gpu::name!()
+        alloc::string::String::from("pasta_Fp")
     }
 }
 
@@ -810,6 +1061,68 @@ fn test_inv() {
     assert_eq!(inv, INV);
 }
 
+#[test]
+fn test_sum_of_products() {
+    let mut rng = crate::tests::rng();
+
+    for n in [0, 1, 2, 3, 17, 100] {
+        let a: std::vec::Vec<_> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+        let b: std::vec::Vec<_> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+        let expected = a
+            .iter()
+            .zip(b.iter())
+            .fold(Fp::zero(), |acc, (a, b)| acc + a * b);
+        assert_eq!(Fp::sum_of_products(&a, &b), expected);
+    }
+
+    // The largest Montgomery limbs, and sums of products that are a multiple of
+    // the modulus.
+    let max =
+        Fp::from_montgomery_limbs([MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]]);
+    let a = vec![max; 1000];
+    assert_eq!(
+        Fp::sum_of_products(&a, &a),
+        max.square() * Fp::from(1000u64)
+    );
+    let x = Fp::random(&mut rng);
+    assert_eq!(
+        Fp::sum_of_products(&[x, x, Fp::one()], &[x, -x, Fp::zero()]),
+        Fp::zero()
+    );
+}
+
+#[test]
+fn test_invert_matches_fermat() {
+    // x^(p - 2)
+    let fermat = |x: &Fp| {
+        x.pow_vartime(&[
+            0x992d30ecffffffff,
+            0x224698fc094cf91b,
+            0x0,
+            0x4000000000000000,
+        ])
+    };
+
+    let mut rng = crate::tests::rng();
+
+    let mut elements = vec![
+        Fp::one(),
+        -Fp::one(),
+        Fp::from(2u64),
+        Fp::TWO_INV,
+        Fp::ROOT_OF_UNITY,
+        Fp::from_raw([u64::MAX, u64::MAX, u64::MAX, 0x3fffffffffffffff]),
+    ];
+    elements.extend((0..1000).map(|_| Fp::random(&mut rng)));
+    for x in elements {
+        let inv = x.invert().unwrap();
+        assert_eq!(inv, fermat(&x));
+        assert_eq!(inv * x, Fp::one());
+    }
+
+    assert!(bool::from(Fp::zero().invert().is_none()));
+}
+
 #[test]
 fn test_sqrt() {
     // NB: TWO_INV is standing in as a "random" field element
@@ -817,6 +1130,28 @@ fn test_sqrt() {
     assert!(v == Fp::TWO_INV || (-v) == Fp::TWO_INV);
 }
 
+#[test]
+fn test_sqrt_tonelli_shanks() {
+    use ff::Field;
+
+    // Tonelli–Shanks is used when the `sqrt-table` feature flag is disabled, so
+    // check it against `sqrt` regardless of the enabled features.
+    let mut rng = crate::tests::rng();
+
+    for a in [Fp::ZERO, Fp::ONE, Fp::ROOT_OF_UNITY]
+        .iter()
+        .cloned()
+        .chain((0..100).map(|_| Fp::random(&mut rng)))
+    {
+        let expected = a.sqrt();
+        let actual = crate::arithmetic::sqrt_tonelli_shanks(&a, &T_MINUS1_OVER2);
+        assert_eq!(bool::from(actual.is_some()), bool::from(expected.is_some()));
+        if let Some(v) = Option::<Fp>::from(actual) {
+            assert_eq!(v.square(), a);
+        }
+    }
+}
+
 #[test]
 fn test_pow_by_t_minus1_over2() {
     // NB: TWO_INV is standing in as a "random" field element
@@ -824,13 +1159,53 @@ fn test_pow_by_t_minus1_over2() {
     assert!(v == ff::Field::pow_vartime(&Fp::TWO_INV, &T_MINUS1_OVER2));
 }
 
+#[test]
+fn test_pow() {
+    // Square-and-multiply, for reference.
+    fn pow_binary(a: &Fp, exp: &[u64]) -> Fp {
+        let mut res = Fp::one();
+        for e in exp.iter().rev() {
+            for i in (0..64).rev() {
+                res = res.square();
+                if (e >> i) & 1 == 1 {
+                    res *= a;
+                }
+            }
+        }
+        res
+    }
+
+    let mut rng = crate::tests::rng();
+
+    let a = Fp::random(&mut rng);
+    let mut exps = vec![
+        vec![],
+        vec![0],
+        vec![1],
+        vec![0xf0],
+        vec![u64::MAX],
+        vec![0, 1],
+        vec![u64::MAX; 4],
+        T_MINUS1_OVER2.to_vec(),
+    ];
+    exps.extend((0..10).map(|_| (0..4).map(|_| rng.next_u64()).collect()));
+    for exp in exps {
+        let expected = pow_binary(&a, &exp);
+        assert_eq!(a.pow(&exp), expected);
+        assert_eq!(a.pow_vartime(&exp), expected);
+    }
+
+    assert_eq!(Fp::zero().pow([0]), Fp::one());
+    assert_eq!(Fp::zero().pow_vartime([3]), Fp::zero());
+}
+
 #[test]
 fn test_sqrt_ratio_and_alt() {
     // (true, sqrt(num/div)), if num and div are nonzero and num/div is a square in the field
     let num = (Fp::TWO_INV).square();
-    let div = Fp::from(25);
+    let div = Fp::from(25u64);
     let div_inverse = div.invert().unwrap();
-    let expected = Fp::TWO_INV * Fp::from(5).invert().unwrap();
+    let expected = Fp::TWO_INV * Fp::from(5u64).invert().unwrap();
     let (is_square, v) = Fp::sqrt_ratio(&num, &div);
     assert!(bool::from(is_square));
     assert!(v == expected || (-v) == expected);
@@ -840,8 +1215,8 @@ fn test_sqrt_ratio_and_alt() {
     assert!(v_alt == v);
 
     // (false, sqrt(ROOT_OF_UNITY * num/div)), if num and div are nonzero and num/div is a nonsquare in the field
-    let num = num * Fp::root_of_unity();
-    let expected = Fp::TWO_INV * Fp::root_of_unity() * Fp::from(5).invert().unwrap();
+    let num = num * Fp::ROOT_OF_UNITY;
+    let expected = Fp::TWO_INV * Fp::ROOT_OF_UNITY * Fp::from(5u64).invert().unwrap();
     let (is_square, v) = Fp::sqrt_ratio(&num, &div);
     assert!(!bool::from(is_square));
     assert!(v == expected || (-v) == expected);
@@ -888,19 +1263,19 @@ fn test_zeta() {
 #[test]
 fn test_root_of_unity() {
     assert_eq!(
-        Fp::root_of_unity().pow_vartime(&[1 << Fp::S, 0, 0, 0]),
+        Fp::ROOT_OF_UNITY.pow_vartime(&[1 << Fp::S, 0, 0, 0]),
         Fp::one()
     );
 }
 
 #[test]
 fn test_inv_root_of_unity() {
-    assert_eq!(Fp::ROOT_OF_UNITY_INV, Fp::root_of_unity().invert().unwrap());
+    assert_eq!(Fp::ROOT_OF_UNITY_INV, Fp::ROOT_OF_UNITY.invert().unwrap());
 }
 
 #[test]
 fn test_inv_2() {
-    assert_eq!(Fp::TWO_INV, Fp::from(2).invert().unwrap());
+    assert_eq!(Fp::TWO_INV, Fp::from(2u64).invert().unwrap());
 }
 
 #[test]
@@ -908,10 +1283,89 @@ fn test_delta() {
     assert_eq!(Fp::DELTA, GENERATOR.pow(&[1u64 << Fp::S, 0, 0, 0]));
     assert_eq!(
         Fp::DELTA,
-        Fp::multiplicative_generator().pow(&[1u64 << Fp::S, 0, 0, 0])
+        Fp::MULTIPLICATIVE_GENERATOR.pow(&[1u64 << Fp::S, 0, 0, 0])
     );
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_invert() {
+    use crate::arithmetic::batch_invert;
+
+    let mut elements: alloc::vec::Vec<_> = (0..10u64).map(|i| Fp::from(i) * Fp::ZETA).collect();
+    let expected: alloc::vec::Vec<_> = elements
+        .iter()
+        .map(|e| e.invert().unwrap_or(Fp::zero()))
+        .collect();
+    let all_inv = batch_invert(&mut elements);
+
+    assert_eq!(elements, expected);
+    assert_eq!(
+        all_inv,
+        expected[1..].iter().fold(Fp::one(), |acc, e| acc * e)
+    );
+}
+
+#[test]
+fn test_batch_mul() {
+    let mut rng = crate::tests::rng();
+
+    let mut a = [Fp::zero(); 11];
+    let mut b = [Fp::zero(); 11];
+    for (a, b) in a.iter_mut().zip(b.iter_mut()) {
+        *a = Fp::random(&mut rng);
+        *b = Fp::random(&mut rng);
+    }
+    // Include the extremes of the field.
+    a[0] = -Fp::one();
+    b[0] = -Fp::one();
+    a[1] = Fp::zero();
+    b[2] = Fp::one();
+
+    for len in 0..=a.len() {
+        let mut product = a;
+        Fp::batch_mul(&mut product[..len], &b[..len]);
+        let mut square = a;
+        Fp::batch_square(&mut square[..len]);
+
+        for i in 0..a.len() {
+            if i < len {
+                assert_eq!(product[i], a[i] * b[i]);
+                assert_eq!(square[i], a[i].square());
+            } else {
+                assert_eq!(product[i], a[i]);
+                assert_eq!(square[i], a[i]);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_sqrt() {
+    let mut rng = crate::tests::rng();
+
+    // Squares, non-squares, zero and one, over a length that is not a multiple of
+    // the number of elements processed in lockstep.
+    let mut a: alloc::vec::Vec<_> = (0..19).map(|_| Fp::random(&mut rng)).collect();
+    a.push(Fp::zero());
+    a.push(Fp::one());
+    a.push(Fp::from(4u64));
+    assert!(a.iter().any(|a| bool::from(a.sqrt().is_none())));
+
+    for len in [0, 1, 8, a.len()] {
+        let roots = Fp::batch_sqrt(&a[..len]);
+        assert_eq!(roots.len(), len);
+        for (a, root) in a.iter().zip(roots.iter()) {
+            let expected = a.sqrt();
+            assert_eq!(bool::from(root.is_some()), bool::from(expected.is_some()));
+            if bool::from(expected.is_some()) {
+                assert_eq!(root.unwrap(), expected.unwrap());
+            }
+        }
+    }
+}
+
 #[cfg(not(target_pointer_width = "64"))]
 #[test]
 fn consistent_modulus_limbs() {
@@ -929,6 +1383,147 @@ fn consistent_modulus_limbs() {
     }
 }
 
+#[test]
+fn test_from_u128() {
+    assert_eq!(Fp::from(0u128), Fp::zero());
+    assert_eq!(
+        Fp::from(0x0123456789abcdefu128),
+        Fp::from(0x0123456789abcdefu64)
+    );
+    assert_eq!(
+        Fp::from(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210_u128),
+        Fp::from(0x0123456789abcdefu64) * Fp::from(1u64 << 32).square()
+            + Fp::from(0xfedcba9876543210u64)
+    );
+    assert_eq!(
+        Fp::from(u128::MAX),
+        Fp::from(1u64 << 32).square().square() - Fp::one()
+    );
+
+    const X: Fp = Fp::from_u128(1 << 100);
+    assert_eq!(X, Fp::from(1u128 << 100));
+}
+
+#[test]
+fn test_from_i64() {
+    assert_eq!(Fp::from(0i64), Fp::zero());
+    assert_eq!(Fp::from(5i64), Fp::from(5u64));
+    assert_eq!(Fp::from(-5i64), -Fp::from(5u64));
+    assert_eq!(Fp::from(i64::MIN), -Fp::from(1u64 << 63));
+    assert_eq!(Fp::from(i64::MAX), Fp::from(i64::MAX as u64));
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!("0".parse::<Fp>(), Ok(Fp::zero()));
+    assert_eq!("0x0".parse::<Fp>(), Ok(Fp::zero()));
+    assert_eq!("1234567890".parse::<Fp>(), Ok(Fp::from(1234567890u64)));
+    assert_eq!("0xdeadBEEF".parse::<Fp>(), Ok(Fp::from(0xdead_beef_u64)));
+    assert_eq!("0X00ff".parse::<Fp>(), Ok(Fp::from(0xffu64)));
+    assert_eq!(
+        "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000000".parse::<Fp>(),
+        Ok(-Fp::one())
+    );
+    assert_eq!(
+        "28948022309329048855892746252171976963363056481941560715954676764349967630336"
+            .parse::<Fp>(),
+        Ok(-Fp::one())
+    );
+
+    assert_eq!("".parse::<Fp>(), Err(ParseFieldError::Empty));
+    assert_eq!("0x".parse::<Fp>(), Err(ParseFieldError::Empty));
+    assert_eq!("-1".parse::<Fp>(), Err(ParseFieldError::InvalidDigit));
+    assert_eq!("12a".parse::<Fp>(), Err(ParseFieldError::InvalidDigit));
+    assert_eq!("0x12g".parse::<Fp>(), Err(ParseFieldError::InvalidDigit));
+    assert_eq!(
+        "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001".parse::<Fp>(),
+        Err(ParseFieldError::OutOfRange)
+    );
+    assert_eq!(
+        "28948022309329048855892746252171976963363056481941560715954676764349967630337"
+            .parse::<Fp>(),
+        Err(ParseFieldError::OutOfRange)
+    );
+    assert_eq!(
+        format!("0x1{}", "0".repeat(64)).parse::<Fp>(),
+        Err(ParseFieldError::OutOfRange)
+    );
+}
+
+#[test]
+fn test_display() {
+    let a = -Fp::one();
+    assert_eq!(
+        format!("{}", a),
+        "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000000"
+    );
+    assert_eq!(
+        format!("{:x}", a),
+        "40000000000000000000000000000000224698fc094cf91b992d30ed00000000"
+    );
+    assert_eq!(format!("{}", a), format!("{:?}", a));
+    assert_eq!(format!("{}", a).parse::<Fp>(), Ok(a));
+    assert_eq!(
+        format!("{:#x}", Fp::one()),
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+    );
+}
+
+#[test]
+fn test_legendre() {
+    use ff::Field;
+
+    let mut rng = crate::tests::rng();
+
+    assert_eq!(Fp::zero().legendre(), 0);
+    assert_eq!(Fp::one().legendre(), 1);
+    assert_eq!(Fp::MULTIPLICATIVE_GENERATOR.legendre(), -1);
+    assert!(bool::from(Fp::zero().is_quadratic_residue()));
+
+    for _ in 0..100 {
+        let a = Fp::random(&mut rng);
+        if a.is_zero_vartime() {
+            continue;
+        }
+        let expected: i8 = if a.sqrt().is_some().into() { 1 } else { -1 };
+        assert_eq!(a.legendre(), expected);
+        assert_eq!(bool::from(a.is_quadratic_residue()), expected == 1);
+        // Euler's criterion
+        let euler = a.pow_vartime(&[
+            0xcc96987680000000,
+            0x11234c7e04a67c8d,
+            0x0000000000000000,
+            0x2000000000000000,
+        ]);
+        assert_eq!(euler, Fp::from(i64::from(expected)));
+        assert_eq!(a.square().legendre(), 1);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hash_to_field() {
+    let us = Fp::hash_to_field("z.cash:test", b"Trans rights now!", 3);
+    assert_eq!(
+        us.iter()
+            .map(|u| format!("{:?}", u))
+            .collect::<std::vec::Vec<_>>(),
+        [
+            "0x054210e6deabde931351480d2352c39d04dce737e2916d7a4abcf1738fa9b02c",
+            "0x21a99538b9b98393f83ac630382db71af9a6a75c19768de86c758025abe01683",
+            "0x203c738a602536f3b80aecf416087191ab3d200fc13664ead9642595ca46c1de",
+        ]
+    );
+
+    // Fewer elements are a prefix of more elements only for the first one, as the
+    // output length is part of the hash input.
+    assert!(Fp::hash_to_field("z.cash:test", b"Trans rights now!", 0).is_empty());
+    assert_ne!(
+        Fp::hash_to_field("z.cash:test", b"Trans rights now!", 2)[..],
+        us[..2]
+    );
+}
+
 #[test]
 fn test_from_u512() {
     assert_eq!(
@@ -950,3 +1545,154 @@ fn test_from_u512() {
         ])
     );
 }
+
+#[test]
+fn test_from_uniform_bytes() {
+    let mut bytes = [0; 64];
+    bytes[..32].copy_from_slice(&(-Fp::one()).to_repr());
+    assert_eq!(Fp::from_uniform_bytes(&bytes), -Fp::one());
+
+    // 2^511 + (modulus - 1) is congruent to 2^511 - 1.
+    bytes[63] = 0x80;
+    let mut expected = [0xff; 64];
+    expected[63] = 0x7f;
+    assert_eq!(
+        Fp::from_uniform_bytes(&bytes),
+        Fp::from_uniform_bytes(&expected)
+    );
+    assert_eq!(
+        Fp::from_uniform_bytes(&bytes),
+        <Fp as FieldExt>::from_bytes_wide(&bytes)
+    );
+}
+
+#[test]
+fn test_montgomery_constants() {
+    const FIVE: Fp = Fp::from_raw([5, 0, 0, 0]);
+    const ONE: Fp = Fp::from_montgomery_limbs(Fp::R);
+    assert_eq!(FIVE, Fp::from(5u64));
+    assert_eq!(ONE, Fp::one());
+    assert_eq!(Fp::from_montgomery_limbs(Fp::R2), Fp::from_raw(Fp::R));
+    assert_eq!(Fp::from_montgomery_limbs(FIVE.to_montgomery_limbs()), FIVE);
+    assert_eq!(Fp::MODULUS_LIMBS[0].wrapping_mul(Fp::INV), u64::MAX);
+    assert_eq!(Fp::from_raw(Fp::MODULUS_LIMBS), Fp::zero());
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut a = Fp::from(0x0123456789abcdef_u64);
+    a.zeroize();
+    assert!(bool::from(a.is_zero()));
+}
+
+#[cfg(feature = "gpu")]
+#[test]
+fn test_gpu_field() {
+    use ec_gpu::GpuField;
+
+    // The limbs are little-endian 32-bit words.
+    fn from_limbs(limbs: alloc::vec::Vec<u32>) -> Fp {
+        assert_eq!(limbs.len(), 8);
+        let mut raw = [0u64; 4];
+        for (i, limb) in limbs.iter().enumerate() {
+            raw[i / 2] |= u64::from(*limb) << (32 * (i % 2));
+        }
+        Fp::from_raw(raw)
+    }
+
+    // The Montgomery constants are R = 2^256 mod p and R^2 mod p.
+    let r = from_limbs(<Fp as GpuField>::one());
+    assert!(!bool::from(r.is_zero()));
+    assert_eq!(from_limbs(<Fp as GpuField>::r2()), r.square());
+    assert_eq!(from_limbs(<Fp as GpuField>::modulus()), Fp::zero());
+
+    // The modulus is p itself, one more than the canonical encoding of -1.
+    let mut modulus = (-Fp::one()).to_repr();
+    modulus[0] += 1;
+    let bytes: alloc::vec::Vec<u8> = <Fp as GpuField>::modulus()
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes())
+        .collect();
+    assert_eq!(bytes, modulus);
+}
+
+#[test]
+fn test_hash() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    assert!(set.insert(Fp::from(7u64)));
+    assert!(set.insert(Fp::zero()));
+    // Equal elements hash equally, however they were computed.
+    assert!(!set.insert(Fp::from(3u64) + Fp::from(4u64)));
+    assert!(!set.insert(-Fp::zero()));
+    assert!(set.contains(&(Fp::from(14u64) * Fp::from(2u64).invert().unwrap())));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_standard_distribution() {
+    use crate::tests::rng;
+    use rand::Rng;
+
+    // Sampling from `Standard` is the same as `Field::random`.
+    let a: Fp = rng().gen();
+    assert_eq!(a, Fp::random(rng()));
+
+    let mut rng = rng();
+    let (a, b): (Fp, Fp) = (rng.gen(), rng.gen());
+    assert_ne!(a, b);
+}
+
+#[cfg(feature = "os-rng")]
+#[test]
+fn test_random_os() {
+    assert_ne!(Fp::random_os(), Fp::random_os());
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!(
+        Fp::from_hex("0100000000000000000000000000000000000000000000000000000000000000"),
+        Ok(Fp::one())
+    );
+    assert_eq!(
+        Fp::from_hex("0200000000000000000000000000000000000000000000000000000000000000"),
+        Ok(Fp::from(2u64))
+    );
+    // The `0x`-prefixed big-endian form written by `Display` is not accepted.
+    assert_eq!(
+        Fp::from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        Err(ParseFieldError::InvalidLength)
+    );
+    #[cfg(feature = "alloc")]
+    {
+        assert_eq!(
+            Fp::one().to_hex(),
+            "0100000000000000000000000000000000000000000000000000000000000000"
+        );
+        let x = -Fp::from(0xabcdu64);
+        assert!(x
+            .to_hex()
+            .chars()
+            .all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+        assert_eq!(Fp::from_hex(&x.to_hex()), Ok(x));
+        assert!(Fp::from_hex(&std::format!("{}", x)).is_err());
+        assert_eq!(std::format!("{:x}", Fp::one()), "0".repeat(63) + "1");
+        assert_ne!(Fp::one().to_hex(), std::format!("{:x}", Fp::one()));
+    }
+
+    assert_eq!(Fp::from_hex(""), Err(ParseFieldError::InvalidLength));
+    assert_eq!(Fp::from_hex("0x01"), Err(ParseFieldError::InvalidLength));
+    assert_eq!(
+        Fp::from_hex("0g00000000000000000000000000000000000000000000000000000000000000"),
+        Err(ParseFieldError::InvalidDigit)
+    );
+    assert_eq!(
+        Fp::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+        Err(ParseFieldError::OutOfRange)
+    );
+}
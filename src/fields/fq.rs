@@ -1,8 +1,10 @@
 use core::fmt;
 use core::ops::{Add, Mul, Neg, Sub};
+use core::str::FromStr;
 
-use ff::PrimeField;
-use rand::RngCore;
+use ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use rand::distributions::{Distribution, Standard};
+use rand::{Rng, RngCore};
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 #[cfg(feature = "sqrt-table")]
@@ -13,6 +15,8 @@ use ff::{FieldBits, PrimeFieldBits};
 
 use crate::arithmetic::{adc, mac, sbb, FieldExt, Group, SqrtRatio};
 
+use super::ParseFieldError;
+
 #[cfg(feature = "sqrt-table")]
 use crate::arithmetic::SqrtTables;
 
@@ -39,6 +43,37 @@ impl fmt::Debug for Fq {
     }
 }
 
+impl fmt::Display for Fq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}", self)
+    }
+}
+
+impl fmt::LowerHex for Fq {
+    /// Formats the canonical integer representation as 64 hex digits, prefixed
+    /// with `0x` when the alternate flag is set.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        for &b in self.to_repr().iter().rev() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Fq {
+    type Err = ParseFieldError;
+
+    /// Parses a `0x`-prefixed hexadecimal or a decimal integer less than the
+    /// field modulus.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let repr = super::parse_repr(s)?;
+        Option::from(Fq::from_repr(repr)).ok_or(ParseFieldError::OutOfRange)
+    }
+}
+
 impl From<bool> for Fq {
     fn from(bit: bool) -> Fq {
         if bit {
@@ -55,6 +90,19 @@ impl From<u64> for Fq {
     }
 }
 
+impl From<u128> for Fq {
+    fn from(val: u128) -> Fq {
+        Fq::from_u128(val)
+    }
+}
+
+impl From<i64> for Fq {
+    fn from(val: i64) -> Fq {
+        let magnitude = Fq::from(val.unsigned_abs());
+        Fq::conditional_select(&magnitude, &-magnitude, Choice::from((val < 0) as u8))
+    }
+}
+
 impl ConstantTimeEq for Fq {
     fn ct_eq(&self, other: &Self) -> Choice {
         self.0[0].ct_eq(&other.0[0])
@@ -71,6 +119,19 @@ impl PartialEq for Fq {
     }
 }
 
+impl core::hash::Hash for Fq {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.to_repr().hash(state);
+    }
+}
+
+impl Distribution<Fq> for Standard {
+    /// Samples a uniformly random field element, as [`ff::Field::random`].
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fq {
+        ff::Field::random(rng)
+    }
+}
+
 impl core::cmp::Ord for Fq {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         let left = self.to_repr();
@@ -166,12 +227,29 @@ impl<'a, 'b> Mul<&'b Fq> for &'a Fq {
 
     #[inline]
     fn mul(self, rhs: &'b Fq) -> Fq {
+        #[cfg(all(
+            feature = "asm",
+            target_arch = "x86_64",
+            target_feature = "adx",
+            target_feature = "bmi2"
+        ))]
+        return Fq(super::asm::mul(&self.0, &rhs.0, &MODULUS.0, INV));
+
+        #[cfg(any(feature = "limb32", not(target_pointer_width = "64")))]
+        #[allow(unreachable_code)]
+        return Fq(super::limb32::mul(&self.0, &rhs.0, &MODULUS.0, INV));
+
+        #[allow(unreachable_code)]
         self.mul(rhs)
     }
 }
 
 impl_binops_additive!(Fq, Fq);
 impl_binops_multiplicative!(Fq, Fq);
+impl_sum_prod!(Fq);
+
+/// The constants for inverting modulo q with safegcd.
+const MODULUS_SAFEGCD: super::safegcd::Modulus = super::safegcd::Modulus::new(&MODULUS.0);
 
 /// INV = -(q^{-1} mod 2^64) mod 2^64
 const INV: u64 = 0x8c46eb20ffffffff;
@@ -246,7 +324,28 @@ impl Default for Fq {
     }
 }
 
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl zeroize::Zeroize for Fq {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
 impl Fq {
+    /// The modulus $q$, as little-endian 64-bit limbs.
+    pub const MODULUS_LIMBS: [u64; 4] = MODULUS.0;
+
+    /// $R = 2^{256} \bmod q$, as little-endian 64-bit limbs. This is the Montgomery
+    /// form of one.
+    pub const R: [u64; 4] = R.0;
+
+    /// $R^2 = 2^{512} \bmod q$, as little-endian 64-bit limbs.
+    pub const R2: [u64; 4] = R2.0;
+
+    /// $-q^{-1} \bmod 2^{64}$, the constant used by Montgomery reduction.
+    pub const INV: u64 = INV;
+
     /// Returns zero, the additive identity.
     #[inline]
     pub const fn zero() -> Fq {
@@ -282,16 +381,126 @@ impl Fq {
         // constant `R2` or `R3`.
         let d0 = Fq([limbs[0], limbs[1], limbs[2], limbs[3]]);
         let d1 = Fq([limbs[4], limbs[5], limbs[6], limbs[7]]);
-        // Convert to Montgomery form
-        d0 * R2 + d1 * R3
+        // Convert to Montgomery form. This uses the portable multiplication, as `d0`
+        // and `d1` may not be fully reduced.
+        (&d0).mul(&R2) + (&d1).mul(&R3)
     }
 
     /// Converts from an integer represented in little endian
     /// into its (congruent) `Fq` representation.
+    ///
+    /// This is a `const fn`, so it can be used to define constants.
     pub const fn from_raw(val: [u64; 4]) -> Self {
         (&Fq(val)).mul(&R2)
     }
 
+    /// Constructs an element from the little-endian 64-bit limbs of its Montgomery
+    /// form $a R \bmod q$, which must be less than the modulus.
+    ///
+    /// Unlike [`Fq::from_raw`], this does not multiply by [`Fq::R2`], so constants
+    /// whose Montgomery form was precomputed can be embedded without any arithmetic.
+    pub const fn from_montgomery_limbs(limbs: [u64; 4]) -> Self {
+        Fq(limbs)
+    }
+
+    /// Returns the little-endian 64-bit limbs of the Montgomery form of this element.
+    pub const fn to_montgomery_limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    /// Converts a 128-bit integer into its `Fq` representation.
+    pub const fn from_u128(val: u128) -> Self {
+        Fq::from_raw([val as u64, (val >> 64) as u64, 0, 0])
+    }
+
+    /// Converts a uniformly random 512-bit little-endian integer, such as the output
+    /// of a 512-bit hash, into a `Fq` by reducing it modulo $q$.
+    ///
+    /// As $q < 2^{255}$, the result is within statistical distance $2^{-257}$ of
+    /// uniform, unlike reducing a 256-bit integer or truncating to 255 bits. This
+    /// is [`FieldExt::from_bytes_wide`], available without importing the trait.
+    pub fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+        <Self as FieldExt>::from_bytes_wide(bytes)
+    }
+
+    /// Samples a uniformly random field element from the operating system's
+    /// random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator fails.
+    #[cfg(feature = "os-rng")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "os-rng")))]
+    pub fn random_os() -> Self {
+        ff::Field::random(rand::rngs::OsRng)
+    }
+
+    /// Encodes this element as 64 lowercase hex digits, without a prefix.
+    ///
+    /// The digits are those of the little-endian encoding returned by
+    /// [`PrimeField::to_repr`], as in the `serde` encoding. This is not the
+    /// [`LowerHex`](fmt::LowerHex) format, which writes the integer big-endian.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_hex(&self) -> alloc::string::String {
+        super::encode_hex(&self.to_repr())
+    }
+
+    /// Decodes an element from the 64 hex digits of its little-endian encoding, as
+    /// written by [`Fq::to_hex`], failing if they are not a canonical encoding.
+    ///
+    /// A `0x` prefix is rejected, as it would be ambiguous with the big-endian
+    /// integer written by `Display` and parsed by `FromStr`.
+    pub fn from_hex(s: &str) -> Result<Self, ParseFieldError> {
+        let repr = super::decode_hex(
+            s,
+            ParseFieldError::InvalidLength,
+            ParseFieldError::InvalidDigit,
+        )?;
+        Option::from(Fq::from_repr(repr)).ok_or(ParseFieldError::OutOfRange)
+    }
+
+    /// Hashes `message` to `count` field elements, using the same
+    /// `expand_message_xmd` construction with BLAKE2b and domain separation as
+    /// the Vesta `hash_to_curve`.
+    ///
+    /// With `count = 2`, this returns the two field elements that
+    /// `hash_to_curve` maps to the curve for the same `domain_prefix` and
+    /// `message`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than 255, or if `domain_prefix` is too long.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn hash_to_field(
+        domain_prefix: &str,
+        message: &[u8],
+        count: usize,
+    ) -> alloc::vec::Vec<Self> {
+        let mut res = alloc::vec![Fq::zero(); count];
+        crate::hashtocurve::hash_to_field(
+            <crate::Eq as crate::arithmetic::CurveExt>::CURVE_ID,
+            domain_prefix,
+            message,
+            &mut res,
+        );
+        res
+    }
+
+    /// Returns the Legendre symbol of this element: $1$ if it is a nonzero square,
+    /// $-1$ if it is a non-square, and $0$ if it is zero.
+    ///
+    /// This is cheaper than attempting to compute a square root.
+    pub fn legendre(&self) -> i8 {
+        crate::arithmetic::legendre_symbol(self)
+    }
+
+    /// Returns whether this element is a square, which includes zero.
+    pub fn is_quadratic_residue(&self) -> Choice {
+        !self.legendre().ct_eq(&-1)
+    }
+
     /// Squares this element.
     #[cfg_attr(not(feature = "uninline-portable"), inline)]
     pub const fn square(&self) -> Fq {
@@ -450,6 +659,8 @@ impl Fq {
     }
 }
 
+impl_batch_ops!(Fq);
+
 impl From<Fq> for [u8; 32] {
     fn from(value: Fq) -> [u8; 32] {
         value.to_repr()
@@ -493,13 +704,8 @@ impl ff::Field for Fq {
         ])
     }
 
-    fn zero() -> Self {
-        Self::zero()
-    }
-
-    fn one() -> Self {
-        Self::one()
-    }
+    const ZERO: Self = Self::zero();
+    const ONE: Self = Self::one();
 
     fn double(&self) -> Self {
         self.double()
@@ -507,6 +713,19 @@ impl ff::Field for Fq {
 
     #[inline(always)]
     fn square(&self) -> Self {
+        #[cfg(all(
+            feature = "asm",
+            target_arch = "x86_64",
+            target_feature = "adx",
+            target_feature = "bmi2"
+        ))]
+        return Fq(super::asm::mul(&self.0, &self.0, &MODULUS.0, INV));
+
+        #[cfg(any(feature = "limb32", not(target_pointer_width = "64")))]
+        #[allow(unreachable_code)]
+        return Fq(super::limb32::mul(&self.0, &self.0, &MODULUS.0, INV));
+
+        #[allow(unreachable_code)]
         self.square()
     }
 
@@ -522,44 +741,77 @@ impl ff::Field for Fq {
         crate::arithmetic::sqrt_tonelli_shanks(self, &T_MINUS1_OVER2)
     }
 
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        #[cfg(feature = "sqrt-table")]
+        {
+            FQ_TABLES.sqrt_ratio(num, div)
+        }
+
+        #[cfg(not(feature = "sqrt-table"))]
+        crate::arithmetic::sqrt_ratio_generic(num, div)
+    }
+
+    #[cfg(feature = "sqrt-table")]
+    fn sqrt_alt(&self) -> (Choice, Self) {
+        FQ_TABLES.sqrt_alt(self)
+    }
+
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
+    ///
+    /// This uses the constant-time safegcd algorithm of Bernstein and Yang.
     fn invert(&self) -> CtOption<Self> {
-        let tmp = self.pow_vartime(&[
-            0x8c46eb20ffffffff,
-            0x224698fc0994a8dd,
-            0x0,
-            0x4000000000000000,
-        ]);
+        // The inverse of x R is x^{-1} R^{-1}, which R^3 brings back to Montgomery
+        // form.
+        let tmp = Fq(super::safegcd::invert(&self.0, &MODULUS_SAFEGCD)) * R3;
 
         CtOption::new(tmp, !self.ct_eq(&Self::zero()))
     }
 
-    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
-        let mut res = Self::one();
-        let mut found_one = false;
-        for e in exp.as_ref().iter().rev() {
-            for i in (0..64).rev() {
-                if found_one {
-                    res = res.square();
-                }
+    /// Exponentiates `self` by `exp`, where `exp` is a little-endian order integer
+    /// exponent, in constant time with respect to both.
+    ///
+    /// Use this when the exponent is secret. Only the number of limbs of `exp` is
+    /// leaked.
+    #[cfg(feature = "windowed-pow")]
+    fn pow<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        crate::arithmetic::pow_windowed(self, exp.as_ref())
+    }
 
-                if ((*e >> i) & 1) == 1 {
-                    found_one = true;
-                    res *= self;
-                }
-            }
-        }
-        res
+    /// Exponentiates `self` by `exp`, where `exp` is a little-endian order integer
+    /// exponent.
+    ///
+    /// This is not constant time with respect to `exp`, and should only be used
+    /// with public exponents.
+    #[cfg(feature = "windowed-pow")]
+    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        crate::arithmetic::pow_vartime_windowed(self, exp.as_ref())
     }
 }
 
 impl ff::PrimeField for Fq {
     type Repr = [u8; 32];
 
+    const MODULUS: &'static str =
+        "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001";
     const NUM_BITS: u32 = 255;
     const CAPACITY: u32 = 254;
+    const TWO_INV: Self = Fq::from_raw([
+        0xc623759080000001,
+        0x11234c7e04ca546e,
+        0x0000000000000000,
+        0x2000000000000000,
+    ]);
+    const MULTIPLICATIVE_GENERATOR: Self = GENERATOR;
     const S: u32 = S;
+    const ROOT_OF_UNITY: Self = ROOT_OF_UNITY;
+    const ROOT_OF_UNITY_INV: Self = Fq::from_raw([
+        0x57eecda0a84b6836,
+        0x4ad38b9084b8a80c,
+        0xf4c8f353124086c1,
+        0x2235e1a7415bf936,
+    ]);
+    const DELTA: Self = DELTA;
 
     fn from_repr(repr: Self::Repr) -> CtOption<Self> {
         let mut tmp = Fq([0, 0, 0, 0]);
@@ -605,12 +857,25 @@ impl ff::PrimeField for Fq {
         Choice::from(self.to_repr()[0] & 1)
     }
 
-    fn multiplicative_generator() -> Self {
-        GENERATOR
+    fn from_u128(v: u128) -> Self {
+        Fq::from_u128(v)
     }
+}
+
+impl WithSmallOrderMulGroup<3> for Fq {
+    const ZETA: Self = Fq::from_raw([
+        0x2aa9d2e050aa0e4f,
+        0x0fed467d47c033af,
+        0x511db4d81cf70f5a,
+        0x06819a58283e528e,
+    ]);
+}
 
-    fn root_of_unity() -> Self {
-        ROOT_OF_UNITY
+impl FromUniformBytes<64> for Fq {
+    /// Converts a 512-bit little endian integer into
+    /// a `Fq` by reducing by the modulus.
+    fn from_uniform_bytes(bytes: &[u8; 64]) -> Fq {
+        <Self as FieldExt>::from_bytes_wide(bytes)
     }
 }
 
@@ -668,38 +933,58 @@ lazy_static! {
     static ref FQ_TABLES: SqrtTables<Fq> = SqrtTables::new(0x116A9E, 1206);
 }
 
+/// The addition chain for [`SqrtRatio::pow_by_t_minus1_over2`], generic over the
+/// element type so that `batch_sqrt` can also evaluate it on several elements at once.
+fn pow_by_t_minus1_over2_chain<T: Copy + Mul<Output = T>>(x: T, square: impl Fn(T) -> T) -> T {
+    let sqr = |x: T, i: u32| (0..i).fold(x, |x, _| square(x));
+
+    let s10 = square(x);
+    let s11 = s10 * x;
+    let s111 = square(s11) * x;
+    let s1001 = s111 * s10;
+    let s1011 = s1001 * s10;
+    let s1101 = s1011 * s10;
+    let sa = sqr(x, 129) * x;
+    let sb = sqr(sa, 7) * s1001;
+    let sc = sqr(sb, 7) * s1101;
+    let sd = sqr(sc, 4) * s11;
+    let se = sqr(sd, 6) * s111;
+    let sf = sqr(se, 3) * s111;
+    let sg = sqr(sf, 10) * s1001;
+    let sh = sqr(sg, 4) * s1001;
+    let si = sqr(sh, 5) * s1001;
+    let sj = sqr(si, 5) * s1001;
+    let sk = sqr(sj, 3) * s1001;
+    let sl = sqr(sk, 4) * s1011;
+    let sm = sqr(sl, 4) * s1011;
+    let sn = sqr(sm, 5) * s11;
+    let so = sqr(sn, 4) * x;
+    let sp = sqr(so, 5) * s11;
+    let sq = sqr(sp, 4) * s111;
+    let sr = sqr(sq, 5) * s1011;
+    let ss = sqr(sr, 3) * x;
+    sqr(ss, 4) // st
+}
+
+/// Finishes the square root of `a` as [`ff::Field::sqrt`] does, given
+/// `w = a^((T-1)/2)`.
+#[cfg(feature = "alloc")]
+fn sqrt_with_pow(a: &Fq, w: &Fq) -> CtOption<Fq> {
+    #[cfg(feature = "sqrt-table")]
+    {
+        let (is_square, res) = FQ_TABLES.sqrt_alt_with_pow(a, w);
+        CtOption::new(res, is_square)
+    }
+
+    #[cfg(not(feature = "sqrt-table"))]
+    crate::arithmetic::sqrt_tonelli_shanks_with_pow(a, w)
+}
+
 impl SqrtRatio for Fq {
     const T_MINUS1_OVER2: [u64; 4] = T_MINUS1_OVER2;
 
     fn pow_by_t_minus1_over2(&self) -> Self {
-        let sqr = |x: Fq, i: u32| (0..i).fold(x, |x, _| x.square());
-
-        let s10 = self.square();
-        let s11 = s10 * self;
-        let s111 = s11.square() * self;
-        let s1001 = s111 * s10;
-        let s1011 = s1001 * s10;
-        let s1101 = s1011 * s10;
-        let sa = sqr(*self, 129) * self;
-        let sb = sqr(sa, 7) * s1001;
-        let sc = sqr(sb, 7) * s1101;
-        let sd = sqr(sc, 4) * s11;
-        let se = sqr(sd, 6) * s111;
-        let sf = sqr(se, 3) * s111;
-        let sg = sqr(sf, 10) * s1001;
-        let sh = sqr(sg, 4) * s1001;
-        let si = sqr(sh, 5) * s1001;
-        let sj = sqr(si, 5) * s1001;
-        let sk = sqr(sj, 3) * s1001;
-        let sl = sqr(sk, 4) * s1011;
-        let sm = sqr(sl, 4) * s1011;
-        let sn = sqr(sm, 5) * s11;
-        let so = sqr(sn, 4) * self;
-        let sp = sqr(so, 5) * s11;
-        let sq = sqr(sp, 4) * s111;
-        let sr = sqr(sq, 5) * s1011;
-        let ss = sqr(sr, 3) * self;
-        sqr(ss, 4) // st
+        pow_by_t_minus1_over2_chain(*self, |x: Fq| x.square())
     }
 
     fn get_lower_32(&self) -> u32 {
@@ -708,45 +993,9 @@ impl SqrtRatio for Fq {
 
         tmp.0[0] as u32
     }
-
-    #[cfg(feature = "sqrt-table")]
-    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
-        FQ_TABLES.sqrt_ratio(num, div)
-    }
-
-    #[cfg(feature = "sqrt-table")]
-    fn sqrt_alt(&self) -> (Choice, Self) {
-        FQ_TABLES.sqrt_alt(self)
-    }
 }
 
 impl FieldExt for Fq {
-    const MODULUS: &'static str =
-        "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001";
-    const ROOT_OF_UNITY_INV: Self = Fq::from_raw([
-        0x57eecda0a84b6836,
-        0x4ad38b9084b8a80c,
-        0xf4c8f353124086c1,
-        0x2235e1a7415bf936,
-    ]);
-    const DELTA: Self = DELTA;
-    const TWO_INV: Self = Fq::from_raw([
-        0xc623759080000001,
-        0x11234c7e04ca546e,
-        0x0000000000000000,
-        0x2000000000000000,
-    ]);
-    const ZETA: Self = Fq::from_raw([
-        0x2aa9d2e050aa0e4f,
-        0x0fed467d47c033af,
-        0x511db4d81cf70f5a,
-        0x06819a58283e528e,
-    ]);
-
-    fn from_u128(v: u128) -> Self {
-        Fq::from_raw([v as u64, (v >> 64) as u64, 0, 0])
-    }
-
     /// Converts a 512-bit little endian integer into
     /// a `Fq` by reducing by the modulus.
     fn from_bytes_wide(bytes: &[u8; 64]) -> Fq {
@@ -769,10 +1018,12 @@ impl FieldExt for Fq {
     }
 }
 
+/// The name is fixed, rather than derived from the source location, so that the
+/// kernels compiled by the build script can be looked up at runtime.
 #[cfg(feature = "gpu")]
 impl ec_gpu::GpuName for Fq {
     fn name() -> alloc::string::String {
-        ec_gpu::name!()
+        alloc::string::String::from("pasta_Fq")
     }
 }
 
@@ -809,6 +1060,68 @@ fn test_inv() {
     assert_eq!(inv, INV);
 }
 
+#[test]
+fn test_sum_of_products() {
+    let mut rng = crate::tests::rng();
+
+    for n in [0, 1, 2, 3, 17, 100] {
+        let a: std::vec::Vec<_> = (0..n).map(|_| Fq::random(&mut rng)).collect();
+        let b: std::vec::Vec<_> = (0..n).map(|_| Fq::random(&mut rng)).collect();
+        let expected = a
+            .iter()
+            .zip(b.iter())
+            .fold(Fq::zero(), |acc, (a, b)| acc + a * b);
+        assert_eq!(Fq::sum_of_products(&a, &b), expected);
+    }
+
+    // The largest Montgomery limbs, and sums of products that are a multiple of
+    // the modulus.
+    let max =
+        Fq::from_montgomery_limbs([MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]]);
+    let a = vec![max; 1000];
+    assert_eq!(
+        Fq::sum_of_products(&a, &a),
+        max.square() * Fq::from(1000u64)
+    );
+    let x = Fq::random(&mut rng);
+    assert_eq!(
+        Fq::sum_of_products(&[x, x, Fq::one()], &[x, -x, Fq::zero()]),
+        Fq::zero()
+    );
+}
+
+#[test]
+fn test_invert_matches_fermat() {
+    // x^(p - 2)
+    let fermat = |x: &Fq| {
+        x.pow_vartime(&[
+            0x8c46eb20ffffffff,
+            0x224698fc0994a8dd,
+            0x0,
+            0x4000000000000000,
+        ])
+    };
+
+    let mut rng = crate::tests::rng();
+
+    let mut elements = vec![
+        Fq::one(),
+        -Fq::one(),
+        Fq::from(2u64),
+        Fq::TWO_INV,
+        Fq::ROOT_OF_UNITY,
+        Fq::from_raw([u64::MAX, u64::MAX, u64::MAX, 0x3fffffffffffffff]),
+    ];
+    elements.extend((0..1000).map(|_| Fq::random(&mut rng)));
+    for x in elements {
+        let inv = x.invert().unwrap();
+        assert_eq!(inv, fermat(&x));
+        assert_eq!(inv * x, Fq::one());
+    }
+
+    assert!(bool::from(Fq::zero().invert().is_none()));
+}
+
 #[test]
 fn test_sqrt() {
     // NB: TWO_INV is standing in as a "random" field element
@@ -816,6 +1129,28 @@ fn test_sqrt() {
     assert!(v == Fq::TWO_INV || (-v) == Fq::TWO_INV);
 }
 
+#[test]
+fn test_sqrt_tonelli_shanks() {
+    use ff::Field;
+
+    // Tonelli–Shanks is used when the `sqrt-table` feature flag is disabled, so
+    // check it against `sqrt` regardless of the enabled features.
+    let mut rng = crate::tests::rng();
+
+    for a in [Fq::ZERO, Fq::ONE, Fq::ROOT_OF_UNITY]
+        .iter()
+        .cloned()
+        .chain((0..100).map(|_| Fq::random(&mut rng)))
+    {
+        let expected = a.sqrt();
+        let actual = crate::arithmetic::sqrt_tonelli_shanks(&a, &T_MINUS1_OVER2);
+        assert_eq!(bool::from(actual.is_some()), bool::from(expected.is_some()));
+        if let Some(v) = Option::<Fq>::from(actual) {
+            assert_eq!(v.square(), a);
+        }
+    }
+}
+
 #[test]
 fn test_pow_by_t_minus1_over2() {
     // NB: TWO_INV is standing in as a "random" field element
@@ -823,13 +1158,53 @@ fn test_pow_by_t_minus1_over2() {
     assert!(v == ff::Field::pow_vartime(&Fq::TWO_INV, &T_MINUS1_OVER2));
 }
 
+#[test]
+fn test_pow() {
+    // Square-and-multiply, for reference.
+    fn pow_binary(a: &Fq, exp: &[u64]) -> Fq {
+        let mut res = Fq::one();
+        for e in exp.iter().rev() {
+            for i in (0..64).rev() {
+                res = res.square();
+                if (e >> i) & 1 == 1 {
+                    res *= a;
+                }
+            }
+        }
+        res
+    }
+
+    let mut rng = crate::tests::rng();
+
+    let a = Fq::random(&mut rng);
+    let mut exps = vec![
+        vec![],
+        vec![0],
+        vec![1],
+        vec![0xf0],
+        vec![u64::MAX],
+        vec![0, 1],
+        vec![u64::MAX; 4],
+        T_MINUS1_OVER2.to_vec(),
+    ];
+    exps.extend((0..10).map(|_| (0..4).map(|_| rng.next_u64()).collect()));
+    for exp in exps {
+        let expected = pow_binary(&a, &exp);
+        assert_eq!(a.pow(&exp), expected);
+        assert_eq!(a.pow_vartime(&exp), expected);
+    }
+
+    assert_eq!(Fq::zero().pow([0]), Fq::one());
+    assert_eq!(Fq::zero().pow_vartime([3]), Fq::zero());
+}
+
 #[test]
 fn test_sqrt_ratio_and_alt() {
     // (true, sqrt(num/div)), if num and div are nonzero and num/div is a square in the field
     let num = (Fq::TWO_INV).square();
-    let div = Fq::from(25);
+    let div = Fq::from(25u64);
     let div_inverse = div.invert().unwrap();
-    let expected = Fq::TWO_INV * Fq::from(5).invert().unwrap();
+    let expected = Fq::TWO_INV * Fq::from(5u64).invert().unwrap();
     let (is_square, v) = Fq::sqrt_ratio(&num, &div);
     assert!(bool::from(is_square));
     assert!(v == expected || (-v) == expected);
@@ -839,8 +1214,8 @@ fn test_sqrt_ratio_and_alt() {
     assert!(v_alt == v);
 
     // (false, sqrt(ROOT_OF_UNITY * num/div)), if num and div are nonzero and num/div is a nonsquare in the field
-    let num = num * Fq::root_of_unity();
-    let expected = Fq::TWO_INV * Fq::root_of_unity() * Fq::from(5).invert().unwrap();
+    let num = num * Fq::ROOT_OF_UNITY;
+    let expected = Fq::TWO_INV * Fq::ROOT_OF_UNITY * Fq::from(5u64).invert().unwrap();
     let (is_square, v) = Fq::sqrt_ratio(&num, &div);
     assert!(!bool::from(is_square));
     assert!(v == expected || (-v) == expected);
@@ -886,19 +1261,19 @@ fn test_zeta() {
 #[test]
 fn test_root_of_unity() {
     assert_eq!(
-        Fq::root_of_unity().pow_vartime(&[1 << Fq::S, 0, 0, 0]),
+        Fq::ROOT_OF_UNITY.pow_vartime(&[1 << Fq::S, 0, 0, 0]),
         Fq::one()
     );
 }
 
 #[test]
 fn test_inv_root_of_unity() {
-    assert_eq!(Fq::ROOT_OF_UNITY_INV, Fq::root_of_unity().invert().unwrap());
+    assert_eq!(Fq::ROOT_OF_UNITY_INV, Fq::ROOT_OF_UNITY.invert().unwrap());
 }
 
 #[test]
 fn test_inv_2() {
-    assert_eq!(Fq::TWO_INV, Fq::from(2).invert().unwrap());
+    assert_eq!(Fq::TWO_INV, Fq::from(2u64).invert().unwrap());
 }
 
 #[test]
@@ -906,10 +1281,89 @@ fn test_delta() {
     assert_eq!(Fq::DELTA, GENERATOR.pow(&[1u64 << Fq::S, 0, 0, 0]));
     assert_eq!(
         Fq::DELTA,
-        Fq::multiplicative_generator().pow(&[1u64 << Fq::S, 0, 0, 0])
+        Fq::MULTIPLICATIVE_GENERATOR.pow(&[1u64 << Fq::S, 0, 0, 0])
     );
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_invert() {
+    use crate::arithmetic::batch_invert;
+
+    let mut elements: alloc::vec::Vec<_> = (0..10u64).map(|i| Fq::from(i) * Fq::ZETA).collect();
+    let expected: alloc::vec::Vec<_> = elements
+        .iter()
+        .map(|e| e.invert().unwrap_or(Fq::zero()))
+        .collect();
+    let all_inv = batch_invert(&mut elements);
+
+    assert_eq!(elements, expected);
+    assert_eq!(
+        all_inv,
+        expected[1..].iter().fold(Fq::one(), |acc, e| acc * e)
+    );
+}
+
+#[test]
+fn test_batch_mul() {
+    let mut rng = crate::tests::rng();
+
+    let mut a = [Fq::zero(); 11];
+    let mut b = [Fq::zero(); 11];
+    for (a, b) in a.iter_mut().zip(b.iter_mut()) {
+        *a = Fq::random(&mut rng);
+        *b = Fq::random(&mut rng);
+    }
+    // Include the extremes of the field.
+    a[0] = -Fq::one();
+    b[0] = -Fq::one();
+    a[1] = Fq::zero();
+    b[2] = Fq::one();
+
+    for len in 0..=a.len() {
+        let mut product = a;
+        Fq::batch_mul(&mut product[..len], &b[..len]);
+        let mut square = a;
+        Fq::batch_square(&mut square[..len]);
+
+        for i in 0..a.len() {
+            if i < len {
+                assert_eq!(product[i], a[i] * b[i]);
+                assert_eq!(square[i], a[i].square());
+            } else {
+                assert_eq!(product[i], a[i]);
+                assert_eq!(square[i], a[i]);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_sqrt() {
+    let mut rng = crate::tests::rng();
+
+    // Squares, non-squares, zero and one, over a length that is not a multiple of
+    // the number of elements processed in lockstep.
+    let mut a: alloc::vec::Vec<_> = (0..19).map(|_| Fq::random(&mut rng)).collect();
+    a.push(Fq::zero());
+    a.push(Fq::one());
+    a.push(Fq::from(4u64));
+    assert!(a.iter().any(|a| bool::from(a.sqrt().is_none())));
+
+    for len in [0, 1, 8, a.len()] {
+        let roots = Fq::batch_sqrt(&a[..len]);
+        assert_eq!(roots.len(), len);
+        for (a, root) in a.iter().zip(roots.iter()) {
+            let expected = a.sqrt();
+            assert_eq!(bool::from(root.is_some()), bool::from(expected.is_some()));
+            if bool::from(expected.is_some()) {
+                assert_eq!(root.unwrap(), expected.unwrap());
+            }
+        }
+    }
+}
+
 #[cfg(not(target_pointer_width = "64"))]
 #[test]
 fn consistent_modulus_limbs() {
@@ -927,6 +1381,147 @@ fn consistent_modulus_limbs() {
     }
 }
 
+#[test]
+fn test_from_u128() {
+    assert_eq!(Fq::from(0u128), Fq::zero());
+    assert_eq!(
+        Fq::from(0x0123456789abcdefu128),
+        Fq::from(0x0123456789abcdefu64)
+    );
+    assert_eq!(
+        Fq::from(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210_u128),
+        Fq::from(0x0123456789abcdefu64) * Fq::from(1u64 << 32).square()
+            + Fq::from(0xfedcba9876543210u64)
+    );
+    assert_eq!(
+        Fq::from(u128::MAX),
+        Fq::from(1u64 << 32).square().square() - Fq::one()
+    );
+
+    const X: Fq = Fq::from_u128(1 << 100);
+    assert_eq!(X, Fq::from(1u128 << 100));
+}
+
+#[test]
+fn test_from_i64() {
+    assert_eq!(Fq::from(0i64), Fq::zero());
+    assert_eq!(Fq::from(5i64), Fq::from(5u64));
+    assert_eq!(Fq::from(-5i64), -Fq::from(5u64));
+    assert_eq!(Fq::from(i64::MIN), -Fq::from(1u64 << 63));
+    assert_eq!(Fq::from(i64::MAX), Fq::from(i64::MAX as u64));
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!("0".parse::<Fq>(), Ok(Fq::zero()));
+    assert_eq!("0x0".parse::<Fq>(), Ok(Fq::zero()));
+    assert_eq!("1234567890".parse::<Fq>(), Ok(Fq::from(1234567890u64)));
+    assert_eq!("0xdeadBEEF".parse::<Fq>(), Ok(Fq::from(0xdead_beef_u64)));
+    assert_eq!("0X00ff".parse::<Fq>(), Ok(Fq::from(0xffu64)));
+    assert_eq!(
+        "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000000".parse::<Fq>(),
+        Ok(-Fq::one())
+    );
+    assert_eq!(
+        "28948022309329048855892746252171976963363056481941647379679742748393362948096"
+            .parse::<Fq>(),
+        Ok(-Fq::one())
+    );
+
+    assert_eq!("".parse::<Fq>(), Err(ParseFieldError::Empty));
+    assert_eq!("0x".parse::<Fq>(), Err(ParseFieldError::Empty));
+    assert_eq!("-1".parse::<Fq>(), Err(ParseFieldError::InvalidDigit));
+    assert_eq!("12a".parse::<Fq>(), Err(ParseFieldError::InvalidDigit));
+    assert_eq!("0x12g".parse::<Fq>(), Err(ParseFieldError::InvalidDigit));
+    assert_eq!(
+        "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001".parse::<Fq>(),
+        Err(ParseFieldError::OutOfRange)
+    );
+    assert_eq!(
+        "28948022309329048855892746252171976963363056481941647379679742748393362948097"
+            .parse::<Fq>(),
+        Err(ParseFieldError::OutOfRange)
+    );
+    assert_eq!(
+        format!("0x1{}", "0".repeat(64)).parse::<Fq>(),
+        Err(ParseFieldError::OutOfRange)
+    );
+}
+
+#[test]
+fn test_display() {
+    let a = -Fq::one();
+    assert_eq!(
+        format!("{}", a),
+        "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000000"
+    );
+    assert_eq!(
+        format!("{:x}", a),
+        "40000000000000000000000000000000224698fc0994a8dd8c46eb2100000000"
+    );
+    assert_eq!(format!("{}", a), format!("{:?}", a));
+    assert_eq!(format!("{}", a).parse::<Fq>(), Ok(a));
+    assert_eq!(
+        format!("{:#x}", Fq::one()),
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+    );
+}
+
+#[test]
+fn test_legendre() {
+    use ff::Field;
+
+    let mut rng = crate::tests::rng();
+
+    assert_eq!(Fq::zero().legendre(), 0);
+    assert_eq!(Fq::one().legendre(), 1);
+    assert_eq!(Fq::MULTIPLICATIVE_GENERATOR.legendre(), -1);
+    assert!(bool::from(Fq::zero().is_quadratic_residue()));
+
+    for _ in 0..100 {
+        let a = Fq::random(&mut rng);
+        if a.is_zero_vartime() {
+            continue;
+        }
+        let expected: i8 = if a.sqrt().is_some().into() { 1 } else { -1 };
+        assert_eq!(a.legendre(), expected);
+        assert_eq!(bool::from(a.is_quadratic_residue()), expected == 1);
+        // Euler's criterion
+        let euler = a.pow_vartime(&[
+            0xc623759080000000,
+            0x11234c7e04ca546e,
+            0x0000000000000000,
+            0x2000000000000000,
+        ]);
+        assert_eq!(euler, Fq::from(i64::from(expected)));
+        assert_eq!(a.square().legendre(), 1);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hash_to_field() {
+    let us = Fq::hash_to_field("z.cash:test", b"Trans rights now!", 3);
+    assert_eq!(
+        us.iter()
+            .map(|u| format!("{:?}", u))
+            .collect::<std::vec::Vec<_>>(),
+        [
+            "0x37f027fab3fa32d6c5811067090da04aa68add8a899137d59520f9e7335090d2",
+            "0x29a6f2ef0bfe22f9727766cb057835bf2c1a053f4b0ff853002b4d1dc8624203",
+            "0x22aac6c16b8ff0f5d5393d3c53c3d0ab8481899f08fcedb4a8165128145009c5",
+        ]
+    );
+
+    // Fewer elements are a prefix of more elements only for the first one, as the
+    // output length is part of the hash input.
+    assert!(Fq::hash_to_field("z.cash:test", b"Trans rights now!", 0).is_empty());
+    assert_ne!(
+        Fq::hash_to_field("z.cash:test", b"Trans rights now!", 2)[..],
+        us[..2]
+    );
+}
+
 #[test]
 fn test_from_u512() {
     assert_eq!(
@@ -948,3 +1543,154 @@ fn test_from_u512() {
         ])
     );
 }
+
+#[test]
+fn test_from_uniform_bytes() {
+    let mut bytes = [0; 64];
+    bytes[..32].copy_from_slice(&(-Fq::one()).to_repr());
+    assert_eq!(Fq::from_uniform_bytes(&bytes), -Fq::one());
+
+    // 2^511 + (modulus - 1) is congruent to 2^511 - 1.
+    bytes[63] = 0x80;
+    let mut expected = [0xff; 64];
+    expected[63] = 0x7f;
+    assert_eq!(
+        Fq::from_uniform_bytes(&bytes),
+        Fq::from_uniform_bytes(&expected)
+    );
+    assert_eq!(
+        Fq::from_uniform_bytes(&bytes),
+        <Fq as FieldExt>::from_bytes_wide(&bytes)
+    );
+}
+
+#[test]
+fn test_montgomery_constants() {
+    const FIVE: Fq = Fq::from_raw([5, 0, 0, 0]);
+    const ONE: Fq = Fq::from_montgomery_limbs(Fq::R);
+    assert_eq!(FIVE, Fq::from(5u64));
+    assert_eq!(ONE, Fq::one());
+    assert_eq!(Fq::from_montgomery_limbs(Fq::R2), Fq::from_raw(Fq::R));
+    assert_eq!(Fq::from_montgomery_limbs(FIVE.to_montgomery_limbs()), FIVE);
+    assert_eq!(Fq::MODULUS_LIMBS[0].wrapping_mul(Fq::INV), u64::MAX);
+    assert_eq!(Fq::from_raw(Fq::MODULUS_LIMBS), Fq::zero());
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut a = Fq::from(0x0123456789abcdef_u64);
+    a.zeroize();
+    assert!(bool::from(a.is_zero()));
+}
+
+#[cfg(feature = "gpu")]
+#[test]
+fn test_gpu_field() {
+    use ec_gpu::GpuField;
+
+    // The limbs are little-endian 32-bit words.
+    fn from_limbs(limbs: alloc::vec::Vec<u32>) -> Fq {
+        assert_eq!(limbs.len(), 8);
+        let mut raw = [0u64; 4];
+        for (i, limb) in limbs.iter().enumerate() {
+            raw[i / 2] |= u64::from(*limb) << (32 * (i % 2));
+        }
+        Fq::from_raw(raw)
+    }
+
+    // The Montgomery constants are R = 2^256 mod p and R^2 mod p.
+    let r = from_limbs(<Fq as GpuField>::one());
+    assert!(!bool::from(r.is_zero()));
+    assert_eq!(from_limbs(<Fq as GpuField>::r2()), r.square());
+    assert_eq!(from_limbs(<Fq as GpuField>::modulus()), Fq::zero());
+
+    // The modulus is p itself, one more than the canonical encoding of -1.
+    let mut modulus = (-Fq::one()).to_repr();
+    modulus[0] += 1;
+    let bytes: alloc::vec::Vec<u8> = <Fq as GpuField>::modulus()
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes())
+        .collect();
+    assert_eq!(bytes, modulus);
+}
+
+#[test]
+fn test_hash() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    assert!(set.insert(Fq::from(7u64)));
+    assert!(set.insert(Fq::zero()));
+    // Equal elements hash equally, however they were computed.
+    assert!(!set.insert(Fq::from(3u64) + Fq::from(4u64)));
+    assert!(!set.insert(-Fq::zero()));
+    assert!(set.contains(&(Fq::from(14u64) * Fq::from(2u64).invert().unwrap())));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_standard_distribution() {
+    use crate::tests::rng;
+    use rand::Rng;
+
+    // Sampling from `Standard` is the same as `Field::random`.
+    let a: Fq = rng().gen();
+    assert_eq!(a, Fq::random(rng()));
+
+    let mut rng = rng();
+    let (a, b): (Fq, Fq) = (rng.gen(), rng.gen());
+    assert_ne!(a, b);
+}
+
+#[cfg(feature = "os-rng")]
+#[test]
+fn test_random_os() {
+    assert_ne!(Fq::random_os(), Fq::random_os());
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!(
+        Fq::from_hex("0100000000000000000000000000000000000000000000000000000000000000"),
+        Ok(Fq::one())
+    );
+    assert_eq!(
+        Fq::from_hex("0200000000000000000000000000000000000000000000000000000000000000"),
+        Ok(Fq::from(2u64))
+    );
+    // The `0x`-prefixed big-endian form written by `Display` is not accepted.
+    assert_eq!(
+        Fq::from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        Err(ParseFieldError::InvalidLength)
+    );
+    #[cfg(feature = "alloc")]
+    {
+        assert_eq!(
+            Fq::one().to_hex(),
+            "0100000000000000000000000000000000000000000000000000000000000000"
+        );
+        let x = -Fq::from(0xabcdu64);
+        assert!(x
+            .to_hex()
+            .chars()
+            .all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+        assert_eq!(Fq::from_hex(&x.to_hex()), Ok(x));
+        assert!(Fq::from_hex(&std::format!("{}", x)).is_err());
+        assert_eq!(std::format!("{:x}", Fq::one()), "0".repeat(63) + "1");
+        assert_ne!(Fq::one().to_hex(), std::format!("{:x}", Fq::one()));
+    }
+
+    assert_eq!(Fq::from_hex(""), Err(ParseFieldError::InvalidLength));
+    assert_eq!(Fq::from_hex("0x01"), Err(ParseFieldError::InvalidLength));
+    assert_eq!(
+        Fq::from_hex("0g00000000000000000000000000000000000000000000000000000000000000"),
+        Err(ParseFieldError::InvalidDigit)
+    );
+    assert_eq!(
+        Fq::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+        Err(ParseFieldError::OutOfRange)
+    );
+}
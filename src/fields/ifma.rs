@@ -0,0 +1,278 @@
+//! Montgomery multiplication of eight independent field elements at a time using
+//! AVX-512 IFMA, selected at runtime when the CPU and OS support it.
+//!
+//! IFMA multiplies 52-bit integers, so each element is held in radix $2^{52}$ as
+//! five limbs, and limb $j$ of all eight elements is held in the lanes of a single
+//! vector. The low and high halves of each product are accumulated without carry
+//! propagation, which is deferred until the end of the multiplication.
+//!
+//! The reduction divides by $2^{260}$ rather than $R = 2^{256}$, so one operand is
+//! scaled by $2^4$ beforehand, and the results are in the same Montgomery form as
+//! the 64-bit scalar implementation.
+
+// The intrinsics in `core::arch` are `unsafe` functions. They are only called once
+// `is_supported` has confirmed that the CPU and OS support them.
+#![allow(unsafe_code)]
+
+use core::arch::x86_64::{
+    __cpuid, __cpuid_count, __m512i, _mm512_add_epi64, _mm512_and_si512, _mm512_loadu_epi64,
+    _mm512_madd52hi_epu64, _mm512_madd52lo_epu64, _mm512_set1_epi64, _mm512_setzero_si512,
+    _mm512_srli_epi64, _mm512_storeu_epi64, _xgetbv,
+};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use super::batch::simd::reduce_once;
+
+const MASK52: u64 = (1 << 52) - 1;
+
+const UNKNOWN: u8 = 0;
+const UNSUPPORTED: u8 = 1;
+const SUPPORTED: u8 = 2;
+
+static SUPPORT: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// Returns whether the CPU supports AVX-512F and AVX-512 IFMA and the OS saves the
+/// AVX-512 register state. The result is cached after the first call.
+pub(crate) fn is_supported() -> bool {
+    match SUPPORT.load(Ordering::Relaxed) {
+        SUPPORTED => true,
+        UNSUPPORTED => false,
+        _ => {
+            let supported = detect();
+            SUPPORT.store(
+                if supported { SUPPORTED } else { UNSUPPORTED },
+                Ordering::Relaxed,
+            );
+            supported
+        }
+    }
+}
+
+#[allow(unused_unsafe)]
+fn detect() -> bool {
+    // SAFETY: `cpuid` is available on every x86_64 processor.
+    let (max_leaf, leaf1) = unsafe { (__cpuid(0).eax, __cpuid(1)) };
+    // OSXSAVE indicates that the OS has enabled XGETBV.
+    if max_leaf < 7 || leaf1.ecx & (1 << 27) == 0 {
+        return false;
+    }
+
+    // SAFETY: XGETBV is enabled, as checked above.
+    let xcr0 = unsafe { xgetbv0() };
+    // The OS must save the SSE and AVX state (bits 1 and 2), and the AVX-512 opmask
+    // and ZMM state (bits 5 to 7).
+    if xcr0 & 0xe6 != 0xe6 {
+        return false;
+    }
+
+    // SAFETY: leaf 7 is supported, as checked above.
+    let leaf7 = unsafe { __cpuid_count(7, 0) };
+    let avx512f = leaf7.ebx & (1 << 16) != 0;
+    let avx512ifma = leaf7.ebx & (1 << 21) != 0;
+    avx512f && avx512ifma
+}
+
+#[target_feature(enable = "xsave")]
+unsafe fn xgetbv0() -> u64 {
+    _xgetbv(0)
+}
+
+/// Converts little-endian 64-bit limbs to five little-endian 52-bit limbs.
+pub(crate) fn to_radix52(x: &[u64; 4]) -> [u64; 5] {
+    [
+        x[0] & MASK52,
+        ((x[0] >> 52) | (x[1] << 12)) & MASK52,
+        ((x[1] >> 40) | (x[2] << 24)) & MASK52,
+        ((x[2] >> 28) | (x[3] << 36)) & MASK52,
+        x[3] >> 16,
+    ]
+}
+
+/// Converts five little-endian 52-bit limbs, representing a value less than
+/// $2^{256}$, to little-endian 64-bit limbs.
+pub(crate) fn from_radix52(x: &[u64; 5]) -> [u64; 4] {
+    [
+        x[0] | (x[1] << 52),
+        (x[1] >> 12) | (x[2] << 40),
+        (x[2] >> 24) | (x[3] << 28),
+        (x[3] >> 36) | (x[4] << 16),
+    ]
+}
+
+/// Computes $\textsf{lhs}_e \cdot \textsf{rhs}_e \cdot R^{-1} \bmod p$ for each of the
+/// eight pairs of Montgomery-form elements, where $R = 2^{256}$, `modulus` is $p$
+/// and `inv` is $-p^{-1} \bmod 2^{64}$.
+///
+/// The inputs must be fully reduced and $p$ must be less than $2^{255}$.
+///
+/// # Panics
+///
+/// Panics if [`is_supported`] returns `false`.
+pub(crate) fn mul_8x(
+    lhs: &[[u64; 4]; 8],
+    rhs: &[[u64; 4]; 8],
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [[u64; 4]; 8] {
+    assert!(is_supported());
+
+    let mut scaled = *lhs;
+    for x in scaled.iter_mut() {
+        for _ in 0..4 {
+            // 2x < 2p < 2^256, so the doubling cannot overflow.
+            let doubled = [
+                x[0] << 1,
+                (x[1] << 1) | (x[0] >> 63),
+                (x[2] << 1) | (x[1] >> 63),
+                (x[3] << 1) | (x[2] >> 63),
+            ];
+            *x = reduce_once(doubled, modulus);
+        }
+    }
+
+    // SAFETY: AVX-512F and AVX-512 IFMA are supported, as checked above.
+    unsafe { mul_8x_ifma(&scaled, rhs, modulus, inv) }
+}
+
+/// Returns the vectors holding each 52-bit limb of the eight elements of `x`.
+#[target_feature(enable = "avx512f")]
+unsafe fn load(x: &[[u64; 4]; 8]) -> [__m512i; 5] {
+    let mut limbs = [[0u64; 8]; 5];
+    for (e, x) in x.iter().enumerate() {
+        for (limbs, limb) in limbs.iter_mut().zip(to_radix52(x).iter()) {
+            limbs[e] = *limb;
+        }
+    }
+
+    let mut out = [_mm512_setzero_si512(); 5];
+    for (out, limbs) in out.iter_mut().zip(limbs.iter()) {
+        *out = _mm512_loadu_epi64(limbs.as_ptr() as *const i64);
+    }
+    out
+}
+
+#[target_feature(enable = "avx512f,avx512ifma")]
+unsafe fn mul_8x_ifma(
+    lhs: &[[u64; 4]; 8],
+    rhs: &[[u64; 4]; 8],
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [[u64; 4]; 8] {
+    let zero = _mm512_setzero_si512();
+    let mask = _mm512_set1_epi64(MASK52 as i64);
+    // -p^{-1} mod 2^52 is the low 52 bits of -p^{-1} mod 2^64.
+    let inv = _mm512_set1_epi64((inv & MASK52) as i64);
+
+    let mut p_limbs = [zero; 5];
+    for (p, limb) in p_limbs.iter_mut().zip(to_radix52(modulus).iter()) {
+        *p = _mm512_set1_epi64(*limb as i64);
+    }
+
+    let lhs_limbs = load(lhs);
+    let rhs_limbs = load(rhs);
+
+    // Each iteration adds at most four 52-bit values to each limb before shifting,
+    // so the limbs stay far below 2^64.
+    let mut t = [zero; 6];
+    for bi in rhs_limbs.iter() {
+        // t += a * b_i
+        for j in 0..5 {
+            t[j] = _mm512_madd52lo_epu64(t[j], lhs_limbs[j], *bi);
+            t[j + 1] = _mm512_madd52hi_epu64(t[j + 1], lhs_limbs[j], *bi);
+        }
+
+        // t = (t + m * p) / 2^52, where m is chosen to clear the lowest limb.
+        let m = _mm512_madd52lo_epu64(zero, t[0], inv);
+        for j in 0..5 {
+            t[j] = _mm512_madd52lo_epu64(t[j], m, p_limbs[j]);
+            t[j + 1] = _mm512_madd52hi_epu64(t[j + 1], m, p_limbs[j]);
+        }
+        let carry = _mm512_srli_epi64(t[0], 52);
+        t[0] = _mm512_add_epi64(t[1], carry);
+        t[1] = t[2];
+        t[2] = t[3];
+        t[3] = t[4];
+        t[4] = t[5];
+        t[5] = zero;
+    }
+
+    // Propagate the deferred carries.
+    for j in 0..4 {
+        let carry = _mm512_srli_epi64(t[j], 52);
+        t[j] = _mm512_and_si512(t[j], mask);
+        t[j + 1] = _mm512_add_epi64(t[j + 1], carry);
+    }
+
+    let mut limbs = [[0u64; 8]; 5];
+    for (limbs, t) in limbs.iter_mut().zip(t.iter()) {
+        _mm512_storeu_epi64(limbs.as_mut_ptr() as *mut i64, *t);
+    }
+
+    let mut out = [[0u64; 4]; 8];
+    for (e, out) in out.iter_mut().enumerate() {
+        let r = from_radix52(&[
+            limbs[0][e],
+            limbs[1][e],
+            limbs[2][e],
+            limbs[3][e],
+            limbs[4][e],
+        ]);
+        *out = reduce_once(r, modulus);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_radix52, is_supported, mul_8x, to_radix52};
+    use crate::fields::batch::MontgomeryLimbs;
+    use crate::{Fp, Fq};
+
+    #[test]
+    fn test_radix52_roundtrip() {
+        let x = [
+            0x0123_4567_89ab_cdef,
+            0xfedc_ba98_7654_3210,
+            0xffff_ffff_ffff_ffff,
+            0x8000_0000_0000_0001,
+        ];
+        let limbs = to_radix52(&x);
+        assert!(limbs.iter().all(|limb| limb >> 52 == 0));
+        assert_eq!(from_radix52(&limbs), x);
+    }
+
+    fn check_mul_8x<F: MontgomeryLimbs>() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..100 {
+            let mut a = [F::ZERO; 8];
+            let mut b = [F::ZERO; 8];
+            for (a, b) in a.iter_mut().zip(b.iter_mut()) {
+                *a = F::random(&mut rng);
+                *b = F::random(&mut rng);
+            }
+            a[0] = -F::ONE;
+            b[0] = -F::ONE;
+
+            let mut lhs = [[0; 4]; 8];
+            let mut rhs = [[0; 4]; 8];
+            for i in 0..8 {
+                lhs[i] = a[i].to_limbs();
+                rhs[i] = b[i].to_limbs();
+            }
+            let r = mul_8x(&lhs, &rhs, &F::MODULUS, F::INV);
+            for i in 0..8 {
+                assert_eq!(F::from_limbs(r[i]), a[i] * b[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_8x() {
+        // This can only be checked on a CPU with AVX-512 IFMA.
+        if is_supported() {
+            check_mul_8x::<Fp>();
+            check_mul_8x::<Fq>();
+        }
+    }
+}
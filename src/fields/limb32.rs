@@ -0,0 +1,123 @@
+//! Montgomery multiplication with 32-bit limbs, for targets such as `wasm32` on
+//! which 64×64 → 128-bit multiplication is not native and is emulated by slow
+//! library calls.
+//!
+//! Each element is split into eight 32-bit limbs, and the reduction is the
+//! word-by-word (CIOS) Montgomery algorithm with 32-bit words, so every product
+//! fits in a `u64`. This computes $a b 2^{-256} \bmod p$ and thus agrees with the
+//! 64-bit implementation. Elements are still stored as four 64-bit limbs.
+
+use crate::arithmetic::{adc, sbb};
+
+/// Compute a + (b * c) + carry, returning the result and the new carry over.
+#[inline(always)]
+const fn mac(a: u32, b: u32, c: u32, carry: u32) -> (u32, u32) {
+    let ret = (a as u64) + ((b as u64) * (c as u64)) + (carry as u64);
+    (ret as u32, (ret >> 32) as u32)
+}
+
+/// Splits little-endian 64-bit limbs into little-endian 32-bit limbs.
+#[inline(always)]
+fn split(x: &[u64; 4]) -> [u32; 8] {
+    [
+        x[0] as u32,
+        (x[0] >> 32) as u32,
+        x[1] as u32,
+        (x[1] >> 32) as u32,
+        x[2] as u32,
+        (x[2] >> 32) as u32,
+        x[3] as u32,
+        (x[3] >> 32) as u32,
+    ]
+}
+
+/// Computes $\textsf{lhs} \cdot \textsf{rhs} \cdot R^{-1} \bmod p$ for the
+/// Montgomery-form elements `lhs` and `rhs`, where $R = 2^{256}$, `modulus` is $p$
+/// and `inv` is $-p^{-1} \bmod 2^{64}$.
+///
+/// The inputs must be fully reduced and $p$ must be less than $2^{255}$.
+#[cfg_attr(not(feature = "uninline-portable"), inline)]
+pub(crate) fn mul(lhs: &[u64; 4], rhs: &[u64; 4], modulus: &[u64; 4], inv: u64) -> [u64; 4] {
+    let lhs = split(lhs);
+    let rhs = split(rhs);
+    let p_limbs = split(modulus);
+    // -p^{-1} mod 2^32 is the low half of -p^{-1} mod 2^64.
+    let inv = inv as u32;
+
+    let mut t = [0u32; 10];
+    for bi in rhs.iter() {
+        // t += a * b_i
+        let mut carry = 0;
+        for (t, aj) in t.iter_mut().zip(lhs.iter()) {
+            let (r, c) = mac(*t, *aj, *bi, carry);
+            *t = r;
+            carry = c;
+        }
+        let sum = u64::from(t[8]) + u64::from(carry);
+        t[8] = sum as u32;
+        t[9] = (sum >> 32) as u32;
+
+        // t = (t + m * p) / 2^32, where m is chosen to clear the lowest word.
+        let m = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac(t[0], m, p_limbs[0], 0);
+        for j in 1..8 {
+            let (r, c) = mac(t[j], m, p_limbs[j], carry);
+            t[j - 1] = r;
+            carry = c;
+        }
+        let sum = u64::from(t[8]) + u64::from(carry);
+        t[7] = sum as u32;
+        t[8] = t[9] + (sum >> 32) as u32;
+    }
+
+    let r0 = u64::from(t[0]) | (u64::from(t[1]) << 32);
+    let r1 = u64::from(t[2]) | (u64::from(t[3]) << 32);
+    let r2 = u64::from(t[4]) | (u64::from(t[5]) << 32);
+    let r3 = u64::from(t[6]) | (u64::from(t[7]) << 32);
+
+    // The result is less than 2p, so at most one subtraction is needed.
+    let (d0, borrow) = sbb(r0, modulus[0], 0);
+    let (d1, borrow) = sbb(r1, modulus[1], borrow);
+    let (d2, borrow) = sbb(r2, modulus[2], borrow);
+    let (d3, borrow) = sbb(r3, modulus[3], borrow);
+
+    let (d0, carry) = adc(d0, modulus[0] & borrow, 0);
+    let (d1, carry) = adc(d1, modulus[1] & borrow, carry);
+    let (d2, carry) = adc(d2, modulus[2] & borrow, carry);
+    let (d3, _) = adc(d3, modulus[3] & borrow, carry);
+
+    [d0, d1, d2, d3]
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+
+    use crate::{Fp, Fq};
+
+    #[test]
+    fn test_mul_matches_portable() {
+        let mut rng = crate::tests::rng();
+
+        let mut fp = [-Fp::one(), Fp::zero(), Fp::one(), Fp::random(&mut rng)];
+        let mut fq = [-Fq::one(), Fq::zero(), Fq::one(), Fq::random(&mut rng)];
+        for _ in 0..1000 {
+            for a in fp.iter() {
+                for b in fp.iter() {
+                    // `*` dispatches to the 32-bit backend, while the inherent
+                    // `const fn` always uses 64-bit limbs.
+                    assert_eq!(a * b, Fp::mul(a, b));
+                }
+                assert_eq!(<Fp as Field>::square(a), Fp::square(a));
+            }
+            for a in fq.iter() {
+                for b in fq.iter() {
+                    assert_eq!(a * b, Fq::mul(a, b));
+                }
+                assert_eq!(<Fq as Field>::square(a), Fq::square(a));
+            }
+            fp[3] = Fp::random(&mut rng);
+            fq[3] = Fq::random(&mut rng);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Montgomery multiplication of two independent field elements at a time using
+//! NEON on `aarch64`.
+//!
+//! This follows the AVX2 backend: each element is split into eight 32-bit limbs,
+//! limb $j$ of both elements is held in the two lanes of a single vector, and the
+//! reduction is the word-by-word (CIOS) Montgomery algorithm with 32-bit words.
+//! NEON's widening multiply-accumulate computes each `t + a * b` step in a single
+//! instruction.
+
+// The intrinsics in `core::arch` are `unsafe` functions. They are only compiled in
+// when the `neon` target feature is statically enabled, so calling them is sound.
+#![allow(unsafe_code)]
+
+use core::arch::aarch64::{
+    uint32x2_t, uint64x2_t, vaddq_u64, vandq_u64, vdup_n_u32, vdupq_n_u64, vld1_u32, vmlal_u32,
+    vmovn_u64, vmul_u32, vshrq_n_u64, vst1q_u64,
+};
+
+use super::batch::simd::reduce_once;
+
+/// Returns the vector whose lane $e$ holds 32-bit limb `limb` of `x[e]`.
+#[inline(always)]
+fn load_limb(x: &[[u64; 4]; 2], limb: usize) -> uint32x2_t {
+    let word = |e: usize| (x[e][limb / 2] >> (32 * (limb % 2))) as u32;
+    let words = [word(0), word(1)];
+    // SAFETY: NEON is statically enabled, and `words` holds two `u32`s.
+    unsafe { vld1_u32(words.as_ptr()) }
+}
+
+/// Returns the two lanes of `v`.
+#[inline(always)]
+fn store(v: uint64x2_t) -> [u64; 2] {
+    let mut out = [0u64; 2];
+    // SAFETY: NEON is statically enabled, and `out` holds two `u64`s.
+    unsafe { vst1q_u64(out.as_mut_ptr(), v) };
+    out
+}
+
+/// Computes $\textsf{lhs}_e \cdot \textsf{rhs}_e \cdot R^{-1} \bmod p$ for each of the
+/// two pairs of Montgomery-form elements, where $R = 2^{256}$, `modulus` is $p$
+/// and `inv` is $-p^{-1} \bmod 2^{64}$.
+///
+/// The inputs must be fully reduced and $p$ must be less than $2^{255}$.
+#[inline]
+pub(crate) fn mul_2x(
+    lhs: &[[u64; 4]; 2],
+    rhs: &[[u64; 4]; 2],
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [[u64; 4]; 2] {
+    // SAFETY: NEON is statically enabled.
+    unsafe {
+        let mask = vdupq_n_u64(0xffff_ffff);
+        // -p^{-1} mod 2^32 is the low half of -p^{-1} mod 2^64.
+        let inv = vdup_n_u32(inv as u32);
+
+        let mut lhs_limbs = [vdup_n_u32(0); 8];
+        let mut p_limbs = [vdup_n_u32(0); 8];
+        for j in 0..8 {
+            lhs_limbs[j] = load_limb(lhs, j);
+            p_limbs[j] = vdup_n_u32((modulus[j / 2] >> (32 * (j % 2))) as u32);
+        }
+
+        // Each lane of `t` holds a 32-bit value, so t[j] + x * y + carry is at most
+        // 2^64 - 1 and no lane ever overflows.
+        let mut t = [vdupq_n_u64(0); 10];
+        for i in 0..8 {
+            let bi = load_limb(rhs, i);
+
+            // t += a * b_i
+            let mut carry = vdupq_n_u64(0);
+            for j in 0..8 {
+                let sum = vmlal_u32(vaddq_u64(t[j], carry), lhs_limbs[j], bi);
+                t[j] = vandq_u64(sum, mask);
+                carry = vshrq_n_u64::<32>(sum);
+            }
+            let sum = vaddq_u64(t[8], carry);
+            t[8] = vandq_u64(sum, mask);
+            t[9] = vshrq_n_u64::<32>(sum);
+
+            // t = (t + m * p) / 2^32, where m is chosen to clear the lowest word.
+            let m = vmul_u32(vmovn_u64(t[0]), inv);
+            let sum = vmlal_u32(t[0], m, p_limbs[0]);
+            let mut carry = vshrq_n_u64::<32>(sum);
+            for j in 1..8 {
+                let sum = vmlal_u32(vaddq_u64(t[j], carry), m, p_limbs[j]);
+                t[j - 1] = vandq_u64(sum, mask);
+                carry = vshrq_n_u64::<32>(sum);
+            }
+            let sum = vaddq_u64(t[8], carry);
+            t[7] = vandq_u64(sum, mask);
+            t[8] = vaddq_u64(t[9], vshrq_n_u64::<32>(sum));
+        }
+
+        let mut words = [[0u64; 2]; 8];
+        for j in 0..8 {
+            words[j] = store(t[j]);
+        }
+
+        let mut out = [[0u64; 4]; 2];
+        for (e, out) in out.iter_mut().enumerate() {
+            let r = [
+                words[0][e] | (words[1][e] << 32),
+                words[2][e] | (words[3][e] << 32),
+                words[4][e] | (words[5][e] << 32),
+                words[6][e] | (words[7][e] << 32),
+            ];
+            *out = reduce_once(r, modulus);
+        }
+        out
+    }
+}
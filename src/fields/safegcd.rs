@@ -0,0 +1,240 @@
+//! Constant-time modular inversion with the safegcd algorithm of Bernstein and
+//! Yang, <https://eprint.iacr.org/2019/266>.
+//!
+//! This follows the `modinv64` implementation of libsecp256k1: integers are held
+//! in five signed 62-bit limbs, and each of the 10 outer iterations applies 59
+//! divsteps to the low limbs of $f$ and $g$ before updating the full integers with
+//! the resulting transition matrix. 590 divsteps suffice for any modulus of at
+//! most 256 bits, so the running time does not depend on the input.
+
+/// The low 62 bits of a limb.
+const M62: u64 = u64::MAX >> 2;
+
+/// An integer $\sum_i v_i 2^{62 i}$ in signed 62-bit limbs.
+type Signed62 = [i64; 5];
+
+/// The transition matrix $[[u, v], [q, r]]$ of 59 divsteps, scaled by $2^{62}$.
+struct Trans2x2 {
+    u: i64,
+    v: i64,
+    q: i64,
+    r: i64,
+}
+
+/// A modulus of at most 256 bits, with the constants for inverting modulo it.
+#[derive(Debug)]
+pub(crate) struct Modulus {
+    modulus: Signed62,
+    /// The inverse of the modulus modulo $2^{62}$.
+    modulus_inv62: u64,
+}
+
+impl Modulus {
+    /// Precomputes the constants for the odd `modulus`, given as little-endian
+    /// limbs.
+    pub(crate) const fn new(modulus: &[u64; 4]) -> Self {
+        // Newton's iteration doubles the number of correct low bits of the inverse,
+        // starting from the 3 that are correct for any odd integer.
+        let mut inv = modulus[0];
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(modulus[0].wrapping_mul(inv)));
+            i += 1;
+        }
+
+        Modulus {
+            modulus: to_signed62(modulus),
+            modulus_inv62: inv & M62,
+        }
+    }
+}
+
+const fn to_signed62(a: &[u64; 4]) -> Signed62 {
+    [
+        (a[0] & M62) as i64,
+        ((a[0] >> 62 | a[1] << 2) & M62) as i64,
+        ((a[1] >> 60 | a[2] << 4) & M62) as i64,
+        ((a[2] >> 58 | a[3] << 6) & M62) as i64,
+        (a[3] >> 56) as i64,
+    ]
+}
+
+/// Converts a normalized integer, with every limb in $[0, 2^{62})$, back to
+/// 64-bit limbs.
+fn from_signed62(a: &Signed62) -> [u64; 4] {
+    let a = a.map(|limb| limb as u64);
+    [
+        a[0] | a[1] << 62,
+        a[1] >> 2 | a[2] << 60,
+        a[2] >> 4 | a[3] << 58,
+        a[3] >> 6 | a[4] << 56,
+    ]
+}
+
+/// Applies 59 divsteps to the low bits `f0` and `g0` of $f$ and $g$, returning
+/// the new $\zeta = -(\delta + 1/2)$ and the transition matrix.
+#[allow(clippy::many_single_char_names)]
+fn divsteps_59(mut zeta: i64, f0: u64, g0: u64) -> (i64, Trans2x2) {
+    // The matrix starts as the identity scaled by 2^3, so that it is scaled by
+    // 2^62 after the 59 steps. Its entries are signed, but are computed modulo
+    // 2^64 as they stay within [-2^62, 2^62].
+    let (mut u, mut v, mut q, mut r) = (8u64, 0u64, 0u64, 8u64);
+    let (mut f, mut g) = (f0, g0);
+
+    for _ in 3..62 {
+        // Masks for zeta < 0, and for g being odd.
+        let mut c1 = (zeta >> 63) as u64;
+        let c2 = (g & 1).wrapping_neg();
+
+        // Conditionally negate f, u and v, and add them to g, q and r.
+        let x = (f ^ c1).wrapping_sub(c1);
+        let y = (u ^ c1).wrapping_sub(c1);
+        let z = (v ^ c1).wrapping_sub(c1);
+        g = g.wrapping_add(x & c2);
+        q = q.wrapping_add(y & c2);
+        r = r.wrapping_add(z & c2);
+
+        // If both conditions hold, swap the roles of f and g, so that zeta becomes
+        // -zeta - 2 instead of zeta - 1.
+        c1 &= c2;
+        zeta = (zeta ^ c1 as i64) - 1;
+        f = f.wrapping_add(g & c1);
+        u = u.wrapping_add(q & c1);
+        v = v.wrapping_add(r & c1);
+
+        g >>= 1;
+        u <<= 1;
+        v <<= 1;
+    }
+
+    (
+        zeta,
+        Trans2x2 {
+            u: u as i64,
+            v: v as i64,
+            q: q as i64,
+            r: r as i64,
+        },
+    )
+}
+
+/// Replaces $d$ and $e$ with $(t [d, e] + M [m_d, m_e]) / 2^{62}$ modulo the
+/// modulus $M$, where $m_d$ and $m_e$ are chosen to make the division exact.
+#[allow(clippy::many_single_char_names)]
+fn update_de(d: &mut Signed62, e: &mut Signed62, t: &Trans2x2, m: &Modulus) {
+    let (u, v, q, r) = (t.u as i128, t.v as i128, t.q as i128, t.r as i128);
+
+    // Start m_d and m_e with the multiples of the modulus that keep the results in
+    // range when d or e are negative.
+    let sd = d[4] >> 63;
+    let se = e[4] >> 63;
+    let mut md = (t.u & sd) + (t.v & se);
+    let mut me = (t.q & sd) + (t.r & se);
+
+    let mut cd = u * d[0] as i128 + v * e[0] as i128;
+    let mut ce = q * d[0] as i128 + r * e[0] as i128;
+
+    // Correct m_d and m_e so that the low 62 bits of the results are zero.
+    let inv = m.modulus_inv62;
+    md -= (inv.wrapping_mul(cd as u64).wrapping_add(md as u64) & M62) as i64;
+    me -= (inv.wrapping_mul(ce as u64).wrapping_add(me as u64) & M62) as i64;
+
+    cd += m.modulus[0] as i128 * md as i128;
+    ce += m.modulus[0] as i128 * me as i128;
+    debug_assert_eq!(cd as u64 & M62, 0);
+    debug_assert_eq!(ce as u64 & M62, 0);
+    cd >>= 62;
+    ce >>= 62;
+
+    for i in 1..5 {
+        cd += u * d[i] as i128 + v * e[i] as i128 + m.modulus[i] as i128 * md as i128;
+        ce += q * d[i] as i128 + r * e[i] as i128 + m.modulus[i] as i128 * me as i128;
+        d[i - 1] = (cd as u64 & M62) as i64;
+        e[i - 1] = (ce as u64 & M62) as i64;
+        cd >>= 62;
+        ce >>= 62;
+    }
+    d[4] = cd as i64;
+    e[4] = ce as i64;
+}
+
+/// Replaces $f$ and $g$ with $t [f, g] / 2^{62}$, which is exact.
+#[allow(clippy::many_single_char_names)]
+fn update_fg(f: &mut Signed62, g: &mut Signed62, t: &Trans2x2) {
+    let (u, v, q, r) = (t.u as i128, t.v as i128, t.q as i128, t.r as i128);
+
+    let mut cf = u * f[0] as i128 + v * g[0] as i128;
+    let mut cg = q * f[0] as i128 + r * g[0] as i128;
+    debug_assert_eq!(cf as u64 & M62, 0);
+    debug_assert_eq!(cg as u64 & M62, 0);
+    cf >>= 62;
+    cg >>= 62;
+
+    for i in 1..5 {
+        cf += u * f[i] as i128 + v * g[i] as i128;
+        cg += q * f[i] as i128 + r * g[i] as i128;
+        f[i - 1] = (cf as u64 & M62) as i64;
+        g[i - 1] = (cg as u64 & M62) as i64;
+        cf >>= 62;
+        cg >>= 62;
+    }
+    f[4] = cf as i64;
+    g[4] = cg as i64;
+}
+
+/// Adds the modulus to `a` if it is negative, and propagates the carries so that
+/// the low limbs are in $[0, 2^{62})$.
+fn add_modulus_if_negative(a: &mut Signed62, m: &Modulus) {
+    let cond_add = a[4] >> 63;
+    for (a, m) in a.iter_mut().zip(m.modulus.iter()) {
+        *a += m & cond_add;
+    }
+    for i in 0..4 {
+        a[i + 1] += a[i] >> 62;
+        a[i] &= M62 as i64;
+    }
+}
+
+/// Brings $d \in (-2M, M)$ to $[0, M)$, negating it first if `sign` is negative.
+fn normalize(d: &mut Signed62, sign: i64, m: &Modulus) {
+    let cond_add = d[4] >> 63;
+    for (d, m) in d.iter_mut().zip(m.modulus.iter()) {
+        *d += m & cond_add;
+    }
+    let cond_negate = sign >> 63;
+    for d in d.iter_mut() {
+        *d = (*d ^ cond_negate) - cond_negate;
+    }
+    for i in 0..4 {
+        d[i + 1] += d[i] >> 62;
+        d[i] &= M62 as i64;
+    }
+
+    add_modulus_if_negative(d, m);
+}
+
+/// Computes the inverse of `a` modulo the modulus, or zero if `a` is zero.
+///
+/// `a` must be less than the modulus. This runs in constant time.
+#[allow(clippy::many_single_char_names)]
+pub(crate) fn invert(a: &[u64; 4], m: &Modulus) -> [u64; 4] {
+    let mut d = [0; 5];
+    let mut e = [1, 0, 0, 0, 0];
+    let mut f = m.modulus;
+    let mut g = to_signed62(a);
+    // zeta = -(delta + 1/2), with delta starting at 1/2.
+    let mut zeta = -1;
+
+    for _ in 0..10 {
+        let (z, t) = divsteps_59(zeta, f[0] as u64, g[0] as u64);
+        zeta = z;
+        update_de(&mut d, &mut e, &t, m);
+        update_fg(&mut f, &mut g, &t);
+    }
+
+    // g is now zero, and f is the gcd of a and the modulus up to sign, which is
+    // ±1 unless a is zero. d is the inverse of a with the same sign as f.
+    debug_assert!(g.iter().all(|limb| *limb == 0));
+    normalize(&mut d, f[4], m);
+    from_signed62(&d)
+}
@@ -0,0 +1,1340 @@
+//! This module provides GPU kernel generation for the Pallas and Vesta curves, via
+//! the `ec-gpu-gen` crate.
+//!
+//! The kernel source returned by [`source_builder`] contains the field arithmetic
+//! for $\mathbb{F}_p$ and $\mathbb{F}_q$, number-theoretic transform kernels for
+//! both fields, multi-scalar multiplication kernels for both curves, and kernels
+//! that hash messages to both curves. Dependents can pass it to
+//! `ec_gpu_gen::generate` from a build script, or compile it at runtime, instead
+//! of assembling the kernels themselves.
+//!
+//! With the `cuda` or `opencl` feature flag, the build script of this crate
+//! generates the same kernels and embeds them, compiled with `nvcc` for CUDA. The
+//! [`msm`], [`fft`], [`ifft`] and [`hash_to_curve_batch`] entry points load them
+//! onto the given devices, so dependents need neither a build script nor a CUDA
+//! toolchain at runtime. The devices can be listed with [`list_devices`] and
+//! selected by their unique id, and when none of them is usable, the entry points
+//! run on the CPU instead and report the [`Backend`] that ran. With the `std`
+//! feature flag, [`msm_async`], [`fft_async`] and [`ifft_async`] run them without
+//! blocking the calling thread, and [`MsmScheduler`] splits multi-scalar
+//! multiplications across several devices.
+
+use alloc::{string::String, vec::Vec};
+
+use ec_gpu::GpuName;
+use ec_gpu_gen::SourceBuilder;
+use ff::Field;
+
+use crate::arithmetic::{CurveExt, SqrtRatio};
+use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq, IsoEp, IsoEq};
+
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+use {
+    crate::{
+        arithmetic::{CurveAffine, FieldExt},
+        fft::EvaluationDomain,
+    },
+    alloc::{string::ToString, sync::Arc, vec},
+    ec_gpu_gen::{
+        fft::FftKernel,
+        multiexp::MultiexpKernel,
+        rust_gpu_tools::{program_closures, Device, Program, UniqueId},
+        threadpool::Worker,
+        EcError, EcResult,
+    },
+    ff::PrimeField,
+};
+
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+use std::{
+    env, fmt,
+    future::Future,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+    thread,
+    time::Instant,
+};
+
+/// Returns a [`SourceBuilder`] that generates the field arithmetic and FFT kernels
+/// for [`Fp`] and [`Fq`], and the multi-scalar multiplication and hash-to-curve
+/// kernels for [`EpAffine`] and [`EqAffine`].
+///
+/// Further kernels can be added to the returned builder before generating the
+/// source with [`SourceBuilder::build_32_bit_limbs`] or
+/// [`SourceBuilder::build_64_bit_limbs`].
+pub fn source_builder() -> SourceBuilder {
+    kernels_source_builder()
+}
+
+include!("cl/source.rs");
+
+/// A curve with a GPU kernel for [`CurveExt::hash_to_curve`], generated by
+/// [`source_builder`].
+pub trait HashToCurveGpu: CurveExt {
+    /// Returns the name of the kernel that hashes to this curve.
+    fn hash_to_curve_kernel() -> String;
+
+    /// Returns the constants that the kernel takes, in the order listed in its
+    /// source.
+    fn hash_to_curve_constants() -> Vec<Self::Base>;
+}
+
+macro_rules! impl_hash_to_curve_gpu {
+    ($name:ident, $name_affine:ident, $iso:ident, $base:ident) => {
+        impl HashToCurveGpu for $name {
+            fn hash_to_curve_kernel() -> String {
+                alloc::format!("{}_hash_to_curve", $name_affine::name())
+            }
+
+            fn hash_to_curve_constants() -> Vec<$base> {
+                let r = $base::from_montgomery_limbs($base::R2);
+                let z = $name::Z;
+                let z_pow = z.pow_vartime(&<$base as SqrtRatio>::T_MINUS1_OVER2);
+
+                let mut constants = alloc::vec![
+                    r,
+                    r.square(),
+                    $iso::a(),
+                    $iso::b(),
+                    z,
+                    $base::from_montgomery_limbs(<$base as SqrtRatio>::T_MINUS1_OVER2),
+                    z_pow.square() * z,
+                    z_pow * z,
+                ];
+                constants.extend_from_slice(&$name::ISOGENY_CONSTANTS);
+                constants
+            }
+        }
+    };
+}
+
+impl_hash_to_curve_gpu!(Ep, EpAffine, IsoEp, Fp);
+impl_hash_to_curve_gpu!(Eq, EqAffine, IsoEq, Fq);
+
+/// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of `bases` $P_i$ and
+/// `scalars` $s_i$ on the given GPU devices, and returns it with the backend that
+/// ran it.
+///
+/// `programs` must contain one program per device, built from kernels generated by
+/// [`source_builder`]. If `programs` is empty or the multiexp kernel cannot be
+/// created from them, the multiplication is performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` have different lengths.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn msm_gpu<C>(
+    programs: Vec<Program>,
+    devices: &[&Device],
+    bases: &[C],
+    scalars: &[C::Scalar],
+) -> EcResult<(C::Curve, Backend)>
+where
+    C: CurveAffine + ec_gpu::GpuName,
+{
+    msm_gpu_with(
+        programs,
+        devices,
+        bases,
+        || Arc::new(bases.to_vec()),
+        scalars,
+    )
+}
+
+/// Computes a multi-scalar multiplication as in [`msm_gpu`], where `shared_bases`
+/// returns `bases` in the form taken by the kernels.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+fn msm_gpu_with<C, B>(
+    programs: Vec<Program>,
+    devices: &[&Device],
+    bases: &[C],
+    shared_bases: B,
+    scalars: &[C::Scalar],
+) -> EcResult<(C::Curve, Backend)>
+where
+    C: CurveAffine + ec_gpu::GpuName,
+    B: FnOnce() -> Arc<Vec<C>>,
+{
+    assert_eq!(bases.len(), scalars.len());
+
+    if !programs.is_empty() {
+        if let Ok(mut kernel) = MultiexpKernel::<C>::create(programs, devices) {
+            let exps = Arc::new(scalars.iter().map(|s| s.to_repr()).collect::<Vec<_>>());
+            let result = kernel.multiexp(&Worker::new(), shared_bases(), exps, 0)?;
+            return Ok((result, Backend::Gpu));
+        }
+    }
+    Ok((crate::msm::multi_scalar_mul(bases, scalars), Backend::Cpu))
+}
+
+/// Performs [`EvaluationDomain::fft`] on `a` on the GPU devices for which
+/// `programs` were built from kernels generated by [`source_builder`], returning
+/// the backend that ran it.
+///
+/// If `programs` is empty or the FFT kernel cannot be created from them, the
+/// transform is performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not the size of `domain`.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn fft_gpu<F>(
+    programs: Vec<Program>,
+    domain: &EvaluationDomain<F>,
+    a: &mut [F],
+) -> EcResult<Backend>
+where
+    F: FieldExt + ec_gpu::GpuName,
+{
+    assert_eq!(a.len(), domain.size());
+
+    if radix_fft_gpu(programs, a, domain.omega(), domain.k())? {
+        Ok(Backend::Gpu)
+    } else {
+        domain.fft(a);
+        Ok(Backend::Cpu)
+    }
+}
+
+/// Performs [`EvaluationDomain::ifft`] on `a` on the GPU devices for which
+/// `programs` were built from kernels generated by [`source_builder`], returning
+/// the backend that ran it.
+///
+/// If `programs` is empty or the FFT kernel cannot be created from them, the
+/// transform is performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not the size of `domain`.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn ifft_gpu<F>(
+    programs: Vec<Program>,
+    domain: &EvaluationDomain<F>,
+    a: &mut [F],
+) -> EcResult<Backend>
+where
+    F: FieldExt + ec_gpu::GpuName,
+{
+    assert_eq!(a.len(), domain.size());
+
+    if radix_fft_gpu(programs, a, domain.omega_inv(), domain.k())? {
+        // The kernel only performs the transform, so scale by n^{-1} here.
+        let n_inv = domain.n_inv();
+        for a in a.iter_mut() {
+            *a *= n_inv;
+        }
+        Ok(Backend::Gpu)
+    } else {
+        domain.ifft(a);
+        Ok(Backend::Cpu)
+    }
+}
+
+/// The kernels embedded by the build script, as a CUDA fatbin.
+#[cfg(feature = "cuda")]
+const CUDA_KERNELS: &[u8] = include_bytes!(env!("_EC_GPU_CUDA_KERNELS"));
+
+/// The kernels embedded by the build script, as OpenCL source.
+#[cfg(feature = "opencl")]
+const OPENCL_KERNELS: &str = include_str!(env!("_EC_GPU_OPENCL_KERNELS"));
+
+/// Returns the OpenCL source of the kernels embedded by the build script, which is
+/// the source generated by [`source_builder`].
+///
+/// This is for dependents that build OpenCL programs themselves; [`program`]
+/// builds it for a device.
+#[cfg(feature = "opencl")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opencl")))]
+pub fn opencl_source() -> &'static str {
+    OPENCL_KERNELS
+}
+
+/// Loads the kernels embedded by the build script onto `device`.
+///
+/// CUDA is preferred for devices that support both frameworks.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn program(device: &Device) -> EcResult<Program> {
+    #[cfg(feature = "cuda")]
+    if let Some(device) = device.cuda_device() {
+        let program = ec_gpu_gen::rust_gpu_tools::cuda::Program::from_bytes(device, CUDA_KERNELS)?;
+        return Ok(Program::Cuda(program));
+    }
+
+    #[cfg(feature = "opencl")]
+    if let Some(device) = device.opencl_device() {
+        let program =
+            ec_gpu_gen::rust_gpu_tools::opencl::Program::from_opencl(device, OPENCL_KERNELS)?;
+        return Ok(Program::Opencl(program));
+    }
+
+    Err(EcError::Simple(
+        "device is not supported by the enabled GPU frameworks",
+    ))
+}
+
+/// The backend that ran an operation of this module.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// The operation ran on GPU devices.
+    Gpu,
+    /// No device was usable, so the operation ran on the CPU, in parallel with the
+    /// `multicore` feature flag.
+    Cpu,
+}
+
+/// Returns all devices supported by the enabled GPU frameworks.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn list_devices() -> Vec<&'static Device> {
+    Device::all()
+}
+
+/// Returns the device with the unique id `id`, which is its PCI bus id or UUID.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn device_by_id(id: UniqueId) -> Option<&'static Device> {
+    list_devices()
+        .into_iter()
+        .find(|device| device.unique_id() == id)
+}
+
+/// The environment variable read by [`configured_devices`].
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "std", any(feature = "cuda", feature = "opencl"))))
+)]
+pub const DEVICES_ENV_VAR: &str = "PASTA_CURVES_GPU_DEVICES";
+
+/// Returns the devices selected by `spec`, a comma-separated list of the unique ids
+/// of devices as they are displayed, such as the PCI bus id `"01:00"`.
+///
+/// `"none"` selects no device, so that the entry points run on the CPU. Fails if
+/// an id does not match any device.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn select_devices(spec: &str) -> EcResult<Vec<&'static Device>> {
+    let spec = spec.trim();
+    if spec.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    let devices = list_devices();
+    spec.split(',')
+        .map(|id| {
+            let id = id.trim();
+            devices
+                .iter()
+                .find(|device| device.unique_id().to_string().eq_ignore_ascii_case(id))
+                .copied()
+                .ok_or(EcError::Simple("no device has the selected unique id"))
+        })
+        .collect()
+}
+
+/// Returns the devices selected by the [`DEVICES_ENV_VAR`] environment variable as
+/// in [`select_devices`], or all devices if it is not set.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "std", any(feature = "cuda", feature = "opencl"))))
+)]
+pub fn configured_devices() -> EcResult<Vec<&'static Device>> {
+    match env::var(DEVICES_ENV_VAR) {
+        Ok(spec) => select_devices(&spec),
+        Err(_) => Ok(list_devices()),
+    }
+}
+
+/// Loads the embedded kernels onto each of `devices` that supports them, returning
+/// the programs and the devices they were loaded onto.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+fn usable_programs<'a>(devices: &[&'a Device]) -> (Vec<Program>, Vec<&'a Device>) {
+    devices
+        .iter()
+        .filter_map(|device| program(device).ok().map(|program| (program, *device)))
+        .unzip()
+}
+
+/// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of `bases` $P_i$ and
+/// `scalars` $s_i$ on `devices`, using the kernels embedded by the build script,
+/// and returns it with the backend that ran it.
+///
+/// If the kernels cannot be loaded onto any of `devices`, the multiplication is
+/// performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` have different lengths.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn msm<C>(
+    devices: &[&Device],
+    bases: &[C],
+    scalars: &[C::Scalar],
+) -> EcResult<(C::Curve, Backend)>
+where
+    C: CurveAffine + ec_gpu::GpuName,
+{
+    let (programs, devices) = usable_programs(devices);
+    msm_gpu(programs, &devices, bases, scalars)
+}
+
+/// Performs [`EvaluationDomain::fft`] on `a` on `devices`, using the kernels
+/// embedded by the build script, and returns the backend that ran it.
+///
+/// If the kernels cannot be loaded onto any of `devices`, the transform is
+/// performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not the size of `domain`.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn fft<F>(devices: &[&Device], domain: &EvaluationDomain<F>, a: &mut [F]) -> EcResult<Backend>
+where
+    F: FieldExt + ec_gpu::GpuName,
+{
+    fft_gpu(usable_programs(devices).0, domain, a)
+}
+
+/// Performs [`EvaluationDomain::ifft`] on `a` on `devices`, using the kernels
+/// embedded by the build script, and returns the backend that ran it.
+///
+/// If the kernels cannot be loaded onto any of `devices`, the transform is
+/// performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not the size of `domain`.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn ifft<F>(devices: &[&Device], domain: &EvaluationDomain<F>, a: &mut [F]) -> EcResult<Backend>
+where
+    F: FieldExt + ec_gpu::GpuName,
+{
+    ifft_gpu(usable_programs(devices).0, domain, a)
+}
+
+/// The number of threads in each work group of the hash-to-curve kernels.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+const HASH_TO_CURVE_LOCAL_WORK_SIZE: usize = 64;
+
+/// Packs `bytes` little-endian into 32-bit words, as the kernels read them.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+fn pack_bytes(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        })
+        .collect()
+}
+
+/// Hashes each of `messages` to a point with [`CurveExt::hash_to_curve`] and
+/// `domain_prefix`, on the first of `programs`, and returns the points with the
+/// backend that hashed them.
+///
+/// `programs` must have been built from kernels generated by [`source_builder`]. If
+/// `programs` is empty or the kernel cannot be created from the first of them, the
+/// messages are hashed on the CPU instead, with the same results. The kernel is not constant time, so it must not be used to hash secret
+/// messages.
+///
+/// # Panics
+///
+/// Panics if `domain_prefix` is too long, like [`CurveExt::hash_to_curve`].
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn hash_to_curve_batch_gpu<C: HashToCurveGpu>(
+    programs: Vec<Program>,
+    domain_prefix: &str,
+    messages: &[&[u8]],
+) -> EcResult<(Vec<C>, Backend)> {
+    let cpu = || {
+        let hasher = C::hash_to_curve(domain_prefix);
+        let points = messages.iter().map(|message| hasher(message)).collect();
+        Ok((points, Backend::Cpu))
+    };
+    let program = match programs.into_iter().next() {
+        Some(program) if !messages.is_empty() => program,
+        _ => return cpu(),
+    };
+
+    // DST = domain_prefix || "-" || CURVE_ID || "_XMD:BLAKE2b_SSWU_RO_"
+    let dst = alloc::format!("{}-{}_XMD:BLAKE2b_SSWU_RO_", domain_prefix, C::CURVE_ID);
+    assert!(domain_prefix.len() < 256 && dst.len() < 256);
+
+    let mut bytes = Vec::new();
+    let mut offsets = vec![0u32];
+    for message in messages {
+        bytes.extend_from_slice(message);
+        let offset = u32::try_from(bytes.len())
+            .map_err(|_| EcError::Simple("messages are longer than 2^32 bytes in total"))?;
+        offsets.push(offset);
+    }
+    let bytes = pack_bytes(&bytes);
+    let dst_words = pack_bytes(dst.as_bytes());
+    let constants = C::hash_to_curve_constants();
+    let n = messages.len();
+    let kernel_name = C::hash_to_curve_kernel();
+
+    let closures = program_closures!(|program, _arg| -> EcResult<Option<Vec<C::Base>>> {
+        let num_groups = (n + HASH_TO_CURVE_LOCAL_WORK_SIZE - 1) / HASH_TO_CURVE_LOCAL_WORK_SIZE;
+        let kernel =
+            match program.create_kernel(&kernel_name, num_groups, HASH_TO_CURVE_LOCAL_WORK_SIZE) {
+                Ok(kernel) => kernel,
+                Err(_) => return Ok(None),
+            };
+
+        let bytes_buffer = program.create_buffer_from_slice(&bytes)?;
+        let offsets_buffer = program.create_buffer_from_slice(&offsets)?;
+        let dst_buffer = program.create_buffer_from_slice(&dst_words)?;
+        let constants_buffer = program.create_buffer_from_slice(&constants)?;
+        let mut coordinates = vec![C::Base::ZERO; 3 * n];
+        let coordinates_buffer = program.create_buffer_from_slice(&coordinates)?;
+        kernel
+            .arg(&bytes_buffer)
+            .arg(&offsets_buffer)
+            .arg(&(n as u32))
+            .arg(&dst_buffer)
+            .arg(&(dst.len() as u32))
+            .arg(&constants_buffer)
+            .arg(&<C::Base as ff::PrimeField>::S)
+            .arg(&coordinates_buffer)
+            .run()?;
+
+        program.read_into_buffer(&coordinates_buffer, &mut coordinates)?;
+        Ok(Some(coordinates))
+    });
+    let coordinates = match program.run(closures, ())? {
+        Some(coordinates) => coordinates,
+        None => return cpu(),
+    };
+
+    // Checking that the results are on the curve catches kernels that were not
+    // generated for this curve.
+    let points = coordinates
+        .chunks(3)
+        .map(|p| {
+            Option::from(C::new_jacobian(p[0], p[1], p[2])).ok_or(EcError::Simple(
+                "hash-to-curve kernel returned an invalid point",
+            ))
+        })
+        .collect::<EcResult<_>>()?;
+    Ok((points, Backend::Gpu))
+}
+
+/// Hashes each of `messages` to a point with [`CurveExt::hash_to_curve`] and
+/// `domain_prefix`, on the first of `devices` that supports the kernels embedded by
+/// the build script, and returns the points with the backend that hashed them.
+///
+/// If the kernels cannot be loaded onto any of `devices`, the messages are hashed
+/// on the CPU instead, with the same results.
+///
+/// # Panics
+///
+/// Panics if `domain_prefix` is too long, like [`CurveExt::hash_to_curve`].
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "cuda", feature = "opencl"))))]
+pub fn hash_to_curve_batch<C: HashToCurveGpu>(
+    devices: &[&Device],
+    domain_prefix: &str,
+    messages: &[&[u8]],
+) -> EcResult<(Vec<C>, Backend)> {
+    hash_to_curve_batch_gpu(usable_programs(devices).0, domain_prefix, messages)
+}
+
+/// A future that resolves to the result of work offloaded to a GPU.
+///
+/// The work runs on its own thread, which wakes the task awaiting the future when
+/// the device completes, so the future can be awaited from any async runtime. If
+/// the work panics, the panic is resumed when the future is polled.
+///
+/// Every call to [`msm_async`], [`fft_async`] or [`ifft_async`] spawns a new OS
+/// thread, which mostly waits for the device and exits when the work completes.
+/// Spawning a thread is cheap next to a GPU dispatch, but callers issuing many
+/// small operations at once should batch them, or bound the number in flight, to
+/// avoid creating as many threads.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "std", any(feature = "cuda", feature = "opencl"))))
+)]
+pub struct GpuFuture<T> {
+    state: Arc<Mutex<GpuFutureState<T>>>,
+}
+
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+struct GpuFutureState<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+impl<T: Send + 'static> GpuFuture<T> {
+    /// Runs `work` on a new thread.
+    fn spawn<W: FnOnce() -> T + Send + 'static>(work: W) -> Self {
+        let state = Arc::new(Mutex::new(GpuFutureState {
+            result: None,
+            waker: None,
+        }));
+
+        let thread_state = state.clone();
+        thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(work));
+            let mut state = thread_state.lock().unwrap();
+            state.result = Some(result);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        GpuFuture { state }
+    }
+}
+
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+impl<T> Future for GpuFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(payload)) => {
+                drop(state);
+                panic::resume_unwind(payload)
+            }
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+impl<T> fmt::Debug for GpuFuture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GpuFuture").finish_non_exhaustive()
+    }
+}
+
+/// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of `bases` $P_i$ and
+/// `scalars` $s_i$ on `devices`, like [`msm`], returning a future that resolves
+/// when the devices complete.
+///
+/// If the kernels cannot be loaded onto any of `devices`, the multiplication is
+/// performed on the CPU instead, on the same thread the devices would be driven
+/// from.
+///
+/// `bases` are shared rather than copied, so commitments to many scalar vectors
+/// can reuse the same bases.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` have different lengths.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "std", any(feature = "cuda", feature = "opencl"))))
+)]
+pub fn msm_async<C>(
+    devices: Vec<&'static Device>,
+    bases: Arc<Vec<C>>,
+    scalars: Vec<C::Scalar>,
+) -> GpuFuture<EcResult<(C::Curve, Backend)>>
+where
+    C: CurveAffine + ec_gpu::GpuName,
+{
+    assert_eq!(bases.len(), scalars.len());
+
+    GpuFuture::spawn(move || {
+        let (programs, devices) = usable_programs(&devices);
+        msm_gpu_with(programs, &devices, &bases, || bases.clone(), &scalars)
+    })
+}
+
+/// Performs [`EvaluationDomain::fft`] on `a` on `devices`, like [`fft`], returning
+/// a future that resolves to the evaluations and the backend that computed them
+/// when the devices complete.
+///
+/// If the kernels cannot be loaded onto any of `devices`, the transform is
+/// performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not the size of `domain`.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "std", any(feature = "cuda", feature = "opencl"))))
+)]
+pub fn fft_async<F>(
+    devices: Vec<&'static Device>,
+    domain: EvaluationDomain<F>,
+    mut a: Vec<F>,
+) -> GpuFuture<EcResult<(Vec<F>, Backend)>>
+where
+    F: FieldExt + ec_gpu::GpuName,
+{
+    assert_eq!(a.len(), domain.size());
+
+    GpuFuture::spawn(move || {
+        let backend = fft(&devices, &domain, &mut a)?;
+        Ok((a, backend))
+    })
+}
+
+/// Performs [`EvaluationDomain::ifft`] on `a` on `devices`, like [`ifft`],
+/// returning a future that resolves to the coefficients and the backend that
+/// computed them when the devices complete.
+///
+/// If the kernels cannot be loaded onto any of `devices`, the transform is
+/// performed on the CPU instead.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not the size of `domain`.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "std", any(feature = "cuda", feature = "opencl"))))
+)]
+pub fn ifft_async<F>(
+    devices: Vec<&'static Device>,
+    domain: EvaluationDomain<F>,
+    mut a: Vec<F>,
+) -> GpuFuture<EcResult<(Vec<F>, Backend)>>
+where
+    F: FieldExt + ec_gpu::GpuName,
+{
+    assert_eq!(a.len(), domain.size());
+
+    GpuFuture::spawn(move || {
+        let backend = ifft(&devices, &domain, &mut a)?;
+        Ok((a, backend))
+    })
+}
+
+/// Splits multi-scalar multiplications across several GPU devices, in proportion to
+/// their measured throughput, and adds up the partial results on the host.
+///
+/// Each call to [`MsmScheduler::msm`] times the multiplication on every device and
+/// updates its throughput estimate, in points per second, so later calls balance
+/// the work better. A device that has not been measured yet is estimated from its
+/// number of compute units, scaled by the points per second per compute unit of
+/// the measured devices, so that all estimates are in the same unit.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "std", any(feature = "cuda", feature = "opencl"))))
+)]
+#[derive(Clone, Debug)]
+pub struct MsmScheduler {
+    devices: Vec<&'static Device>,
+    compute_units: Vec<f64>,
+    /// The measured throughput of each device, in points per second.
+    measured: Vec<Option<f64>>,
+}
+
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+impl MsmScheduler {
+    /// Creates a scheduler for `devices`.
+    pub fn new(devices: Vec<&'static Device>) -> Self {
+        let compute_units = devices
+            .iter()
+            .map(|device| f64::from(device.compute_units().max(1)))
+            .collect();
+        let measured = vec![None; devices.len()];
+        MsmScheduler {
+            devices,
+            compute_units,
+            measured,
+        }
+    }
+
+    /// Creates a scheduler for all devices supported by the enabled GPU frameworks.
+    pub fn all_devices() -> Self {
+        Self::new(Device::all())
+    }
+
+    /// Returns the devices of this scheduler.
+    pub fn devices(&self) -> &[&'static Device] {
+        &self.devices
+    }
+
+    /// Returns the current throughput estimate of each device, in points per second
+    /// once any device has been measured, and in compute units before that.
+    pub fn throughputs(&self) -> Vec<f64> {
+        estimate_throughputs(&self.compute_units, &self.measured)
+    }
+
+    /// Returns the range of `n` inputs that [`MsmScheduler::msm`] would give to
+    /// each device.
+    pub fn partition(&self, n: usize) -> Vec<Range<usize>> {
+        partition(&self.throughputs(), n)
+    }
+
+    /// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of `bases` $P_i$
+    /// and `scalars` $s_i$, running one share of the inputs on each device
+    /// concurrently, and returns it with the backend that ran it.
+    ///
+    /// The share of each device on which the kernels cannot be loaded is computed on
+    /// the CPU instead, so if the scheduler has no usable devices, the whole
+    /// multiplication is performed on the CPU.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `scalars` have different lengths.
+    pub fn msm<C>(&mut self, bases: &[C], scalars: &[C::Scalar]) -> EcResult<(C::Curve, Backend)>
+    where
+        C: CurveAffine + ec_gpu::GpuName,
+    {
+        assert_eq!(bases.len(), scalars.len());
+        if self.devices.is_empty() {
+            return Ok((crate::msm::multi_scalar_mul(bases, scalars), Backend::Cpu));
+        }
+
+        let handles: Vec<_> = self
+            .partition(bases.len())
+            .into_iter()
+            .zip(self.devices.iter())
+            .enumerate()
+            .filter(|(_, (range, _))| !range.is_empty())
+            .map(|(i, (range, device))| {
+                let device = *device;
+                let bases = Arc::new(bases[range.clone()].to_vec());
+                let exps = Arc::new(
+                    scalars[range.clone()]
+                        .iter()
+                        .map(|s| s.to_repr())
+                        .collect::<Vec<_>>(),
+                );
+                let handle = thread::spawn(move || {
+                    let (programs, devices) = usable_programs(&[device]);
+                    let mut kernel = match MultiexpKernel::<C>::create(programs, &devices) {
+                        Ok(kernel) if !devices.is_empty() => kernel,
+                        _ => return Ok(None),
+                    };
+                    let start = Instant::now();
+                    let result = kernel.multiexp(&Worker::new(), bases, exps, 0)?;
+                    Ok(Some((result, start.elapsed())))
+                });
+                (i, range, handle)
+            })
+            .collect();
+
+        let mut acc = <C::Curve as group::Group>::identity();
+        let mut backend = Backend::Cpu;
+        let mut error = None;
+        for (i, range, handle) in handles {
+            let result: EcResult<_> = handle
+                .join()
+                .unwrap_or_else(|payload| panic::resume_unwind(payload));
+            match result {
+                Ok(Some((partial, elapsed))) => {
+                    acc += partial;
+                    backend = Backend::Gpu;
+                    let seconds = elapsed.as_secs_f64();
+                    if seconds > 0.0 {
+                        self.measured[i] = Some(update_throughput(
+                            self.measured[i],
+                            range.len() as f64 / seconds,
+                        ));
+                    }
+                }
+                Ok(None) => {
+                    acc += crate::msm::multi_scalar_mul(&bases[range.clone()], &scalars[range]);
+                }
+                Err(e) => error = error.or(Some(e)),
+            }
+        }
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok((acc, backend)),
+        }
+    }
+}
+
+/// Splits `0..n` into consecutive ranges proportional to `weights`.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+fn partition(weights: &[f64], n: usize) -> Vec<Range<usize>> {
+    let total: f64 = weights.iter().sum();
+    let mut ranges = Vec::with_capacity(weights.len());
+    let mut start = 0;
+    let mut cumulative = 0.0;
+    for (i, weight) in weights.iter().enumerate() {
+        cumulative += weight;
+        let end = if i + 1 == weights.len() {
+            n
+        } else {
+            ((n as f64 * cumulative / total) as usize).clamp(start, n)
+        };
+        ranges.push(start..end);
+        start = end;
+    }
+    ranges
+}
+
+/// Estimates the throughput of each device from its `compute_units` and its
+/// `measured` throughput, if any.
+///
+/// Once any device has been measured, the unmeasured devices are given the mean
+/// throughput per compute unit of the measured ones, so that they still receive a
+/// share of the inputs in proportion to their size.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+fn estimate_throughputs(compute_units: &[f64], measured: &[Option<f64>]) -> Vec<f64> {
+    let (points, units) = compute_units
+        .iter()
+        .zip(measured)
+        .filter_map(|(units, measured)| measured.map(|m| (m, *units)))
+        .fold((0.0, 0.0), |(p, u), (m, units)| (p + m, u + units));
+    let per_unit = if units > 0.0 { points / units } else { 1.0 };
+    compute_units
+        .iter()
+        .zip(measured)
+        .map(|(units, measured)| measured.unwrap_or(units * per_unit))
+        .collect()
+}
+
+/// Combines a measured throughput `estimate`, if any, with a new measurement,
+/// weighting them equally so that the estimate follows changes in load without
+/// jumping on a single outlier.
+#[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+fn update_throughput(estimate: Option<f64>, measured: f64) -> f64 {
+    match estimate {
+        Some(estimate) => (estimate + measured) / 2.0,
+        None => measured,
+    }
+}
+
+/// Runs the radix-2 FFT kernel on `a` with the given primitive
+/// $2^{\textsf{log_n}}$-th root of unity, returning `false` without modifying `a`
+/// if no kernel could be created from `programs`.
+#[cfg(any(feature = "cuda", feature = "opencl"))]
+fn radix_fft_gpu<F>(programs: Vec<Program>, a: &mut [F], omega: F, log_n: u32) -> EcResult<bool>
+where
+    F: FieldExt + ec_gpu::GpuName,
+{
+    if programs.is_empty() {
+        return Ok(false);
+    }
+    let mut kernel = match FftKernel::<F>::create(programs) {
+        Ok(kernel) => kernel,
+        Err(_) => return Ok(false),
+    };
+
+    kernel.radix_fft_many(&mut [a], &[omega], &[log_n])?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use ec_gpu::{GpuField, GpuName};
+    use ff::Field;
+
+    use super::HashToCurveGpu;
+    use crate::arithmetic::FieldExt;
+    use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq, IsoEp, IsoEpAffine, IsoEq, IsoEqAffine};
+
+    #[test]
+    fn test_gpu_names() {
+        let names: Vec<String> = vec![
+            EpAffine::name(),
+            EqAffine::name(),
+            IsoEpAffine::name(),
+            IsoEqAffine::name(),
+            Ep::name(),
+            Eq::name(),
+            IsoEp::name(),
+            IsoEq::name(),
+        ];
+
+        // Kernels for different types must not share names.
+        for (i, a) in names.iter().enumerate() {
+            for b in &names[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert_eq!(
+            Ep::name(),
+            alloc::format!("{}_projective", EpAffine::name())
+        );
+
+        // The build script generates kernels for these names.
+        assert_eq!(Fp::name(), "pasta_Fp");
+        assert_eq!(Fq::name(), "pasta_Fq");
+        assert_eq!(EpAffine::name(), "pasta_EpAffine");
+        assert_eq!(EqAffine::name(), "pasta_EqAffine");
+    }
+
+    // The limbs from which the build script generates the kernels.
+    include!("cl/fields.rs");
+
+    #[test]
+    fn test_build_script_limbs() {
+        fn u64_to_u32(limbs: &[u64; 4]) -> Vec<u32> {
+            limbs
+                .iter()
+                .flat_map(|limb| [*limb as u32, (limb >> 32) as u32])
+                .collect()
+        }
+
+        assert_eq!(<Fp as GpuField>::modulus(), u64_to_u32(&FP_LIMBS.0));
+        assert_eq!(<Fp as GpuField>::one(), u64_to_u32(&FP_LIMBS.1));
+        assert_eq!(<Fp as GpuField>::r2(), u64_to_u32(&FP_LIMBS.2));
+        assert_eq!(<Fq as GpuField>::modulus(), u64_to_u32(&FQ_LIMBS.0));
+        assert_eq!(<Fq as GpuField>::one(), u64_to_u32(&FQ_LIMBS.1));
+        assert_eq!(<Fq as GpuField>::r2(), u64_to_u32(&FQ_LIMBS.2));
+    }
+
+    /// The `sqrt_ratio` of the hash-to-curve kernels, with the constants passed to
+    /// them.
+    fn kernel_sqrt_ratio<F: FieldExt>(u: F, v: F, c: &[F]) -> (bool, F) {
+        let pow2 = |x: F, k: u32| (0..k).fold(x, |x, _| x.square());
+
+        let mut tv1 = c[6];
+        let mut tv2 = (1..F::S).fold(v, |t, _| t.square() * v);
+        let mut tv3 = tv2.square() * v;
+        let mut tv5 = (u * tv3).pow_vartime(&F::T_MINUS1_OVER2) * tv2;
+        tv2 = tv5 * v;
+        tv3 = tv5 * u;
+        let mut tv4 = tv3 * tv2;
+        let is_qr = pow2(tv4, F::S - 1) == F::ONE;
+        tv2 = tv3 * c[7];
+        tv5 = tv4 * tv1;
+        if !is_qr {
+            tv3 = tv2;
+            tv4 = tv5;
+        }
+        for i in (2..=F::S).rev() {
+            let e1 = pow2(tv4, i - 2) == F::ONE;
+            tv2 = tv3 * tv1;
+            tv1 = tv1.square();
+            tv5 = tv4 * tv1;
+            if !e1 {
+                tv3 = tv2;
+                tv4 = tv5;
+            }
+        }
+        (is_qr, tv3)
+    }
+
+    fn check_hash_to_curve_constants<C: HashToCurveGpu>()
+    where
+        C::Base: FieldExt,
+    {
+        let constants = C::hash_to_curve_constants();
+        assert_eq!(constants.len(), 21);
+
+        // A square root of u / v, or of Z * u / v if u / v is not square.
+        let z = constants[4];
+        for (u, v) in [(1u64, 1u64), (2, 1), (5, 3), (7, 11), (1 << 40, 9)] {
+            let (u, v) = (C::Base::from(u), C::Base::from(v));
+            let (is_qr, y) = kernel_sqrt_ratio(u, v, &constants);
+            assert_eq!(is_qr, bool::from(C::Base::sqrt_ratio(&u, &v).0));
+            assert_eq!(y.square() * v, if is_qr { u } else { z * u });
+        }
+    }
+
+    #[test]
+    fn test_hash_to_curve_constants() {
+        check_hash_to_curve_constants::<Ep>();
+        check_hash_to_curve_constants::<Eq>();
+
+        // The first two constants convert plain limbs into Montgomery form.
+        let c = Ep::hash_to_curve_constants();
+        let limbs = [1, 2, 3, 4];
+        assert_eq!(Fp::from_montgomery_limbs(limbs) * c[0], Fp::from_raw(limbs));
+        assert_eq!(
+            Fp::from_montgomery_limbs(limbs) * c[1],
+            Fp::from_raw(limbs) * Fp::from_raw([0, 0, 0, 1 << 62]) * Fp::from(4u64)
+        );
+
+        // The kernels map both field elements to the curve before adding them.
+        let u0 = Fp::from(17u64);
+        let u1 = -Fp::from(5u64);
+        let q0 = Ep::map_to_curve_simple_swu(&u0);
+        let q1 = Ep::map_to_curve_simple_swu(&u1);
+        assert_eq!(Ep::iso_map(&(q0 + q1)), Ep::iso_map(&q0) + Ep::iso_map(&q1));
+    }
+
+    #[test]
+    fn test_hash_to_curve_source() {
+        let source = super::hash_to_curve_source();
+        assert!(source.contains(&Ep::hash_to_curve_kernel()));
+        assert!(source.contains(&Eq::hash_to_curve_kernel()));
+        assert!(!source.contains("CURVE") && !source.contains("FIELD"));
+    }
+
+    #[cfg(any(feature = "cuda", feature = "opencl"))]
+    #[test]
+    fn test_hash_to_curve_cpu_fallback() {
+        use super::{hash_to_curve_batch, hash_to_curve_batch_gpu, Backend};
+        use crate::arithmetic::CurveExt;
+
+        let messages: [&[u8]; 3] = [b"", b"hello", &[0xab; 300]];
+        let expected: Vec<_> = messages
+            .iter()
+            .map(|message| Ep::hash_to_curve("z.cash:test")(message))
+            .collect();
+        assert_eq!(
+            hash_to_curve_batch_gpu::<Ep>(Vec::new(), "z.cash:test", &messages).unwrap(),
+            (expected.clone(), Backend::Cpu)
+        );
+        assert_eq!(
+            hash_to_curve_batch::<Ep>(&[], "z.cash:test", &messages).unwrap(),
+            (expected, Backend::Cpu)
+        );
+        assert!(hash_to_curve_batch::<Eq>(&[], "z.cash:test", &[])
+            .unwrap()
+            .0
+            .is_empty());
+    }
+
+    #[cfg(any(feature = "cuda", feature = "opencl"))]
+    #[test]
+    fn test_hash_to_curve_gpu_matches_cpu() {
+        use super::{hash_to_curve_batch, list_devices, Backend, Device, HashToCurveGpu};
+
+        fn check<C: HashToCurveGpu>(devices: &[&Device]) {
+            let long = [0x5a; 1000];
+            let messages: Vec<&[u8]> = (0..200).map(|len| &long[..len * 5]).collect();
+            let (points, backend) =
+                hash_to_curve_batch::<C>(devices, "z.cash:test", &messages).unwrap();
+            assert_eq!(backend, Backend::Gpu);
+            for (message, point) in messages.iter().zip(points.iter()) {
+                assert_eq!(*point, C::hash_to_curve("z.cash:test")(message));
+            }
+        }
+
+        // This runs the kernels only on machines with a supported device.
+        let devices = list_devices();
+        if devices.is_empty() {
+            return;
+        }
+        check::<Ep>(&devices);
+        check::<Eq>(&devices);
+    }
+
+    #[cfg(feature = "opencl")]
+    #[test]
+    fn test_opencl_source() {
+        let source = super::opencl_source();
+        for name in [Fp::name(), Fq::name(), EpAffine::name(), EqAffine::name()] {
+            assert!(source.contains(&name));
+        }
+    }
+
+    #[cfg(any(feature = "cuda", feature = "opencl"))]
+    #[test]
+    fn test_fft_cpu_fallback() {
+        use super::{fft, fft_gpu, ifft, ifft_gpu, Backend};
+        use crate::{fft::EvaluationDomain, Fp};
+
+        let domain = EvaluationDomain::<Fp>::new(4);
+        let coeffs: Vec<_> = (0..16u64).map(Fp::from).collect();
+
+        let mut expected = coeffs.clone();
+        domain.fft(&mut expected);
+
+        let mut a = coeffs.clone();
+        assert_eq!(fft_gpu(Vec::new(), &domain, &mut a).unwrap(), Backend::Cpu);
+        assert_eq!(a, expected);
+
+        assert_eq!(ifft_gpu(Vec::new(), &domain, &mut a).unwrap(), Backend::Cpu);
+        assert_eq!(a, coeffs);
+
+        // Without devices, no embedded kernels are loaded.
+        assert_eq!(fft(&[], &domain, &mut a).unwrap(), Backend::Cpu);
+        assert_eq!(a, expected);
+        assert_eq!(ifft(&[], &domain, &mut a).unwrap(), Backend::Cpu);
+        assert_eq!(a, coeffs);
+    }
+
+    #[cfg(any(feature = "cuda", feature = "opencl"))]
+    #[test]
+    fn test_msm_cpu_fallback() {
+        use group::{prime::PrimeCurveAffine, Group};
+
+        use super::{msm, msm_gpu, Backend};
+
+        let bases = [
+            EpAffine::generator(),
+            (Ep::generator() * Fq::from(3u64)).into(),
+        ];
+        let scalars = [Fq::from(5u64), Fq::from(7u64)];
+        assert_eq!(
+            msm(&[], &bases, &scalars).unwrap(),
+            (Ep::generator() * Fq::from(26u64), Backend::Cpu)
+        );
+        assert_eq!(
+            msm_gpu(Vec::new(), &[], &bases, &scalars).unwrap(),
+            (Ep::generator() * Fq::from(26u64), Backend::Cpu)
+        );
+    }
+
+    #[cfg(any(feature = "cuda", feature = "opencl"))]
+    #[test]
+    fn test_select_devices() {
+        use super::select_devices;
+
+        assert!(select_devices("none").unwrap().is_empty());
+        assert!(select_devices(" NONE ").unwrap().is_empty());
+        assert!(select_devices("ff:ff").is_err());
+    }
+
+    /// Polls `future` to completion on the current thread.
+    #[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake};
+        use std::thread::{self, Thread};
+
+        struct ThreadWaker(Thread);
+
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::boxed::Box::pin(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+    #[test]
+    fn test_fft_async_cpu_fallback() {
+        use super::{fft_async, ifft_async, Backend};
+        use crate::fft::EvaluationDomain;
+
+        let domain = EvaluationDomain::<Fq>::new(5);
+        let coeffs: Vec<_> = (0..32u64).map(Fq::from).collect();
+
+        let mut expected = coeffs.clone();
+        domain.fft(&mut expected);
+
+        // Both transforms can be in flight at once.
+        let evals = fft_async(Vec::new(), domain, coeffs.clone());
+        let again = ifft_async(Vec::new(), domain, expected.clone());
+        assert_eq!(block_on(evals).unwrap(), (expected, Backend::Cpu));
+        assert_eq!(block_on(again).unwrap(), (coeffs, Backend::Cpu));
+    }
+
+    #[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+    #[test]
+    fn test_msm_async_cpu_fallback() {
+        use group::{prime::PrimeCurveAffine, Group};
+        use std::sync::Arc;
+
+        use super::{msm_async, Backend};
+
+        let bases = Arc::new(vec![
+            EpAffine::generator(),
+            (Ep::generator() * Fq::from(3u64)).into(),
+        ]);
+
+        // Both multiplications can share the bases and be in flight at once.
+        let first = msm_async(
+            Vec::new(),
+            bases.clone(),
+            vec![Fq::from(5u64), Fq::from(7u64)],
+        );
+        let second = msm_async(Vec::new(), bases, vec![Fq::from(1u64), Fq::from(2u64)]);
+        assert_eq!(
+            block_on(first).unwrap(),
+            (Ep::generator() * Fq::from(26u64), Backend::Cpu)
+        );
+        assert_eq!(
+            block_on(second).unwrap(),
+            (Ep::generator() * Fq::from(7u64), Backend::Cpu)
+        );
+    }
+
+    #[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+    #[test]
+    fn test_msm_partition() {
+        use group::{prime::PrimeCurveAffine, Group};
+
+        use super::{estimate_throughputs, partition, update_throughput, Backend, MsmScheduler};
+
+        assert_eq!(partition(&[1.0], 10), vec![0..10]);
+        assert_eq!(partition(&[1.0, 1.0], 10), vec![0..5, 5..10]);
+        assert_eq!(partition(&[3.0, 1.0], 100), vec![0..75, 75..100]);
+        assert_eq!(partition(&[1.0, 2.0, 1.0], 3), vec![0..0, 0..2, 2..3]);
+        assert_eq!(partition(&[1.0, 1.0], 0), vec![0..0, 0..0]);
+
+        // The ranges always cover every input exactly once.
+        let ranges = partition(&[0.3, 1.7, 5.0, 2.9], 1001);
+        assert_eq!(ranges.first().unwrap().start, 0);
+        assert_eq!(ranges.last().unwrap().end, 1001);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+
+        // The first measurement replaces the prior, and later ones are averaged in.
+        assert!((update_throughput(None, 300.0) - 300.0).abs() < 1e-9);
+        assert!((update_throughput(Some(100.0), 300.0) - 200.0).abs() < 1e-9);
+
+        // Before any measurement, the estimates are the compute units.
+        assert_eq!(
+            estimate_throughputs(&[8.0, 24.0], &[None, None]),
+            vec![8.0, 24.0]
+        );
+
+        // Unmeasured devices are scaled to points per second, and still get a share.
+        let measured = [Some(update_throughput(None, 50_000.0)), None];
+        let estimates = estimate_throughputs(&[8.0, 24.0], &measured);
+        assert_eq!(estimates, vec![50_000.0, 150_000.0]);
+        assert_eq!(partition(&estimates, 1000), vec![0..250, 250..1000]);
+
+        let estimates = estimate_throughputs(&[10.0, 10.0, 20.0], &[Some(1e6), Some(3e6), None]);
+        assert_eq!(estimates, vec![1e6, 3e6, 4e6]);
+        assert!(partition(&estimates, 80)
+            .iter()
+            .all(|range| !range.is_empty()));
+
+        let mut scheduler = MsmScheduler::new(Vec::new());
+        assert!(scheduler.partition(10).is_empty());
+        assert_eq!(
+            scheduler.msm(&[EpAffine::generator()], &[Fq::ONE]).unwrap(),
+            (Ep::generator(), Backend::Cpu)
+        );
+    }
+
+    #[cfg(all(feature = "std", any(feature = "cuda", feature = "opencl")))]
+    #[test]
+    #[should_panic]
+    fn test_fft_async_wrong_size() {
+        use crate::fft::EvaluationDomain;
+
+        super::fft_async(
+            Vec::new(),
+            EvaluationDomain::<Fp>::new(3),
+            vec![Fp::ZERO; 4],
+        );
+    }
+}
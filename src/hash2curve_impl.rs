@@ -0,0 +1,176 @@
+//! Support for the hash-to-curve interface of the `elliptic-curve` crate.
+//!
+//! The field elements implement [`FromOkm`] and [`MapToCurve`], and the curves have
+//! inherent `hash_from_bytes`, `encode_from_bytes` and `hash_to_scalar` methods
+//! with the signatures of the provided methods of [`GroupDigest`], so protocols
+//! can use any [`ExpandMsg`] implementation from the RustCrypto ecosystem.
+//!
+//! The curves do not implement [`GroupDigest`] itself, as it requires
+//! `CurveArithmetic`, whose field encodings are big-endian `GenericArray`s rather
+//! than the little-endian `[u8; 32]` representations of this crate.
+//!
+//! [`GroupDigest`]: elliptic_curve::hash2curve::GroupDigest
+
+use elliptic_curve::generic_array::{typenum::U48, GenericArray};
+use elliptic_curve::hash2curve::{hash_to_field, ExpandMsg, FromOkm, MapToCurve};
+
+use crate::arithmetic::FieldExt;
+use crate::curves::{Ep, Eq};
+use crate::fields::{Fp, Fq};
+
+macro_rules! impl_from_okm {
+    ($field:ident, $curve:ident) => {
+        impl FromOkm for $field {
+            // L = ceil((ceil(log2(p)) + k) / 8) for a 255-bit field and k = 128.
+            type Length = U48;
+
+            fn from_okm(data: &GenericArray<u8, U48>) -> Self {
+                let mut little = [0u8; 64];
+                little[..48].copy_from_slice(data);
+                little[..48].reverse();
+                $field::from_bytes_wide(&little)
+            }
+        }
+
+        impl MapToCurve for $field {
+            type Output = $curve;
+
+            fn map_to_curve(&self) -> $curve {
+                $curve::map_to_curve(self)
+            }
+        }
+    };
+}
+
+impl_from_okm!(Fp, Ep);
+impl_from_okm!(Fq, Eq);
+
+macro_rules! impl_group_digest {
+    ($curve:ident, $base:ident, $scalar:ident) => {
+        impl $curve {
+            /// Hashes the concatenation of `msgs` to a point with the domain separation
+            /// tag `dsts`, using the message expansion `X`.
+            ///
+            /// This is `hash_to_curve` from RFC 9380, as in
+            /// [`GroupDigest::hash_from_bytes`](elliptic_curve::hash2curve::GroupDigest::hash_from_bytes).
+            pub fn hash_from_bytes<'a, X: ExpandMsg<'a>>(
+                msgs: &[&[u8]],
+                dsts: &'a [&'a [u8]],
+            ) -> elliptic_curve::Result<Self> {
+                let mut u = [$base::default(); 2];
+                hash_to_field::<X, _>(msgs, dsts, &mut u)?;
+                Ok(u[0].map_to_curve() + u[1].map_to_curve())
+            }
+
+            /// Encodes the concatenation of `msgs` as a point with the domain separation
+            /// tag `dsts`, using the message expansion `X`.
+            ///
+            /// This is `encode_to_curve` from RFC 9380, as in
+            /// [`GroupDigest::encode_from_bytes`](elliptic_curve::hash2curve::GroupDigest::encode_from_bytes).
+            /// Its output is not uniformly distributed.
+            pub fn encode_from_bytes<'a, X: ExpandMsg<'a>>(
+                msgs: &[&[u8]],
+                dsts: &'a [&'a [u8]],
+            ) -> elliptic_curve::Result<Self> {
+                let mut u = [$base::default()];
+                hash_to_field::<X, _>(msgs, dsts, &mut u)?;
+                Ok(u[0].map_to_curve())
+            }
+
+            /// Hashes the concatenation of `msgs` to a scalar with the domain separation
+            /// tag `dsts`, using the message expansion `X`, as in
+            /// [`GroupDigest::hash_to_scalar`](elliptic_curve::hash2curve::GroupDigest::hash_to_scalar).
+            pub fn hash_to_scalar<'a, X: ExpandMsg<'a>>(
+                msgs: &[&[u8]],
+                dsts: &'a [&'a [u8]],
+            ) -> elliptic_curve::Result<$scalar> {
+                let mut u = [$scalar::default()];
+                hash_to_field::<X, _>(msgs, dsts, &mut u)?;
+                Ok(u[0])
+            }
+        }
+    };
+}
+
+impl_group_digest!(Ep, Fp, Fq);
+impl_group_digest!(Eq, Fq, Fp);
+
+#[cfg(test)]
+mod tests {
+    use elliptic_curve::hash2curve::ExpandMsgXmd;
+    use sha2::Sha256;
+
+    use crate::pallas;
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn test_hash_from_bytes_matches_hash_to_curve_with() {
+        use crate::vesta;
+
+        let dst = b"QUUX-V01-CS02-with-pallas_XMD:SHA-256_SSWU_RO_";
+        for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+            assert_eq!(
+                pallas::Point::hash_from_bytes::<ExpandMsgXmd<Sha256>>(&[msg], &[dst]).unwrap(),
+                pallas::Point::hash_to_curve_with::<crate::ExpandMsgXmd<Sha256>>(dst, msg)
+            );
+        }
+
+        let dst = b"QUUX-V01-CS02-with-vesta_XMD:SHA-256_SSWU_RO_";
+        assert_eq!(
+            vesta::Point::hash_from_bytes::<ExpandMsgXmd<Sha256>>(&[b"ab", b"c"], &[dst]).unwrap(),
+            vesta::Point::hash_to_curve_with::<crate::ExpandMsgXmd<Sha256>>(dst, b"abc")
+        );
+    }
+
+    #[test]
+    fn test_encode_from_bytes_and_hash_to_scalar() {
+        use alloc::format;
+        use group::Curve;
+
+        use crate::arithmetic::CurveAffine;
+        use crate::vesta;
+
+        // Generated by scripts/hash_to_curve_vectors.py, an independent
+        // implementation of RFC 9380 in Python, for the message "abc".
+        let dst: &[u8] = b"QUUX-V01-CS02-with-pallas_XMD:SHA-256_SSWU_NU_";
+        let p = pallas::Point::encode_from_bytes::<ExpandMsgXmd<Sha256>>(&[b"abc"], &[dst])
+            .unwrap()
+            .to_affine()
+            .coordinates()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", p.x()),
+            "0x380f75ad1dfcb5b7d6b32df4862170de41f86ac4916e88854883684f6a728d82"
+        );
+        assert_eq!(
+            format!("{:?}", p.y()),
+            "0x214b0544f285f4058d5ba230ea615dcd1ed05f10396f629dc2f3026fbf05c252"
+        );
+        let s = pallas::Point::hash_to_scalar::<ExpandMsgXmd<Sha256>>(&[b"abc"], &[dst]).unwrap();
+        assert_eq!(
+            format!("{:?}", s),
+            "0x0413203ebf9667ea0e3e3428129de455ba9ff048c2c3e3a4d7472394992594dc"
+        );
+
+        // The messages are concatenated.
+        let dst: &[u8] = b"QUUX-V01-CS02-with-vesta_XMD:SHA-256_SSWU_NU_";
+        let p = vesta::Point::encode_from_bytes::<ExpandMsgXmd<Sha256>>(&[b"a", b"bc"], &[dst])
+            .unwrap()
+            .to_affine()
+            .coordinates()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", p.x()),
+            "0x274e9045bf0377ef2b86fb996534bc3718b9bce691e9aa27be0952d9617b109e"
+        );
+        assert_eq!(
+            format!("{:?}", p.y()),
+            "0x09c494e8c1fc2a363bd645ebb77778423363ebf4049dd84e0efb37f94c8067cd"
+        );
+        let s = vesta::Point::hash_to_scalar::<ExpandMsgXmd<Sha256>>(&[b"abc"], &[dst]).unwrap();
+        assert_eq!(
+            format!("{:?}", s),
+            "0x207f6df65b8d6b0af1e39a492003d39763d84641509cad2b96cb755d07c09c8e"
+        );
+    }
+}
@@ -1,77 +1,419 @@
 //! This module implements "simplified SWU" hashing to short Weierstrass curves
 //! with a = 0.
 
-use static_assertions::const_assert;
+use core::fmt;
+use core::marker::PhantomData;
+
+#[cfg(feature = "digest")]
+use digest_crate::{
+    core_api::BlockSizeUser, typenum::Unsigned, ExtendableOutput, FixedOutput, Output, Update,
+    XofReader,
+};
 use subtle::ConstantTimeEq;
 
 use crate::arithmetic::{CurveExt, FieldExt};
 
 /// Hashes over a message and writes the output to all of `buf`.
+///
+/// This is `hash_to_field` from the hash-to-curve draft with `expand_message_xmd`
+/// instantiated with BLAKE2b, producing one 64-byte chunk per field element.
+///
+/// # Panics
+///
+/// Panics if `buf` holds more than 255 elements.
 pub fn hash_to_field<F: FieldExt>(
     curve_id: &str,
     domain_prefix: &str,
     message: &[u8],
-    buf: &mut [F; 2],
+    buf: &mut [F],
 ) {
-    assert!(domain_prefix.len() < 256);
-    assert!((22 + curve_id.len() + domain_prefix.len()) < 256);
-
-    // Assume that the field size is 32 bytes and k is 256, where k is defined in
-    // <https://www.ietf.org/archive/id/draft-irtf-cfrg-hash-to-curve-10.html#name-security-considerations-3>.
-    const CHUNKLEN: usize = 64;
-    const_assert!(CHUNKLEN * 2 < 256);
-
-    // Input block size of BLAKE2b.
-    const R_IN_BYTES: usize = 128;
-
-    let personal = [0u8; 16];
-    let empty_hasher = blake2b_simd::Params::new()
-        .hash_length(CHUNKLEN)
-        .personal(&personal)
-        .to_state();
-
-    let b_0 = empty_hasher
-        .clone()
-        .update(&[0; R_IN_BYTES])
-        .update(message)
-        .update(&[0, (CHUNKLEN * 2) as u8, 0])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_SSWU_RO_")
-        .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
-        .finalize();
-
-    let b_1 = empty_hasher
-        .clone()
-        .update(b_0.as_array())
-        .update(&[1])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_SSWU_RO_")
-        .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
-        .finalize();
-
-    let b_2 = {
-        let mut empty_hasher = empty_hasher;
-        for (l, r) in b_0.as_array().iter().zip(b_1.as_array().iter()) {
-            empty_hasher.update(&[*l ^ *r]);
+    let mut hasher = MessageHasher::new(curve_id, domain_prefix);
+    hasher.update(message);
+    hasher.finalize(buf);
+}
+
+// Assume that the field size is 32 bytes and k is 256, where k is defined in
+// <https://www.ietf.org/archive/id/draft-irtf-cfrg-hash-to-curve-10.html#name-security-considerations-3>.
+const CHUNKLEN: usize = 64;
+
+/// The state of [`hash_to_field`] while the message is being absorbed.
+///
+/// The message only enters `expand_message_xmd` through the first hash `b_0`,
+/// right after a block of zeros, so it can be absorbed in pieces.
+#[derive(Clone)]
+pub(crate) struct MessageHasher<'a> {
+    curve_id: &'a str,
+    domain_prefix: &'a str,
+    empty_hasher: blake2b_simd::State,
+    b_0: blake2b_simd::State,
+}
+
+impl<'a> MessageHasher<'a> {
+    pub(crate) fn new(curve_id: &'a str, domain_prefix: &'a str) -> Self {
+        assert!(domain_prefix.len() < 256);
+        assert!((22 + curve_id.len() + domain_prefix.len()) < 256);
+
+        // Input block size of BLAKE2b.
+        const R_IN_BYTES: usize = 128;
+
+        let personal = [0u8; 16];
+        let empty_hasher = blake2b_simd::Params::new()
+            .hash_length(CHUNKLEN)
+            .personal(&personal)
+            .to_state();
+
+        let mut b_0 = empty_hasher.clone();
+        b_0.update(&[0; R_IN_BYTES]);
+
+        MessageHasher {
+            curve_id,
+            domain_prefix,
+            empty_hasher,
+            b_0,
         }
-        empty_hasher
-            .update(&[2])
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        self.b_0.update(data);
+    }
+
+    pub(crate) fn finalize<F: FieldExt>(self, buf: &mut [F]) {
+        let MessageHasher {
+            curve_id,
+            domain_prefix,
+            empty_hasher,
+            mut b_0,
+        } = self;
+
+        // expand_message_xmd requires ell = len_in_bytes / CHUNKLEN <= 255.
+        assert!(buf.len() <= 255);
+        let len_in_bytes = CHUNKLEN * buf.len();
+
+        let b_0 = b_0
+            .update(&[(len_in_bytes >> 8) as u8, len_in_bytes as u8, 0])
+            .update(domain_prefix.as_bytes())
+            .update(b"-")
+            .update(curve_id.as_bytes())
+            .update(b"_XMD:BLAKE2b_SSWU_RO_")
+            .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
+            .finalize();
+
+        let mut b_i = empty_hasher
+            .clone()
+            .update(b_0.as_array())
+            .update(&[1])
             .update(domain_prefix.as_bytes())
             .update(b"-")
             .update(curve_id.as_bytes())
             .update(b"_XMD:BLAKE2b_SSWU_RO_")
             .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
-            .finalize()
-    };
+            .finalize();
+
+        for (i, buf) in buf.iter_mut().enumerate() {
+            if i > 0 {
+                let mut hasher = empty_hasher.clone();
+                for (l, r) in b_0.as_array().iter().zip(b_i.as_array().iter()) {
+                    hasher.update(&[*l ^ *r]);
+                }
+                b_i = hasher
+                    .update(&[(i + 1) as u8])
+                    .update(domain_prefix.as_bytes())
+                    .update(b"-")
+                    .update(curve_id.as_bytes())
+                    .update(b"_XMD:BLAKE2b_SSWU_RO_")
+                    .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
+                    .finalize();
+            }
+
+            let mut little = [0u8; CHUNKLEN];
+            little.copy_from_slice(b_i.as_array());
+            little.reverse();
+            *buf = F::from_bytes_wide(&little);
+        }
+    }
+}
+
+/// An incremental form of [`CurveExt::hash_to_curve`], which absorbs the message
+/// in pieces.
+///
+/// This avoids concatenating a message that is read in chunks, for example from a
+/// file, before hashing it. Feeding the same bytes to [`CurveHasher::update`], in
+/// any number of calls, gives the same point as `C::hash_to_curve(domain_prefix)`
+/// applied to the whole message:
+///
+/// ```
+/// # extern crate fil_pasta_curves as pasta_curves;
+/// use pasta_curves::arithmetic::CurveExt;
+/// use pasta_curves::{pallas, CurveHasher};
+///
+/// let mut hasher = CurveHasher::<pallas::Point>::new("z.cash:test");
+/// hasher.update(b"Trans ").update(b"rights now!");
+/// assert_eq!(
+///     hasher.finalize(),
+///     pallas::Point::hash_to_curve("z.cash:test")(b"Trans rights now!"),
+/// );
+/// ```
+///
+/// With the `std` feature, it also implements [`std::io::Write`], so a reader can
+/// be hashed with [`std::io::copy`].
+#[derive(Clone)]
+pub struct CurveHasher<'a, C: CurveExt> {
+    pub(crate) inner: MessageHasher<'a>,
+    _marker: PhantomData<C>,
+}
+
+impl<'a, C: CurveExt> fmt::Debug for CurveHasher<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CurveHasher")
+            .field("curve_id", &self.inner.curve_id)
+            .field("domain_prefix", &self.inner.domain_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, C: CurveExt> CurveHasher<'a, C> {
+    /// Starts hashing a message to a point of `C`, with the same domain separation
+    /// as `C::hash_to_curve(domain_prefix)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `domain_prefix` is too long for the domain separation tag to fit
+    /// in 255 bytes.
+    pub fn new(domain_prefix: &'a str) -> Self {
+        CurveHasher {
+            inner: MessageHasher::new(C::CURVE_ID, domain_prefix),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Appends `data` to the message.
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.inner.update(data);
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, C: CurveExt> std::io::Write for CurveHasher<'a, C> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The prefix of domain separation tags longer than 255 bytes, which are hashed
+/// before use as described in [RFC 9380, section 5.3.3].
+///
+/// [RFC 9380, section 5.3.3]: https://www.rfc-editor.org/rfc/rfc9380.html#section-5.3.3
+#[cfg(feature = "digest")]
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// A variant of `expand_message` from [RFC 9380], which expands a message and a
+/// domain separation tag into a uniformly random byte string.
+///
+/// This is implemented by [`ExpandMsgXmd`] for hash functions with a fixed output
+/// size, and by [`ExpandMsgXof`] for extendable-output functions. Domain
+/// separation tags longer than 255 bytes are hashed first, as the RFC requires.
+///
+/// [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html#name-expand_message
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub trait ExpandMessage: Sized {
+    /// Starts expanding `message` to `len_in_bytes` bytes, using the domain
+    /// separation tag `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len_in_bytes` is too large for this variant.
+    fn init(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Self;
+
+    /// Writes the next `out.len()` bytes of the expanded message to `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this would read past the `len_in_bytes` bytes passed to
+    /// [`ExpandMessage::init`].
+    fn fill_bytes(&mut self, out: &mut [u8]);
+}
+
+/// `expand_message_xmd` instantiated with the hash function `H`, such as SHA-256.
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub struct ExpandMsgXmd<H: FixedOutput> {
+    b_0: Output<H>,
+    b_i: Output<H>,
+    // DST_prime, the domain separation tag followed by its length.
+    dst_prime: [u8; 256],
+    dst_prime_len: usize,
+    index: u8,
+    offset: usize,
+    remaining: usize,
+}
+
+#[cfg(feature = "digest")]
+impl<H: FixedOutput> fmt::Debug for ExpandMsgXmd<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpandMsgXmd")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<H: BlockSizeUser + Default + FixedOutput + Update> ExpandMessage for ExpandMsgXmd<H> {
+    fn init(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Self {
+        let b_in_bytes = H::OutputSize::USIZE;
+        let ell = (len_in_bytes + b_in_bytes - 1) / b_in_bytes;
+        assert!(ell <= 255 && len_in_bytes <= 0xffff);
+
+        let mut dst_prime = [0; 256];
+        let dst_len = if dst.len() > 255 {
+            let dst = H::default()
+                .chain(OVERSIZE_DST_PREFIX)
+                .chain(dst)
+                .finalize_fixed();
+            dst_prime[..dst.len()].copy_from_slice(&dst);
+            dst.len()
+        } else {
+            dst_prime[..dst.len()].copy_from_slice(dst);
+            dst.len()
+        };
+        dst_prime[dst_len] = dst_len as u8;
+        let dst_prime_len = dst_len + 1;
+
+        let mut hasher = H::default();
+        for _ in 0..H::BlockSize::USIZE {
+            hasher.update(&[0]);
+        }
+        let b_0 = hasher
+            .chain(message)
+            .chain([(len_in_bytes >> 8) as u8, len_in_bytes as u8, 0])
+            .chain(&dst_prime[..dst_prime_len])
+            .finalize_fixed();
 
-    for (big, buf) in [b_1, b_2].iter().zip(buf.iter_mut()) {
-        let mut little = [0u8; CHUNKLEN];
-        little.copy_from_slice(big.as_array());
-        little.reverse();
+        let b_i = H::default()
+            .chain(&b_0)
+            .chain([1])
+            .chain(&dst_prime[..dst_prime_len])
+            .finalize_fixed();
+
+        ExpandMsgXmd {
+            b_0,
+            b_i,
+            dst_prime,
+            dst_prime_len,
+            index: 1,
+            offset: 0,
+            remaining: len_in_bytes,
+        }
+    }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        assert!(out.len() <= self.remaining);
+        self.remaining -= out.len();
+
+        for byte in out.iter_mut() {
+            if self.offset == self.b_i.len() {
+                // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+                let mut hasher = H::default();
+                for (l, r) in self.b_0.iter().zip(self.b_i.iter()) {
+                    hasher.update(&[*l ^ *r]);
+                }
+                self.index += 1;
+                self.b_i = hasher
+                    .chain([self.index])
+                    .chain(&self.dst_prime[..self.dst_prime_len])
+                    .finalize_fixed();
+                self.offset = 0;
+            }
+            *byte = self.b_i[self.offset];
+            self.offset += 1;
+        }
+    }
+}
+
+/// `expand_message_xof` instantiated with the extendable-output function `H`,
+/// such as SHAKE128.
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub struct ExpandMsgXof<H: ExtendableOutput> {
+    reader: H::Reader,
+    remaining: usize,
+}
+
+#[cfg(feature = "digest")]
+impl<H: ExtendableOutput> fmt::Debug for ExpandMsgXof<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpandMsgXof")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<H: Default + ExtendableOutput + Update> ExpandMessage for ExpandMsgXof<H> {
+    fn init(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Self {
+        assert!(len_in_bytes <= 0xffff);
+
+        // ceil(2 * k / 8) bytes for k = 128.
+        let mut hashed_dst = [0; 32];
+        let dst = if dst.len() > 255 {
+            H::default()
+                .chain(OVERSIZE_DST_PREFIX)
+                .chain(dst)
+                .finalize_xof()
+                .read(&mut hashed_dst);
+            &hashed_dst[..]
+        } else {
+            dst
+        };
+
+        let reader = H::default()
+            .chain(message)
+            .chain([(len_in_bytes >> 8) as u8, len_in_bytes as u8])
+            .chain(dst)
+            .chain([dst.len() as u8])
+            .finalize_xof();
+
+        ExpandMsgXof {
+            reader,
+            remaining: len_in_bytes,
+        }
+    }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        assert!(out.len() <= self.remaining);
+        self.remaining -= out.len();
+        self.reader.read(out);
+    }
+}
+
+/// Hashes over a message with the domain separation tag `dst` and writes the
+/// output to all of `buf`.
+///
+/// This is `hash_to_field` from RFC 9380 with the message expansion `X`. Unlike
+/// [`hash_to_field`], which produces 64 bytes per field element for compatibility
+/// with Zcash, this uses the RFC's $L = 48$ bytes for a security level of
+/// $k = 128$ bits.
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub fn hash_to_field_with<F: FieldExt, X: ExpandMessage>(
+    dst: &[u8],
+    message: &[u8],
+    buf: &mut [F],
+) {
+    // L = ceil((ceil(log2(p)) + k) / 8) for a 255-bit field and k = 128.
+    const L: usize = 48;
+
+    let mut expander = X::init(message, dst, L * buf.len());
+    for buf in buf.iter_mut() {
+        let mut little = [0u8; 64];
+        expander.fill_bytes(&mut little[..L]);
+        little[..L].reverse();
         *buf = F::from_bytes_wide(&little);
     }
 }
@@ -136,7 +478,7 @@ pub fn map_to_curve_simple_swu<F: FieldExt, C: CurveExt<Base = F>, I: CurveExt<B
     let b = I::b();
     let z_u2 = z * u.square();
     let ta = z_u2.square() + z_u2;
-    let num_x1 = b * (ta + F::one());
+    let num_x1 = b * (ta + F::ONE);
     let div = a * F::conditional_select(&-ta, &z, ta.is_zero());
     let num2_x1 = num_x1.square();
     let div2 = div.square();
@@ -175,3 +517,171 @@ pub fn map_to_curve_simple_swu<F: FieldExt, C: CurveExt<Base = F>, I: CurveExt<B
 
     I::new_jacobian(num_x * div, y * div3, div).unwrap()
 }
+
+#[cfg(all(test, feature = "digest"))]
+pub(crate) mod tests {
+    use sha2::Sha256;
+    use sha3::Shake128;
+    use std::string::String;
+
+    use super::{hash_to_field_with, ExpandMessage, ExpandMsgXmd, ExpandMsgXof};
+    use crate::{Fp, Fq};
+
+    /// Helpers for checking the SHA-256 suites against test vectors.
+    #[cfg(feature = "sha256")]
+    pub(crate) mod suite {
+        use ff::Field;
+        use sha2::Sha256;
+        use std::string::String;
+
+        use crate::arithmetic::{CurveAffine, CurveExt};
+        use crate::hashtocurve::{hash_to_field_with, ExpandMsgXmd};
+
+        /// A hash-to-curve test vector, with the fields of RFC 9380, appendix J.
+        pub(crate) struct TestVector {
+            pub(crate) p: (&'static str, &'static str),
+            pub(crate) u: [&'static str; 2],
+            pub(crate) q0: (&'static str, &'static str),
+            pub(crate) q1: (&'static str, &'static str),
+        }
+
+        /// The messages of the RFC 9380 test vectors, in order.
+        fn messages() -> [std::vec::Vec<u8>; 5] {
+            [
+                b"".to_vec(),
+                b"abc".to_vec(),
+                b"abcdef0123456789".to_vec(),
+                [&b"q128_"[..], &[b'q'; 128]].concat(),
+                [&b"a512_"[..], &[b'a'; 512]].concat(),
+            ]
+        }
+
+        fn coordinates<C: CurveExt>(p: &C) -> (String, String) {
+            let p = p.to_affine().coordinates().unwrap();
+            (format!("{:?}", p.x()), format!("{:?}", p.y()))
+        }
+
+        fn pair(p: &(&str, &str)) -> (String, String) {
+            (p.0.into(), p.1.into())
+        }
+
+        /// Checks a SHA-256 suite against `vectors`, where `map_to_curve` maps a field
+        /// element to the curve.
+        pub(crate) fn check_suite<C: CurveExt>(
+            dst: &[u8],
+            hash_to_curve: fn(&[u8], &[u8]) -> C,
+            map_to_curve: impl Fn(&C::Base) -> C,
+            vectors: &[TestVector],
+        ) {
+            assert_eq!(vectors.len(), 5);
+            for (msg, vector) in messages().iter().zip(vectors.iter()) {
+                let mut u = [C::Base::ZERO; 2];
+                hash_to_field_with::<_, ExpandMsgXmd<Sha256>>(dst, msg, &mut u);
+                assert_eq!(format!("{:?}", u[0]), vector.u[0]);
+                assert_eq!(format!("{:?}", u[1]), vector.u[1]);
+                assert_eq!(coordinates(&map_to_curve(&u[0])), pair(&vector.q0));
+                assert_eq!(coordinates(&map_to_curve(&u[1])), pair(&vector.q1));
+                assert_eq!(coordinates(&hash_to_curve(dst, msg)), pair(&vector.p));
+            }
+        }
+    }
+
+    fn expand<X: ExpandMessage>(message: &[u8], dst: &[u8], len_in_bytes: usize) -> String {
+        let mut expander = X::init(message, dst, len_in_bytes);
+        let mut out = vec![0; len_in_bytes];
+        // Reading in uneven pieces gives the same output as a single read.
+        let (first, rest) = out.split_at_mut(len_in_bytes / 3);
+        expander.fill_bytes(first);
+        expander.fill_bytes(rest);
+        out.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // Test vectors from RFC 9380, appendix K.1.
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            expand::<ExpandMsgXmd<Sha256>>(b"", dst, 0x20),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            expand::<ExpandMsgXmd<Sha256>>(b"abc", dst, 0x80),
+            "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a\
+             647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635\
+             bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00\
+             058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"
+        );
+
+        let long_dst = [
+            &b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-"[..],
+            &[b'1'; 208],
+        ]
+        .concat();
+        assert_eq!(
+            expand::<ExpandMsgXmd<Sha256>>(b"", &long_dst, 0x20),
+            "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3"
+        );
+    }
+
+    #[test]
+    fn test_expand_message_xof() {
+        // Test vectors from RFC 9380, appendix K.3.
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        assert_eq!(
+            expand::<ExpandMsgXof<Shake128>>(b"", dst, 0x20),
+            "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2"
+        );
+        assert_eq!(
+            expand::<ExpandMsgXof<Shake128>>(b"abc", dst, 0x80),
+            "c952f0c8e529ca8824acc6a4cab0e782fc3648c563ddb00da7399f2ae35654f4\
+             860ec671db2356ba7baa55a34a9d7f79197b60ddae6e64768a37d699a7832349\
+             6db3878c8d64d909d0f8a7de4927dcab0d3dbbc26cb20a49eceb0530b431cdf4\
+             7bc8c0fa3e0d88f53b318b6739fbed7d7634974f1b5c386d6230c76260d5337a"
+        );
+
+        let long_dst = [
+            &b"QUUX-V01-CS02-with-expander-SHAKE128-long-DST-"[..],
+            &[b'1'; 210],
+        ]
+        .concat();
+        assert_eq!(
+            expand::<ExpandMsgXof<Shake128>>(b"", &long_dst, 0x20),
+            "827c6216330a122352312bccc0c8d6e7a146c5257a776dbd9ad9d75cd880fc53"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_expand_message_overread() {
+        let mut expander = ExpandMsgXmd::<Sha256>::init(b"abc", b"test", 32);
+        expander.fill_bytes(&mut [0; 33]);
+    }
+
+    #[test]
+    fn test_hash_to_field_with() {
+        let mut us = [Fp::zero(); 2];
+        hash_to_field_with::<_, ExpandMsgXmd<Sha256>>(
+            b"pallas_XMD:SHA-256_SSWU_RO_",
+            b"abc",
+            &mut us,
+        );
+        assert_eq!(
+            format!("{:?}", us),
+            "[0x2be5293b901707877b49245e677a20a1d854019cc5e854f0df903fb5678b4914, \
+             0x0fd30b6004ae59327702bdb135e96b253ad113a1872cf824966194a1fbffa446]"
+        );
+
+        let mut us = [Fq::zero(); 3];
+        hash_to_field_with::<_, ExpandMsgXof<Shake128>>(
+            b"vesta_XOF:SHAKE128_SSWU_RO_",
+            b"abc",
+            &mut us,
+        );
+        assert_eq!(
+            format!("{:?}", us),
+            "[0x23f20645c951227aa93201b18551b1065cffbf6e8b90a1e7fd1347107efde557, \
+             0x0012b09aaac673028105d31c2c06e3400281da3c39af1750f1dcf40a5ca564a1, \
+             0x28bd0334d48b6d5ddaf09e0434931f508ccc8872136d5f14bc63722b7f4649fa]"
+        );
+    }
+}
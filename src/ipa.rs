@@ -0,0 +1,361 @@
+//! A polynomial commitment scheme based on the inner product argument of
+//! [BCCGP16] and [BBBPWM18], as used by Halo for recursion over the Pasta cycle.
+//!
+//! A polynomial $a(X) = \sum_i a_i X^i$ of degree less than $n = 2^k$ is committed
+//! to as the Pedersen vector commitment $P = \langle \mathbf{a}, \mathbf{G} \rangle$.
+//! An opening proves that $a(x) = v$, which is the inner product of $\mathbf{a}$ and
+//! $\mathbf{b} = (1, x, \ldots, x^{n - 1})$, with $k$ rounds that each halve both
+//! vectors. Proofs contain $2k$ points and one scalar.
+//!
+//! This is a minimal reference implementation: commitments are not hiding,
+//! openings are not zero knowledge, and verification takes time linear in $n$.
+//! The Fiat–Shamir challenges are derived with BLAKE2b.
+//!
+//! [BCCGP16]: https://eprint.iacr.org/2016/263
+//! [BBBPWM18]: https://eprint.iacr.org/2017/1066
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use ff::{Field, PrimeField};
+use group::{Curve, Group as _};
+
+use crate::arithmetic::{CurveAffine, CurveExt, FieldExt};
+use crate::msm::multi_scalar_mul;
+
+/// The domain prefix used to derive the generators with `hash_to_curve`.
+const GENERATORS_DOMAIN: &str = "fil_pasta_curves:ipa";
+
+/// The BLAKE2b personalization of the Fiat–Shamir transcript.
+const TRANSCRIPT_PERSONALIZATION: &[u8; 16] = b"fil_pasta_IPA_FS";
+
+/// A Fiat–Shamir transcript, which absorbs the prover's messages and squeezes
+/// challenges from a running BLAKE2b state.
+struct Transcript {
+    state: blake2b_simd::State,
+}
+
+impl Transcript {
+    fn new() -> Self {
+        Transcript {
+            state: blake2b_simd::Params::new()
+                .hash_length(64)
+                .personal(TRANSCRIPT_PERSONALIZATION)
+                .to_state(),
+        }
+    }
+
+    fn absorb_point<C: CurveAffine>(&mut self, point: &C) {
+        self.state.update(point.to_bytes().as_ref());
+    }
+
+    fn absorb_scalar<F: PrimeField>(&mut self, scalar: &F) {
+        self.state.update(scalar.to_repr().as_ref());
+    }
+
+    fn squeeze_challenge<F: FieldExt>(&mut self) -> F {
+        let hash = self.state.finalize();
+        // Later challenges depend on this one.
+        self.state.update(hash.as_bytes());
+        F::from_bytes_wide(hash.as_array())
+    }
+}
+
+/// Computes $\langle \mathbf{a}, \mathbf{b} \rangle$.
+fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .fold(F::ZERO, |acc, (a, b)| acc + *a * b)
+}
+
+/// Computes $(u^{-1}, u^{-2}, u^2)$ for the challenge $u$ of a round.
+fn challenge_powers<F: Field>(u: F) -> Option<(F, F, F)> {
+    Option::from(u.invert()).map(|u_inv: F| (u_inv, u_inv.square(), u.square()))
+}
+
+/// Public parameters for committing to polynomials of degree less than $2^k$ and
+/// opening them.
+#[derive(Clone, Debug)]
+pub struct Params<C: CurveAffine> {
+    k: u32,
+    g: Vec<C>,
+    u: C,
+}
+
+impl<C: CurveAffine> Params<C> {
+    /// Derives the parameters for polynomials of degree less than $2^k$, hashing to
+    /// the curve to obtain generators with no known discrete logarithm relation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if $k$ exceeds the 2-adicity of the scalar field, as no evaluation
+    /// domain over it has $2^k$ points.
+    pub fn new(k: u32) -> Self {
+        assert!(
+            k <= C::ScalarExt::S,
+            "the scalar field has no evaluation domain of size 2^{}",
+            k
+        );
+
+        let n = 1usize << k;
+        let hasher = C::CurveExt::hash_to_curve(GENERATORS_DOMAIN);
+
+        let g_projective: Vec<_> = (0..n as u64).map(|i| hasher(&i.to_le_bytes())).collect();
+        let mut g = vec![C::identity(); n];
+        C::CurveExt::batch_normalize(&g_projective, &mut g);
+        let u = hasher(b"U").to_affine();
+
+        Params { k, g, u }
+    }
+
+    /// Returns $k$, the base-2 logarithm of the maximum number of coefficients.
+    pub fn k(&self) -> u32 {
+        self.k
+    }
+
+    /// Commits to the polynomial with the coefficients `poly`, in order of
+    /// increasing degree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `poly` has more than $2^k$ coefficients.
+    pub fn commit(&self, poly: &[C::ScalarExt]) -> C {
+        assert!(poly.len() <= self.g.len());
+        multi_scalar_mul(&self.g[..poly.len()], poly).to_affine()
+    }
+
+    /// Computes a proof that the polynomial with the coefficients `poly` evaluates
+    /// to `poly(x)` at `x`.
+    ///
+    /// This is not constant time, and the proof reveals information about `poly`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `poly` has more than $2^k$ coefficients.
+    #[allow(clippy::many_single_char_names)]
+    pub fn open(&self, poly: &[C::ScalarExt], x: C::ScalarExt) -> Proof<C> {
+        assert!(poly.len() <= self.g.len());
+
+        let mut a = poly.to_vec();
+        a.resize(self.g.len(), C::ScalarExt::ZERO);
+        let mut b: Vec<_> = core::iter::successors(Some(C::ScalarExt::ONE), |acc| Some(*acc * x))
+            .take(self.g.len())
+            .collect();
+        let mut g = self.g.clone();
+
+        let mut transcript = Transcript::new();
+        let u = self.bind_statement(
+            &mut transcript,
+            &self.commit(poly),
+            &x,
+            &inner_product(&a, &b),
+        );
+
+        let mut rounds = Vec::with_capacity(self.k as usize);
+        let mut g_projective = vec![C::CurveExt::identity(); g.len() / 2];
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+
+            let l = (multi_scalar_mul(g_hi, a_lo) + u * inner_product(a_lo, b_hi)).to_affine();
+            let r = (multi_scalar_mul(g_lo, a_hi) + u * inner_product(a_hi, b_lo)).to_affine();
+            transcript.absorb_point(&l);
+            transcript.absorb_point(&r);
+            rounds.push((l, r));
+
+            let challenge: C::ScalarExt = transcript.squeeze_challenge();
+            // A zero challenge occurs with negligible probability.
+            let (challenge_inv, _, _) = challenge_powers(challenge).unwrap();
+
+            // a' = a_lo * u + a_hi * u^{-1}
+            // b' = b_lo * u^{-1} + b_hi * u
+            // G' = G_lo * u^{-1} + G_hi * u
+            a = a_lo
+                .iter()
+                .zip(a_hi.iter())
+                .map(|(lo, hi)| *lo * challenge + *hi * challenge_inv)
+                .collect();
+            b = b_lo
+                .iter()
+                .zip(b_hi.iter())
+                .map(|(lo, hi)| *lo * challenge_inv + *hi * challenge)
+                .collect();
+            g_projective.truncate(half);
+            for (acc, (lo, hi)) in g_projective.iter_mut().zip(g_lo.iter().zip(g_hi.iter())) {
+                *acc = *lo * challenge_inv + *hi * challenge;
+            }
+            g.truncate(half);
+            C::CurveExt::batch_normalize(&g_projective, &mut g);
+        }
+
+        Proof { rounds, a: a[0] }
+    }
+
+    /// Verifies a `proof` that the polynomial committed to by `commitment`
+    /// evaluates to `v` at `x`.
+    #[allow(clippy::many_single_char_names)]
+    pub fn verify(
+        &self,
+        commitment: &C,
+        x: C::ScalarExt,
+        v: C::ScalarExt,
+        proof: &Proof<C>,
+    ) -> bool {
+        if proof.rounds.len() != self.k as usize {
+            return false;
+        }
+
+        let mut transcript = Transcript::new();
+        let u = self.bind_statement(&mut transcript, commitment, &x, &v);
+
+        // P' = P + [v] U + sum_j ([u_j^2] L_j + [u_j^{-2}] R_j)
+        let mut p = commitment.to_curve() + u * v;
+        let mut challenges = Vec::with_capacity(proof.rounds.len());
+        for (l, r) in proof.rounds.iter() {
+            transcript.absorb_point(l);
+            transcript.absorb_point(r);
+            let challenge: C::ScalarExt = transcript.squeeze_challenge();
+            let (challenge_inv, challenge_inv_sq, challenge_sq) = match challenge_powers(challenge)
+            {
+                Some(powers) => powers,
+                None => return false,
+            };
+            p += *l * challenge_sq + *r * challenge_inv_sq;
+            challenges.push((challenge, challenge_inv));
+        }
+
+        // The folded generator is G_final = sum_i [s_i] G_i, where s_i is the product
+        // of u_j or u_j^{-1} depending on whether bit k - 1 - j of i is set. We
+        // build s from the last round to the first. The folded b is
+        // b_final = prod_j (u_j^{-1} + u_j x^{2^{k - 1 - j}}).
+        let mut s = vec![C::ScalarExt::ONE];
+        let mut b = C::ScalarExt::ONE;
+        let mut x_power = x;
+        for (challenge, challenge_inv) in challenges.iter().rev() {
+            s = s
+                .iter()
+                .map(|s| *s * challenge_inv)
+                .chain(s.iter().map(|s| *s * challenge))
+                .collect();
+            b *= *challenge_inv + *challenge * x_power;
+            x_power = x_power.square();
+        }
+
+        let scalars: Vec<_> = s.iter().map(|s| *s * proof.a).collect();
+        let expected = multi_scalar_mul(&self.g, &scalars) + u * (proof.a * b);
+        p == expected
+    }
+
+    /// Absorbs the statement into `transcript` and returns the generator $[\xi] U$
+    /// for the challenge $\xi$, which binds the proof to the claimed evaluation.
+    fn bind_statement(
+        &self,
+        transcript: &mut Transcript,
+        commitment: &C,
+        x: &C::ScalarExt,
+        v: &C::ScalarExt,
+    ) -> C::CurveExt {
+        transcript.absorb_point(commitment);
+        transcript.absorb_scalar(x);
+        transcript.absorb_scalar(v);
+        let xi: C::ScalarExt = transcript.squeeze_challenge();
+        self.u * xi
+    }
+}
+
+/// A proof that a committed polynomial evaluates to a claimed value, created by
+/// [`Params::open`] and checked by [`Params::verify`].
+#[derive(Clone, Debug)]
+pub struct Proof<C: CurveAffine> {
+    rounds: Vec<(C, C)>,
+    a: C::ScalarExt,
+}
+
+impl<C: CurveAffine> Proof<C> {
+    /// Returns the points $(L_j, R_j)$ sent in each round.
+    pub fn rounds(&self) -> &[(C, C)] {
+        &self.rounds
+    }
+
+    /// Returns the final folded coefficient.
+    pub fn a(&self) -> C::ScalarExt {
+        self.a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ff::Field;
+    use group::Curve;
+
+    use super::{Params, Proof};
+    use crate::arithmetic::CurveAffine;
+    use crate::{pallas, vesta};
+
+    fn eval<F: Field>(poly: &[F], x: F) -> F {
+        poly.iter().rev().fold(F::ZERO, |acc, c| acc * x + c)
+    }
+
+    fn check_ipa<C: CurveAffine>() {
+        let mut rng = crate::tests::rng();
+
+        let params = Params::<C>::new(3);
+        for len in [8, 5, 1, 0] {
+            let poly: Vec<_> = (0..len).map(|_| C::ScalarExt::random(&mut rng)).collect();
+            let x = C::ScalarExt::random(&mut rng);
+            let v = eval(&poly, x);
+            let commitment = params.commit(&poly);
+
+            let proof = params.open(&poly, x);
+            assert_eq!(proof.rounds().len(), 3);
+            assert!(params.verify(&commitment, x, v, &proof));
+
+            // Wrong claims are rejected.
+            assert!(!params.verify(&commitment, x, v + C::ScalarExt::ONE, &proof));
+            if len > 1 {
+                // Constant polynomials have the same value at every point.
+                assert!(!params.verify(&commitment, x + C::ScalarExt::ONE, v, &proof));
+            }
+            let other = (commitment.to_curve() + C::generator()).to_affine();
+            assert!(!params.verify(&other, x, v, &proof));
+
+            // Tampered proofs are rejected.
+            let mut tampered = proof.clone();
+            tampered.a += C::ScalarExt::ONE;
+            assert!(!params.verify(&commitment, x, v, &tampered));
+            let mut tampered = proof.clone();
+            tampered.rounds[1].0 = C::generator();
+            assert!(!params.verify(&commitment, x, v, &tampered));
+            let truncated = Proof {
+                rounds: proof.rounds[1..].to_vec(),
+                a: proof.a,
+            };
+            assert!(!params.verify(&commitment, x, v, &truncated));
+        }
+
+        // Commitments are additively homomorphic.
+        let p: Vec<_> = (0..8).map(|_| C::ScalarExt::random(&mut rng)).collect();
+        let q: Vec<_> = (0..8).map(|_| C::ScalarExt::random(&mut rng)).collect();
+        let sum: Vec<_> = p.iter().zip(q.iter()).map(|(p, q)| *p + q).collect();
+        assert_eq!(
+            params.commit(&sum),
+            (params.commit(&p).to_curve() + params.commit(&q)).to_affine()
+        );
+    }
+
+    #[test]
+    fn test_ipa() {
+        check_ipa::<pallas::Affine>();
+        check_ipa::<vesta::Affine>();
+    }
+
+    #[test]
+    #[should_panic(expected = "no evaluation domain of size 2^33")]
+    fn test_params_too_large() {
+        Params::<pallas::Affine>::new(33);
+    }
+}
@@ -0,0 +1,254 @@
+//! Secret and public key types for the Pallas curve.
+//!
+//! A [`SecretKey`] wraps a nonzero scalar $x \in \mathbb{F}_q$, and the matching
+//! [`PublicKey`] wraps the point $[x] G$, where $G$ is the Pallas generator. Unlike
+//! raw field elements, secret keys do not implement `Copy` or print their value
+//! with `Debug`, and with the `zeroize` feature flag they are zeroized when
+//! dropped.
+
+use core::fmt;
+
+use ff::{Field, PrimeField};
+use group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::pallas;
+
+/// A secret key, which is a nonzero Pallas scalar.
+#[derive(Clone)]
+pub struct SecretKey(pallas::Scalar);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretKey").finish_non_exhaustive()
+    }
+}
+
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+/// Secret keys are compared in constant time.
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+    }
+}
+
+impl Eq for SecretKey {}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
+impl SecretKey {
+    /// Generates a random secret key.
+    pub fn random(mut rng: impl RngCore) -> Self {
+        loop {
+            let x = pallas::Scalar::random(&mut rng);
+            if !bool::from(x.is_zero()) {
+                return SecretKey(x);
+            }
+        }
+    }
+
+    /// Generates a random secret key with the operating system's random number
+    /// generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator fails.
+    #[cfg(feature = "os-rng")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "os-rng")))]
+    pub fn generate() -> Self {
+        Self::random(rand::rngs::OsRng)
+    }
+
+    /// Constructs a secret key from a scalar, failing if it is zero.
+    pub fn from_scalar(x: pallas::Scalar) -> CtOption<Self> {
+        let is_nonzero = !x.is_zero();
+        CtOption::new(SecretKey(x), is_nonzero)
+    }
+
+    /// Parses a secret key from the little-endian encoding of its scalar, failing
+    /// if it is not canonical or is zero.
+    pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+        pallas::Scalar::from_repr(*bytes).and_then(Self::from_scalar)
+    }
+
+    /// Returns the little-endian encoding of the scalar of this secret key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_repr()
+    }
+
+    /// Returns the scalar of this secret key.
+    pub fn as_scalar(&self) -> &pallas::Scalar {
+        &self.0
+    }
+
+    /// Returns the public key for this secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from(self)
+    }
+}
+
+/// A public key, which is a Pallas point other than the identity.
+///
+/// Every constructor checks that the point is on the curve, so the signature
+/// schemes and key exchange can rely on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey(pallas::Affine);
+
+impl ConstantTimeEq for PublicKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for PublicKey {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        PublicKey(pallas::Affine::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl<'a> From<&'a SecretKey> for PublicKey {
+    fn from(sk: &'a SecretKey) -> PublicKey {
+        PublicKey((pallas::Point::generator() * sk.0).to_affine())
+    }
+}
+
+impl From<PublicKey> for pallas::Affine {
+    fn from(pk: PublicKey) -> pallas::Affine {
+        pk.0
+    }
+}
+
+impl PublicKey {
+    /// Constructs a public key from a point, failing if it is the identity or is
+    /// not on the curve. Points built with unchecked APIs can be off the curve.
+    pub fn from_point(p: pallas::Affine) -> CtOption<Self> {
+        let is_valid = p.ct_is_on_curve() & !p.is_identity();
+        CtOption::new(PublicKey(p), is_valid)
+    }
+
+    /// Parses a public key from the compressed encoding of its point, failing if
+    /// it is not a valid point encoding or encodes the identity.
+    pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+        pallas::Affine::from_bytes(bytes).and_then(Self::from_point)
+    }
+
+    /// Returns the compressed encoding of the point of this public key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Returns the point of this public key.
+    pub fn as_point(&self) -> &pallas::Affine {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding, UncompressedEncoding};
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    use super::{PublicKey, SecretKey};
+    use crate::pallas;
+
+    #[test]
+    fn test_keys() {
+        let mut rng = crate::tests::rng();
+
+        let sk = SecretKey::random(&mut rng);
+        let pk = PublicKey::from(&sk);
+        assert_eq!(sk.public_key(), pk);
+        assert_eq!(
+            *pk.as_point(),
+            (pallas::Point::generator() * sk.as_scalar()).to_affine()
+        );
+        assert_eq!(pallas::Affine::from(pk), *pk.as_point());
+
+        // Secrets are not printed.
+        assert_eq!(std::format!("{:?}", sk), "SecretKey { .. }");
+
+        let other = SecretKey::random(&mut rng);
+        assert!(bool::from(sk.ct_eq(&sk.clone())));
+        assert!(!bool::from(sk.ct_eq(&other)));
+        assert!(!bool::from(pk.ct_eq(&other.public_key())));
+        assert_eq!(
+            PublicKey::conditional_select(&pk, &other.public_key(), Choice::from(1)),
+            other.public_key()
+        );
+    }
+
+    #[test]
+    fn test_key_encoding() {
+        let mut rng = crate::tests::rng();
+
+        let sk = SecretKey::random(&mut rng);
+        assert_eq!(SecretKey::from_bytes(&sk.to_bytes()).unwrap(), sk);
+        let pk = sk.public_key();
+        assert_eq!(PublicKey::from_bytes(&pk.to_bytes()).unwrap(), pk);
+
+        // Zero, non-canonical scalars, the identity and invalid points are rejected.
+        assert!(bool::from(SecretKey::from_bytes(&[0; 32]).is_none()));
+        assert!(bool::from(SecretKey::from_bytes(&[0xff; 32]).is_none()));
+        assert!(bool::from(
+            SecretKey::from_scalar(pallas::Scalar::zero()).is_none()
+        ));
+        assert!(bool::from(
+            PublicKey::from_bytes(&pallas::Affine::identity().to_bytes()).is_none()
+        ));
+        assert!(bool::from(PublicKey::from_bytes(&[0xff; 32]).is_none()));
+        assert!(bool::from(
+            PublicKey::from_point(pallas::Affine::identity()).is_none()
+        ));
+        assert_eq!(PublicKey::from_point(*pk.as_point()).unwrap(), pk);
+
+        // (1, 1) is not on the curve, so a point decoded from it without checks is
+        // rejected.
+        let mut off_curve = <pallas::Affine as UncompressedEncoding>::Uncompressed::default();
+        off_curve.as_mut()[0] = 1;
+        off_curve.as_mut()[32] = 1;
+        let off_curve = pallas::Affine::from_uncompressed_unchecked(&off_curve).unwrap();
+        assert!(bool::from(PublicKey::from_point(off_curve).is_none()));
+    }
+
+    #[cfg(feature = "os-rng")]
+    #[test]
+    fn test_generate() {
+        let sk = SecretKey::generate();
+        assert_ne!(sk, SecretKey::generate());
+        assert!(!bool::from(sk.public_key().as_point().is_identity()));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use ff::Field;
+        use zeroize::Zeroize;
+
+        let mut sk = SecretKey::from_scalar(pallas::Scalar::one()).unwrap();
+        sk.zeroize();
+        assert!(bool::from(sk.as_scalar().is_zero()));
+    }
+}
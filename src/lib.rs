@@ -16,30 +16,176 @@ extern crate alloc;
 #[macro_use]
 extern crate std;
 
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
 #[macro_use]
 mod macros;
 mod curves;
 mod fields;
 
 pub mod arithmetic;
+pub mod fft;
+pub mod keys;
 pub mod pallas;
 pub mod vesta;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+
+#[cfg(feature = "alloc")]
+mod basepoint_table;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod batch;
+
+#[cfg(feature = "signatures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signatures")))]
+pub mod ecdh;
+
+#[cfg(feature = "signatures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signatures")))]
+pub mod ecdsa;
+
+#[cfg(feature = "signatures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signatures")))]
+pub mod ecvrf;
+
 #[cfg(feature = "alloc")]
 mod hashtocurve;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod ipa;
+
+#[cfg(feature = "alloc")]
+mod msm;
+
+#[cfg(feature = "multicore")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multicore")))]
+pub mod multicore;
+
+#[cfg(feature = "multiformats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multiformats")))]
+pub mod multiformats;
+
+#[cfg(feature = "signatures")]
+mod nonce;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod poly;
+
+#[cfg(feature = "base64")]
+mod base64_impl;
+
+#[cfg(feature = "borsh")]
+mod borsh_impl;
+
+#[cfg(feature = "crypto-bigint")]
+mod crypto_bigint_impl;
+
+#[cfg(feature = "num-bigint")]
+mod num_bigint_impl;
+
+#[cfg(feature = "hash2curve")]
+mod hash2curve_impl;
+
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+pub mod pod;
+
+#[cfg(feature = "signatures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signatures")))]
+pub mod schnorr;
+
 #[cfg(feature = "serde")]
 mod serde_impl;
 
+#[cfg(feature = "alloc")]
+mod serde_object;
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub mod wasm;
+
+#[cfg(feature = "capi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capi")))]
+#[allow(unsafe_code)]
+pub mod capi;
+
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+#[allow(unsafe_code)]
+pub mod python;
+
+#[cfg(feature = "alloc")]
+mod wnaf;
+
+#[cfg(feature = "gpu")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gpu")))]
+pub mod gpu;
+
+#[cfg(feature = "metal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metal")))]
+pub mod metal;
+
+#[cfg(feature = "base64")]
+#[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+pub use base64_impl::ParseBase64Error;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use basepoint_table::BasepointTable;
 pub use curves::*;
 pub use fields::*;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use hashtocurve::CurveHasher;
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub use hashtocurve::{hash_to_field_with, ExpandMessage, ExpandMsgXmd, ExpandMsgXof};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use msm::MsmConfig;
+#[cfg(feature = "num-bigint")]
+#[cfg_attr(docsrs, doc(cfg(feature = "num-bigint")))]
+pub use num_bigint_impl::TryFromBigUintError;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use serde_impl::{Compact, UncheckedPoint};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use serde_object::SerdeObject;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use wnaf::WnafContext;
 
 pub extern crate group;
 
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    /// Returns the deterministically seeded RNG used across the tests.
+    pub(crate) fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn test_endo_consistency() {
-    use crate::arithmetic::{CurveExt, FieldExt};
+    use crate::arithmetic::CurveExt;
+    use ff::WithSmallOrderMulGroup;
     use group::Group;
 
     let a = pallas::Point::generator();
@@ -47,3 +193,27 @@ fn test_endo_consistency() {
     let a = vesta::Point::generator();
     assert_eq!(a * vesta::Scalar::ZETA, a.endo());
 }
+
+#[test]
+fn test_apply_endomorphism() {
+    use ff::WithSmallOrderMulGroup;
+    use group::{prime::PrimeCurveAffine, Curve};
+
+    let a = pallas::Affine::generator();
+    assert_eq!(
+        (a * pallas::Scalar::ZETA).to_affine(),
+        a.apply_endomorphism()
+    );
+    let a = vesta::Affine::generator();
+    assert_eq!(
+        (a * vesta::Scalar::ZETA).to_affine(),
+        a.apply_endomorphism()
+    );
+
+    // The identity is fixed by the endomorphism.
+    assert!(bool::from(
+        pallas::Affine::identity()
+            .apply_endomorphism()
+            .is_identity()
+    ));
+}
@@ -151,3 +151,19 @@ macro_rules! impl_binops_multiplicative {
         }
     };
 }
+
+macro_rules! impl_sum_prod {
+    ($f:ident) => {
+        impl<T: ::core::borrow::Borrow<$f>> ::core::iter::Sum<T> for $f {
+            fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+                iter.fold(<$f as ::ff::Field>::ZERO, |acc, item| acc + item.borrow())
+            }
+        }
+
+        impl<T: ::core::borrow::Borrow<$f>> ::core::iter::Product<T> for $f {
+            fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+                iter.fold(<$f as ::ff::Field>::ONE, |acc, item| acc * item.borrow())
+            }
+        }
+    };
+}
@@ -0,0 +1,647 @@
+//! This module provides multi-scalar multiplication on Apple GPUs via Metal.
+//!
+//! The kernel source returned by [`msl_source`] is written in the Metal Shading
+//! Language, and contains Montgomery arithmetic for $\mathbb{F}_p$ and
+//! $\mathbb{F}_q$ and multi-scalar multiplication kernels for both curves. Each GPU
+//! thread accumulates a chunk of the inputs with a shared double-and-add, and the
+//! partial sums are added on the CPU.
+//!
+//! [`Msm`] compiles the kernels for the system default device. When Metal is not
+//! available, including on platforms other than macOS, it falls back to
+//! [`Ep::multi_scalar_mul`] and [`Eq::multi_scalar_mul`] on the CPU.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use ff::{Field, PrimeField};
+
+use crate::arithmetic::{CurveAffine, CurveExt, FieldExt};
+use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq};
+
+/// The number of 32-bit limbs in a field element.
+const LIMBS: usize = 8;
+
+/// The maximum number of GPU threads used for a multi-scalar multiplication.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const MAX_THREADS: usize = 1 << 13;
+
+const FIELD_TEMPLATE: &str = r#"
+struct FIELD { uint v[8]; };
+
+constant uint FIELD_P[8] = { @MODULUS@ };
+constant uint FIELD_ONE[8] = { @ONE@ };
+constant uint FIELD_INV = @INV@;
+
+FIELD FIELD_zero() {
+    FIELD r;
+    for (int i = 0; i < 8; i++) { r.v[i] = 0; }
+    return r;
+}
+
+FIELD FIELD_one() {
+    FIELD r;
+    for (int i = 0; i < 8; i++) { r.v[i] = FIELD_ONE[i]; }
+    return r;
+}
+
+bool FIELD_is_zero(FIELD a) {
+    uint acc = 0;
+    for (int i = 0; i < 8; i++) { acc |= a.v[i]; }
+    return acc == 0;
+}
+
+bool FIELD_gte_p(FIELD a) {
+    for (int i = 7; i >= 0; i--) {
+        if (a.v[i] > FIELD_P[i]) { return true; }
+        if (a.v[i] < FIELD_P[i]) { return false; }
+    }
+    return true;
+}
+
+FIELD FIELD_sub_p(FIELD a) {
+    FIELD r;
+    ulong borrow = 0;
+    for (int i = 0; i < 8; i++) {
+        ulong d = (ulong)a.v[i] - (ulong)FIELD_P[i] - borrow;
+        r.v[i] = (uint)d;
+        borrow = d >> 63;
+    }
+    return r;
+}
+
+FIELD FIELD_add(FIELD a, FIELD b) {
+    FIELD r;
+    ulong carry = 0;
+    for (int i = 0; i < 8; i++) {
+        ulong s = (ulong)a.v[i] + (ulong)b.v[i] + carry;
+        r.v[i] = (uint)s;
+        carry = s >> 32;
+    }
+    if (carry != 0 || FIELD_gte_p(r)) { r = FIELD_sub_p(r); }
+    return r;
+}
+
+FIELD FIELD_sub(FIELD a, FIELD b) {
+    FIELD r;
+    ulong borrow = 0;
+    for (int i = 0; i < 8; i++) {
+        ulong d = (ulong)a.v[i] - (ulong)b.v[i] - borrow;
+        r.v[i] = (uint)d;
+        borrow = d >> 63;
+    }
+    if (borrow != 0) {
+        ulong carry = 0;
+        for (int i = 0; i < 8; i++) {
+            ulong s = (ulong)r.v[i] + (ulong)FIELD_P[i] + carry;
+            r.v[i] = (uint)s;
+            carry = s >> 32;
+        }
+    }
+    return r;
+}
+
+// Montgomery multiplication with R = 2^256 (CIOS).
+FIELD FIELD_mul(FIELD a, FIELD b) {
+    uint t[10];
+    for (int i = 0; i < 10; i++) { t[i] = 0; }
+    for (int i = 0; i < 8; i++) {
+        ulong c = 0;
+        for (int j = 0; j < 8; j++) {
+            ulong s = (ulong)t[j] + (ulong)a.v[j] * (ulong)b.v[i] + c;
+            t[j] = (uint)s;
+            c = s >> 32;
+        }
+        ulong s = (ulong)t[8] + c;
+        t[8] = (uint)s;
+        t[9] = (uint)(s >> 32);
+
+        uint m = t[0] * FIELD_INV;
+        s = (ulong)t[0] + (ulong)m * (ulong)FIELD_P[0];
+        c = s >> 32;
+        for (int j = 1; j < 8; j++) {
+            s = (ulong)t[j] + (ulong)m * (ulong)FIELD_P[j] + c;
+            t[j - 1] = (uint)s;
+            c = s >> 32;
+        }
+        s = (ulong)t[8] + c;
+        t[7] = (uint)s;
+        t[8] = t[9] + (uint)(s >> 32);
+    }
+    FIELD r;
+    for (int i = 0; i < 8; i++) { r.v[i] = t[i]; }
+    if (t[8] != 0 || FIELD_gte_p(r)) { r = FIELD_sub_p(r); }
+    return r;
+}
+"#;
+
+const CURVE_TEMPLATE: &str = r#"
+struct CURVE_jacobian { FIELD x; FIELD y; FIELD z; };
+
+CURVE_jacobian CURVE_identity() {
+    CURVE_jacobian r;
+    r.x = FIELD_zero();
+    r.y = FIELD_zero();
+    r.z = FIELD_zero();
+    return r;
+}
+
+// dbl-2009-l, for a = 0.
+CURVE_jacobian CURVE_double(CURVE_jacobian p) {
+    FIELD a = FIELD_mul(p.x, p.x);
+    FIELD b = FIELD_mul(p.y, p.y);
+    FIELD c = FIELD_mul(b, b);
+    FIELD d = FIELD_add(p.x, b);
+    d = FIELD_sub(FIELD_sub(FIELD_mul(d, d), a), c);
+    d = FIELD_add(d, d);
+    FIELD e = FIELD_add(FIELD_add(a, a), a);
+    FIELD f = FIELD_mul(e, e);
+    FIELD c8 = FIELD_add(c, c);
+    c8 = FIELD_add(c8, c8);
+    c8 = FIELD_add(c8, c8);
+
+    CURVE_jacobian r;
+    r.x = FIELD_sub(FIELD_sub(f, d), d);
+    r.y = FIELD_sub(FIELD_mul(e, FIELD_sub(d, r.x)), c8);
+    r.z = FIELD_mul(p.y, p.z);
+    r.z = FIELD_add(r.z, r.z);
+    return r;
+}
+
+// madd-2007-bl, for an affine point q other than the identity.
+CURVE_jacobian CURVE_add_affine(CURVE_jacobian p, FIELD qx, FIELD qy) {
+    CURVE_jacobian r;
+    if (FIELD_is_zero(p.z)) {
+        r.x = qx;
+        r.y = qy;
+        r.z = FIELD_one();
+        return r;
+    }
+
+    FIELD z1z1 = FIELD_mul(p.z, p.z);
+    FIELD u2 = FIELD_mul(qx, z1z1);
+    FIELD s2 = FIELD_mul(FIELD_mul(qy, p.z), z1z1);
+    FIELD h = FIELD_sub(u2, p.x);
+    FIELD rr = FIELD_sub(s2, p.y);
+    rr = FIELD_add(rr, rr);
+    if (FIELD_is_zero(h)) {
+        if (FIELD_is_zero(rr)) { return CURVE_double(p); }
+        return CURVE_identity();
+    }
+
+    FIELD hh = FIELD_mul(h, h);
+    FIELD i = FIELD_add(hh, hh);
+    i = FIELD_add(i, i);
+    FIELD j = FIELD_mul(h, i);
+    FIELD v = FIELD_mul(p.x, i);
+    FIELD y1j = FIELD_mul(p.y, j);
+    y1j = FIELD_add(y1j, y1j);
+
+    r.x = FIELD_sub(FIELD_sub(FIELD_sub(FIELD_mul(rr, rr), j), v), v);
+    r.y = FIELD_sub(FIELD_mul(rr, FIELD_sub(v, r.x)), y1j);
+    r.z = FIELD_add(p.z, h);
+    r.z = FIELD_sub(FIELD_sub(FIELD_mul(r.z, r.z), z1z1), hh);
+    return r;
+}
+
+// Bases are affine (x, y) in Montgomery form and scalars are canonical, both as
+// little-endian 32-bit limbs. Each thread writes the Jacobian (x, y, z) of its
+// partial sum in Montgomery form.
+kernel void CURVE_msm(
+    device const uint *bases [[buffer(0)]],
+    device const uint *scalars [[buffer(1)]],
+    device uint *partials [[buffer(2)]],
+    constant uint &n [[buffer(3)]],
+    constant uint &chunk [[buffer(4)]],
+    uint tid [[thread_position_in_grid]])
+{
+    uint start = tid * chunk;
+    uint end = min(start + chunk, n);
+
+    CURVE_jacobian acc = CURVE_identity();
+    for (int bit = 255; bit >= 0; bit--) {
+        acc = CURVE_double(acc);
+        for (uint i = start; i < end; i++) {
+            if (((scalars[8 * i + bit / 32] >> (bit % 32)) & 1) != 0) {
+                FIELD x;
+                FIELD y;
+                for (int k = 0; k < 8; k++) {
+                    x.v[k] = bases[16 * i + k];
+                    y.v[k] = bases[16 * i + 8 + k];
+                }
+                acc = CURVE_add_affine(acc, x, y);
+            }
+        }
+    }
+
+    for (int k = 0; k < 8; k++) {
+        partials[24 * tid + k] = acc.x.v[k];
+        partials[24 * tid + 8 + k] = acc.y.v[k];
+        partials[24 * tid + 16 + k] = acc.z.v[k];
+    }
+}
+"#;
+
+/// Returns the little-endian 32-bit limbs of the canonical encoding of `a`.
+fn to_limbs<F: PrimeField<Repr = [u8; 32]>>(a: &F) -> [u32; LIMBS] {
+    let repr = a.to_repr();
+    let mut limbs = [0; LIMBS];
+    for (limb, bytes) in limbs.iter_mut().zip(repr.chunks(4)) {
+        *limb = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    limbs
+}
+
+/// Parses a field element from the little-endian 32-bit limbs of its canonical
+/// encoding.
+fn from_limbs<F: PrimeField<Repr = [u8; 32]>>(limbs: &[u32]) -> Option<F> {
+    let mut repr = [0; 32];
+    for (bytes, limb) in repr.chunks_mut(4).zip(limbs) {
+        bytes.copy_from_slice(&limb.to_le_bytes());
+    }
+    Option::from(F::from_repr(repr))
+}
+
+/// Returns the Montgomery factor $R = 2^{256} \bmod p$ of `F`.
+fn montgomery_r<F: FieldExt>() -> F {
+    let mut wide = [0; 64];
+    wide[32] = 1;
+    F::from_bytes_wide(&wide)
+}
+
+/// Returns the source of the field arithmetic for `F`, with the given name.
+fn field_source<F: FieldExt + PrimeField<Repr = [u8; 32]>>(name: &str) -> String {
+    let join = |limbs: [u32; LIMBS]| {
+        limbs
+            .iter()
+            .map(|limb| alloc::format!("0x{:08x}", limb))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    // The modulus is one more than -1, and its lowest byte cannot overflow as the
+    // modulus is odd.
+    let mut modulus = to_limbs(&-F::ONE);
+    modulus[0] += 1;
+
+    // -p^{-1} mod 2^32, computed as p^{2^31 - 1} = p^{-1} mod 2^32.
+    let mut inv = 1u32;
+    for _ in 0..31 {
+        inv = inv.wrapping_mul(inv);
+        inv = inv.wrapping_mul(modulus[0]);
+    }
+    let inv = inv.wrapping_neg();
+
+    FIELD_TEMPLATE
+        .replace("@MODULUS@", &join(modulus))
+        .replace("@ONE@", &join(to_limbs(&montgomery_r::<F>())))
+        .replace("@INV@", &alloc::format!("0x{:08x}", inv))
+        .replace("FIELD", name)
+}
+
+/// Returns the Metal Shading Language source of the field arithmetic for
+/// [`Fp`] and [`Fq`], and of the `pallas_msm` and `vesta_msm` kernels.
+pub fn msl_source() -> String {
+    let mut source = String::from("#include <metal_stdlib>\nusing namespace metal;\n");
+    source.push_str(&field_source::<Fp>("pasta_Fp"));
+    source.push_str(&field_source::<Fq>("pasta_Fq"));
+    source.push_str(
+        &CURVE_TEMPLATE
+            .replace("CURVE", "pallas")
+            .replace("FIELD", "pasta_Fp"),
+    );
+    source.push_str(
+        &CURVE_TEMPLATE
+            .replace("CURVE", "vesta")
+            .replace("FIELD", "pasta_Fq"),
+    );
+    source
+}
+
+/// Packs the bases other than the identity, in Montgomery form, and their scalars
+/// into limbs for the kernels. Returns the number of packed terms.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn pack<C>(bases: &[C], scalars: &[C::ScalarExt]) -> (Vec<u32>, Vec<u32>, usize)
+where
+    C: CurveAffine,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+    C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+{
+    let r = montgomery_r::<C::Base>();
+    let mut packed_bases = Vec::with_capacity(bases.len() * 2 * LIMBS);
+    let mut packed_scalars = Vec::with_capacity(scalars.len() * LIMBS);
+    let mut n = 0;
+    for (base, scalar) in bases.iter().zip(scalars.iter()) {
+        let coordinates = base.coordinates();
+        if bool::from(coordinates.is_some()) {
+            let coordinates = coordinates.unwrap();
+            packed_bases.extend_from_slice(&to_limbs(&(*coordinates.x() * r)));
+            packed_bases.extend_from_slice(&to_limbs(&(*coordinates.y() * r)));
+            packed_scalars.extend_from_slice(&to_limbs(scalar));
+            n += 1;
+        }
+    }
+    (packed_bases, packed_scalars, n)
+}
+
+/// Sums the partial sums written by the kernels, which are Jacobian coordinates
+/// in Montgomery form.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn unpack<C>(partials: &[u32]) -> C
+where
+    C: CurveExt,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let r_inv = montgomery_r::<C::Base>().invert().unwrap();
+    let coordinate = |limbs: &[u32]| {
+        from_limbs::<C::Base>(limbs).expect("kernel returned a non-canonical coordinate") * r_inv
+    };
+
+    partials
+        .chunks(3 * LIMBS)
+        .map(|p| {
+            let x = coordinate(&p[..LIMBS]);
+            let y = coordinate(&p[LIMBS..2 * LIMBS]);
+            let z = coordinate(&p[2 * LIMBS..]);
+            C::new_jacobian(x, y, z).expect("kernel returned a point that is not on the curve")
+        })
+        .fold(C::identity(), |acc, p| acc + p)
+}
+
+#[cfg(target_os = "macos")]
+mod backend {
+    use alloc::vec::Vec;
+    use core::{ffi::c_void, mem};
+
+    use metal_crate::{
+        CommandQueue, CompileOptions, ComputePipelineState, Device, MTLResourceOptions, MTLSize,
+    };
+
+    use super::{msl_source, LIMBS, MAX_THREADS};
+
+    pub(super) struct Context {
+        device: Device,
+        queue: CommandQueue,
+        pub(super) pallas: ComputePipelineState,
+        pub(super) vesta: ComputePipelineState,
+    }
+
+    impl Context {
+        /// Compiles the kernels for the system default device.
+        pub(super) fn new() -> Option<Self> {
+            let device = Device::system_default()?;
+            let library = device
+                .new_library_with_source(&msl_source(), &CompileOptions::new())
+                .ok()?;
+            let pipeline = |name: &str| {
+                let function = library.get_function(name, None).ok()?;
+                device
+                    .new_compute_pipeline_state_with_function(&function)
+                    .ok()
+            };
+            let pallas = pipeline("pallas_msm")?;
+            let vesta = pipeline("vesta_msm")?;
+            let queue = device.new_command_queue();
+            Some(Context {
+                device,
+                queue,
+                pallas,
+                vesta,
+            })
+        }
+
+        /// Runs `pipeline` on `n` packed terms, returning the partial sums.
+        #[allow(unsafe_code)]
+        pub(super) fn run(
+            &self,
+            pipeline: &ComputePipelineState,
+            bases: &[u32],
+            scalars: &[u32],
+            n: usize,
+        ) -> Vec<u32> {
+            let threads = n.min(MAX_THREADS);
+            let chunk = (n + threads - 1) / threads;
+            let (n, chunk) = (n as u32, chunk as u32);
+
+            let options = MTLResourceOptions::StorageModeShared;
+            let buffer = |limbs: &[u32]| {
+                self.device.new_buffer_with_data(
+                    limbs.as_ptr() as *const c_void,
+                    mem::size_of_val(limbs) as u64,
+                    options,
+                )
+            };
+            let bases = buffer(bases);
+            let scalars = buffer(scalars);
+            let partials_len = threads * 3 * LIMBS;
+            let partials = self
+                .device
+                .new_buffer((partials_len * mem::size_of::<u32>()) as u64, options);
+
+            let command_buffer = self.queue.new_command_buffer();
+            let encoder = command_buffer.new_compute_command_encoder();
+            encoder.set_compute_pipeline_state(pipeline);
+            encoder.set_buffer(0, Some(&bases), 0);
+            encoder.set_buffer(1, Some(&scalars), 0);
+            encoder.set_buffer(2, Some(&partials), 0);
+            encoder.set_bytes(
+                3,
+                mem::size_of::<u32>() as u64,
+                &n as *const u32 as *const c_void,
+            );
+            encoder.set_bytes(
+                4,
+                mem::size_of::<u32>() as u64,
+                &chunk as *const u32 as *const c_void,
+            );
+            let width = pipeline
+                .max_total_threads_per_threadgroup()
+                .min(threads as u64);
+            encoder.dispatch_threads(
+                MTLSize {
+                    width: threads as u64,
+                    height: 1,
+                    depth: 1,
+                },
+                MTLSize {
+                    width,
+                    height: 1,
+                    depth: 1,
+                },
+            );
+            encoder.end_encoding();
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+
+            let contents = partials.contents() as *const u32;
+            // Safety: the buffer holds `partials_len` limbs, written by the kernel.
+            unsafe { core::slice::from_raw_parts(contents, partials_len) }.to_vec()
+        }
+    }
+}
+
+/// Multi-scalar multiplication on the system default Metal device, falling back to
+/// the CPU when Metal is unavailable.
+pub struct Msm {
+    #[cfg(target_os = "macos")]
+    context: Option<backend::Context>,
+}
+
+impl fmt::Debug for Msm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Msm")
+            .field("is_gpu", &self.is_gpu())
+            .finish()
+    }
+}
+
+impl Default for Msm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Msm {
+    /// Compiles the kernels for the system default Metal device, if there is one.
+    pub fn new() -> Self {
+        Msm {
+            #[cfg(target_os = "macos")]
+            context: backend::Context::new(),
+        }
+    }
+
+    /// Returns `true` if the multi-scalar multiplications run on a GPU, and `false`
+    /// if they fall back to the CPU.
+    #[allow(clippy::let_and_return)]
+    pub fn is_gpu(&self) -> bool {
+        let is_gpu = false;
+        #[cfg(target_os = "macos")]
+        let is_gpu = self.context.is_some();
+        is_gpu
+    }
+
+    /// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of Pallas `bases`
+    /// $P_i$ and `scalars` $s_i$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `scalars` have different lengths.
+    pub fn pallas(&self, bases: &[EpAffine], scalars: &[Fq]) -> Ep {
+        assert_eq!(bases.len(), scalars.len());
+
+        #[cfg(target_os = "macos")]
+        if let Some(context) = &self.context {
+            let (bases, scalars, n) = pack(bases, scalars);
+            if n == 0 {
+                return Ep::identity();
+            }
+            return unpack(&context.run(&context.pallas, &bases, &scalars, n));
+        }
+
+        Ep::multi_scalar_mul(bases, scalars)
+    }
+
+    /// Computes the multi-scalar multiplication $\sum_i [s_i] P_i$ of Vesta `bases`
+    /// $P_i$ and `scalars` $s_i$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `scalars` have different lengths.
+    pub fn vesta(&self, bases: &[EqAffine], scalars: &[Fp]) -> Eq {
+        assert_eq!(bases.len(), scalars.len());
+
+        #[cfg(target_os = "macos")]
+        if let Some(context) = &self.context {
+            let (bases, scalars, n) = pack(bases, scalars);
+            if n == 0 {
+                return Eq::identity();
+            }
+            return unpack(&context.run(&context.vesta, &bases, &scalars, n));
+        }
+
+        Eq::multi_scalar_mul(bases, scalars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+
+    use super::{montgomery_r, msl_source, pack, to_limbs, unpack, Msm, LIMBS};
+    use crate::arithmetic::CurveExt;
+    use crate::{Ep, EpAffine, Eq, Fp, Fq};
+
+    #[test]
+    fn test_msl_source() {
+        let source = msl_source();
+        for name in ["pasta_Fp_mul", "pasta_Fq_mul", "pallas_msm", "vesta_msm"] {
+            assert!(source.contains(name));
+        }
+        // All placeholders are substituted.
+        for placeholder in ["FIELD", "CURVE", "@"] {
+            assert!(!source.contains(placeholder));
+        }
+        assert!(source.contains("0x992d30ed, 0x094cf91b"));
+    }
+
+    #[test]
+    #[allow(clippy::many_single_char_names)]
+    fn test_pack_unpack() {
+        let mut rng = crate::tests::rng();
+
+        let points: Vec<_> = (0..4).map(|_| Ep::random(&mut rng)).collect();
+        let mut bases: Vec<_> = points.iter().map(|p| p.to_affine()).collect();
+        bases.push(EpAffine::identity());
+        let scalars: Vec<_> = (0..5).map(|_| Fq::random(&mut rng)).collect();
+
+        // The identity is skipped.
+        let (packed_bases, packed_scalars, n) = pack(&bases, &scalars);
+        assert_eq!(n, 4);
+        assert_eq!(packed_bases.len(), 4 * 2 * LIMBS);
+        assert_eq!(&packed_scalars[..LIMBS], &to_limbs(&scalars[0]));
+
+        // Partial sums in Montgomery form are summed.
+        let r = montgomery_r::<Fp>();
+        let partials: Vec<u32> = points
+            .iter()
+            .flat_map(|p| {
+                let (x, y, z) = p.jacobian_coordinates();
+                let mut limbs = Vec::new();
+                for c in [x, y, z] {
+                    limbs.extend_from_slice(&to_limbs(&(c * r)));
+                }
+                limbs
+            })
+            .collect();
+        assert_eq!(
+            unpack::<Ep>(&partials),
+            points.iter().fold(Ep::identity(), |acc, p| acc + p)
+        );
+        assert_eq!(unpack::<Ep>(&[]), Ep::identity());
+    }
+
+    #[test]
+    fn test_msm() {
+        let mut rng = crate::tests::rng();
+        let msm = Msm::new();
+        #[cfg(not(target_os = "macos"))]
+        assert!(!msm.is_gpu());
+
+        let bases: Vec<_> = (0..33).map(|_| Ep::random(&mut rng).to_affine()).collect();
+        let scalars: Vec<_> = (0..33).map(|_| Fq::random(&mut rng)).collect();
+        assert_eq!(
+            msm.pallas(&bases, &scalars),
+            Ep::multi_scalar_mul(&bases, &scalars)
+        );
+
+        let bases: Vec<_> = (0..33).map(|_| Eq::random(&mut rng).to_affine()).collect();
+        let scalars: Vec<_> = (0..33).map(|_| Fp::random(&mut rng)).collect();
+        assert_eq!(
+            msm.vesta(&bases, &scalars),
+            Eq::multi_scalar_mul(&bases, &scalars)
+        );
+        assert_eq!(msm.vesta(&[], &[]), Eq::identity());
+    }
+}
@@ -0,0 +1,665 @@
+//! This module implements multi-scalar multiplication using the bucket method
+//! (also known as Pippenger's algorithm).
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+
+use ff::PrimeField;
+use group::Group as _;
+
+use crate::arithmetic::CurveAffine;
+
+/// Tuning parameters for multi-scalar multiplication with the bucket method.
+///
+/// The defaults suit commodity hardware; the best parameters for a given machine
+/// depend on its core count and cache sizes, and are best found by benchmarking.
+///
+/// ```
+/// # extern crate fil_pasta_curves as pasta_curves;
+/// use pasta_curves::{pallas, MsmConfig};
+///
+/// let config = MsmConfig {
+///     window_bits: Some(12),
+///     ..MsmConfig::default()
+/// };
+/// let g = pallas::Affine::default();
+/// pallas::Point::multi_scalar_mul_with_config(&[g], &[pallas::Scalar::one()], &config);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MsmConfig {
+    /// The window size in bits, between 1 and 20, or `None` to choose it from the
+    /// number of terms in each chunk.
+    pub window_bits: Option<usize>,
+    /// The number of terms in a chunk from which the buckets are accumulated with
+    /// batched affine additions.
+    pub batch_affine_threshold: usize,
+    /// The number of terms evaluated together, whose results are then summed, or
+    /// `None` to evaluate all terms together. With the `multicore` feature flag,
+    /// `None` instead splits large inputs into one chunk per thread.
+    pub chunk_size: Option<usize>,
+    /// The number of terms from which the chunks are evaluated in parallel on the
+    /// current rayon thread pool, with the `multicore` feature flag.
+    pub parallel_threshold: usize,
+}
+
+impl MsmConfig {
+    /// Returns the default configuration.
+    pub const fn new() -> Self {
+        MsmConfig {
+            window_bits: None,
+            batch_affine_threshold: BATCH_AFFINE_THRESHOLD,
+            chunk_size: None,
+            parallel_threshold: PARALLEL_THRESHOLD,
+        }
+    }
+}
+
+impl Default for MsmConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the window size, in bits, used by the bucket method for an MSM of
+/// `num_terms` terms.
+fn window_size(num_terms: usize) -> usize {
+    if num_terms < 4 {
+        1
+    } else if num_terms < 32 {
+        3
+    } else {
+        // Approximately ln(num_terms), which is close to optimal in practice.
+        let log2 = (usize::BITS - num_terms.leading_zeros()) as usize;
+        (log2 * 69 / 100 + 1).min(MAX_WINDOW_BITS)
+    }
+}
+
+/// Extracts the `c`-bit window at position `segment` from a little-endian
+/// scalar encoding.
+fn get_window(segment: usize, c: usize, bytes: &[u8]) -> usize {
+    let skip_bits = segment * c;
+    let skip_bytes = skip_bits / 8;
+
+    if skip_bytes >= bytes.len() {
+        return 0;
+    }
+
+    let mut v = [0; 8];
+    for (v, o) in v.iter_mut().zip(bytes[skip_bytes..].iter()) {
+        *v = *o;
+    }
+
+    let mut tmp = u64::from_le_bytes(v);
+    tmp >>= skip_bits - (skip_bytes * 8);
+    tmp %= 1 << c;
+
+    tmp as usize
+}
+
+/// A bucket accumulator that avoids converting to projective coordinates until
+/// a second point has been added to it.
+#[derive(Clone, Copy)]
+enum Bucket<C: CurveAffine> {
+    None,
+    Affine(C),
+    Projective(C::Curve),
+}
+
+impl<C: CurveAffine> Bucket<C> {
+    fn add_assign(&mut self, other: &C) {
+        *self = match *self {
+            Bucket::None => Bucket::Affine(*other),
+            Bucket::Affine(a) => Bucket::Projective(a + *other),
+            Bucket::Projective(mut a) => {
+                a += *other;
+                Bucket::Projective(a)
+            }
+        }
+    }
+
+    fn add(self, mut other: C::Curve) -> C::Curve {
+        match self {
+            Bucket::None => other,
+            Bucket::Affine(a) => {
+                other += a;
+                other
+            }
+            Bucket::Projective(a) => other + a,
+        }
+    }
+}
+
+/// The default number of terms from which the buckets are accumulated with batched
+/// affine additions, rather than one mixed addition at a time. Below this the
+/// sorting and bookkeeping cost more than the shared inversions save.
+const BATCH_AFFINE_THRESHOLD: usize = 256;
+
+/// The default number of terms from which the chunks of an MSM are evaluated in
+/// parallel. Below this the bucket passes of the split inputs cost more than the
+/// parallelism saves.
+const PARALLEL_THRESHOLD: usize = 1 << 12;
+
+/// The largest window size in bits. A window of $c$ bits needs $2^{c-1}$ buckets
+/// per chunk, so beyond this the buckets take tens of megabytes per thread and no
+/// longer fit in any cache, which costs more than the fewer windows save.
+const MAX_WINDOW_BITS: usize = 20;
+
+/// Sums the points assigned to each bucket, given as `(bucket, point)` pairs, using
+/// [`CurveAffine::batch_add`].
+///
+/// The pairs are sorted by bucket, and in each round adjacent points in the same
+/// bucket are added together in one batch, halving the number of points in every
+/// bucket until each has a single point left.
+fn batch_affine_buckets<C: CurveAffine>(mut entries: Vec<(usize, C)>) -> Vec<(usize, C)> {
+    entries.sort_unstable_by_key(|(bucket, _)| *bucket);
+
+    let mut lhs = Vec::with_capacity(entries.len() / 2);
+    let mut rhs = Vec::with_capacity(entries.len() / 2);
+    let mut slots = Vec::with_capacity(entries.len() / 2);
+    loop {
+        lhs.clear();
+        rhs.clear();
+        slots.clear();
+
+        let mut next = Vec::with_capacity(entries.len());
+        let mut i = 0;
+        while i < entries.len() {
+            if i + 1 < entries.len() && entries[i].0 == entries[i + 1].0 {
+                slots.push(next.len());
+                next.push(entries[i]);
+                lhs.push(entries[i].1);
+                rhs.push(entries[i + 1].1);
+                i += 2;
+            } else {
+                next.push(entries[i]);
+                i += 1;
+            }
+        }
+
+        if lhs.is_empty() {
+            return entries;
+        }
+
+        C::batch_add(&mut lhs, &rhs);
+        for (&slot, sum) in slots.iter().zip(lhs.iter()) {
+            next[slot].1 = *sum;
+        }
+        entries = next;
+    }
+}
+
+/// Computes $\sum_i [\textsf{scalars}_i] \textsf{bases}_i$ using the bucket method
+/// with signed digits.
+///
+/// For large inputs, the buckets of each window are accumulated with batched affine
+/// additions.
+///
+/// Panics if `bases` and `scalars` have different lengths.
+pub(crate) fn multi_scalar_mul<C: CurveAffine>(bases: &[C], scalars: &[C::ScalarExt]) -> C::Curve {
+    multi_scalar_mul_with_config(bases, scalars, &MsmConfig::new())
+}
+
+/// Computes $\sum_i [\textsf{scalars}_i] \textsf{bases}_i$ as in
+/// [`multi_scalar_mul`], with the parameters in `config`.
+///
+/// Panics if `bases` and `scalars` have different lengths, or if `config` is
+/// invalid.
+pub(crate) fn multi_scalar_mul_with_config<C: CurveAffine>(
+    bases: &[C],
+    scalars: &[C::ScalarExt],
+    config: &MsmConfig,
+) -> C::Curve {
+    assert_eq!(bases.len(), scalars.len());
+
+    let scalars: Vec<_> = scalars.iter().map(|s| s.to_repr()).collect();
+    chunked_msm(bases, &scalars, C::ScalarExt::NUM_BITS as usize, config)
+}
+
+/// Computes $\sum_i [\textsf{scalars}_i] \textsf{bases}_i$ for 64-bit scalars.
+///
+/// Only the windows covering the bit length of the largest scalar are processed, so
+/// this skips the doublings and bucket passes for the high windows, which would all
+/// be empty.
+///
+/// Panics if `bases` and `scalars` have different lengths.
+pub(crate) fn multi_scalar_mul_u64<C: CurveAffine>(bases: &[C], scalars: &[u64]) -> C::Curve {
+    assert_eq!(bases.len(), scalars.len());
+
+    let num_bits = scalars
+        .iter()
+        .map(|s| u64::BITS - s.leading_zeros())
+        .max()
+        .unwrap_or(0);
+    let scalars: Vec<_> = scalars.iter().map(|s| s.to_le_bytes()).collect();
+    chunked_msm(bases, &scalars, num_bits as usize, &MsmConfig::new())
+}
+
+/// Splits the terms into chunks as configured by `config`, and sums the results of
+/// [`bucket_msm`] on each chunk.
+fn chunked_msm<C: CurveAffine, S: AsRef<[u8]> + Sync>(
+    bases: &[C],
+    scalars: &[S],
+    num_bits: usize,
+    config: &MsmConfig,
+) -> C::Curve {
+    assert!(config.chunk_size != Some(0), "chunk size must be positive");
+    if let Some(c) = config.window_bits {
+        assert!(
+            (1..=MAX_WINDOW_BITS).contains(&c),
+            "window size must be between 1 and {} bits",
+            MAX_WINDOW_BITS
+        );
+    }
+
+    let n = bases.len();
+    #[cfg(feature = "multicore")]
+    let parallel = n >= config.parallel_threshold && rayon::current_num_threads() > 1;
+
+    let chunk_size = match config.chunk_size {
+        Some(chunk_size) => chunk_size,
+        #[cfg(feature = "multicore")]
+        None if parallel => (n + rayon::current_num_threads() - 1) / rayon::current_num_threads(),
+        None => n,
+    };
+    if chunk_size >= n {
+        return bucket_msm(bases, scalars, num_bits, config);
+    }
+
+    #[cfg(feature = "multicore")]
+    if parallel {
+        use rayon::prelude::*;
+
+        let partials: Vec<_> = bases
+            .par_chunks(chunk_size)
+            .zip(scalars.par_chunks(chunk_size))
+            .map(|(bases, scalars)| bucket_msm(bases, scalars, num_bits, config))
+            .collect();
+        return partials.iter().fold(C::Curve::identity(), |acc, p| acc + p);
+    }
+
+    bases
+        .chunks(chunk_size)
+        .zip(scalars.chunks(chunk_size))
+        .fold(C::Curve::identity(), |acc, (bases, scalars)| {
+            acc + bucket_msm(bases, scalars, num_bits, config)
+        })
+}
+
+/// The bucket method, for little-endian scalar encodings with at most `num_bits`
+/// significant bits.
+///
+/// The scalars are recoded into signed `c`-bit digits in $[-2^{c-1}, 2^{c-1}]$, by
+/// carrying one into the next window whenever a window exceeds $2^{c-1}$. A negative
+/// digit adds the negated base, which is free in affine coordinates, to the bucket of
+/// its absolute value, so each window only needs $2^{c-1}$ buckets instead of
+/// $2^c - 1$, halving the cost of the summation by parts.
+///
+/// The windows are processed from the least significant one, recoding each with a
+/// carry per scalar, so that the digits are never stored. The window sums are then
+/// combined from the most significant one.
+fn bucket_msm<C: CurveAffine, S: AsRef<[u8]>>(
+    bases: &[C],
+    scalars: &[S],
+    num_bits: usize,
+    config: &MsmConfig,
+) -> C::Curve {
+    let c = config
+        .window_bits
+        .unwrap_or_else(|| window_size(bases.len()));
+    // The top window covers at most c - 1 bits of the scalar, so that adding a carry
+    // to it cannot carry out of it.
+    let segments = num_bits / c + 1;
+    let half = 1 << (c - 1);
+
+    let mut carries = vec![false; scalars.len()];
+    let mut window_sums = Vec::with_capacity(segments);
+    let mut buckets: Vec<Bucket<C>> = vec![Bucket::None; half as usize];
+
+    for segment in 0..segments {
+        for bucket in buckets.iter_mut() {
+            *bucket = Bucket::None;
+        }
+
+        let terms = scalars
+            .iter()
+            .zip(carries.iter_mut())
+            .zip(bases.iter())
+            .filter_map(|((scalar, carry), base)| {
+                let window = get_window(segment, c, scalar.as_ref()) as i64 + *carry as i64;
+                *carry = window > half;
+                let digit = window - ((*carry as i64) << c);
+                match digit.cmp(&0) {
+                    cmp::Ordering::Greater => Some((digit as usize - 1, *base)),
+                    cmp::Ordering::Less => Some(((-digit) as usize - 1, -*base)),
+                    cmp::Ordering::Equal => None,
+                }
+            });
+        if bases.len() >= config.batch_affine_threshold {
+            for (bucket, sum) in batch_affine_buckets(terms.collect()) {
+                buckets[bucket] = Bucket::Affine(sum);
+            }
+        } else {
+            for (bucket, base) in terms {
+                buckets[bucket].add_assign(&base);
+            }
+        }
+
+        // Summation by parts:
+        // e.g. 3a + 2b + 1c = a +
+        //                    (a) + b +
+        //                    ((a) + b) + c
+        let mut running_sum = C::Curve::identity();
+        let mut window_sum = C::Curve::identity();
+        for bucket in buckets.iter().rev() {
+            running_sum = bucket.add(running_sum);
+            window_sum += &running_sum;
+        }
+        window_sums.push(window_sum);
+    }
+
+    let mut acc = C::Curve::identity();
+    for window_sum in window_sums.iter().rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+        acc += window_sum;
+    }
+    acc
+}
+
+/// The bucket method with unsigned window digits, which needs twice as many buckets
+/// as [`bucket_msm`]. This is kept as a reference implementation for testing.
+#[cfg(test)]
+fn bucket_msm_unsigned<C: CurveAffine, S: AsRef<[u8]>>(
+    bases: &[C],
+    scalars: &[S],
+    num_bits: usize,
+) -> C::Curve {
+    let c = window_size(bases.len());
+    let segments = (num_bits + c - 1) / c;
+
+    let mut acc = C::Curve::identity();
+    let mut buckets: Vec<Bucket<C>> = vec![Bucket::None; (1 << c) - 1];
+
+    for segment in (0..segments).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+
+        for bucket in buckets.iter_mut() {
+            *bucket = Bucket::None;
+        }
+
+        if bases.len() >= BATCH_AFFINE_THRESHOLD {
+            let entries = scalars
+                .iter()
+                .zip(bases.iter())
+                .filter_map(|(scalar, base)| {
+                    let window = get_window(segment, c, scalar.as_ref());
+                    (window != 0).then(|| (window - 1, *base))
+                })
+                .collect();
+            for (bucket, sum) in batch_affine_buckets(entries) {
+                buckets[bucket] = Bucket::Affine(sum);
+            }
+        } else {
+            for (scalar, base) in scalars.iter().zip(bases.iter()) {
+                let window = get_window(segment, c, scalar.as_ref());
+                if window != 0 {
+                    buckets[window - 1].add_assign(base);
+                }
+            }
+        }
+
+        // Summation by parts:
+        // e.g. 3a + 2b + 1c = a +
+        //                    (a) + b +
+        //                    ((a) + b) + c
+        let mut running_sum = C::Curve::identity();
+        for bucket in buckets.iter().rev() {
+            running_sum = bucket.add(running_sum);
+            acc += &running_sum;
+        }
+    }
+
+    acc
+}
+
+/// Computes the multi-scalar multiplication of the `(base, scalar)` pairs yielded by
+/// `terms`, buffering at most `chunk_size` of them at a time.
+///
+/// Each chunk is evaluated with [`multi_scalar_mul`] and the results are summed, so
+/// the buffers are reused across chunks and the terms never need to be held in
+/// memory all at once.
+///
+/// Panics if `chunk_size` is zero.
+pub(crate) fn multi_scalar_mul_chunked<
+    C: CurveAffine,
+    I: IntoIterator<Item = (C, C::ScalarExt)>,
+>(
+    terms: I,
+    chunk_size: usize,
+) -> C::Curve {
+    assert!(chunk_size > 0, "chunk size must be positive");
+
+    let mut bases = Vec::with_capacity(chunk_size);
+    let mut scalars = Vec::with_capacity(chunk_size);
+    let mut acc = C::Curve::identity();
+    let mut terms = terms.into_iter().peekable();
+    while terms.peek().is_some() {
+        bases.clear();
+        scalars.clear();
+        for (base, scalar) in terms.by_ref().take(chunk_size) {
+            bases.push(base);
+            scalars.push(scalar);
+        }
+        acc += multi_scalar_mul(&bases, &scalars);
+    }
+
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ff::{Field, PrimeField};
+    use group::prime::PrimeCurveAffine;
+    use rand::RngCore;
+
+    use super::{bucket_msm, bucket_msm_unsigned, window_size, MsmConfig, MAX_WINDOW_BITS};
+    use crate::arithmetic::CurveExt;
+    use crate::{pallas, vesta};
+
+    fn naive_msm<C: CurveExt>(bases: &[C::AffineExt], scalars: &[C::ScalarExt]) -> C {
+        bases
+            .iter()
+            .zip(scalars.iter())
+            .fold(C::identity(), |acc, (base, scalar)| acc + *base * *scalar)
+    }
+
+    fn check_msm<C: CurveExt, F: Fn(&[C::AffineExt], &[C::ScalarExt]) -> C>(msm: F) {
+        let mut rng = crate::tests::rng();
+
+        for &n in [0, 1, 2, 3, 10, 33, 100, 300].iter() {
+            let bases: Vec<_> = (0..n).map(|_| C::random(&mut rng).to_affine()).collect();
+            let scalars: Vec<_> = (0..n).map(|_| C::ScalarExt::random(&mut rng)).collect();
+            assert_eq!(msm(&bases, &scalars), naive_msm::<C>(&bases, &scalars));
+        }
+
+        // Edge cases: zero scalars, repeated bases, and the identity.
+        let g = C::generator().to_affine();
+        let bases = vec![g, g, C::AffineExt::identity(), -g];
+        let scalars = vec![
+            C::ScalarExt::ZERO,
+            C::ScalarExt::ONE,
+            C::ScalarExt::ONE,
+            -C::ScalarExt::ONE,
+        ];
+        assert_eq!(msm(&bases, &scalars), C::generator().double());
+
+        // The same edge cases with enough terms to use batched affine additions.
+        let bases: Vec<_> = bases.iter().cycle().take(400).cloned().collect();
+        let scalars: Vec<_> = scalars.iter().cycle().take(400).cloned().collect();
+        assert_eq!(
+            msm(&bases, &scalars),
+            C::generator() * C::ScalarExt::from(200u64)
+        );
+    }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        check_msm::<pallas::Point, _>(pallas::Point::multi_scalar_mul);
+        check_msm::<vesta::Point, _>(vesta::Point::multi_scalar_mul);
+    }
+
+    fn check_msm_u64<C: CurveExt>(msm: fn(&[C::AffineExt], &[u64]) -> C) {
+        let mut rng = crate::tests::rng();
+
+        for &n in [0, 1, 3, 33, 300].iter() {
+            for &bits in [1, 20, 32, 63, 64].iter() {
+                let bases: Vec<_> = (0..n).map(|_| C::random(&mut rng).to_affine()).collect();
+                let scalars: Vec<_> = (0..n).map(|_| rng.next_u64() >> (64 - bits)).collect();
+                let field_scalars: Vec<_> =
+                    scalars.iter().map(|&s| C::ScalarExt::from(s)).collect();
+                assert_eq!(
+                    msm(&bases, &scalars),
+                    naive_msm::<C>(&bases, &field_scalars)
+                );
+            }
+        }
+
+        let g = C::generator().to_affine();
+        assert_eq!(msm(&[g, g], &[0, 0]), C::identity());
+        assert_eq!(
+            msm(&[g, -g], &[u64::MAX, 1]),
+            C::generator() * C::ScalarExt::from(u64::MAX - 1)
+        );
+    }
+
+    fn check_signed_digits<C: CurveExt>() {
+        let mut rng = crate::tests::rng();
+
+        // Scalars whose windows sit at or around half the window range, which are the
+        // boundary cases of the recoding.
+        let mut halves = C::ScalarExt::ZERO;
+        for _ in 0..63 {
+            halves = halves.double().double().double().double() + C::ScalarExt::from(8u64);
+        }
+        let edge = [-C::ScalarExt::ONE, halves, -halves, halves.double()];
+
+        for &n in [1, 5, 40, 300].iter() {
+            let bases: Vec<_> = (0..n).map(|_| C::random(&mut rng).to_affine()).collect();
+            let scalars: Vec<_> = (0..n)
+                .map(|i| match edge.get(i % 8) {
+                    Some(s) => *s,
+                    None => C::ScalarExt::random(&mut rng),
+                })
+                .collect();
+            let reprs: Vec<_> = scalars.iter().map(|s| s.to_repr()).collect();
+            let num_bits = C::ScalarExt::NUM_BITS as usize;
+            assert_eq!(
+                bucket_msm(&bases, &reprs, num_bits, &MsmConfig::new()),
+                bucket_msm_unsigned(&bases, &reprs, num_bits)
+            );
+        }
+    }
+
+    #[test]
+    fn test_signed_digits() {
+        check_signed_digits::<pallas::Point>();
+        check_signed_digits::<vesta::Point>();
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_u64() {
+        check_msm_u64::<pallas::Point>(pallas::Point::multi_scalar_mul_u64);
+        check_msm_u64::<vesta::Point>(vesta::Point::multi_scalar_mul_u64);
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_with_config() {
+        use super::multi_scalar_mul_with_config;
+
+        let configs = [
+            MsmConfig::new(),
+            MsmConfig {
+                window_bits: Some(1),
+                ..MsmConfig::new()
+            },
+            MsmConfig {
+                window_bits: Some(16),
+                batch_affine_threshold: 2,
+                ..MsmConfig::new()
+            },
+            MsmConfig {
+                chunk_size: Some(7),
+                batch_affine_threshold: usize::MAX,
+                ..MsmConfig::new()
+            },
+            MsmConfig {
+                chunk_size: Some(64),
+                parallel_threshold: 0,
+                ..MsmConfig::new()
+            },
+            MsmConfig {
+                parallel_threshold: 0,
+                ..MsmConfig::new()
+            },
+        ];
+        for config in configs.iter() {
+            check_msm::<pallas::Point, _>(|bases, scalars| {
+                multi_scalar_mul_with_config(bases, scalars, config)
+            });
+        }
+        check_msm::<vesta::Point, _>(|bases, scalars| {
+            vesta::Point::multi_scalar_mul_with_config(bases, scalars, &configs[4])
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multi_scalar_mul_with_zero_window() {
+        let config = MsmConfig {
+            window_bits: Some(0),
+            ..MsmConfig::new()
+        };
+        pallas::Point::multi_scalar_mul_with_config(&[], &[], &config);
+    }
+
+    #[test]
+    fn test_window_size() {
+        assert_eq!(window_size(0), 1);
+        assert_eq!(window_size(1 << 16), 12);
+        assert_eq!(window_size(usize::MAX), MAX_WINDOW_BITS);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multi_scalar_mul_with_large_window() {
+        let config = MsmConfig {
+            window_bits: Some(21),
+            ..MsmConfig::new()
+        };
+        pallas::Point::multi_scalar_mul_with_config(&[], &[], &config);
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_chunked() {
+        for &chunk_size in [1, 7, 256].iter() {
+            check_msm::<pallas::Point, _>(|bases, scalars| {
+                pallas::Point::multi_scalar_mul_chunked(
+                    bases.iter().cloned().zip(scalars.iter().cloned()),
+                    chunk_size,
+                )
+            });
+            check_msm::<vesta::Point, _>(|bases, scalars| {
+                vesta::Point::multi_scalar_mul_chunked(
+                    bases.iter().cloned().zip(scalars.iter().cloned()),
+                    chunk_size,
+                )
+            });
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! This module controls the rayon thread pool that the parallel APIs of this crate
+//! run on.
+//!
+//! With the `multicore` feature flag, the transforms of
+//! [`EvaluationDomain`](crate::fft::EvaluationDomain), and the APIs built on them
+//! such as [`Polynomial::mul_fft`](crate::poly::Polynomial::mul_fft), large
+//! multi-scalar multiplications as configured by
+//! [`MsmConfig`](crate::MsmConfig), and `Fp::batch_sqrt` and `Fq::batch_sqrt`
+//! split their work across the current rayon thread pool. Outside of any pool
+//! that is the global pool, which is shared with every other user of rayon in the
+//! process. Running them within [`in_pool`] confines them to a dedicated pool
+//! instead, so that proving work cannot starve latency-sensitive tasks on the
+//! global pool:
+//!
+//! ```
+//! # extern crate fil_pasta_curves as pasta_curves;
+//! use pasta_curves::{fft::EvaluationDomain, multicore, Fp};
+//!
+//! let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+//! let domain = EvaluationDomain::<Fp>::new(12);
+//! let mut a = vec![Fp::from(3u64); domain.size()];
+//! multicore::in_pool(Some(&pool), || domain.fft(&mut a));
+//! ```
+
+pub use rayon::ThreadPool;
+
+/// Runs `op` on `pool`, so that the parallel APIs it calls split their work across
+/// the threads of `pool`, or on the current thread pool if `pool` is `None`.
+///
+/// This blocks until `op` completes.
+pub fn in_pool<OP, R>(pool: Option<&ThreadPool>, op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use ff::Field;
+
+    use super::in_pool;
+    use crate::{fft::EvaluationDomain, Fp};
+
+    #[test]
+    fn test_in_pool() {
+        let mut rng = crate::tests::rng();
+
+        let domain = EvaluationDomain::<Fp>::new(11);
+        let coeffs: Vec<_> = (0..domain.size()).map(|_| Fp::random(&mut rng)).collect();
+        let mut expected = coeffs.clone();
+        domain.fft(&mut expected);
+
+        for threads in [1, 3] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let mut a = coeffs.clone();
+            let num_threads = in_pool(Some(&pool), || {
+                domain.fft(&mut a);
+                rayon::current_num_threads()
+            });
+            assert_eq!(num_threads, threads);
+            assert_eq!(a, expected);
+        }
+
+        let mut a = coeffs;
+        in_pool(None, || domain.fft(&mut a));
+        assert_eq!(a, expected);
+    }
+}
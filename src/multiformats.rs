@@ -0,0 +1,451 @@
+//! Multicodec and multibase encodings of field elements and points.
+//!
+//! The [multicodec] encoding of a value is its canonical 32-byte representation
+//! prefixed with the unsigned varint of a code identifying its type, and its
+//! [multibase] encoding is a string of those bytes with a one-character prefix
+//! identifying the base. Together these let field elements and commitments be
+//! embedded in CIDs and IPLD data, and be decoded without knowing their type in
+//! advance.
+//!
+//! No codes are registered in the multicodec table for the Pasta curves, so the
+//! codes used here are in its private use range, `0x300000` to `0x3fffff`. They
+//! are only meaningful to applications that agree on them.
+//!
+//! **The codes are unstable.** They may change in a future release, for example
+//! if codes for the Pasta curves are registered, so they should not yet be used in
+//! data that must be decodable by later versions of this crate.
+//!
+//! ```
+//! # extern crate fil_pasta_curves as pasta_curves;
+//! use group::prime::PrimeCurveAffine;
+//! use pasta_curves::{
+//!     multiformats::{Multibase, Multicodec},
+//!     pallas,
+//! };
+//!
+//! let g = pallas::Affine::generator();
+//! let s = g.to_multibase(Multibase::Base32);
+//! assert!(s.starts_with('b'));
+//! assert_eq!(pallas::Affine::from_multibase(&s), Ok(g));
+//! assert_eq!(pallas::Affine::from_multicodec(&g.to_multicodec()), Ok(g));
+//! ```
+//!
+//! [multicodec]: https://github.com/multiformats/multicodec
+//! [multibase]: https://github.com/multiformats/multibase
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use ff::PrimeField;
+use group::GroupEncoding;
+
+use crate::curves::{EpAffine, EqAffine};
+use crate::fields::{Fp, Fq};
+
+/// The multicodec code of a Pallas base field element, [`Fp`]. This is unstable,
+/// like the other codes of this module.
+pub const PALLAS_BASE_CODE: u64 = 0x30_7001;
+/// The multicodec code of a Pallas scalar, [`Fq`]. This is unstable, like the
+/// other codes of this module.
+pub const PALLAS_SCALAR_CODE: u64 = 0x30_7002;
+/// The multicodec code of a compressed Pallas point, [`EpAffine`]. This is
+/// unstable, like the other codes of this module.
+pub const PALLAS_POINT_CODE: u64 = 0x30_7003;
+/// The multicodec code of a compressed Vesta point, [`EqAffine`]. This is
+/// unstable, like the other codes of this module.
+pub const VESTA_POINT_CODE: u64 = 0x30_7004;
+
+/// An error which can be returned when decoding a multicodec or multibase
+/// encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseMulticodecError {
+    /// The code prefix is not a minimally encoded varint of at most 9 bytes.
+    InvalidVarint,
+    /// The code prefix is not the code of the expected type.
+    UnexpectedCode(u64),
+    /// The encoding after the code prefix is not 32 bytes long.
+    InvalidLength,
+    /// The multibase prefix is not supported, or the string contains a character
+    /// outside its alphabet.
+    InvalidMultibase,
+    /// The bytes do not encode a field element or a point on the curve.
+    InvalidEncoding,
+}
+
+impl fmt::Display for ParseMulticodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMulticodecError::InvalidVarint => f.write_str("invalid multicodec varint"),
+            ParseMulticodecError::UnexpectedCode(code) => {
+                write!(f, "unexpected multicodec code {:#x}", code)
+            }
+            ParseMulticodecError::InvalidLength => f.write_str("expected 32 bytes after the code"),
+            ParseMulticodecError::InvalidMultibase => f.write_str("invalid multibase string"),
+            ParseMulticodecError::InvalidEncoding => {
+                f.write_str("bytes don't encode a field element or point")
+            }
+        }
+    }
+}
+
+/// A multibase base, identified by the prefix of the encoded string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Multibase {
+    /// Lowercase RFC 4648 base32 without padding, with the prefix `b`. This is the
+    /// default base of CIDv1 strings.
+    Base32,
+    /// Bitcoin's base58, with the prefix `z`.
+    Base58Btc,
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Appends the unsigned varint encoding of `n` to `out`.
+fn write_varint(mut n: u64, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Reads a minimally encoded unsigned varint of at most 9 bytes from the start of
+/// `bytes`, returning it and the remaining bytes.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), ParseMulticodecError> {
+    let mut n = 0;
+    for (i, &b) in bytes.iter().enumerate().take(9) {
+        n |= u64::from(b & 0x7f) << (7 * i);
+        if b & 0x80 == 0 {
+            // A final zero byte could have been omitted.
+            if b == 0 && i > 0 {
+                return Err(ParseMulticodecError::InvalidVarint);
+            }
+            return Ok((n, &bytes[i + 1..]));
+        }
+    }
+    Err(ParseMulticodecError::InvalidVarint)
+}
+
+fn encode_base32(bytes: &[u8], s: &mut String) {
+    let (mut acc, mut bits) = (0u32, 0);
+    for &b in bytes {
+        acc = acc << 8 | u32::from(b);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            s.push(BASE32_ALPHABET[(acc >> bits & 0x1f) as usize] as char);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        s.push(BASE32_ALPHABET[(acc << (5 - bits) & 0x1f) as usize] as char);
+    }
+}
+
+fn decode_base32(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() * 5 / 8);
+    let (mut acc, mut bits) = (0u32, 0);
+    for c in s.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)?;
+        acc = acc << 5 | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    // Leftover bits are padding, and must be zero and less than a full symbol.
+    if acc != 0 || bits >= 5 {
+        return None;
+    }
+    Some(out)
+}
+
+fn encode_base58(bytes: &[u8], s: &mut String) {
+    // Little-endian base-58 digits of the big-endian integer `bytes`.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &b in bytes {
+        let mut carry = u32::from(b);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // Each leading zero byte is encoded as a leading `1`.
+    for _ in bytes.iter().take_while(|&&b| b == 0) {
+        s.push('1');
+    }
+    for &digit in digits.iter().rev() {
+        s.push(BASE58_ALPHABET[digit as usize] as char);
+    }
+}
+
+/// Decodes a base58 string of at most `max_bytes` bytes.
+fn decode_base58(s: &str, max_bytes: usize) -> Option<Vec<u8>> {
+    // Decoding takes time quadratic in the length of `s`, so strings longer than
+    // any encoding of `max_bytes` bytes are rejected first. Each byte takes
+    // log(256) / log(58) < 1.38 digits.
+    if s.len() > max_bytes * 138 / 100 + 1 {
+        return None;
+    }
+
+    // Little-endian bytes of the integer.
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len());
+    for c in s.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    bytes.extend(core::iter::repeat(0).take(zeros));
+    bytes.reverse();
+    Some(bytes)
+}
+
+/// A type with a multicodec code, whose canonical encoding can be wrapped in
+/// multicodec and multibase encodings.
+pub trait Multicodec: Sized {
+    /// The multicodec code of this type.
+    const CODE: u64;
+
+    /// Returns the canonical encoding of this value, prefixed with the varint of
+    /// [`Self::CODE`].
+    fn to_multicodec(&self) -> Vec<u8>;
+
+    /// Decodes a value from its canonical encoding prefixed with the varint of
+    /// [`Self::CODE`].
+    fn from_multicodec(bytes: &[u8]) -> Result<Self, ParseMulticodecError>;
+
+    /// Returns the multibase string of the multicodec encoding of this value.
+    fn to_multibase(&self, base: Multibase) -> String {
+        let bytes = self.to_multicodec();
+        let mut s = String::with_capacity(2 * bytes.len());
+        match base {
+            Multibase::Base32 => {
+                s.push('b');
+                encode_base32(&bytes, &mut s);
+            }
+            Multibase::Base58Btc => {
+                s.push('z');
+                encode_base58(&bytes, &mut s);
+            }
+        }
+        s
+    }
+
+    /// Decodes a value from the multibase string of its multicodec encoding, in
+    /// any of the supported bases.
+    fn from_multibase(s: &str) -> Result<Self, ParseMulticodecError> {
+        let mut prefix = Vec::new();
+        write_varint(Self::CODE, &mut prefix);
+        let max_bytes = prefix.len() + 32;
+
+        let mut chars = s.chars();
+        let bytes = match chars.next() {
+            Some('b') => decode_base32(chars.as_str()),
+            Some('z') => decode_base58(chars.as_str(), max_bytes),
+            _ => None,
+        }
+        .ok_or(ParseMulticodecError::InvalidMultibase)?;
+        Self::from_multicodec(&bytes)
+    }
+}
+
+/// Checks the code prefix of `bytes` and returns the 32 bytes that follow it.
+fn strip_code(bytes: &[u8], code: u64) -> Result<[u8; 32], ParseMulticodecError> {
+    let (found, rest) = read_varint(bytes)?;
+    if found != code {
+        return Err(ParseMulticodecError::UnexpectedCode(found));
+    }
+    let mut repr = [0; 32];
+    if rest.len() != repr.len() {
+        return Err(ParseMulticodecError::InvalidLength);
+    }
+    repr.copy_from_slice(rest);
+    Ok(repr)
+}
+
+macro_rules! impl_multicodec {
+    ($t:ident, $code:ident, |$value:ident| $encode:expr, |$bytes:ident| $decode:expr) => {
+        impl Multicodec for $t {
+            const CODE: u64 = $code;
+
+            fn to_multicodec(&self) -> Vec<u8> {
+                let $value = self;
+                let mut bytes = Vec::with_capacity(36);
+                write_varint(Self::CODE, &mut bytes);
+                bytes.extend_from_slice(&$encode);
+                bytes
+            }
+
+            fn from_multicodec(bytes: &[u8]) -> Result<Self, ParseMulticodecError> {
+                let $bytes = strip_code(bytes, Self::CODE)?;
+                Option::from($decode).ok_or(ParseMulticodecError::InvalidEncoding)
+            }
+        }
+    };
+}
+
+impl_multicodec!(
+    Fp,
+    PALLAS_BASE_CODE,
+    |f| f.to_repr(),
+    |bytes| Fp::from_repr(bytes)
+);
+impl_multicodec!(Fq, PALLAS_SCALAR_CODE, |f| f.to_repr(), |bytes| {
+    Fq::from_repr(bytes)
+});
+impl_multicodec!(EpAffine, PALLAS_POINT_CODE, |p| p.to_bytes(), |bytes| {
+    EpAffine::from_bytes(&bytes)
+});
+impl_multicodec!(EqAffine, VESTA_POINT_CODE, |p| p.to_bytes(), |bytes| {
+    EqAffine::from_bytes(&bytes)
+});
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+
+    use super::*;
+    use crate::curves::{Ep, Eq};
+
+    #[test]
+    fn test_varint() {
+        for (n, encoding) in [
+            (0, &[0x00][..]),
+            (1, &[0x01][..]),
+            (0x7f, &[0x7f][..]),
+            (0x80, &[0x80, 0x01][..]),
+            (0x3fff, &[0xff, 0x7f][..]),
+            (PALLAS_BASE_CODE, &[0x81, 0xe0, 0xc1, 0x01][..]),
+        ] {
+            let mut bytes = Vec::new();
+            write_varint(n, &mut bytes);
+            assert_eq!(bytes, encoding);
+            assert_eq!(read_varint(&bytes), Ok((n, &[][..])));
+        }
+
+        // Non-minimal, truncated and overlong varints are rejected.
+        let invalid = [&[0x80, 0x00][..], &[][..], &[0x80][..], &[0xff; 10][..]];
+        for bytes in invalid {
+            assert_eq!(read_varint(bytes), Err(ParseMulticodecError::InvalidVarint));
+        }
+    }
+
+    #[test]
+    fn test_bases() {
+        // Vectors from the multibase test suite, for "yes mani !".
+        let mut s = String::new();
+        encode_base32(b"yes mani !", &mut s);
+        assert_eq!(s, "pfsxgidnmfxgsibb");
+        assert_eq!(decode_base32(&s).unwrap(), b"yes mani !");
+        let mut s = String::new();
+        encode_base58(b"yes mani !", &mut s);
+        assert_eq!(s, "7paNL19xttacUY");
+        assert_eq!(decode_base58(&s, 32).unwrap(), b"yes mani !");
+
+        // Leading zero bytes are kept.
+        let mut s = String::new();
+        encode_base58(&[0, 0, 1], &mut s);
+        assert_eq!(s, "112");
+        assert_eq!(decode_base58(&s, 32).unwrap(), [0, 0, 1]);
+
+        assert!(decode_base32("pfsxgidnmfxgsib1").is_none());
+        assert!(decode_base32("a").is_none());
+        assert!(decode_base58("7paNL19xttac0Y", 32).is_none());
+    }
+
+    #[test]
+    fn test_multicodec_roundtrip() {
+        let mut rng = crate::tests::rng();
+
+        fn roundtrip<T: Multicodec + Copy + fmt::Debug + PartialEq>(t: T) {
+            assert_eq!(T::from_multicodec(&t.to_multicodec()), Ok(t));
+            for base in [Multibase::Base32, Multibase::Base58Btc] {
+                assert_eq!(T::from_multibase(&t.to_multibase(base)), Ok(t));
+            }
+        }
+
+        for _ in 0..20 {
+            roundtrip(Fp::random(&mut rng));
+            roundtrip(Fq::random(&mut rng));
+            roundtrip(Ep::random(&mut rng).to_affine());
+            roundtrip(Eq::random(&mut rng).to_affine());
+        }
+        roundtrip(Fp::zero());
+        roundtrip(EpAffine::identity());
+    }
+
+    #[test]
+    fn test_multicodec_errors() {
+        let g = EpAffine::generator();
+        let bytes = g.to_multicodec();
+        assert_eq!(bytes.len(), 36);
+        assert_eq!(bytes[..4], [0x83, 0xe0, 0xc1, 0x01]);
+        assert_eq!(bytes[4..], g.to_bytes());
+
+        // The code must match the type.
+        assert_eq!(
+            EqAffine::from_multicodec(&bytes),
+            Err(ParseMulticodecError::UnexpectedCode(PALLAS_POINT_CODE))
+        );
+        assert_eq!(
+            EpAffine::from_multicodec(&bytes[..35]),
+            Err(ParseMulticodecError::InvalidLength)
+        );
+
+        let mut invalid = Vec::new();
+        write_varint(PALLAS_SCALAR_CODE, &mut invalid);
+        invalid.extend_from_slice(&[0xff; 32]);
+        assert_eq!(
+            Fq::from_multicodec(&invalid),
+            Err(ParseMulticodecError::InvalidEncoding)
+        );
+
+        // Only the base32 and base58btc prefixes are supported.
+        let s = g.to_multibase(Multibase::Base58Btc);
+        assert!(s.starts_with('z'));
+        assert_eq!(
+            EpAffine::from_multibase(&s.replacen('z', "m", 1)),
+            Err(ParseMulticodecError::InvalidMultibase)
+        );
+        assert_eq!(
+            EpAffine::from_multibase(""),
+            Err(ParseMulticodecError::InvalidMultibase)
+        );
+
+        // Base58 strings longer than any encoding are rejected before decoding.
+        let long = format!("z{}", "1".repeat(51));
+        assert_eq!(
+            Fp::from_multibase(&long),
+            Err(ParseMulticodecError::InvalidMultibase)
+        );
+        let long = format!("z{}", "2".repeat(1 << 20));
+        assert_eq!(
+            Fp::from_multibase(&long),
+            Err(ParseMulticodecError::InvalidMultibase)
+        );
+        assert_eq!(
+            Fp::from_multibase(&format!("z{}", "1".repeat(50))),
+            Err(ParseMulticodecError::UnexpectedCode(0))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! Deterministic nonce derivation for the signature schemes.
+//!
+//! In the style of RFC 6979 and BIP 340, a nonce is derived by hashing the secret
+//! key and the signed data, so that signing needs no randomness and nonces never
+//! repeat across different messages. The hash is BLAKE2b-512, personalized by the
+//! scheme, and its output is reduced into the scalar field with
+//! `FieldExt::from_bytes_wide`.
+
+use crate::arithmetic::FieldExt;
+
+/// Derives a nonce from the secret key `sk` and the concatenation of `inputs`,
+/// using the BLAKE2b personalization `personalization` to separate schemes.
+pub(crate) fn derive_nonce<F: FieldExt>(personalization: &[u8; 16], sk: &F, inputs: &[&[u8]]) -> F {
+    let mut state = blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(personalization)
+        .to_state();
+    state.update(sk.to_repr().as_ref());
+    for input in inputs {
+        state.update(input);
+    }
+    F::from_bytes_wide(state.finalize().as_array())
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+
+    use super::derive_nonce;
+    use crate::Fq;
+
+    #[test]
+    fn test_derive_nonce() {
+        let sk = Fq::from(5u64);
+        let k = derive_nonce(b"fil_pasta_test__", &sk, &[b"hello"]);
+        assert!(!bool::from(k.is_zero()));
+
+        // Nonces are deterministic, and only depend on the concatenated inputs.
+        assert_eq!(derive_nonce(b"fil_pasta_test__", &sk, &[b"he", b"llo"]), k);
+
+        // The key, the inputs and the personalization are all bound.
+        assert_ne!(
+            derive_nonce(b"fil_pasta_test__", &Fq::one(), &[b"hello"]),
+            k
+        );
+        assert_ne!(derive_nonce(b"fil_pasta_test__", &sk, &[b"hellO"]), k);
+        assert_ne!(derive_nonce(b"fil_pasta_other_", &sk, &[b"hello"]), k);
+    }
+}
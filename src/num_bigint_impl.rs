@@ -0,0 +1,99 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use ff::PrimeField;
+use num_bigint::BigUint;
+
+use crate::fields::{Fp, Fq};
+
+/// The error returned when converting a [`BigUint`] that is not less than the
+/// field modulus into a field element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromBigUintError(());
+
+impl fmt::Display for TryFromBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("integer is not less than the field modulus")
+    }
+}
+
+macro_rules! impl_num_bigint {
+    ($field:ident) => {
+        impl From<&$field> for BigUint {
+            fn from(f: &$field) -> BigUint {
+                BigUint::from_bytes_le(&f.to_repr())
+            }
+        }
+
+        impl From<$field> for BigUint {
+            fn from(f: $field) -> BigUint {
+                BigUint::from(&f)
+            }
+        }
+
+        impl TryFrom<&BigUint> for $field {
+            type Error = TryFromBigUintError;
+
+            fn try_from(n: &BigUint) -> Result<Self, Self::Error> {
+                let bytes = n.to_bytes_le();
+                let mut repr = <$field as PrimeField>::Repr::default();
+                if bytes.len() > repr.len() {
+                    return Err(TryFromBigUintError(()));
+                }
+                repr[..bytes.len()].copy_from_slice(&bytes);
+                Option::from($field::from_repr(repr)).ok_or(TryFromBigUintError(()))
+            }
+        }
+    };
+}
+
+impl_num_bigint!(Fp);
+impl_num_bigint!(Fq);
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use ff::Field;
+    use num_bigint::BigUint;
+
+    use super::TryFromBigUintError;
+    use crate::{Fp, Fq};
+
+    fn check_field<F>()
+    where
+        F: Field + for<'a> TryFrom<&'a BigUint, Error = TryFromBigUintError>,
+        for<'a> BigUint: From<&'a F>,
+    {
+        let mut rng = crate::tests::rng();
+
+        let zero = BigUint::from_bytes_le(&[0]);
+        let one = BigUint::from_bytes_le(&[1]);
+        assert_eq!(BigUint::from(&F::ZERO), zero);
+        assert_eq!(BigUint::from(&F::ONE), one);
+        assert_eq!(F::try_from(&zero), Ok(F::ZERO));
+        assert_eq!(F::try_from(&one), Ok(F::ONE));
+
+        for _ in 0..100 {
+            let a = F::random(&mut rng);
+            assert_eq!(F::try_from(&BigUint::from(&a)), Ok(a));
+        }
+
+        let modulus_minus_one = BigUint::from(&-F::ONE);
+        assert_eq!(F::try_from(&modulus_minus_one), Ok(-F::ONE));
+        assert_eq!(
+            F::try_from(&(modulus_minus_one + 1u32)),
+            Err(TryFromBigUintError(()))
+        );
+        assert_eq!(
+            F::try_from(&BigUint::from_bytes_le(&[0xff; 33])),
+            Err(TryFromBigUintError(()))
+        );
+    }
+
+    #[test]
+    fn test_biguint_conversions() {
+        check_field::<Fp>();
+        check_field::<Fq>();
+    }
+}
@@ -1,6 +1,6 @@
 //! The Pallas and iso-Pallas elliptic curve groups.
 
-use super::{Ep, EpAffine, Fp, Fq};
+use super::{Ep, EpAffine, Fp, Fq, IsoEp, IsoEpAffine};
 
 /// The base field of the Pallas and iso-Pallas curves.
 pub type Base = Fp;
@@ -14,6 +14,19 @@ pub type Point = Ep;
 /// A Pallas point in the affine coordinate space (or the point at infinity).
 pub type Affine = EpAffine;
 
+/// An iso-Pallas point in the projective coordinate space.
+pub type IsoPoint = IsoEp;
+
+/// An iso-Pallas point in the affine coordinate space (or the point at infinity).
+pub type IsoAffine = IsoEpAffine;
+
+/// The ID of the RFC 9380 hash-to-curve suite for Pallas with `expand_message_xmd`
+/// and SHA-256, which is implemented by [`Point::hash_to_curve_with`] with
+/// [`ExpandMsgXmd<Sha256>`](crate::ExpandMsgXmd).
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub const SUITE_ID_XMD_SHA256: &str = "pallas_XMD:SHA-256_SSWU_RO_";
+
 #[cfg(feature = "alloc")]
 #[test]
 #[allow(clippy::many_single_char_names)]
@@ -63,6 +76,9 @@ fn test_iso_map() {
     let p2 = super::hashtocurve::iso_map::<_, Point, super::IsoEp>(&r2, &Ep::ISOGENY_CONSTANTS);
     assert!(bool::from(p2.is_on_curve()));
     assert!(p2 == p.double());
+
+    assert_eq!(Point::iso_map(&r), p);
+    assert_eq!(Point::iso_map(&r2), p2);
 }
 
 #[cfg(feature = "alloc")]
@@ -162,3 +178,221 @@ fn test_hash_to_curve() {
     assert!(bool::from(p.is_on_curve()));
     assert!(bool::from(p.is_identity()));
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_map_to_curve() {
+    use crate::arithmetic::CurveExt;
+    use crate::hashtocurve;
+    use ff::Field;
+
+    let mut rng = crate::tests::rng();
+
+    for u in [Base::zero(), Base::one(), Base::random(&mut rng)] {
+        let q = Point::map_to_curve_simple_swu(&u);
+        assert_eq!(
+            q,
+            hashtocurve::map_to_curve_simple_swu::<Base, Point, IsoPoint>(
+                &u,
+                Point::THETA,
+                Point::Z
+            )
+        );
+        assert!(bool::from(q.is_on_curve()));
+        assert_eq!(Point::map_to_curve(&u), Point::iso_map(&q));
+    }
+
+    let us = Base::hash_to_field("z.cash:test", b"Trans rights now!", 2);
+    assert_eq!(
+        Point::iso_map(
+            &(Point::map_to_curve_simple_swu(&us[0]) + Point::map_to_curve_simple_swu(&us[1]))
+        ),
+        Point::hash_to_curve("z.cash:test")(b"Trans rights now!")
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hash_to_field_matches_hash_to_curve() {
+    use crate::arithmetic::CurveExt;
+    use crate::curves::{Ep, IsoEp};
+    use crate::hashtocurve;
+
+    let us = Base::hash_to_field("z.cash:test", b"Trans rights now!", 2);
+    let r = hashtocurve::map_to_curve_simple_swu::<Base, Ep, IsoEp>(&us[0], Ep::THETA, Ep::Z)
+        + hashtocurve::map_to_curve_simple_swu::<Base, Ep, IsoEp>(&us[1], Ep::THETA, Ep::Z);
+    assert_eq!(
+        hashtocurve::iso_map::<Base, Ep, IsoEp>(&r, &Ep::ISOGENY_CONSTANTS),
+        Point::hash_to_curve("z.cash:test")(b"Trans rights now!")
+    );
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_hash_to_curve_with() {
+    use crate::arithmetic::CurveExt;
+    use crate::ExpandMsgXmd;
+    use group::Group;
+    use sha2::Sha256;
+
+    let dst = b"pallas_XMD:SHA-256_SSWU_RO_";
+    let hash = |msg: &[u8]| Point::hash_to_curve_with::<ExpandMsgXmd<Sha256>>(dst, msg);
+    let p = hash(b"abc");
+    assert!(bool::from(p.is_on_curve()));
+    assert!(!bool::from(p.is_identity()));
+    assert_eq!(p, hash(b"abc"));
+    assert_ne!(p, hash(b"abcd"));
+    assert_ne!(
+        p,
+        Point::hash_to_curve_with::<ExpandMsgXmd<Sha256>>(b"other", b"abc")
+    );
+}
+
+#[cfg(feature = "sha256")]
+#[test]
+fn test_hash_to_curve_sha256() {
+    use crate::hashtocurve::tests::suite::{check_suite, TestVector};
+
+    // Vectors in the format of RFC 9380, appendix J, for the messages "", "abc",
+    // "abcdef0123456789", "q128_qqq..." and "a512_aaa...". They are generated by
+    // scripts/hash_to_curve_vectors.py, an independent implementation of the RFC in
+    // Python with the constants of the Zcash protocol specification.
+    check_suite(
+        b"QUUX-V01-CS02-with-pallas_XMD:SHA-256_SSWU_RO_",
+        Point::hash_to_curve_sha256,
+        |u| {
+            let q = crate::hashtocurve::map_to_curve_simple_swu::<_, Point, super::IsoEp>(
+                u,
+                Point::THETA,
+                Point::Z,
+            );
+            crate::hashtocurve::iso_map(&q, &Point::ISOGENY_CONSTANTS)
+        },
+        &[
+            TestVector {
+                p: (
+                    "0x08d9956f24425586fa155c4aabdfdd6627e5078c4f9d40b12a735a1f47521a8b",
+                    "0x328efeab447f35caab39209b4d8e17b31443ce0c24f3a1ec6b8db51399e7f146",
+                ),
+                u: [
+                    "0x01dd51ec1e22f4d0fda07a241f8dfa7f9653b6b1a90777f811ee07ab7547d887",
+                    "0x13f3a6294a601110d8d03cf79d11ce0c23f769919ba506bfeb7c20daa122fc78",
+                ],
+                q0: (
+                    "0x032a9cf9ab3775a1d7e69f57e5e1192295d373d7a702b651b5268dc1f072b12d",
+                    "0x2a2df781878ffada044e2243d475491539f27f563743aa22f686e72a5c14deee",
+                ),
+                q1: (
+                    "0x3efb3049a613ce8b023bfca060c580d7dbeb08677820394c5441915f2abb0d1c",
+                    "0x3099180b28ef551bc1e8dcc4d36b4a0fe88d66121feecb85a7b76f671f948fe6",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x354714330e244e1ba390e700f62f122ea90c7b10efed2e2c960501f3939c2a71",
+                    "0x010908f7cfc94e040bf741e4759d71cf598d3298cb51173ce77ee363d6109417",
+                ),
+                u: [
+                    "0x18c83b524f1855fd795ab6c881c0f490baab5ef86cb77416fcf2e46df5b784ae",
+                    "0x145f96537ee8c4444bc4782f984c1a9360b032d371997c775af841a66f38fa18",
+                ],
+                q0: (
+                    "0x2a3e3b59729ceb17356b7c8b2fea741f6dc7e31d346268c811632d2318f4240a",
+                    "0x31c4bd2686bb9bf3e08f72d924797878dbc09cc75b4264b3e57187834f3afef2",
+                ),
+                q1: (
+                    "0x0f5a99ae3d499ed5b5ff4e1dfc86c435c3cc7ad2c501e9c1aed079d8003dcb07",
+                    "0x23bc1f576d15e8e58bdbdc7d5cd80fe9261cec63dff8a27621b34d191ce5d11d",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x19f3d994235c0a2ccd3578bf184cf2bbd75323bd9ff39c9315cd1630f83c1fc9",
+                    "0x3094fd161b8631bd8022b6a2fb2a3cd7aa4defa5632ddba948d62b1b41bd4814",
+                ),
+                u: [
+                    "0x27b595e4f562fa40da11c5daa84260076edf334b448df35fc4111c72e63aa5bf",
+                    "0x1d212a52efd00688b0684e07fbf4d00f99fe072c7c6234dc95fdf552ed5ad789",
+                ],
+                q0: (
+                    "0x3d800b3ae8efb28de04a0ad90140ea804a8d14577fe8626249f2fa6598ea4330",
+                    "0x1a74033ae15227969924c2b61923459d4009767d387fa498d54f1271adf5b67f",
+                ),
+                q1: (
+                    "0x1a14d326539aed64eaf0b97aa644eef5237423e057ba9269dd9e1e9aa6650e1b",
+                    "0x0b41c23fba2d83e2871cc4bfd40be7b85c5afd482c53012dbc9d43bc10f988ee",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x2b8178e549d489bbec297d59730175f66685afac6dc73e3d6c61af1f5fbf53fd",
+                    "0x2a3ea06c15dfd79c9d4e9e42633663972fb8bf1d451359ddebd6f901f865b7a1",
+                ),
+                u: [
+                    "0x2b9af4ede1b7d1dd3c1e34b5099a8e568c9054820183e3cea078131c56d2bfcc",
+                    "0x352feac58e921a78a205f58940a50a102faf62f32d3f2ff524fa22560711a615",
+                ],
+                q0: (
+                    "0x14762dafa94c8f4e766fdf1a53713977d99779016150c686f73fc228363fcc61",
+                    "0x1cdc801439ae5b0f733533f71789b3deb892069d0ac2203e2b81744475d53de0",
+                ),
+                q1: (
+                    "0x0e00c17c015d4e18ec65bb9e42e83decc426aac330be3c9191f3f20adec9524c",
+                    "0x0fcd59dae9a9940d72446a2c4ee64942b3dfb8e6c4d5d4c657fe91b25a3c4663",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x1616fedf433f569f85d62ab9c8479a2c784badc236f2b28fceea0acf68df46b1",
+                    "0x0802cd395392951f7295b3596b76328b3246bd7f9efd1d87a7af627a0478b1a5",
+                ),
+                u: [
+                    "0x0aaf607575e2291daae2a2bd8f0e7d1e3335a014930c8d61a1e3bf41d7f4cae8",
+                    "0x3dfd3389c7c61d984c418355b856a52db1271d26b3eb37c8eda18e169d121b9e",
+                ],
+                q0: (
+                    "0x3674f7f35e2906ea042893ef685d0067f76a35aaf9f4c2e3869aae6df2052076",
+                    "0x2d76e41fd8b6b5266f594f3cb1ebad5a17d3bb80a96ea6889157a89405a527af",
+                ),
+                q1: (
+                    "0x039fb03f5fcdf3672f3991e39111fdd506fb5be150b03babea7cb867cb3a7077",
+                    "0x30590cf535731977f77a47aa2f9bfd77f2bc7ebc25a76b26802b950c5e361bba",
+                ),
+            },
+        ],
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_curve_hasher() {
+    use alloc::vec::Vec;
+
+    use crate::arithmetic::CurveExt;
+    use crate::CurveHasher;
+
+    let message: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    let expected = Point::hash_to_curve("z.cash:test")(&message);
+    for chunk_size in [1, 7, 128, 1000] {
+        let mut hasher = CurveHasher::<Point>::new("z.cash:test");
+        for chunk in message.chunks(chunk_size) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), expected);
+    }
+
+    assert_eq!(
+        CurveHasher::<Point>::new("z.cash:test").finalize(),
+        Point::hash_to_curve("z.cash:test")(&[])
+    );
+
+    #[cfg(feature = "std")]
+    {
+        let mut hasher = CurveHasher::<crate::vesta::Point>::new("z.cash:test");
+        std::io::copy(&mut &message[..], &mut hasher).unwrap();
+        assert_eq!(
+            hasher.finalize(),
+            crate::vesta::Point::hash_to_curve("z.cash:test")(&message)
+        );
+    }
+}
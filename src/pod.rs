@@ -0,0 +1,152 @@
+//! This module provides plain-old-data wrappers for the 32-byte encodings of
+//! field elements and points, so that buffers of encodings can be reinterpreted
+//! without copying via [`bytemuck`].
+//!
+//! The wrappers only hold bytes: decoding them is checked exactly as it is for
+//! [`PrimeField::from_repr`] and [`GroupEncoding::from_bytes`].
+
+use ff::PrimeField;
+use group::{Curve, GroupEncoding};
+use subtle::CtOption;
+
+use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq};
+
+/// The 32-byte canonical encoding of a field element, as returned by
+/// [`PrimeField::to_repr`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct FieldBytes(pub [u8; 32]);
+
+// SAFETY: `FieldBytes` is a `repr(transparent)` wrapper around `[u8; 32]`, which is
+// valid for any bit pattern, including all zeroes.
+#[allow(unsafe_code)]
+unsafe impl bytemuck::Zeroable for FieldBytes {}
+#[allow(unsafe_code)]
+unsafe impl bytemuck::Pod for FieldBytes {}
+
+impl FieldBytes {
+    /// Decodes these bytes as an element of $\mathbb{F}_p$, failing if they are not
+    /// a canonical encoding.
+    pub fn to_fp(&self) -> CtOption<Fp> {
+        Fp::from_repr(self.0)
+    }
+
+    /// Decodes these bytes as an element of $\mathbb{F}_q$, failing if they are not
+    /// a canonical encoding.
+    pub fn to_fq(&self) -> CtOption<Fq> {
+        Fq::from_repr(self.0)
+    }
+}
+
+impl From<Fp> for FieldBytes {
+    fn from(f: Fp) -> Self {
+        FieldBytes(f.to_repr())
+    }
+}
+
+impl From<Fq> for FieldBytes {
+    fn from(f: Fq) -> Self {
+        FieldBytes(f.to_repr())
+    }
+}
+
+impl AsRef<[u8]> for FieldBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The 32-byte compressed encoding of a point, as returned by
+/// [`GroupEncoding::to_bytes`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PointBytes(pub [u8; 32]);
+
+// SAFETY: `PointBytes` is a `repr(transparent)` wrapper around `[u8; 32]`, which is
+// valid for any bit pattern, including all zeroes.
+#[allow(unsafe_code)]
+unsafe impl bytemuck::Zeroable for PointBytes {}
+#[allow(unsafe_code)]
+unsafe impl bytemuck::Pod for PointBytes {}
+
+impl PointBytes {
+    /// Decodes these bytes as a Pallas point, failing if they do not encode a point
+    /// on the curve.
+    pub fn to_ep_affine(&self) -> CtOption<EpAffine> {
+        EpAffine::from_bytes(&self.0)
+    }
+
+    /// Decodes these bytes as a Vesta point, failing if they do not encode a point
+    /// on the curve.
+    pub fn to_eq_affine(&self) -> CtOption<EqAffine> {
+        EqAffine::from_bytes(&self.0)
+    }
+}
+
+impl From<EpAffine> for PointBytes {
+    fn from(p: EpAffine) -> Self {
+        PointBytes(p.to_bytes())
+    }
+}
+
+impl From<EqAffine> for PointBytes {
+    fn from(p: EqAffine) -> Self {
+        PointBytes(p.to_bytes())
+    }
+}
+
+impl From<Ep> for PointBytes {
+    fn from(p: Ep) -> Self {
+        PointBytes::from(p.to_affine())
+    }
+}
+
+impl From<Eq> for PointBytes {
+    fn from(p: Eq) -> Self {
+        PointBytes::from(p.to_affine())
+    }
+}
+
+impl AsRef<[u8]> for PointBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{prime::PrimeCurveAffine, Group};
+
+    use super::{FieldBytes, PointBytes};
+    use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq};
+
+    #[test]
+    fn test_cast_slice() {
+        let fields = [FieldBytes::from(Fp::one()), FieldBytes::from(-Fq::one())];
+        let bytes: &[u8] = bytemuck::cast_slice(&fields);
+        assert_eq!(bytes.len(), 64);
+
+        let fields: &[FieldBytes] = bytemuck::cast_slice(bytes);
+        assert_eq!(fields[0].to_fp().unwrap(), Fp::one());
+        assert_eq!(fields[1].to_fq().unwrap(), -Fq::one());
+
+        let points = [
+            PointBytes::from(Ep::generator()),
+            PointBytes::from(EqAffine::generator()),
+        ];
+        let bytes: &[u8] = bytemuck::cast_slice(&points);
+        let points: &[PointBytes] = bytemuck::cast_slice(bytes);
+        assert_eq!(points[0].to_ep_affine().unwrap(), EpAffine::generator());
+        assert_eq!(points[1].to_eq_affine().unwrap(), EqAffine::generator());
+
+        // Decoding is checked.
+        assert!(bool::from(FieldBytes([0xff; 32]).to_fp().is_none()));
+        assert!(bool::from(PointBytes([0xff; 32]).to_eq_affine().is_none()));
+        assert!(bool::from(
+            PointBytes::from(Eq::identity())
+                .to_eq_affine()
+                .unwrap()
+                .is_identity()
+        ));
+    }
+}
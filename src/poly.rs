@@ -0,0 +1,569 @@
+//! This module provides dense univariate polynomials over $\mathbb{F}_p$ and
+//! $\mathbb{F}_q$ in coefficient form.
+//!
+//! Products of large polynomials are computed with the transforms of
+//! [`EvaluationDomain`], and products of small ones with schoolbook
+//! multiplication, which is faster below [`FFT_MUL_THRESHOLD`] coefficients.
+//!
+//! Polynomials can be interpolated from arbitrary points with [`interpolate`] in
+//! $O(n^2)$ time, or from evaluations over a domain with [`interpolate_domain`] in
+//! $O(n \log n)$ time. Similarly, [`lagrange_basis`] and [`lagrange_basis_domain`]
+//! evaluate the Lagrange basis polynomials of a set of points at some other point,
+//! which suffices to evaluate the interpolated polynomial without computing its
+//! coefficients.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::arithmetic::{batch_invert, FieldExt};
+use crate::fft::EvaluationDomain;
+
+/// The number of coefficients of the smaller factor from which [`Polynomial`]
+/// multiplication uses FFTs rather than the schoolbook method.
+pub const FFT_MUL_THRESHOLD: usize = 64;
+
+/// A polynomial $\sum_i a_i X^i$, stored as its coefficients $a_i$ from the
+/// constant term up.
+///
+/// The coefficients never have trailing zeros, so the zero polynomial has no
+/// coefficients and equal polynomials have equal representations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polynomial<F: FieldExt> {
+    coeffs: Vec<F>,
+}
+
+impl<F: FieldExt> Polynomial<F> {
+    /// Constructs the polynomial with the coefficients `coeffs`, from the constant
+    /// term up.
+    pub fn new(coeffs: Vec<F>) -> Self {
+        let mut poly = Polynomial { coeffs };
+        poly.trim();
+        poly
+    }
+
+    /// Returns the zero polynomial.
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    /// Returns `true` if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Returns the degree of this polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Returns the coefficients of this polynomial, from the constant term up.
+    pub fn coeffs(&self) -> &[F] {
+        &self.coeffs
+    }
+
+    /// Returns the coefficients of this polynomial, from the constant term up.
+    pub fn into_coeffs(self) -> Vec<F> {
+        self.coeffs
+    }
+
+    /// Evaluates this polynomial at `x` with Horner's rule.
+    pub fn evaluate(&self, x: F) -> F {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(F::ZERO, |acc, coeff| acc * x + coeff)
+    }
+
+    /// Divides this polynomial by `divisor`, returning the quotient and the
+    /// remainder, whose degree is less than that of `divisor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor_degree = divisor.degree().expect("division by the zero polynomial");
+        if self.coeffs.len() <= divisor_degree {
+            return (Self::zero(), self.clone());
+        }
+
+        let lead_inv = divisor.coeffs[divisor_degree].invert().unwrap();
+        let mut rem = self.coeffs.clone();
+        let mut quotient = vec![F::ZERO; rem.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let q = rem[i + divisor_degree] * lead_inv;
+            for (r, d) in rem[i..].iter_mut().zip(divisor.coeffs.iter()) {
+                *r -= q * d;
+            }
+            quotient[i] = q;
+        }
+        rem.truncate(divisor_degree);
+
+        (Self::new(quotient), Self::new(rem))
+    }
+
+    /// Multiplies this polynomial by `rhs` with the schoolbook method.
+    pub fn mul_schoolbook(&self, rhs: &Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+
+        let mut coeffs = vec![F::ZERO; self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (c, b) in coeffs[i..].iter_mut().zip(rhs.coeffs.iter()) {
+                *c += *a * b;
+            }
+        }
+        Self::new(coeffs)
+    }
+
+    /// Multiplies this polynomial by `rhs` by evaluating both over an
+    /// [`EvaluationDomain`] large enough for the product.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the product has degree $2^{32}$ or more, which exceeds the largest
+    /// domain of the field.
+    pub fn mul_fft(&self, rhs: &Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+
+        let len = self.coeffs.len() + rhs.coeffs.len() - 1;
+        let domain = EvaluationDomain::<F>::new(len.next_power_of_two().trailing_zeros());
+
+        let mut a = self.coeffs.clone();
+        a.resize(domain.size(), F::ZERO);
+        domain.fft(&mut a);
+        let mut b = rhs.coeffs.clone();
+        b.resize(domain.size(), F::ZERO);
+        domain.fft(&mut b);
+
+        for (a, b) in a.iter_mut().zip(b.iter()) {
+            *a *= b;
+        }
+        domain.ifft(&mut a);
+        a.truncate(len);
+        Self::new(a)
+    }
+
+    /// Removes the trailing zero coefficients.
+    fn trim(&mut self) {
+        let len = self
+            .coeffs
+            .iter()
+            .rposition(|c| !bool::from(c.is_zero()))
+            .map_or(0, |i| i + 1);
+        self.coeffs.truncate(len);
+    }
+}
+
+impl<F: FieldExt> From<Vec<F>> for Polynomial<F> {
+    fn from(coeffs: Vec<F>) -> Self {
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<'a, F: FieldExt> Neg for &'a Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn neg(self) -> Polynomial<F> {
+        Polynomial {
+            coeffs: self.coeffs.iter().map(|c| -*c).collect(),
+        }
+    }
+}
+
+impl<F: FieldExt> Neg for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn neg(self) -> Polynomial<F> {
+        -&self
+    }
+}
+
+impl<'a, 'b, F: FieldExt> Add<&'b Polynomial<F>> for &'a Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn add(self, rhs: &'b Polynomial<F>) -> Polynomial<F> {
+        let (long, short) = if self.coeffs.len() >= rhs.coeffs.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut coeffs = long.coeffs.clone();
+        for (c, s) in coeffs.iter_mut().zip(short.coeffs.iter()) {
+            *c += s;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<'a, 'b, F: FieldExt> Sub<&'b Polynomial<F>> for &'a Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn sub(self, rhs: &'b Polynomial<F>) -> Polynomial<F> {
+        let mut coeffs = self.coeffs.clone();
+        if coeffs.len() < rhs.coeffs.len() {
+            coeffs.resize(rhs.coeffs.len(), F::ZERO);
+        }
+        for (c, r) in coeffs.iter_mut().zip(rhs.coeffs.iter()) {
+            *c -= r;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<'a, 'b, F: FieldExt> Mul<&'b Polynomial<F>> for &'a Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, rhs: &'b Polynomial<F>) -> Polynomial<F> {
+        if self.coeffs.len().min(rhs.coeffs.len()) < FFT_MUL_THRESHOLD {
+            self.mul_schoolbook(rhs)
+        } else {
+            self.mul_fft(rhs)
+        }
+    }
+}
+
+impl<'a, F: FieldExt> Mul<F> for &'a Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, rhs: F) -> Polynomial<F> {
+        Polynomial::new(self.coeffs.iter().map(|c| *c * rhs).collect())
+    }
+}
+
+macro_rules! impl_owned_binop {
+    ($trait:ident, $method:ident) => {
+        impl<'b, F: FieldExt> $trait<&'b Polynomial<F>> for Polynomial<F> {
+            type Output = Polynomial<F>;
+
+            fn $method(self, rhs: &'b Polynomial<F>) -> Polynomial<F> {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<'a, F: FieldExt> $trait<Polynomial<F>> for &'a Polynomial<F> {
+            type Output = Polynomial<F>;
+
+            fn $method(self, rhs: Polynomial<F>) -> Polynomial<F> {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<F: FieldExt> $trait<Polynomial<F>> for Polynomial<F> {
+            type Output = Polynomial<F>;
+
+            fn $method(self, rhs: Polynomial<F>) -> Polynomial<F> {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_owned_binop!(Add, add);
+impl_owned_binop!(Sub, sub);
+impl_owned_binop!(Mul, mul);
+
+impl<F: FieldExt> Mul<F> for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, rhs: F) -> Polynomial<F> {
+        &self * rhs
+    }
+}
+
+/// Returns the polynomial of degree less than $n$ through the $n$ points
+/// $(x_i, y_i)$ of `points`.
+///
+/// # Panics
+///
+/// Panics if two of the points have the same $x_i$.
+pub fn interpolate<F: FieldExt>(points: &[(F, F)]) -> Polynomial<F> {
+    let xs: Vec<_> = points.iter().map(|(x, _)| *x).collect();
+    let mut weights = barycentric_denominators(&xs);
+    batch_invert(&mut weights);
+
+    // The vanishing polynomial of the x_i, of degree n.
+    let mut vanishing = vec![F::ONE];
+    for x in xs.iter() {
+        vanishing.insert(0, F::ZERO);
+        for i in 0..vanishing.len() - 1 {
+            let c = vanishing[i + 1] * x;
+            vanishing[i] -= c;
+        }
+    }
+
+    // Add y_i w_i times the vanishing polynomial divided by X - x_i, computed by
+    // synthetic division, for each point.
+    let mut coeffs = vec![F::ZERO; points.len()];
+    for ((x, y), w) in points.iter().zip(weights.iter()) {
+        let scale = *y * w;
+        let mut q = F::ZERO;
+        for i in (0..coeffs.len()).rev() {
+            q = vanishing[i + 1] + q * x;
+            coeffs[i] += q * scale;
+        }
+    }
+    Polynomial::new(coeffs)
+}
+
+/// Returns the evaluations at `z` of the Lagrange basis polynomials of `xs`, the
+/// polynomials $L_i$ of degree less than $n$ such that $L_i(x_i) = 1$ and
+/// $L_i(x_j) = 0$ for $j \ne i$.
+///
+/// The polynomial through the points $(x_i, y_i)$ evaluates to
+/// $\sum_i y_i L_i(z)$ at `z`. This takes $O(n^2)$ time.
+///
+/// # Panics
+///
+/// Panics if two of the `xs` are equal.
+pub fn lagrange_basis<F: FieldExt>(xs: &[F], z: F) -> Vec<F> {
+    let mut basis = barycentric_denominators(xs);
+    batch_invert(&mut basis);
+
+    // Multiply by the products of z - x_j over j != i, from the prefix and suffix
+    // products, which also works when z is one of the xs.
+    let mut prefix = F::ONE;
+    for (l, x) in basis.iter_mut().zip(xs.iter()) {
+        *l *= prefix;
+        prefix *= z - x;
+    }
+    let mut suffix = F::ONE;
+    for (l, x) in basis.iter_mut().zip(xs.iter()).rev() {
+        *l *= suffix;
+        suffix *= z - x;
+    }
+    basis
+}
+
+/// Returns the products $\prod_{j \ne i} (x_i - x_j)$.
+///
+/// # Panics
+///
+/// Panics if two of the `xs` are equal.
+fn barycentric_denominators<F: FieldExt>(xs: &[F]) -> Vec<F> {
+    xs.iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            let d = xs
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(F::ONE, |acc, (_, x_j)| acc * (*x_i - x_j));
+            assert!(!bool::from(d.is_zero()), "the points must be distinct");
+            d
+        })
+        .collect()
+}
+
+/// Returns the polynomial of degree less than $n$ whose evaluations at
+/// $\omega^0, \ldots, \omega^{n-1}$ are `evals`, where $\omega$ generates `domain`
+/// of size $n$, in $O(n \log n)$ time.
+///
+/// # Panics
+///
+/// Panics if `evals.len()` is not the size of the domain.
+pub fn interpolate_domain<F: FieldExt>(domain: &EvaluationDomain<F>, evals: &[F]) -> Polynomial<F> {
+    let mut coeffs = evals.to_vec();
+    domain.ifft(&mut coeffs);
+    Polynomial::new(coeffs)
+}
+
+/// Returns the evaluations at `z` of the Lagrange basis polynomials of `domain`,
+/// in $O(n)$ time.
+///
+/// Over the domain of size $n$ generated by $\omega$, these are
+///
+/// $$L_i(z) = \frac{\omega^i (z^n - 1)}{n (z - \omega^i)},$$
+///
+/// and if $z$ is in the domain they are zero except for $L_i(\omega^i) = 1$.
+///
+/// [`evaluate_barycentric`] combines these with the evaluations of a polynomial.
+pub fn lagrange_basis_domain<F: FieldExt>(domain: &EvaluationDomain<F>, z: F) -> Vec<F> {
+    let mut omega_i = F::ONE;
+    let mut powers = Vec::with_capacity(domain.size());
+    for _ in 0..domain.size() {
+        powers.push(omega_i);
+        omega_i *= domain.omega();
+    }
+
+    if let Some(i) = powers.iter().position(|w| *w == z) {
+        let mut basis = vec![F::ZERO; domain.size()];
+        basis[i] = F::ONE;
+        return basis;
+    }
+
+    let mut basis: Vec<_> = powers.iter().map(|w| z - w).collect();
+    batch_invert(&mut basis);
+    let scale = domain.evaluate_vanishing_polynomial(z) * domain.n_inv();
+    for (l, w) in basis.iter_mut().zip(powers.iter()) {
+        *l *= *w * scale;
+    }
+    basis
+}
+
+/// Evaluates at `z` the polynomial of degree less than $n$ whose evaluations at
+/// $\omega^0, \ldots, \omega^{n-1}$ are `evals`, with the barycentric formula
+///
+/// $$p(z) = \frac{z^n - 1}{n} \sum_i \frac{\omega^i p(\omega^i)}{z - \omega^i},$$
+///
+/// in $O(n)$ time and without computing the coefficients.
+///
+/// # Panics
+///
+/// Panics if `evals.len()` is not the size of the domain.
+pub fn evaluate_barycentric<F: FieldExt>(domain: &EvaluationDomain<F>, evals: &[F], z: F) -> F {
+    assert_eq!(evals.len(), domain.size());
+    lagrange_basis_domain(domain, z)
+        .iter()
+        .zip(evals.iter())
+        .fold(F::ZERO, |acc, (l, e)| acc + *l * e)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use ff::Field;
+    use rand_xorshift::XorShiftRng;
+
+    use super::{
+        evaluate_barycentric, interpolate, interpolate_domain, lagrange_basis,
+        lagrange_basis_domain, Polynomial, FFT_MUL_THRESHOLD,
+    };
+    use crate::fft::EvaluationDomain;
+    use crate::{Fp, Fq};
+
+    fn random_poly<F: crate::arithmetic::FieldExt>(
+        rng: &mut XorShiftRng,
+        len: usize,
+    ) -> Polynomial<F> {
+        Polynomial::new((0..len).map(|_| F::random(&mut *rng)).collect())
+    }
+
+    #[test]
+    fn test_representation() {
+        let p = Polynomial::new(vec![Fp::one(), Fp::zero(), Fp::from(3u64), Fp::zero()]);
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(p.coeffs().len(), 3);
+        assert_eq!(p.evaluate(Fp::from(2u64)), Fp::from(13u64));
+
+        let zero = Polynomial::<Fp>::new(vec![Fp::zero(); 4]);
+        assert!(zero.is_zero());
+        assert_eq!(zero, Polynomial::zero());
+        assert_eq!(zero.degree(), None);
+        assert_eq!(&p + &-&p, zero);
+        assert_eq!(&p - &p.clone(), zero);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut rng = crate::tests::rng();
+
+        for (m, n) in [(0, 5), (1, 1), (3, 7), (FFT_MUL_THRESHOLD, 100), (200, 300)] {
+            let a: Polynomial<Fq> = random_poly(&mut rng, m);
+            let b: Polynomial<Fq> = random_poly(&mut rng, n);
+            let x = Fq::random(&mut rng);
+
+            assert_eq!((&a + &b).evaluate(x), a.evaluate(x) + b.evaluate(x));
+            assert_eq!((&a - &b).evaluate(x), a.evaluate(x) - b.evaluate(x));
+            assert_eq!((&a * x).evaluate(x), a.evaluate(x) * x);
+
+            let product = a.mul_schoolbook(&b);
+            assert_eq!(product, a.mul_fft(&b));
+            assert_eq!(product, &a * &b);
+            assert_eq!(product.evaluate(x), a.evaluate(x) * b.evaluate(x));
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let mut rng = crate::tests::rng();
+
+        for (m, n) in [(0, 1), (3, 5), (5, 5), (20, 1), (100, 37)] {
+            let a: Polynomial<Fp> = random_poly(&mut rng, m);
+            let b: Polynomial<Fp> = random_poly(&mut rng, n);
+
+            let (q, r) = a.div_rem(&b);
+            assert!(r.degree() < b.degree());
+            assert_eq!(&(&q * &b) + &r, a);
+        }
+
+        // Dividing by X - z leaves the evaluation at z as the remainder.
+        let a: Polynomial<Fp> = random_poly(&mut rng, 10);
+        let z = Fp::random(&mut rng);
+        let (_, r) = a.div_rem(&Polynomial::new(vec![-z, Fp::one()]));
+        assert_eq!(r.coeffs(), &[a.evaluate(z)][..]);
+
+        let coeffs: Vec<_> = a.coeffs().to_vec();
+        assert_eq!(Polynomial::from(coeffs), a);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let mut rng = crate::tests::rng();
+
+        for n in [0, 1, 2, 9] {
+            let poly: Polynomial<Fp> = random_poly(&mut rng, n);
+            let xs: Vec<_> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+            let points: Vec<_> = xs.iter().map(|x| (*x, poly.evaluate(*x))).collect();
+            assert_eq!(interpolate(&points), poly);
+
+            let z = Fp::random(&mut rng);
+            let basis = lagrange_basis(&xs, z);
+            let sum = points
+                .iter()
+                .zip(basis.iter())
+                .fold(Fp::zero(), |acc, ((_, y), l)| acc + *y * l);
+            assert_eq!(sum, poly.evaluate(z));
+
+            for (i, x) in xs.iter().enumerate() {
+                let basis = lagrange_basis(&xs, *x);
+                for (j, l) in basis.iter().enumerate() {
+                    assert_eq!(*l, if i == j { Fp::one() } else { Fp::zero() });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_interpolate_domain() {
+        let mut rng = crate::tests::rng();
+
+        for k in [0, 1, 4] {
+            let domain = EvaluationDomain::<Fq>::new(k);
+            let poly: Polynomial<Fq> = random_poly(&mut rng, domain.size());
+            let xs: Vec<_> = (0..domain.size() as u64)
+                .map(|i| domain.omega().pow_vartime([i]))
+                .collect();
+            let evals: Vec<_> = xs.iter().map(|x| poly.evaluate(*x)).collect();
+            assert_eq!(interpolate_domain(&domain, &evals), poly);
+
+            let z = Fq::random(&mut rng);
+            assert_eq!(lagrange_basis_domain(&domain, z), lagrange_basis(&xs, z));
+            assert_eq!(evaluate_barycentric(&domain, &evals, z), poly.evaluate(z));
+            assert_eq!(
+                evaluate_barycentric(&domain, &evals, xs[0]),
+                poly.evaluate(xs[0])
+            );
+            assert_eq!(
+                lagrange_basis_domain(&domain, xs[xs.len() - 1]),
+                lagrange_basis(&xs, xs[xs.len() - 1])
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_interpolate_repeated_points() {
+        interpolate(&[(Fp::one(), Fp::one()), (Fp::one(), Fp::zero())]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_by_zero() {
+        Polynomial::new(vec![Fp::one()]).div_rem(&Polynomial::zero());
+    }
+}
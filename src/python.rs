@@ -0,0 +1,290 @@
+//! Bindings for Python, generated with PyO3.
+//!
+//! The `pasta_curves` Python module exposes the `Fp` and `Fq` field elements and
+//! the `PallasPoint` and `VestaPoint` curve points as classes supporting the
+//! arithmetic operators, equality and their byte encodings. Field elements are
+//! encoded as their 32-byte little-endian encodings, and points as their 32-byte
+//! compressed encodings or, for `to_uncompressed`, as the 64-byte concatenation
+//! of the little-endian encodings of their coordinates.
+//!
+//! To build the extension module, run
+//! `cargo rustc --release --features python,pyo3/extension-module --crate-type cdylib`
+//! and rename the library to `pasta_curves.so` (or `pasta_curves.pyd` on
+//! Windows).
+
+use alloc::format;
+use alloc::string::String;
+
+use ff::{Field, PrimeField};
+use group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::PyBytes;
+
+use crate::arithmetic::CurveExt;
+use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq};
+
+/// Copies `bytes` into a fixed-length encoding.
+fn read_repr<R: AsMut<[u8]> + Default>(bytes: &[u8]) -> PyResult<R> {
+    let mut repr = R::default();
+    if bytes.len() != repr.as_mut().len() {
+        return Err(PyValueError::new_err("unexpected length of encoding"));
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    Ok(repr)
+}
+
+macro_rules! field_class {
+    ($class:ident, $field:ident, $name:literal) => {
+        #[doc = concat!("An element of `", stringify!($field), "`, exposed to Python as `", $name, "`.")]
+        #[pyclass(name = $name)]
+        #[derive(Clone, Copy, Debug)]
+        pub struct $class(pub $field);
+
+        #[pymethods]
+        impl $class {
+            #[new]
+            fn new(value: u64) -> Self {
+                $class($field::from(value))
+            }
+
+            #[staticmethod]
+            fn zero() -> Self {
+                $class($field::zero())
+            }
+
+            #[staticmethod]
+            fn one() -> Self {
+                $class($field::one())
+            }
+
+            #[staticmethod]
+            fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+                let repr = read_repr(bytes)?;
+                Option::from($field::from_repr(repr))
+                    .map($class)
+                    .ok_or_else(|| PyValueError::new_err("bytes are not a canonical encoding"))
+            }
+
+            fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+                PyBytes::new(py, &self.0.to_repr())
+            }
+
+            fn is_zero(&self) -> bool {
+                bool::from(self.0.is_zero())
+            }
+
+            fn square(&self) -> Self {
+                $class(self.0.square())
+            }
+
+            fn invert(&self) -> Option<Self> {
+                Option::from(self.0.invert()).map($class)
+            }
+
+            fn sqrt(&self) -> Option<Self> {
+                Option::from(self.0.sqrt()).map($class)
+            }
+
+            fn __add__(&self, other: Self) -> Self {
+                $class(self.0 + other.0)
+            }
+
+            fn __sub__(&self, other: Self) -> Self {
+                $class(self.0 - other.0)
+            }
+
+            fn __mul__(&self, other: Self) -> Self {
+                $class(self.0 * other.0)
+            }
+
+            fn __neg__(&self) -> Self {
+                $class(-self.0)
+            }
+
+            fn __richcmp__(&self, other: Self, op: CompareOp, py: Python<'_>) -> PyObject {
+                match op {
+                    CompareOp::Eq => (self.0 == other.0).into_py(py),
+                    CompareOp::Ne => (self.0 != other.0).into_py(py),
+                    _ => py.NotImplemented(),
+                }
+            }
+
+            fn __repr__(&self) -> String {
+                format!("{}({:?})", $name, self.0)
+            }
+        }
+    };
+}
+
+field_class!(PyFp, Fp, "Fp");
+field_class!(PyFq, Fq, "Fq");
+
+macro_rules! point_class {
+    ($class:ident, $point:ident, $affine:ident, $scalar:ident, $name:literal) => {
+        #[doc = concat!("A point of `", stringify!($point), "`, exposed to Python as `", $name, "`.")]
+        #[pyclass(name = $name)]
+        #[derive(Clone, Copy, Debug)]
+        pub struct $class(pub $point);
+
+        #[pymethods]
+        impl $class {
+            #[staticmethod]
+            fn identity() -> Self {
+                $class(Group::identity())
+            }
+
+            #[staticmethod]
+            fn generator() -> Self {
+                $class(Group::generator())
+            }
+
+            #[staticmethod]
+            fn hash_to_curve(domain_prefix: &str, message: &[u8]) -> PyResult<Self> {
+                // The domain separation tag must fit in 255 bytes.
+                if 22 + <$point as CurveExt>::CURVE_ID.len() + domain_prefix.len() >= 256 {
+                    return Err(PyValueError::new_err("domain prefix is too long"));
+                }
+                Ok($class(CurveExt::hash_to_curve(domain_prefix)(message)))
+            }
+
+            #[staticmethod]
+            fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+                let repr = read_repr(bytes)?;
+                Option::from($affine::from_bytes(&repr))
+                    .map(|p: $affine| $class(p.into()))
+                    .ok_or_else(|| PyValueError::new_err("bytes don't encode a point on the curve"))
+            }
+
+            #[staticmethod]
+            fn from_uncompressed(bytes: &[u8]) -> PyResult<Self> {
+                let repr = read_repr(bytes)?;
+                Option::from($affine::from_uncompressed(&repr))
+                    .map(|p: $affine| $class(p.into()))
+                    .ok_or_else(|| PyValueError::new_err("bytes don't encode a point on the curve"))
+            }
+
+            fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+                PyBytes::new(py, self.0.to_affine().to_bytes().as_ref())
+            }
+
+            fn to_uncompressed<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+                PyBytes::new(py, self.0.to_affine().to_uncompressed().as_ref())
+            }
+
+            fn is_identity(&self) -> bool {
+                bool::from(self.0.is_identity())
+            }
+
+            fn double(&self) -> Self {
+                $class(self.0.double())
+            }
+
+            fn __add__(&self, other: Self) -> Self {
+                $class(self.0 + other.0)
+            }
+
+            fn __sub__(&self, other: Self) -> Self {
+                $class(self.0 - other.0)
+            }
+
+            fn __mul__(&self, scalar: $scalar) -> Self {
+                $class(self.0 * scalar.0)
+            }
+
+            fn __rmul__(&self, scalar: $scalar) -> Self {
+                $class(self.0 * scalar.0)
+            }
+
+            fn __neg__(&self) -> Self {
+                $class(-self.0)
+            }
+
+            fn __richcmp__(&self, other: Self, op: CompareOp, py: Python<'_>) -> PyObject {
+                match op {
+                    CompareOp::Eq => (self.0 == other.0).into_py(py),
+                    CompareOp::Ne => (self.0 != other.0).into_py(py),
+                    _ => py.NotImplemented(),
+                }
+            }
+
+            fn __repr__(&self) -> String {
+                format!("{}({:?})", $name, self.0.to_affine())
+            }
+        }
+    };
+}
+
+point_class!(PyPallasPoint, Ep, EpAffine, PyFq, "PallasPoint");
+point_class!(PyVestaPoint, Eq, EqAffine, PyFp, "VestaPoint");
+
+/// Initializes the `pasta_curves` Python module.
+#[pymodule]
+fn pasta_curves(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyFp>()?;
+    m.add_class::<PyFq>()?;
+    m.add_class::<PyPallasPoint>()?;
+    m.add_class::<PyVestaPoint>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use ff::PrimeField;
+    use group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+
+    use super::{PyFp, PyFq, PyPallasPoint, PyVestaPoint};
+    use crate::arithmetic::CurveExt;
+    use crate::pallas;
+
+    #[test]
+    fn test_bindings() {
+        let a = PyFq::new(5);
+        let b = PyFq::from_bytes(&pallas::Scalar::from(7u64).to_repr()).unwrap();
+        assert_eq!(a.__mul__(b).0, pallas::Scalar::from(35u64));
+        assert_eq!(a.__sub__(b).0, -pallas::Scalar::from(2u64));
+        assert_eq!(a.invert().unwrap().0 * a.0, pallas::Scalar::one());
+        assert!(PyFq::zero().invert().is_none());
+        assert!(PyFp::new(4).sqrt().is_some());
+
+        let g = PyPallasPoint::generator();
+        let p = g.__mul__(a);
+        assert_eq!(p.0, pallas::Point::generator() * pallas::Scalar::from(5u64));
+        assert!(g.__sub__(g).is_identity());
+        assert_eq!(
+            PyPallasPoint::from_bytes(&p.0.to_affine().to_bytes())
+                .unwrap()
+                .0,
+            p.0
+        );
+        assert_eq!(
+            PyPallasPoint::from_uncompressed(p.0.to_affine().to_uncompressed().as_ref())
+                .unwrap()
+                .0,
+            p.0
+        );
+        assert_eq!(
+            PyPallasPoint::hash_to_curve("z.cash:test", b"hello")
+                .unwrap()
+                .0,
+            pallas::Point::hash_to_curve("z.cash:test")(b"hello")
+        );
+    }
+
+    #[test]
+    fn test_bindings_reject_invalid_input() {
+        assert!(PyFq::from_bytes(&[0xff; 32]).is_err());
+        assert!(PyFq::from_bytes(&[0; 31]).is_err());
+        assert!(PyPallasPoint::from_bytes(&[0xff; 32]).is_err());
+        assert!(PyPallasPoint::from_bytes(&[0; 33]).is_err());
+
+        // The longest prefix whose domain separation tag fits in 255 bytes.
+        let prefix = "a".repeat(255 - 22 - "pallas".len());
+        assert!(PyPallasPoint::hash_to_curve(&prefix, b"hello").is_ok());
+        assert!(PyPallasPoint::hash_to_curve(&format!("{}a", prefix), b"hello").is_err());
+        assert!(PyVestaPoint::hash_to_curve(&"a".repeat(256), b"hello").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Schnorr signatures over the Pallas curve.
+//!
+//! A signature on a message $m$ under the [`PublicKey`] $P = [x] G$ of the
+//! [`SecretKey`] $x$, where $G$ is the Pallas generator, is a pair $(R, s)$ with
+//! $R = [k] G$ and
+//! $s = k + c \cdot x$. The nonce $k$ is derived deterministically from $x$ and
+//! $m$, so signing needs no randomness. The challenge $c \in \mathbb{F}_q$ is
+//! derived from $R$, $P$ and $m$ with the crate's `hash_to_field`, using the
+//! domain prefix [`CHALLENGE_DOMAIN`]. A signature is valid if
+//! $[s] G = R + [c] P$.
+
+use alloc::vec::Vec;
+
+use ff::PrimeField;
+use group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding};
+use subtle::CtOption;
+
+use crate::arithmetic::CurveExt;
+use crate::hashtocurve::hash_to_field;
+use crate::keys::{PublicKey, SecretKey};
+use crate::nonce::derive_nonce;
+use crate::pallas;
+
+/// The domain prefix used to hash the challenge.
+pub const CHALLENGE_DOMAIN: &str = "fil_pasta_curves:schnorr";
+
+/// The BLAKE2b personalization used to derive nonces.
+const NONCE_PERSONALIZATION: &[u8; 16] = b"fil_pasta_Sch_k_";
+
+/// A Schnorr signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    r: pallas::Affine,
+    s: pallas::Scalar,
+}
+
+impl Signature {
+    /// Returns the encoding of this signature, which is the compressed encoding of
+    /// $R$ followed by the little-endian encoding of $s$.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut res = [0; 64];
+        res[..32].copy_from_slice(&self.r.to_bytes());
+        res[32..].copy_from_slice(&self.s.to_repr());
+        res
+    }
+
+    /// Parses a signature from its encoding, failing if $R$ is not a valid point
+    /// encoding or $s$ is not canonical.
+    pub fn from_bytes(bytes: &[u8; 64]) -> CtOption<Self> {
+        let mut r = [0; 32];
+        r.copy_from_slice(&bytes[..32]);
+        let mut s = [0; 32];
+        s.copy_from_slice(&bytes[32..]);
+
+        pallas::Affine::from_bytes(&r)
+            .and_then(|r| pallas::Scalar::from_repr(s).map(|s| Signature { r, s }))
+    }
+}
+
+/// Computes the challenge $c$ for the nonce commitment `r`, the public key `pk`
+/// and the message `msg`.
+fn challenge(r: &pallas::Affine, pk: &pallas::Affine, msg: &[u8]) -> pallas::Scalar {
+    let mut input = Vec::with_capacity(64 + msg.len());
+    input.extend_from_slice(&r.to_bytes());
+    input.extend_from_slice(&pk.to_bytes());
+    input.extend_from_slice(msg);
+
+    let mut c = [pallas::Scalar::zero()];
+    hash_to_field(pallas::Point::CURVE_ID, CHALLENGE_DOMAIN, &input, &mut c);
+    c[0]
+}
+
+/// Signs `msg` with the secret key `sk`.
+///
+/// Signing is deterministic: the same key and message always give the same
+/// signature.
+pub fn sign(sk: &SecretKey, msg: &[u8]) -> Signature {
+    let x = sk.as_scalar();
+    let k = derive_nonce(NONCE_PERSONALIZATION, x, &[msg]);
+    let r = (pallas::Point::generator() * k).to_affine();
+    let c = challenge(&r, sk.public_key().as_point(), msg);
+    Signature { r, s: k + c * x }
+}
+
+/// Verifies the signature `sig` on `msg` under the public key `pk`.
+///
+/// This is not constant time.
+pub fn verify(pk: &PublicKey, msg: &[u8], sig: &Signature) -> bool {
+    let pk = pk.as_point();
+    let c = challenge(&sig.r, pk, msg);
+    pallas::Point::generator() * sig.s == sig.r.to_curve() + *pk * c
+}
+
+#[cfg(test)]
+mod tests {
+    use group::{prime::PrimeCurveAffine, Curve};
+
+    use super::{sign, verify, Signature};
+    use crate::keys::SecretKey;
+    use crate::pallas;
+
+    #[test]
+    fn test_sign_verify() {
+        let mut rng = crate::tests::rng();
+
+        let sk = SecretKey::random(&mut rng);
+        let pk = sk.public_key();
+        let sig = sign(&sk, b"hello");
+        assert!(verify(&pk, b"hello", &sig));
+
+        // The message, the key and the signature are all bound.
+        assert!(!verify(&pk, b"hellO", &sig));
+        let other = SecretKey::random(&mut rng).public_key();
+        assert!(!verify(&other, b"hello", &sig));
+        let mut tampered = sig;
+        tampered.s += pallas::Scalar::one();
+        assert!(!verify(&pk, b"hello", &tampered));
+        tampered = sig;
+        tampered.r = (tampered.r + pallas::Affine::generator()).to_affine();
+        assert!(!verify(&pk, b"hello", &tampered));
+
+        // Signing is deterministic, but nonces differ between messages.
+        assert_eq!(sig, sign(&sk, b"hello"));
+        assert_ne!(sig.r, sign(&sk, b"hellO").r);
+    }
+
+    #[test]
+    fn test_signature_encoding() {
+        let mut rng = crate::tests::rng();
+
+        let sk = SecretKey::random(&mut rng);
+        let sig = sign(&sk, b"hello");
+        let bytes = sig.to_bytes();
+        assert_eq!(Signature::from_bytes(&bytes).unwrap(), sig);
+
+        // Non-canonical scalars are rejected.
+        let mut bytes = sig.to_bytes();
+        bytes[32..].copy_from_slice(&[0xff; 32]);
+        assert!(bool::from(Signature::from_bytes(&bytes).is_none()));
+
+        // Invalid points are rejected.
+        let mut bytes = sig.to_bytes();
+        bytes[..32].copy_from_slice(&[0xff; 32]);
+        assert!(bool::from(Signature::from_bytes(&bytes).is_none()));
+    }
+}
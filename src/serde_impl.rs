@@ -1,48 +1,121 @@
+use core::fmt;
+
 use ff::PrimeField;
-use group::GroupEncoding;
+use group::{GroupEncoding, UncompressedEncoding};
 use serde_crate::{
-    de::Error as DeserializeError, Deserialize, Deserializer, Serialize, Serializer,
+    de::{Error as DeserializeError, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
+#[cfg(feature = "alloc")]
+use crate::{
+    arithmetic::{CurveAffine, CurveExt},
+    basepoint_table::{BasepointTable, WINDOW_SIZE},
+    wnaf::WnafContext,
+};
 use crate::{
-    curves::{Ep, EpAffine, Eq, EqAffine},
+    curves::{Ep, EpAffine, Eq, EqAffine, IsoEp, IsoEpAffine, IsoEq, IsoEqAffine},
     fields::{Fp, Fq},
     group::Curve,
 };
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Displays bytes as a `0x`-prefixed hex string.
+struct HexBytes<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for HexBytes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("0x")?;
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
 
 /// Serializes bytes to human readable or compact representation.
 ///
 /// Depending on whether the serializer is a human readable one or not, the bytes are either
-/// encoded as a hex string or a list of bytes.
-fn serialize_bytes<S: Serializer>(bytes: [u8; 32], s: S) -> Result<S::Ok, S::Error> {
+/// encoded as a `0x`-prefixed hex string or a list of bytes.
+fn serialize_bytes<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
     if s.is_human_readable() {
-        hex::serde::serialize(bytes, s)
+        s.collect_str(&HexBytes(bytes))
     } else {
-        bytes.serialize(s)
+        let mut tuple = s.serialize_tuple(bytes.len())?;
+        for byte in bytes {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
     }
 }
 
-/// Deserialize bytes from human readable or compact representation.
+/// Visitor that fills a fixed-length buffer with deserialized bytes.
+///
+/// Accepts a hex string, with or without a `0x` prefix, a list of bytes (which older
+/// versions of this crate emitted for human readable formats), or a byte string.
+struct BytesVisitor<'a>(&'a mut [u8]);
+
+impl<'a, 'de> Visitor<'de> for BytesVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a hex string or a list of {} bytes",
+            self.0.len()
+        )
+    }
+
+    fn visit_str<E: DeserializeError>(self, v: &str) -> Result<Self::Value, E> {
+        hex::decode_to_slice(v.strip_prefix("0x").unwrap_or(v), self.0).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: DeserializeError>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != self.0.len() {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        self.0.copy_from_slice(v);
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let len = self.0.len();
+        for i in 0..len {
+            self.0[i] = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(A::Error::invalid_length(len + 1, &self));
+        }
+        Ok(())
+    }
+}
+
+/// Deserialize bytes from human readable or compact representation into `bytes`.
 ///
 /// Depending on whether the deserializer is a human readable one or not, the bytes are either
-/// decoded from a hex string or a list of bytes.
-fn deserialize_bytes<'de, D: Deserializer<'de>>(d: D) -> Result<[u8; 32], D::Error> {
+/// decoded from a hex string (or a list of bytes) or from a list of bytes.
+fn deserialize_bytes<'de, D: Deserializer<'de>>(d: D, bytes: &mut [u8]) -> Result<(), D::Error> {
     if d.is_human_readable() {
-        hex::serde::deserialize(d)
+        d.deserialize_any(BytesVisitor(bytes))
     } else {
-        <[u8; 32]>::deserialize(d)
+        d.deserialize_tuple(bytes.len(), BytesVisitor(bytes))
     }
 }
 
 impl Serialize for Fp {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        serialize_bytes(self.to_repr(), s)
+        serialize_bytes(&self.to_repr(), s)
     }
 }
 
 impl<'de> Deserialize<'de> for Fp {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let bytes = deserialize_bytes(d)?;
+        let mut bytes = [0; 32];
+        deserialize_bytes(d, &mut bytes)?;
         match Fp::from_repr(bytes).into() {
             Some(fq) => Ok(fq),
             None => Err(D::Error::custom(
@@ -54,13 +127,14 @@ impl<'de> Deserialize<'de> for Fp {
 
 impl Serialize for Fq {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        serialize_bytes(self.to_repr(), s)
+        serialize_bytes(&self.to_repr(), s)
     }
 }
 
 impl<'de> Deserialize<'de> for Fq {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let bytes = deserialize_bytes(d)?;
+        let mut bytes = [0; 32];
+        deserialize_bytes(d, &mut bytes)?;
         match Fq::from_repr(bytes).into() {
             Some(fq) => Ok(fq),
             None => Err(D::Error::custom(
@@ -72,13 +146,14 @@ impl<'de> Deserialize<'de> for Fq {
 
 impl Serialize for EpAffine {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        serialize_bytes(self.to_bytes(), s)
+        serialize_bytes(&self.to_bytes(), s)
     }
 }
 
 impl<'de> Deserialize<'de> for EpAffine {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let bytes = deserialize_bytes(d)?;
+        let mut bytes = [0; 32];
+        deserialize_bytes(d, &mut bytes)?;
         match EpAffine::from_bytes(&bytes).into() {
             Some(ep_affine) => Ok(ep_affine),
             None => Err(D::Error::custom(
@@ -90,13 +165,14 @@ impl<'de> Deserialize<'de> for EpAffine {
 
 impl Serialize for EqAffine {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        serialize_bytes(self.to_bytes(), s)
+        serialize_bytes(&self.to_bytes(), s)
     }
 }
 
 impl<'de> Deserialize<'de> for EqAffine {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let bytes = deserialize_bytes(d)?;
+        let mut bytes = [0; 32];
+        deserialize_bytes(d, &mut bytes)?;
         match EqAffine::from_bytes(&bytes).into() {
             Some(eq_affine) => Ok(eq_affine),
             None => Err(D::Error::custom(
@@ -130,6 +206,249 @@ impl<'de> Deserialize<'de> for Eq {
     }
 }
 
+impl Serialize for IsoEpAffine {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), s)
+    }
+}
+
+impl<'de> Deserialize<'de> for IsoEpAffine {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let mut bytes = [0; 32];
+        deserialize_bytes(d, &mut bytes)?;
+        match IsoEpAffine::from_bytes(&bytes).into() {
+            Some(p) => Ok(p),
+            None => Err(D::Error::custom(
+                "deserialized bytes don't encode an iso-Pallas curve point",
+            )),
+        }
+    }
+}
+
+impl Serialize for IsoEqAffine {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), s)
+    }
+}
+
+impl<'de> Deserialize<'de> for IsoEqAffine {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let mut bytes = [0; 32];
+        deserialize_bytes(d, &mut bytes)?;
+        match IsoEqAffine::from_bytes(&bytes).into() {
+            Some(p) => Ok(p),
+            None => Err(D::Error::custom(
+                "deserialized bytes don't encode an iso-Vesta curve point",
+            )),
+        }
+    }
+}
+
+impl Serialize for IsoEp {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        IsoEpAffine::serialize(&self.to_affine(), s)
+    }
+}
+
+impl<'de> Deserialize<'de> for IsoEp {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Ok(Self::from(IsoEpAffine::deserialize(d)?))
+    }
+}
+
+impl Serialize for IsoEq {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        IsoEqAffine::serialize(&self.to_affine(), s)
+    }
+}
+
+impl<'de> Deserialize<'de> for IsoEq {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Ok(Self::from(IsoEqAffine::deserialize(d)?))
+    }
+}
+
+/// A point that is serialized with its uncompressed encoding, and deserialized without
+/// checking that it is on the curve.
+///
+/// This skips the square root needed to decompress a point, as well as the curve equation
+/// check, so it is considerably faster to deserialize than the point itself. It must only
+/// be used for trusted input: deserializing a `T` directly rejects invalid points, while
+/// `UncheckedPoint<T>` will accept coordinates that are not on the curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UncheckedPoint<T>(pub T);
+
+impl<T: UncompressedEncoding> Serialize for UncheckedPoint<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(self.0.to_uncompressed().as_ref(), s)
+    }
+}
+
+impl<'de, T: UncompressedEncoding> Deserialize<'de> for UncheckedPoint<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let mut bytes = T::Uncompressed::default();
+        deserialize_bytes(d, bytes.as_mut())?;
+        match T::from_uncompressed_unchecked(&bytes).into() {
+            Some(p) => Ok(UncheckedPoint(p)),
+            None => Err(D::Error::custom(
+                "deserialized bytes don't encode curve point coordinates",
+            )),
+        }
+    }
+}
+
+/// A field element or point that is serialized as a single byte string in binary formats.
+///
+/// Binary formats otherwise encode field elements and points as a tuple of 32 bytes, which
+/// formats such as CBOR encode one byte at a time. `Compact<T>` passes the same 32-byte
+/// encoding to [`Serializer::serialize_bytes`] instead, at the cost of whatever length
+/// prefix the format uses for byte strings. Human readable formats are unaffected.
+///
+/// Formats that already write tuples of bytes without overhead gain nothing from this
+/// wrapper, and may lose space. In particular bincode writes the tuple as 32 bytes, but
+/// a `Compact` value as 40, because it prefixes byte strings with their length as a
+/// `u64`. Use `Compact` with formats such as CBOR or MessagePack, not bincode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Compact<T>(pub T);
+
+macro_rules! impl_compact {
+    ($t:ty, |$value:ident| $encode:expr, |$bytes:ident| $decode:expr, $what:literal) => {
+        impl Serialize for Compact<$t> {
+            fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                if s.is_human_readable() {
+                    self.0.serialize(s)
+                } else {
+                    let $value = &self.0;
+                    s.serialize_bytes(&$encode)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Compact<$t> {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                if d.is_human_readable() {
+                    <$t>::deserialize(d).map(Compact)
+                } else {
+                    let mut $bytes = [0; 32];
+                    d.deserialize_bytes(BytesVisitor(&mut $bytes))?;
+                    match Option::<$t>::from($decode) {
+                        Some(value) => Ok(Compact(value)),
+                        None => Err(D::Error::custom(concat!(
+                            "deserialized bytes don't encode a ",
+                            $what
+                        ))),
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_compact!(
+    Fp,
+    |f| f.to_repr(),
+    |bytes| Fp::from_repr(bytes),
+    "Pallas field element"
+);
+impl_compact!(
+    Fq,
+    |f| f.to_repr(),
+    |bytes| Fq::from_repr(bytes),
+    "Vesta field element"
+);
+impl_compact!(
+    EpAffine,
+    |p| p.to_bytes(),
+    |bytes| EpAffine::from_bytes(&bytes),
+    "Pallas curve point"
+);
+impl_compact!(
+    EqAffine,
+    |p| p.to_bytes(),
+    |bytes| EqAffine::from_bytes(&bytes),
+    "Vesta curve point"
+);
+impl_compact!(
+    Ep,
+    |p| p.to_affine().to_bytes(),
+    |bytes| EpAffine::from_bytes(&bytes).map(Ep::from),
+    "Pallas curve point"
+);
+impl_compact!(
+    Eq,
+    |p| p.to_affine().to_bytes(),
+    |bytes| EqAffine::from_bytes(&bytes).map(Eq::from),
+    "Vesta curve point"
+);
+impl_compact!(
+    IsoEpAffine,
+    |p| p.to_bytes(),
+    |bytes| IsoEpAffine::from_bytes(&bytes),
+    "iso-Pallas curve point"
+);
+impl_compact!(
+    IsoEqAffine,
+    |p| p.to_bytes(),
+    |bytes| IsoEqAffine::from_bytes(&bytes),
+    "iso-Vesta curve point"
+);
+impl_compact!(
+    IsoEp,
+    |p| p.to_affine().to_bytes(),
+    |bytes| IsoEpAffine::from_bytes(&bytes).map(IsoEp::from),
+    "iso-Pallas curve point"
+);
+impl_compact!(
+    IsoEq,
+    |p| p.to_affine().to_bytes(),
+    |bytes| IsoEqAffine::from_bytes(&bytes).map(IsoEq::from),
+    "iso-Vesta curve point"
+);
+
+#[cfg(feature = "alloc")]
+impl<C: CurveAffine + Serialize> Serialize for BasepointTable<C> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.windows.serialize(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, C: CurveAffine + Deserialize<'de>> Deserialize<'de> for BasepointTable<C> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let windows = Vec::<[C; WINDOW_SIZE]>::deserialize(d)?;
+        if windows.len() != BasepointTable::<C>::NUM_WINDOWS {
+            return Err(D::Error::invalid_length(
+                windows.len(),
+                &"one window per 4 bits of the scalar",
+            ));
+        }
+        Ok(BasepointTable { windows })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: CurveExt> Serialize for WnafContext<C>
+where
+    C::AffineExt: Serialize,
+{
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        (self.window, &self.table).serialize(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, C: CurveExt> Deserialize<'de> for WnafContext<C>
+where
+    C::AffineExt: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let (window, table) = <(usize, Vec<C::AffineExt>)>::deserialize(d)?;
+        WnafContext::from_parts(window, table).ok_or_else(|| {
+            D::Error::custom("deserialized wNAF table doesn't match its window size")
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,6 +666,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serde_projective_normalizes() {
+        // Doubling leaves the points with z != 1, so these exercise normalization.
+        let p = Ep::generator().double();
+        assert_eq!(
+            serde_json::to_vec(&p).unwrap(),
+            serde_json::to_vec(&p.to_affine()).unwrap()
+        );
+        assert_eq!(
+            bincode::serialize(&p).unwrap(),
+            bincode::serialize(&p.to_affine()).unwrap()
+        );
+
+        let q = Eq::generator().double();
+        assert_eq!(
+            serde_json::to_vec(&q).unwrap(),
+            serde_json::to_vec(&q.to_affine()).unwrap()
+        );
+        assert_eq!(
+            bincode::serialize(&q).unwrap(),
+            bincode::serialize(&q.to_affine()).unwrap()
+        );
+    }
+
     #[test]
     fn serde_ep_affine() {
         let mut rng = XorShiftRng::from_seed([
@@ -444,4 +787,182 @@ mod tests {
             f
         );
     }
+
+    #[test]
+    fn serde_iso() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..100 {
+            let p = IsoEp::random(&mut rng);
+            test_roundtrip(&p);
+            test_roundtrip(&p.to_affine());
+            test_roundtrip(&Compact(p));
+            let q = IsoEq::random(&mut rng);
+            test_roundtrip(&q);
+            test_roundtrip(&q.to_affine());
+            test_roundtrip(&Compact(q.to_affine()));
+        }
+        test_roundtrip(&IsoEp::identity());
+        test_roundtrip(&IsoEqAffine::identity());
+
+        // The encoding is the same compressed form as for the main curves.
+        let p = IsoEp::random(&mut rng).to_affine();
+        assert_eq!(bincode::serialize(&p).unwrap(), p.to_bytes());
+        assert_eq!(
+            bincode::serialize(&IsoEp::from(p)).unwrap(),
+            bincode::serialize(&p).unwrap()
+        );
+
+        assert!(bincode::deserialize::<IsoEpAffine>(&[0xff; 32]).is_err());
+        assert!(bincode::deserialize::<IsoEq>(&[0xff; 32]).is_err());
+    }
+
+    #[test]
+    fn serde_hex_formats() {
+        let one = Fp::one();
+        assert_eq!(
+            serde_json::to_string(&one).unwrap(),
+            r#""0x0100000000000000000000000000000000000000000000000000000000000000""#
+        );
+        assert_eq!(
+            serde_json::to_string(&EqAffine::generator()).unwrap(),
+            r#""0x0000000021eb468cdda89409fc98462200000000000000000000000000000040""#
+        );
+
+        // Hex strings are accepted with or without the `0x` prefix.
+        assert_eq!(
+            serde_json::from_slice::<Fp>(
+                br#""0x0100000000000000000000000000000000000000000000000000000000000000""#
+            )
+            .unwrap(),
+            one
+        );
+
+        // Strings written before the prefix was added are still accepted.
+        assert_eq!(
+            serde_json::from_slice::<Fp>(
+                br#""0100000000000000000000000000000000000000000000000000000000000000""#
+            )
+            .unwrap(),
+            one
+        );
+        assert_eq!(
+            serde_json::from_slice::<EqAffine>(
+                br#""0000000021eb468cdda89409fc98462200000000000000000000000000000040""#
+            )
+            .unwrap(),
+            EqAffine::generator()
+        );
+        assert_eq!(
+            serde_json::from_slice::<Eq>(
+                br#""0000000021eb468cdda89409fc98462200000000000000000000000000000040""#
+            )
+            .unwrap(),
+            Eq::generator()
+        );
+
+        // The legacy list-of-bytes form is still accepted.
+        assert_eq!(
+            serde_json::from_slice::<Fp>(
+                b"[1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]"
+            )
+            .unwrap(),
+            one
+        );
+
+        // Binary formats keep the compact form.
+        assert_eq!(bincode::serialize(&one).unwrap().len(), 32);
+
+        assert!(serde_json::from_slice::<Fp>(br#""0x01""#).is_err());
+        assert!(serde_json::from_slice::<Fp>(b"[1,0,0,0]").is_err());
+        assert!(serde_json::from_slice::<Fp>(
+            b"[1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn serde_unchecked_point() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..100 {
+            test_roundtrip(&UncheckedPoint(Ep::random(&mut rng).to_affine()));
+            test_roundtrip(&UncheckedPoint(Eq::random(&mut rng).to_affine()));
+        }
+        test_roundtrip(&UncheckedPoint(EpAffine::identity()));
+        test_roundtrip(&UncheckedPoint(EqAffine::identity()));
+
+        let p = UncheckedPoint(EpAffine::generator());
+        assert_eq!(bincode::serialize(&p).unwrap().len(), 64);
+        assert_eq!(
+            serde_json::to_string(&p).unwrap(),
+            r#""0x00000000ed302d991bf94c09fc984622000000000000000000000000000000400200000000000000000000000000000000000000000000000000000000000000""#
+        );
+
+        // (1, 1) is not on the curve: it is only accepted through the unchecked wrapper.
+        let off_curve = br#""0x01000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000""#;
+        assert!(serde_json::from_slice::<UncheckedPoint<EpAffine>>(off_curve).is_ok());
+
+        // Non-canonical coordinates are still rejected.
+        let mut non_canonical = [0xff; 64];
+        non_canonical[32..].copy_from_slice(&[0; 32]);
+        assert!(bincode::deserialize::<UncheckedPoint<EpAffine>>(&non_canonical).is_err());
+    }
+
+    #[test]
+    fn serde_compact() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..100 {
+            test_roundtrip(&Compact(Fp::random(&mut rng)));
+            test_roundtrip(&Compact(Fq::random(&mut rng)));
+            test_roundtrip(&Compact(Ep::random(&mut rng)));
+            test_roundtrip(&Compact(Eq::random(&mut rng)));
+            test_roundtrip(&Compact(Ep::random(&mut rng).to_affine()));
+            test_roundtrip(&Compact(Eq::random(&mut rng).to_affine()));
+        }
+        test_roundtrip(&Compact(Ep::identity()));
+
+        // Binary formats use a single byte string, which bincode prefixes with its
+        // length as a u64.
+        let g = EpAffine::generator();
+        assert_eq!(bincode::serialize(&g).unwrap().len(), 32);
+        let mut expected = vec![32, 0, 0, 0, 0, 0, 0, 0];
+        expected.extend_from_slice(&g.to_bytes());
+        assert_eq!(bincode::serialize(&Compact(g)).unwrap(), expected);
+
+        // Human readable formats are unchanged.
+        assert_eq!(
+            serde_json::to_vec(&Compact(g)).unwrap(),
+            serde_json::to_vec(&g).unwrap()
+        );
+
+        // Decoding is checked.
+        let mut invalid = vec![32, 0, 0, 0, 0, 0, 0, 0];
+        invalid.extend_from_slice(&[0xff; 32]);
+        assert!(bincode::deserialize::<Compact<Fp>>(&invalid).is_err());
+        assert!(bincode::deserialize::<Compact<EqAffine>>(&invalid).is_err());
+        let mut short = vec![31, 0, 0, 0, 0, 0, 0, 0];
+        short.extend_from_slice(&[0; 31]);
+        assert!(bincode::deserialize::<Compact<Fp>>(&short).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn serde_precomputed_tables() {
+        let table = BasepointTable::new(&EpAffine::generator());
+        test_roundtrip(&table);
+        let context = WnafContext::new(&Eq::generator(), 10);
+        test_roundtrip(&context);
+
+        // Tables of the wrong shape are rejected.
+        let mut windows = table.windows;
+        windows.pop();
+        let bytes = bincode::serialize(&windows).unwrap();
+        assert!(bincode::deserialize::<BasepointTable<EpAffine>>(&bytes).is_err());
+        let bytes = bincode::serialize(&(context.window + 1, &context.table)).unwrap();
+        assert!(bincode::deserialize::<WnafContext<Eq>>(&bytes).is_err());
+        let bytes = bincode::serialize(&(1usize, Vec::<EqAffine>::new())).unwrap();
+        assert!(bincode::deserialize::<WnafContext<Eq>>(&bytes).is_err());
+    }
 }
@@ -0,0 +1,311 @@
+//! Raw serialization compatible with the `SerdeObject` trait of `halo2curves`.
+//!
+//! Raw encodings skip the conversions out of and into Montgomery form, so that
+//! large structures such as proving keys can be written and read quickly. A field
+//! element is encoded as the little-endian bytes of the little-endian 64-bit limbs
+//! of its Montgomery form, an affine point as the raw encodings of its $x$ and $y$
+//! coordinates (with the identity as $(0, 0)$), and a projective point as the raw
+//! encodings of its Jacobian coordinates $X$, $Y$ and $Z$. These are the encodings
+//! used by `halo2curves`, so data written by `halo2`-based stacks can be read
+//! directly with this crate's types. They are not portable across curve
+//! implementations that use a different internal representation.
+
+#[cfg(feature = "std")]
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io;
+
+use ff::PrimeField;
+
+use crate::arithmetic::{CurveAffine, CurveExt};
+use crate::basepoint_table::{BasepointTable, WINDOW_SIZE};
+use crate::fields::{Fp, Fq};
+use crate::wnaf::WnafContext;
+
+/// Serialization of the internal representation of field elements and points.
+pub trait SerdeObject: Sized {
+    /// The length of the raw encoding, in bytes.
+    const RAW_SIZE: usize;
+
+    /// Decodes a raw encoding without checking that it is valid, so it must only be
+    /// used for trusted data, such as data this process wrote itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not [`Self::RAW_SIZE`] bytes long.
+    fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self;
+
+    /// Decodes a raw encoding, returning `None` if `bytes` has the wrong length,
+    /// if a field element is not less than the modulus, or if a point is not on the
+    /// curve.
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self>;
+
+    /// Returns the raw encoding.
+    fn to_raw_bytes(&self) -> Vec<u8>;
+
+    /// Reads a raw encoding from `reader` without checking that it is valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if reading fails.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    fn read_raw_unchecked<R: io::Read>(reader: &mut R) -> Self {
+        let mut bytes = vec![0; Self::RAW_SIZE];
+        reader
+            .read_exact(&mut bytes)
+            .expect("failed to read raw encoding");
+        Self::from_raw_bytes_unchecked(&bytes)
+    }
+
+    /// Reads a raw encoding from `reader`, failing with
+    /// [`io::ErrorKind::InvalidData`] if it is not valid.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    fn read_raw<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let mut bytes = vec![0; Self::RAW_SIZE];
+        reader.read_exact(&mut bytes)?;
+        Self::from_raw_bytes(&bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid raw encoding"))
+    }
+
+    /// Writes the raw encoding to `writer`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    fn write_raw<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_raw_bytes())
+    }
+}
+
+/// Returns whether the little-endian integer `a` is at most `b`.
+fn le_bytes_at_most(a: &[u8], b: &[u8]) -> bool {
+    for (a, b) in a.iter().rev().zip(b.iter().rev()) {
+        if a != b {
+            return a < b;
+        }
+    }
+    true
+}
+
+macro_rules! impl_serde_object {
+    ($field:ident) => {
+        impl SerdeObject for $field {
+            const RAW_SIZE: usize = 32;
+
+            fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
+                assert_eq!(bytes.len(), Self::RAW_SIZE);
+                let mut limbs = [0; 4];
+                for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+                    let mut buf = [0; 8];
+                    buf.copy_from_slice(chunk);
+                    *limb = u64::from_le_bytes(buf);
+                }
+                $field(limbs)
+            }
+
+            fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+                if bytes.len() != Self::RAW_SIZE
+                    || !le_bytes_at_most(bytes, &(-$field::one()).to_repr())
+                {
+                    return None;
+                }
+                Some(Self::from_raw_bytes_unchecked(bytes))
+            }
+
+            fn to_raw_bytes(&self) -> Vec<u8> {
+                self.0.iter().flat_map(|limb| limb.to_le_bytes()).collect()
+            }
+        }
+    };
+}
+
+impl_serde_object!(Fp);
+impl_serde_object!(Fq);
+
+/// A table is encoded as the raw encodings of its points, window by window.
+impl<C: CurveAffine + SerdeObject> SerdeObject for BasepointTable<C> {
+    const RAW_SIZE: usize = BasepointTable::<C>::NUM_WINDOWS * WINDOW_SIZE * C::RAW_SIZE;
+
+    fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), Self::RAW_SIZE);
+        let windows = bytes
+            .chunks(WINDOW_SIZE * C::RAW_SIZE)
+            .map(|chunk| {
+                let mut window = [C::identity(); WINDOW_SIZE];
+                for (p, bytes) in window.iter_mut().zip(chunk.chunks(C::RAW_SIZE)) {
+                    *p = C::from_raw_bytes_unchecked(bytes);
+                }
+                window
+            })
+            .collect();
+        BasepointTable { windows }
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::RAW_SIZE {
+            return None;
+        }
+        let windows = bytes
+            .chunks(WINDOW_SIZE * C::RAW_SIZE)
+            .map(|chunk| {
+                let mut window = [C::identity(); WINDOW_SIZE];
+                for (p, bytes) in window.iter_mut().zip(chunk.chunks(C::RAW_SIZE)) {
+                    *p = C::from_raw_bytes(bytes)?;
+                }
+                Some(window)
+            })
+            .collect::<Option<_>>()?;
+        Some(BasepointTable { windows })
+    }
+
+    fn to_raw_bytes(&self) -> Vec<u8> {
+        self.windows
+            .iter()
+            .flatten()
+            .flat_map(|p| p.to_raw_bytes())
+            .collect()
+    }
+}
+
+/// The length of a context's raw encoding depends on its window size, so it
+/// doesn't implement [`SerdeObject`], but it provides the same methods.
+impl<C: CurveExt> WnafContext<C>
+where
+    C::AffineExt: SerdeObject,
+{
+    /// Decodes a raw encoding without checking that the points are valid, so it must
+    /// only be used for trusted data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bytes` doesn't match the window size it encodes.
+    pub fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
+        let (&window, points) = bytes.split_first().expect("empty raw encoding");
+        assert_eq!(points.len() % C::AffineExt::RAW_SIZE, 0);
+        let table = points
+            .chunks(C::AffineExt::RAW_SIZE)
+            .map(C::AffineExt::from_raw_bytes_unchecked)
+            .collect();
+        Self::from_parts(window as usize, table).expect("raw encoding has the wrong length")
+    }
+
+    /// Decodes a raw encoding, returning `None` if the length of `bytes` doesn't
+    /// match the window size it encodes, or if a point is not on the curve.
+    pub fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&window, points) = bytes.split_first()?;
+        if points.len() % C::AffineExt::RAW_SIZE != 0 {
+            return None;
+        }
+        let table = points
+            .chunks(C::AffineExt::RAW_SIZE)
+            .map(C::AffineExt::from_raw_bytes)
+            .collect::<Option<_>>()?;
+        Self::from_parts(window as usize, table)
+    }
+
+    /// Returns the raw encoding: the window size as a single byte, followed by the
+    /// raw encodings of the points of the table.
+    pub fn to_raw_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + self.table.len() * C::AffineExt::RAW_SIZE);
+        bytes.push(self.window as u8);
+        for p in &self.table {
+            bytes.extend(p.to_raw_bytes());
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ff::{Field, PrimeField};
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+
+    use super::SerdeObject;
+    use crate::{BasepointTable, Ep, EpAffine, Eq, EqAffine, Fp, Fq, WnafContext};
+
+    fn test_round_trip<T: SerdeObject + core::fmt::Debug + PartialEq>(value: T) {
+        let bytes = value.to_raw_bytes();
+        assert_eq!(bytes.len(), T::RAW_SIZE);
+        assert_eq!(T::from_raw_bytes(&bytes).unwrap(), value);
+        assert_eq!(T::from_raw_bytes_unchecked(&bytes), value);
+        assert!(T::from_raw_bytes(&bytes[1..]).is_none());
+
+        #[cfg(feature = "std")]
+        {
+            let mut buf = Vec::new();
+            value.write_raw(&mut buf).unwrap();
+            assert_eq!(buf, bytes);
+            assert_eq!(T::read_raw(&mut &buf[..]).unwrap(), value);
+            assert_eq!(T::read_raw_unchecked(&mut &buf[..]), value);
+            assert!(T::read_raw(&mut &buf[1..]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_serde_object() {
+        let mut rng = crate::tests::rng();
+
+        for _ in 0..10 {
+            test_round_trip(Fp::random(&mut rng));
+            test_round_trip(Fq::random(&mut rng));
+            test_round_trip(Ep::random(&mut rng));
+            test_round_trip(Eq::random(&mut rng));
+            test_round_trip(Ep::random(&mut rng).to_affine());
+            test_round_trip(Eq::random(&mut rng).to_affine());
+        }
+        test_round_trip(Ep::identity());
+        test_round_trip(EpAffine::identity());
+        test_round_trip(EqAffine::identity());
+
+        // The raw encoding is the Montgomery form, so one is encoded as R mod p.
+        let r: [u64; 4] = [
+            0x34786d38fffffffd,
+            0x992c350be41914ad,
+            0xffffffffffffffff,
+            0x3fffffffffffffff,
+        ];
+        assert_eq!(
+            Fp::one().to_raw_bytes(),
+            r.iter()
+                .flat_map(|limb| limb.to_le_bytes())
+                .collect::<Vec<_>>()
+        );
+
+        // Values that are not less than the modulus are rejected.
+        let mut p = (-Fp::one()).to_repr();
+        p[0] += 1;
+        assert!(Fp::from_raw_bytes(&p).is_none());
+        assert!(Fp::from_raw_bytes(&(-Fp::one()).to_repr()).is_some());
+
+        // Points that are not on the curve are rejected.
+        let mut off_curve = EpAffine::generator().to_raw_bytes();
+        off_curve[32] ^= 1;
+        assert!(EpAffine::from_raw_bytes(&off_curve).is_none());
+        let mut off_curve = Ep::generator().to_raw_bytes();
+        off_curve[32] ^= 1;
+        assert!(Ep::from_raw_bytes(&off_curve).is_none());
+    }
+
+    #[test]
+    fn test_serde_object_tables() {
+        let table = BasepointTable::new(&EqAffine::generator());
+        test_round_trip(table.clone());
+        let mut bytes = table.to_raw_bytes();
+        bytes[EqAffine::RAW_SIZE + 32] ^= 1;
+        assert!(BasepointTable::<EqAffine>::from_raw_bytes(&bytes).is_none());
+
+        for &num_scalars in [1, 1000].iter() {
+            let context = WnafContext::new(&Ep::generator(), num_scalars);
+            let bytes = context.to_raw_bytes();
+            assert_eq!(WnafContext::<Ep>::from_raw_bytes(&bytes).unwrap(), context);
+            assert_eq!(WnafContext::<Ep>::from_raw_bytes_unchecked(&bytes), context);
+            assert!(WnafContext::<Ep>::from_raw_bytes(&bytes[..bytes.len() - 1]).is_none());
+            assert!(WnafContext::<Ep>::from_raw_bytes(&bytes[..bytes.len() - 64]).is_none());
+        }
+        assert!(WnafContext::<Ep>::from_raw_bytes(&[]).is_none());
+        assert!(WnafContext::<Ep>::from_raw_bytes(&[0xff]).is_none());
+    }
+}
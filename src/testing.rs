@@ -0,0 +1,174 @@
+//! Strategies for property testing with `proptest`.
+//!
+//! The field and point strategies mostly generate uniformly distributed values,
+//! but also the edge cases that tend to break arithmetic and encodings: zero, one
+//! and $-1$ for field elements, and the identity and the generator for points.
+//! The `*_non_canonical_repr` and `*_invalid_encoding` strategies generate byte
+//! patterns that decoding must reject.
+
+use ff::PrimeField;
+use group::{prime::PrimeCurveAffine, Curve};
+use proptest::prelude::*;
+
+use crate::arithmetic::FieldExt;
+use crate::curves::{EpAffine, EqAffine};
+use crate::fields::{Fp, Fq};
+
+/// Returns the little-endian encoding of the modulus of `F` plus `k`.
+fn modulus_plus<F: PrimeField<Repr = [u8; 32]>>(k: u8) -> [u8; 32] {
+    let mut repr = (-F::ONE).to_repr();
+    let mut carry = k as u16 + 1;
+    for byte in repr.iter_mut() {
+        let sum = *byte as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+    repr
+}
+
+macro_rules! field_strategies {
+    ($field:ident, $name:ident, $non_canonical:ident) => {
+        #[doc = concat!("Generates `", stringify!($field), "` elements.")]
+        pub fn $name() -> impl Strategy<Value = $field> {
+            prop_oneof![
+                1 => Just($field::zero()),
+                1 => Just($field::one()),
+                1 => Just(-$field::one()),
+                7 => (any::<[u8; 32]>(), any::<[u8; 32]>()).prop_map(|(lo, hi)| {
+                    let mut wide = [0; 64];
+                    wide[..32].copy_from_slice(&lo);
+                    wide[32..].copy_from_slice(&hi);
+                    $field::from_bytes_wide(&wide)
+                }),
+            ]
+        }
+
+        #[doc = concat!(
+            "Generates byte strings that are not canonical encodings of `",
+            stringify!($field),
+            "` elements: the modulus plus a small offset, and values with the top bit set."
+        )]
+        pub fn $non_canonical() -> impl Strategy<Value = [u8; 32]> {
+            prop_oneof![
+                any::<u8>().prop_map(modulus_plus::<$field>),
+                any::<[u8; 32]>().prop_map(|mut bytes| {
+                    bytes[31] |= 0x80;
+                    bytes
+                }),
+            ]
+        }
+    };
+}
+
+field_strategies!(Fp, fp, fp_non_canonical_repr);
+field_strategies!(Fq, fq, fq_non_canonical_repr);
+
+macro_rules! point_strategies {
+    ($name_affine:ident, $base:ident, $scalar:ident, $name:ident, $invalid:ident) => {
+        #[doc = concat!(
+            "Generates `",
+            stringify!($name_affine),
+            "` points, as multiples of the generator."
+        )]
+        pub fn $name() -> impl Strategy<Value = $name_affine> {
+            prop_oneof![
+                1 => Just($name_affine::identity()),
+                1 => Just($name_affine::generator()),
+                8 => $scalar().prop_map(|s| ($name_affine::generator() * s).to_affine()),
+            ]
+        }
+
+        #[doc = concat!(
+            "Generates byte strings that are not valid compressed encodings of `",
+            stringify!($name_affine),
+            "` points: non-canonical x-coordinates with either sign, and the identity \
+             with the sign bit set."
+        )]
+        pub fn $invalid() -> impl Strategy<Value = [u8; 32]> {
+            let mut identity_with_sign = [0; 32];
+            identity_with_sign[31] = 0x80;
+            prop_oneof![
+                (any::<u8>(), any::<bool>()).prop_map(|(k, sign)| {
+                    let mut bytes = modulus_plus::<$base>(k);
+                    bytes[31] |= (sign as u8) << 7;
+                    bytes
+                }),
+                Just(identity_with_sign),
+            ]
+        }
+    };
+}
+
+point_strategies!(EpAffine, Fp, fq, pallas_point, pallas_invalid_encoding);
+point_strategies!(EqAffine, Fq, fp, vesta_point, vesta_invalid_encoding);
+
+#[cfg(test)]
+mod tests {
+    use ff::PrimeField;
+    use group::GroupEncoding;
+    use proptest::prelude::*;
+    use proptest::test_runner::TestRunner;
+
+    use super::*;
+    use crate::arithmetic::CurveAffine;
+
+    #[test]
+    fn test_field_strategies() {
+        let mut runner = TestRunner::default();
+        runner
+            .run(&fp(), |a| {
+                prop_assert_eq!(Fp::from_repr(a.to_repr()).unwrap(), a);
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&fq(), |a| {
+                prop_assert_eq!(Fq::from_repr(a.to_repr()).unwrap(), a);
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&fp_non_canonical_repr(), |bytes| {
+                prop_assert!(bool::from(Fp::from_repr(bytes).is_none()));
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&fq_non_canonical_repr(), |bytes| {
+                prop_assert!(bool::from(Fq::from_repr(bytes).is_none()));
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_point_strategies() {
+        let mut runner = TestRunner::default();
+        runner
+            .run(&pallas_point(), |p| {
+                prop_assert!(bool::from(p.is_on_curve()));
+                prop_assert_eq!(EpAffine::from_bytes(&p.to_bytes()).unwrap(), p);
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&vesta_point(), |p| {
+                prop_assert!(bool::from(p.is_on_curve()));
+                prop_assert_eq!(EqAffine::from_bytes(&p.to_bytes()).unwrap(), p);
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&pallas_invalid_encoding(), |bytes| {
+                prop_assert!(bool::from(EpAffine::from_bytes(&bytes).is_none()));
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&vesta_invalid_encoding(), |bytes| {
+                prop_assert!(bool::from(EqAffine::from_bytes(&bytes).is_none()));
+                Ok(())
+            })
+            .unwrap();
+    }
+}
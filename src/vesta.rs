@@ -1,6 +1,6 @@
 //! The Vesta and iso-Vesta elliptic curve groups.
 
-use super::{Eq, EqAffine, Fp, Fq};
+use super::{Eq, EqAffine, Fp, Fq, IsoEq, IsoEqAffine};
 
 /// The base field of the Vesta and iso-Vesta curves.
 pub type Base = Fq;
@@ -14,6 +14,48 @@ pub type Point = Eq;
 /// A Vesta point in the affine coordinate space (or the point at infinity).
 pub type Affine = EqAffine;
 
+/// An iso-Vesta point in the projective coordinate space.
+pub type IsoPoint = IsoEq;
+
+/// An iso-Vesta point in the affine coordinate space (or the point at infinity).
+pub type IsoAffine = IsoEqAffine;
+
+/// The ID of the RFC 9380 hash-to-curve suite for Vesta with `expand_message_xmd`
+/// and SHA-256, which is implemented by [`Point::hash_to_curve_with`] with
+/// [`ExpandMsgXmd<Sha256>`](crate::ExpandMsgXmd).
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub const SUITE_ID_XMD_SHA256: &str = "vesta_XMD:SHA-256_SSWU_RO_";
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iso_map() {
+    use crate::arithmetic::CurveExt;
+    use group::{Curve, Group, GroupEncoding};
+
+    let mut rng = crate::tests::rng();
+
+    // The isogeny is a group homomorphism onto Vesta.
+    for _ in 0..10 {
+        let a = IsoPoint::random(&mut rng);
+        let b = IsoPoint::random(&mut rng);
+        assert!(bool::from(a.is_on_curve()));
+        let a_affine = a.to_affine();
+        assert_eq!(
+            IsoAffine::from_bytes(&a_affine.to_bytes()).unwrap(),
+            a_affine
+        );
+        assert!(bool::from(Point::iso_map(&a).is_on_curve()));
+        assert_eq!(
+            Point::iso_map(&(a + b)),
+            Point::iso_map(&a) + Point::iso_map(&b)
+        );
+    }
+    assert!(bool::from(
+        Point::iso_map(&IsoPoint::identity()).is_identity()
+    ));
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn test_map_to_curve_simple_swu() {
@@ -70,3 +112,187 @@ fn test_hash_to_curve() {
         format!("{:?}", z) == "0x1b58d4aa4d68c3f4d9916b77c79ff9911597a27f2ee46244e98eb9615172d2ad"
     );
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_map_to_curve() {
+    use crate::arithmetic::CurveExt;
+    use crate::hashtocurve;
+    use ff::Field;
+
+    let mut rng = crate::tests::rng();
+
+    for u in [Base::zero(), Base::one(), Base::random(&mut rng)] {
+        let q = Point::map_to_curve_simple_swu(&u);
+        assert_eq!(
+            q,
+            hashtocurve::map_to_curve_simple_swu::<Base, Point, IsoPoint>(
+                &u,
+                Point::THETA,
+                Point::Z
+            )
+        );
+        assert!(bool::from(q.is_on_curve()));
+        assert_eq!(Point::map_to_curve(&u), Point::iso_map(&q));
+    }
+
+    let us = Base::hash_to_field("z.cash:test", b"Trans rights now!", 2);
+    assert_eq!(
+        Point::iso_map(
+            &(Point::map_to_curve_simple_swu(&us[0]) + Point::map_to_curve_simple_swu(&us[1]))
+        ),
+        Point::hash_to_curve("z.cash:test")(b"Trans rights now!")
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hash_to_field_matches_hash_to_curve() {
+    use crate::arithmetic::CurveExt;
+    use crate::curves::{Eq, IsoEq};
+    use crate::hashtocurve;
+
+    let us = Base::hash_to_field("z.cash:test", b"Trans rights now!", 2);
+    let r = hashtocurve::map_to_curve_simple_swu::<Base, Eq, IsoEq>(&us[0], Eq::THETA, Eq::Z)
+        + hashtocurve::map_to_curve_simple_swu::<Base, Eq, IsoEq>(&us[1], Eq::THETA, Eq::Z);
+    assert_eq!(
+        hashtocurve::iso_map::<Base, Eq, IsoEq>(&r, &Eq::ISOGENY_CONSTANTS),
+        Point::hash_to_curve("z.cash:test")(b"Trans rights now!")
+    );
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_hash_to_curve_with() {
+    use crate::arithmetic::CurveExt;
+    use crate::ExpandMsgXmd;
+    use group::Group;
+    use sha2::Sha256;
+
+    let dst = b"vesta_XMD:SHA-256_SSWU_RO_";
+    let hash = |msg: &[u8]| Point::hash_to_curve_with::<ExpandMsgXmd<Sha256>>(dst, msg);
+    let p = hash(b"abc");
+    assert!(bool::from(p.is_on_curve()));
+    assert!(!bool::from(p.is_identity()));
+    assert_eq!(p, hash(b"abc"));
+    assert_ne!(p, hash(b"abcd"));
+    assert_ne!(
+        p,
+        Point::hash_to_curve_with::<ExpandMsgXmd<Sha256>>(b"other", b"abc")
+    );
+}
+
+#[cfg(feature = "sha256")]
+#[test]
+fn test_hash_to_curve_sha256() {
+    use crate::hashtocurve::tests::suite::{check_suite, TestVector};
+
+    // Vectors in the format of RFC 9380, appendix J, for the messages "", "abc",
+    // "abcdef0123456789", "q128_qqq..." and "a512_aaa...". They are generated by
+    // scripts/hash_to_curve_vectors.py, an independent implementation of the RFC in
+    // Python with the constants of the Zcash protocol specification.
+    check_suite(
+        b"QUUX-V01-CS02-with-vesta_XMD:SHA-256_SSWU_RO_",
+        Point::hash_to_curve_sha256,
+        |u| {
+            let q = crate::hashtocurve::map_to_curve_simple_swu::<_, Point, super::IsoEq>(
+                u,
+                Point::THETA,
+                Point::Z,
+            );
+            crate::hashtocurve::iso_map(&q, &Point::ISOGENY_CONSTANTS)
+        },
+        &[
+            TestVector {
+                p: (
+                    "0x03dd8ee421e44e89c2c088cb46505002e32f8c6566f1d2583a7973d6d69d418d",
+                    "0x182ec24db0070de1291b930f0e46aa847a0b7e4a006befba43cafe1a700d0a26",
+                ),
+                u: [
+                    "0x198ec85dccc4a327ee1723578c4709a6f0394d4cd8a2a64f8c3ab9edff59e70e",
+                    "0x3d0a372628a7431fab01869e78488096b4c7bf53a9f8e47f1219acb262fd5da3",
+                ],
+                q0: (
+                    "0x3c9b1949c94708eaaf9548f16c18b07a503c1acd49e2f1c7cdfdbd74e30d81e6",
+                    "0x0c30a173718525b793a70d7e146fe14c4e1f2b1379a35fc1eaee4a4101d9df04",
+                ),
+                q1: (
+                    "0x201f36d11d128d0017f62e572ce16b9c1f68ece2f2065270b054d74c8f7938dd",
+                    "0x033bafb400439cbaef7ef85dd41372b2368cda428c680e136ac645e73a467108",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x39d1ec3185cd0d9923c8c8f510be92501c2209872e88ffcbb61a13ea5ef6a26b",
+                    "0x115e8945aa1a8c7d829773b34e559915980533dc3f7e70711465f6776ed4e8bf",
+                ),
+                u: [
+                    "0x1e92e5c7f5d08048b5a2a5af1b9f136588938db8682c026e0eec5243e9ead0b8",
+                    "0x31106be8a100736c256cbdabf506ca1da5bbdd0948fc74c90e92cc33619882be",
+                ],
+                q0: (
+                    "0x08678ea6919ba4f4f5e7da9d85ff1ea52c7b73ee91d42ae52bb9061cc3afba91",
+                    "0x30c8982274f9285318c084fc1de7145f4f211295d0978141f4df7d3bdffd63a9",
+                ),
+                q1: (
+                    "0x340638fe94c3c038dc4ce1a508872d2e0a01367f6d7329002cdd21311677800c",
+                    "0x3b282fe2f3bb0dcf5a8f60f7c0c994e6abd50f9a69c50ee061113e13c48d055d",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x3e04cc434a78eb6d55ee0b6bd738acfdb8e81a770126bb42a90323e7acce3354",
+                    "0x0af9003666f04668098993bbdf662aa20c1e8afb08815ea4484c79f58240fc75",
+                ),
+                u: [
+                    "0x1db82a29ec48900423e2016659f1d6178f48761e3630a63248632e2688142a8e",
+                    "0x2c062da9934a157c72a3eb78330a80a352447d1132081d566dc5040c9a49f848",
+                ],
+                q0: (
+                    "0x385e76a52f4a18bb492acc155d6c5c4c39ee75ab92e647e0e8bc07f5a7c47876",
+                    "0x096a8da49363ccebfd270620f9d7c002972f3930ceafbe88ac5193aedad1f61c",
+                ),
+                q1: (
+                    "0x173cf18a5c87c9a19c4560f754f54982ab95b9cfea1413342820ba9064f102f3",
+                    "0x0e78f999474c75c13e6327b937d913cc38be18072331b6168a47a57cdbc44eac",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x1f94518352663d27e2a3f5cad74af1efa39d1e8e8364a7ce95994418ea717e8c",
+                    "0x01c381fb60c7a3a99d1958721b9e27b4c1bf05fe2124e750479fddc135232a1d",
+                ),
+                u: [
+                    "0x2278cce43d90f3e098e223829bbcd179a16c9ce6bf51a4bd21b8bf91dce0de8e",
+                    "0x00eb673dae1ddbdc0a683247762f70887c8aaf38c43591dc1e98a24fbb365da0",
+                ],
+                q0: (
+                    "0x05b704ca7a46a851cd8ebfc8ac347596ce2201a757bcaea6c67863f26f26b617",
+                    "0x1e21868eb3faaf218cae87efb4242207a8051017de6f5706dd6dd496ee4e0316",
+                ),
+                q1: (
+                    "0x1f0a0a2f18e7b82eccad35d1fc796d91049715d089e5cdc64b0d5a9acbde68bb",
+                    "0x0b2f57b181e58600cad6dde11d58568ac5f9be37ef8c121eda07d07281ac0c24",
+                ),
+            },
+            TestVector {
+                p: (
+                    "0x2d8cab8a78bc9445c1b42355176635bdcd09ca0f6221ad6942358a19dfd5cdde",
+                    "0x03e758a2f46827b4201286e2cfc68d2da5c68e8b7ff7c3811076f8ce11dcd91d",
+                ),
+                u: [
+                    "0x0a362d6c62881eabcfa5129d1e0c53a5873527fb5c5be3cc24632b699bbe3231",
+                    "0x30a3884ebb47e7420c2bdf70acd3be85f6d7352c8aca01688df6696ab32c661c",
+                ],
+                q0: (
+                    "0x0e858d57f6f89e545fd055d5dff199335c6f63577ca674f0d3b2ef0deb4e4144",
+                    "0x3a54b032b22253dfc5a255d3acd324d1910bbe9de7f728be643697fd76b3db06",
+                ),
+                q1: (
+                    "0x16fd3273aa59288713c1ab8b9082ba9a74bf29e46dcdbabfc25d701c9a760647",
+                    "0x3840a6ef9d57b8672ea359a9066a81e976b509133dce460f7cce6af458aebec8",
+                ),
+            },
+        ],
+    );
+}
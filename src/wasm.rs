@@ -0,0 +1,190 @@
+//! Bindings for JavaScript, generated with `wasm-bindgen`.
+//!
+//! Points are passed as their 32-byte compressed encodings and scalars as their
+//! 32-byte little-endian encodings. Slices of several points or scalars are the
+//! concatenations of their encodings.
+
+use alloc::vec::Vec;
+
+use ff::PrimeField;
+use group::{Curve as _, UncompressedEncoding};
+use wasm_bindgen::prelude::*;
+
+use crate::arithmetic::{CurveAffine, CurveExt};
+use crate::curves::{EpAffine, EqAffine};
+
+/// Parses a compressed point encoding.
+fn read_point<C: CurveAffine>(bytes: &[u8]) -> Result<C, &'static str> {
+    let mut repr = C::Repr::default();
+    if bytes.len() != repr.as_ref().len() {
+        return Err("unexpected length of point encoding");
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    Option::from(C::from_bytes(&repr)).ok_or("bytes don't encode a point on the curve")
+}
+
+/// Parses a little-endian scalar encoding.
+fn read_scalar<C: CurveAffine>(bytes: &[u8]) -> Result<C::ScalarExt, &'static str> {
+    let mut repr = <C::ScalarExt as PrimeField>::Repr::default();
+    if bytes.len() != repr.as_ref().len() {
+        return Err("unexpected length of scalar encoding");
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    Option::from(C::ScalarExt::from_repr(repr)).ok_or("bytes don't encode a canonical scalar")
+}
+
+fn decompress<C: CurveAffine + UncompressedEncoding>(
+    point: &[u8],
+) -> Result<Vec<u8>, &'static str> {
+    let p = read_point::<C>(point)?;
+    Ok(p.to_uncompressed().as_ref().to_vec())
+}
+
+fn scalar_mul<C: CurveAffine>(point: &[u8], scalar: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let p = read_point::<C>(point)?;
+    let s = read_scalar::<C>(scalar)?;
+    Ok((p * s).to_affine().to_bytes().as_ref().to_vec())
+}
+
+fn msm<C: CurveAffine>(points: &[u8], scalars: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if points.len() % 32 != 0 || scalars.len() % 32 != 0 {
+        return Err("encodings must be concatenations of 32-byte chunks");
+    }
+    if points.len() != scalars.len() {
+        return Err("numbers of points and scalars differ");
+    }
+
+    let bases = points
+        .chunks(32)
+        .map(read_point::<C>)
+        .collect::<Result<Vec<_>, _>>()?;
+    let scalars = scalars
+        .chunks(32)
+        .map(read_scalar::<C>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let res = crate::msm::multi_scalar_mul(&bases, &scalars);
+    Ok(res.to_affine().to_bytes().as_ref().to_vec())
+}
+
+fn hash_to_curve<C: CurveAffine>(domain_prefix: &str, message: &[u8]) -> Vec<u8> {
+    let hasher = C::CurveExt::hash_to_curve(domain_prefix);
+    hasher(message).to_affine().to_bytes().as_ref().to_vec()
+}
+
+/// Decompresses a Pallas point, returning the 64-byte concatenation of the
+/// little-endian encodings of its coordinates.
+#[wasm_bindgen]
+pub fn pallas_decompress(point: &[u8]) -> Result<Vec<u8>, JsError> {
+    decompress::<EpAffine>(point).map_err(JsError::new)
+}
+
+/// Multiplies a Pallas point by a scalar, returning the compressed result.
+#[wasm_bindgen]
+pub fn pallas_scalar_mul(point: &[u8], scalar: &[u8]) -> Result<Vec<u8>, JsError> {
+    scalar_mul::<EpAffine>(point, scalar).map_err(JsError::new)
+}
+
+/// Computes the multi-scalar multiplication of Pallas `points` and `scalars`,
+/// returning the compressed result.
+///
+/// This is not constant time with respect to the scalars.
+#[wasm_bindgen]
+pub fn pallas_msm(points: &[u8], scalars: &[u8]) -> Result<Vec<u8>, JsError> {
+    msm::<EpAffine>(points, scalars).map_err(JsError::new)
+}
+
+/// Hashes `message` to a Pallas point with the given domain prefix, returning
+/// the compressed result.
+#[wasm_bindgen]
+pub fn pallas_hash_to_curve(domain_prefix: &str, message: &[u8]) -> Vec<u8> {
+    hash_to_curve::<EpAffine>(domain_prefix, message)
+}
+
+/// Decompresses a Vesta point, returning the 64-byte concatenation of the
+/// little-endian encodings of its coordinates.
+#[wasm_bindgen]
+pub fn vesta_decompress(point: &[u8]) -> Result<Vec<u8>, JsError> {
+    decompress::<EqAffine>(point).map_err(JsError::new)
+}
+
+/// Multiplies a Vesta point by a scalar, returning the compressed result.
+#[wasm_bindgen]
+pub fn vesta_scalar_mul(point: &[u8], scalar: &[u8]) -> Result<Vec<u8>, JsError> {
+    scalar_mul::<EqAffine>(point, scalar).map_err(JsError::new)
+}
+
+/// Computes the multi-scalar multiplication of Vesta `points` and `scalars`,
+/// returning the compressed result.
+///
+/// This is not constant time with respect to the scalars.
+#[wasm_bindgen]
+pub fn vesta_msm(points: &[u8], scalars: &[u8]) -> Result<Vec<u8>, JsError> {
+    msm::<EqAffine>(points, scalars).map_err(JsError::new)
+}
+
+/// Hashes `message` to a Vesta point with the given domain prefix, returning
+/// the compressed result.
+#[wasm_bindgen]
+pub fn vesta_hash_to_curve(domain_prefix: &str, message: &[u8]) -> Vec<u8> {
+    hash_to_curve::<EqAffine>(domain_prefix, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::{Field, PrimeField};
+    use group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding, UncompressedEncoding};
+
+    use super::{decompress, hash_to_curve, msm, scalar_mul};
+    use crate::arithmetic::CurveExt;
+    use crate::pallas;
+
+    #[test]
+    fn test_bindings() {
+        let mut rng = crate::tests::rng();
+
+        let p = pallas::Point::random(&mut rng).to_affine();
+        let q = pallas::Point::random(&mut rng).to_affine();
+        let a = pallas::Scalar::random(&mut rng);
+        let b = pallas::Scalar::random(&mut rng);
+
+        assert_eq!(
+            decompress::<pallas::Affine>(&p.to_bytes()).unwrap(),
+            p.to_uncompressed().as_ref()
+        );
+        assert_eq!(
+            scalar_mul::<pallas::Affine>(&p.to_bytes(), &a.to_repr()).unwrap(),
+            (p * a).to_affine().to_bytes()
+        );
+
+        let points = [p.to_bytes(), q.to_bytes()].concat();
+        let scalars = [a.to_repr(), b.to_repr()].concat();
+        assert_eq!(
+            msm::<pallas::Affine>(&points, &scalars).unwrap(),
+            (p * a + q * b).to_affine().to_bytes()
+        );
+        assert_eq!(
+            msm::<pallas::Affine>(&[], &[]).unwrap(),
+            pallas::Affine::identity().to_bytes()
+        );
+
+        assert_eq!(
+            hash_to_curve::<pallas::Affine>("z.cash:test", b"hello"),
+            pallas::Point::hash_to_curve("z.cash:test")(b"hello")
+                .to_affine()
+                .to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_bindings_reject_invalid_input() {
+        let p = pallas::Affine::generator().to_bytes();
+        let s = pallas::Scalar::one().to_repr();
+
+        assert!(decompress::<pallas::Affine>(&p[..31]).is_err());
+        assert!(decompress::<pallas::Affine>(&[0xff; 32]).is_err());
+        assert!(scalar_mul::<pallas::Affine>(&p, &[0xff; 32]).is_err());
+        assert!(msm::<pallas::Affine>(&p, &[s, s].concat()).is_err());
+        assert!(msm::<pallas::Affine>(&p[..16], &s[..16]).is_err());
+    }
+}
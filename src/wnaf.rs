@@ -0,0 +1,243 @@
+//! This module implements variable-time scalar multiplication using the windowed
+//! non-adjacent form (wNAF) of the scalar.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::Mul;
+
+use ff::PrimeField;
+use group::WnafGroup;
+
+use crate::arithmetic::CurveExt;
+
+/// Reads up to 57 bits of a little-endian byte encoding, starting at bit `pos`.
+fn get_bits(bytes: &[u8], pos: usize) -> u64 {
+    let skip_bytes = pos / 8;
+
+    let mut v = [0; 8];
+    for (v, o) in v.iter_mut().zip(bytes[skip_bytes..].iter()) {
+        *v = *o;
+    }
+
+    u64::from_le_bytes(v) >> (pos - skip_bytes * 8)
+}
+
+/// Returns the width-`window` NAF digits of a little-endian scalar encoding,
+/// least significant first. Every nonzero digit is odd and lies strictly between
+/// $-2^{\textsf{window}-1}$ and $2^{\textsf{window}-1}$.
+///
+/// The most significant bit of the encoding must be zero, which holds for the
+/// canonical encodings of both scalar fields.
+pub(crate) fn wnaf_form(bytes: &[u8], window: usize) -> Vec<i64> {
+    debug_assert!((2..=57).contains(&window));
+
+    let bit_len = bytes.len() * 8;
+    let width = 1u64 << window;
+    let window_mask = width - 1;
+
+    let mut wnaf = Vec::with_capacity(bit_len + 1);
+    let mut pos = 0;
+    let mut carry = 0;
+    while pos < bit_len {
+        let window_val = carry + (get_bits(bytes, pos) & window_mask);
+
+        if window_val & 1 == 0 {
+            wnaf.push(0);
+            pos += 1;
+        } else {
+            if window_val < width / 2 {
+                carry = 0;
+                wnaf.push(window_val as i64);
+            } else {
+                carry = 1;
+                wnaf.push((window_val as i64).wrapping_sub(width as i64));
+            }
+            wnaf.extend(core::iter::repeat(0).take(window - 1));
+            pos += window;
+        }
+    }
+
+    // The encoding's top bit is zero, so any digits past its end are zero.
+    wnaf.truncate(bit_len);
+    wnaf
+}
+
+/// Returns the odd multiples $[1] B, [3] B, \ldots, [2^{\textsf{window}-1} - 1] B$
+/// of `base`, which are the points needed to evaluate width-`window` NAF digits.
+pub(crate) fn wnaf_table<C: CurveExt>(base: &C, window: usize) -> Vec<C::AffineExt> {
+    let mut table = Vec::with_capacity(1 << (window - 2));
+    let dbl = base.double();
+    let mut acc = *base;
+    for _ in 0..(1 << (window - 2)) {
+        table.push(acc);
+        acc += dbl;
+    }
+
+    let mut affine = vec![C::AffineExt::default(); table.len()];
+    C::batch_normalize(&table, &mut affine);
+    affine
+}
+
+/// Adds $[d] B$ to `acc`, where `table` is the output of [`wnaf_table`] for $B$.
+pub(crate) fn wnaf_add<C: CurveExt>(acc: &mut C, table: &[C::AffineExt], digit: i64) {
+    match digit.cmp(&0) {
+        Ordering::Greater => *acc += table[(digit / 2) as usize],
+        Ordering::Less => *acc -= table[(-digit / 2) as usize],
+        Ordering::Equal => (),
+    }
+}
+
+/// The window size used by [`double_scalar_mul`].
+const DOUBLE_SCALAR_MUL_WINDOW: usize = 5;
+
+/// Computes $[a] P + [b] Q$ by interleaving the wNAF forms of `a` and `b`, so that
+/// both multiplications share a single chain of doublings.
+pub(crate) fn double_scalar_mul<C: CurveExt>(
+    a: &C::ScalarExt,
+    p: &C,
+    b: &C::ScalarExt,
+    q: &C,
+) -> C {
+    let a_wnaf = wnaf_form(a.to_repr().as_ref(), DOUBLE_SCALAR_MUL_WINDOW);
+    let b_wnaf = wnaf_form(b.to_repr().as_ref(), DOUBLE_SCALAR_MUL_WINDOW);
+    let p_table = wnaf_table(p, DOUBLE_SCALAR_MUL_WINDOW);
+    let q_table = wnaf_table(q, DOUBLE_SCALAR_MUL_WINDOW);
+
+    let mut acc = C::identity();
+    for (&a_digit, &b_digit) in a_wnaf.iter().zip(b_wnaf.iter()).rev() {
+        acc = acc.double();
+        wnaf_add(&mut acc, &p_table, a_digit);
+        wnaf_add(&mut acc, &q_table, b_digit);
+    }
+
+    acc
+}
+
+/// A reusable wNAF precomputation for a fixed base point.
+///
+/// Building the context computes a table of odd multiples of the base once, so
+/// that repeated multiplications of the same base avoid rebuilding it. The window
+/// size is chosen by [`WnafGroup::recommended_wnaf_for_num_scalars`].
+///
+/// Multiplication is variable-time, and must not be used with secret scalars.
+///
+/// Contexts can be converted to and from raw bytes with
+/// [`WnafContext::to_raw_bytes`] and [`WnafContext::from_raw_bytes`], and with the
+/// `serde` feature they also implement `Serialize` and `Deserialize`. As for
+/// [`BasepointTable`](crate::BasepointTable), decoding checks the points and the
+/// length of the table but not that they are multiples of a single base.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WnafContext<C: CurveExt> {
+    pub(crate) table: Vec<C::AffineExt>,
+    pub(crate) window: usize,
+}
+
+impl<C: CurveExt> WnafContext<C> {
+    /// Constructs a context from its window size and table, returning `None` if the
+    /// window size is out of range or the table has the wrong length for it.
+    pub(crate) fn from_parts(window: usize, table: Vec<C::AffineExt>) -> Option<Self> {
+        if (2..=57).contains(&window) && Some(table.len()) == 1usize.checked_shl(window as u32 - 2)
+        {
+            Some(WnafContext { table, window })
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: CurveExt + WnafGroup> WnafContext<C> {
+    /// Precomputes a wNAF table for `base`, sized for multiplying it by roughly
+    /// `num_scalars` scalars.
+    pub fn new(base: &C, num_scalars: usize) -> Self {
+        let window = C::recommended_wnaf_for_num_scalars(num_scalars);
+        WnafContext {
+            table: wnaf_table(base, window),
+            window,
+        }
+    }
+
+    /// Returns the window size of this context.
+    pub fn window_size(&self) -> usize {
+        self.window
+    }
+}
+
+impl<'a, 'b, C: CurveExt> Mul<&'b C::ScalarExt> for &'a WnafContext<C> {
+    type Output = C;
+
+    fn mul(self, scalar: &'b C::ScalarExt) -> C {
+        let wnaf = wnaf_form(scalar.to_repr().as_ref(), self.window);
+
+        let mut acc = C::identity();
+        for &digit in wnaf.iter().rev() {
+            acc = acc.double();
+            wnaf_add(&mut acc, &self.table, digit);
+        }
+
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+    use group::WnafGroup;
+
+    use super::WnafContext;
+    use crate::arithmetic::CurveExt;
+    use crate::{pallas, vesta};
+
+    fn check_wnaf_context<C: CurveExt + WnafGroup>() {
+        let mut rng = crate::tests::rng();
+
+        for &num_scalars in [1, 10, 1000].iter() {
+            let base = C::random(&mut rng);
+            let context = WnafContext::new(&base, num_scalars);
+            assert_eq!(
+                context.window_size(),
+                C::recommended_wnaf_for_num_scalars(num_scalars)
+            );
+
+            for _ in 0..10 {
+                let s = C::ScalarExt::random(&mut rng);
+                assert_eq!(&context * &s, base * s);
+            }
+            assert_eq!(&context * &C::ScalarExt::ZERO, C::identity());
+            assert_eq!(&context * &-C::ScalarExt::ONE, -base);
+        }
+    }
+
+    #[test]
+    fn test_wnaf_context() {
+        check_wnaf_context::<pallas::Point>();
+        check_wnaf_context::<vesta::Point>();
+    }
+
+    fn check_double_scalar_mul<C: CurveExt, F: Fn(&C::ScalarExt, &C, &C::ScalarExt, &C) -> C>(
+        double_scalar_mul: F,
+    ) {
+        let mut rng = crate::tests::rng();
+
+        let g = C::generator();
+        for _ in 0..10 {
+            let p = C::random(&mut rng);
+            let a = C::ScalarExt::random(&mut rng);
+            let b = C::ScalarExt::random(&mut rng);
+            assert_eq!(double_scalar_mul(&a, &g, &b, &p), g * a + p * b);
+        }
+
+        // Edge cases: zero scalars, cancellation, and the identity.
+        let one = C::ScalarExt::ONE;
+        let zero = C::ScalarExt::ZERO;
+        assert_eq!(double_scalar_mul(&zero, &g, &zero, &g), C::identity());
+        assert_eq!(double_scalar_mul(&one, &g, &-one, &g), C::identity());
+        assert_eq!(double_scalar_mul(&one, &g, &one, &C::identity()), g);
+    }
+
+    #[test]
+    fn test_double_scalar_mul() {
+        check_double_scalar_mul::<pallas::Point, _>(pallas::Point::double_scalar_mul);
+        check_double_scalar_mul::<vesta::Point, _>(vesta::Point::double_scalar_mul);
+    }
+}